@@ -12,6 +12,10 @@ fn main() {
             println!("cargo:rustc-link-lib=framework=DiskArbitration");
             // IOKit is not available on iOS: https://developer.apple.com/documentation/iokit
             println!("cargo:rustc-link-lib=framework=IOKit");
+            // CoreWLAN is used to get the wireless interfaces signal strength.
+            println!("cargo:rustc-link-lib=framework=CoreWLAN");
+            // SystemConfiguration is used to get the DNS servers.
+            println!("cargo:rustc-link-lib=framework=SystemConfiguration");
         }
 
         println!("cargo:rustc-link-lib=framework=Foundation");
@@ -95,6 +95,14 @@ fn print_help() {
         &mut io::stdout(),
         "temperature        : Displays components' temperature"
     );
+    writeln!(
+        &mut io::stdout(),
+        "fans               : Displays fans' speed"
+    );
+    writeln!(
+        &mut io::stdout(),
+        "gpus               : Displays GPUs' information"
+    );
     writeln!(
         &mut io::stdout(),
         "disks              : Displays disks' information"
@@ -127,6 +135,10 @@ fn print_help() {
         &mut io::stdout(),
         "load_avg           : Displays system load average"
     );
+    writeln!(
+        &mut io::stdout(),
+        "battery            : Displays battery information"
+    );
     writeln!(
         &mut io::stdout(),
         "frequency          : Displays processor frequency"
@@ -237,6 +249,12 @@ fn interpret_input(input: &str, sys: &mut System) -> bool {
             writeln!(&mut io::stdout(), "five minutes   : {}%", load_avg.five);
             writeln!(&mut io::stdout(), "fifteen minutes: {}%", load_avg.fifteen);
         }
+        "battery" => {
+            match sys.get_battery() {
+                Some(battery) => writeln!(&mut io::stdout(), "{:?}", battery),
+                None => writeln!(&mut io::stdout(), "No battery found"),
+            };
+        }
         e if e.starts_with("show ") => {
             let tmp: Vec<&str> = e.split(' ').collect();
 
@@ -253,7 +271,7 @@ fn interpret_input(input: &str, sys: &mut System) -> bool {
                 };
             } else {
                 let proc_name = tmp[1];
-                for proc_ in sys.get_process_by_name(proc_name) {
+                for proc_ in sys.get_processes_by_name(proc_name) {
                     writeln!(&mut io::stdout(), "==== {} ====", proc_.name());
                     writeln!(&mut io::stdout(), "{:?}", proc_);
                 }
@@ -264,6 +282,16 @@ fn interpret_input(input: &str, sys: &mut System) -> bool {
                 writeln!(&mut io::stdout(), "{:?}", component);
             }
         }
+        "fans" => {
+            for fan in sys.get_fans() {
+                writeln!(&mut io::stdout(), "{:?}", fan);
+            }
+        }
+        "gpus" => {
+            for gpu in sys.get_gpus() {
+                writeln!(&mut io::stdout(), "{:?}", gpu);
+            }
+        }
         "network" => {
             for (interface_name, data) in sys.get_networks().iter() {
                 writeln!(
@@ -18,11 +18,18 @@ use std::path::{Path, PathBuf};
 /// Struct containing a disk information.
 pub struct Disk {
     pub(crate) type_: DiskType,
+    pub(crate) is_removable: bool,
     pub(crate) name: OsString,
+    pub(crate) device_name: OsString,
     pub(crate) file_system: Vec<u8>,
     pub(crate) mount_point: PathBuf,
     pub(crate) total_space: u64,
     pub(crate) available_space: u64,
+    pub(crate) mount_options: Vec<String>,
+    pub(crate) read_count: u64,
+    pub(crate) old_read_count: u64,
+    pub(crate) write_count: u64,
+    pub(crate) old_write_count: u64,
 }
 
 impl DiskExt for Disk {
@@ -34,6 +41,10 @@ impl DiskExt for Disk {
         &self.name
     }
 
+    fn get_device_name(&self) -> &OsStr {
+        &self.device_name
+    }
+
     fn get_file_system(&self) -> &[u8] {
         &self.file_system
     }
@@ -50,16 +61,61 @@ impl DiskExt for Disk {
         self.available_space
     }
 
+    fn is_read_only(&self) -> bool {
+        self.mount_options.iter().any(|o| o == "ro")
+    }
+
+    fn is_removable(&self) -> bool {
+        self.is_removable
+    }
+
+    fn get_mount_options(&self) -> &[String] {
+        &self.mount_options
+    }
+
+    fn get_read_count(&self) -> u64 {
+        self.read_count.saturating_sub(self.old_read_count)
+    }
+
+    fn get_total_read_count(&self) -> u64 {
+        self.read_count
+    }
+
+    fn get_write_count(&self) -> u64 {
+        self.write_count.saturating_sub(self.old_write_count)
+    }
+
+    fn get_total_write_count(&self) -> u64 {
+        self.write_count
+    }
+
+    fn get_io_utilization(&self) -> f32 {
+        0.
+    }
+
+    fn get_queue_depth(&self) -> u64 {
+        0
+    }
+
     fn refresh(&mut self) -> bool {
         unsafe {
             let mut stat: statfs = mem::zeroed();
             let mount_point_cpath = to_cpath(&self.mount_point);
             if statfs(mount_point_cpath.as_ptr() as *const i8, &mut stat) == 0 {
                 self.available_space = u64::from(stat.f_bsize) * stat.f_bavail;
-                true
             } else {
-                false
+                return false;
+            }
+        }
+        #[cfg(target_os = "macos")]
+        {
+            if let Some((read_count, write_count)) = get_operation_counts(&self.device_name) {
+                self.old_read_count = self.read_count;
+                self.read_count = read_count;
+                self.old_write_count = self.write_count;
+                self.write_count = write_count;
             }
         }
+        true
     }
 }
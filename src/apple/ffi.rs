@@ -4,6 +4,17 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
+// Each binding is only used when the features of its module are enabled.
+#![cfg_attr(
+    not(all(
+        feature = "process",
+        feature = "cpu",
+        feature = "disk",
+        feature = "component"
+    )),
+    allow(dead_code, unused_imports)
+)]
+
 use libc::{
     c_int, c_uchar, c_ushort, c_void, mach_msg_type_number_t, natural_t, processor_flavor_t,
     processor_info_array_t,
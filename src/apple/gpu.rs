@@ -0,0 +1,7 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+pub use crate::sys::inner::gpu::*;
@@ -6,6 +6,8 @@
 
 use crate::ComponentExt;
 
+use std::path::Path;
+
 /// Dummy struct representing a component since iOS doesn't support
 /// obtaining CPU information.
 pub struct Component {}
@@ -27,5 +29,9 @@ impl ComponentExt for Component {
         ""
     }
 
+    fn get_device_path(&self) -> Option<&Path> {
+        None
+    }
+
     fn refresh(&mut self) {}
 }
@@ -0,0 +1,23 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::FanExt;
+
+/// Dummy struct representing a fan since iOS doesn't support
+/// obtaining fan information.
+pub struct Fan {}
+
+impl FanExt for Fan {
+    fn get_rpm(&self) -> u32 {
+        0
+    }
+
+    fn get_label(&self) -> &str {
+        ""
+    }
+
+    fn refresh(&mut self) {}
+}
@@ -0,0 +1,31 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::GpuExt;
+
+/// Dummy struct representing a GPU since iOS doesn't support
+/// obtaining GPU information.
+pub struct Gpu {}
+
+impl GpuExt for Gpu {
+    fn get_name(&self) -> &str {
+        ""
+    }
+
+    fn get_total_memory(&self) -> u64 {
+        0
+    }
+
+    fn get_used_memory(&self) -> u64 {
+        0
+    }
+
+    fn get_usage(&self) -> f32 {
+        0.
+    }
+
+    fn refresh(&mut self) {}
+}
@@ -4,6 +4,12 @@
 // Copyright (c) 2021 Guillaume Gomez
 //
 
+#[cfg(feature = "component")]
 pub mod component;
+#[cfg(feature = "component")]
+pub mod fan;
+#[cfg(feature = "system")]
+pub mod gpu;
 pub mod ffi {}
+#[cfg(feature = "process")]
 pub use crate::sys::app_store::process;
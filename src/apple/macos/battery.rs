@@ -0,0 +1,102 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::ffi;
+use crate::sys::inner::gpu::get_u64;
+use crate::sys::utils::get_dict_value;
+use crate::{Battery, BatteryState};
+
+use core_foundation_sys::base::{kCFAllocatorDefault, CFGetTypeID, CFRelease, CFTypeRef};
+use core_foundation_sys::number::{CFBooleanGetTypeID, CFBooleanGetValue, CFBooleanRef};
+
+/// Value returned by the battery when a time estimation isn't available yet.
+const UNKNOWN_TIME: u64 = 65_535;
+
+unsafe fn get_bool(value: CFTypeRef) -> Option<bool> {
+    if CFGetTypeID(value) == CFBooleanGetTypeID() {
+        Some(CFBooleanGetValue(value as CFBooleanRef))
+    } else {
+        None
+    }
+}
+
+/// Converts a time in minutes into seconds.
+fn get_time(minutes: Option<u64>) -> Option<u64> {
+    match minutes {
+        Some(0) | Some(UNKNOWN_TIME) | None => None,
+        Some(minutes) => Some(minutes * 60),
+    }
+}
+
+pub(crate) fn get_battery() -> Option<Battery> {
+    let mut master_port = 0;
+    let mut iterator: ffi::io_iterator_t = 0;
+
+    unsafe {
+        ffi::IOMasterPort(ffi::MACH_PORT_NULL, &mut master_port);
+
+        let matching_dictionary =
+            ffi::IOServiceMatching(b"AppleSmartBattery\0".as_ptr() as *const i8);
+        let result =
+            ffi::IOServiceGetMatchingServices(master_port, matching_dictionary, &mut iterator);
+        if result != ffi::KIO_RETURN_SUCCESS {
+            sysinfo_debug!("Error: IOServiceGetMatchingServices() = {}", result);
+            return None;
+        }
+        let entry = ffi::IOIteratorNext(iterator);
+        ffi::IOObjectRelease(iterator);
+        // Desktops don't have a battery.
+        if entry == 0 {
+            return None;
+        }
+        let mut properties = std::ptr::null_mut();
+        let result =
+            ffi::IORegistryEntryCreateCFProperties(entry, &mut properties, kCFAllocatorDefault, 0);
+        ffi::IOObjectRelease(entry);
+        if result != ffi::KIO_RETURN_SUCCESS || properties.is_null() {
+            return None;
+        }
+        let installed = get_dict_value(properties, b"BatteryInstalled\0", |v| get_bool(v));
+        let current = get_dict_value(properties, b"CurrentCapacity\0", |v| get_u64(v));
+        let max = get_dict_value(properties, b"MaxCapacity\0", |v| get_u64(v));
+        let is_charging = get_dict_value(properties, b"IsCharging\0", |v| get_bool(v));
+        let fully_charged = get_dict_value(properties, b"FullyCharged\0", |v| get_bool(v));
+        let time_to_empty = get_dict_value(properties, b"AvgTimeToEmpty\0", |v| get_u64(v));
+        let time_to_full = get_dict_value(properties, b"AvgTimeToFull\0", |v| get_u64(v));
+        let external_connected =
+            get_dict_value(properties, b"ExternalConnected\0", |v| get_bool(v));
+        CFRelease(properties as _);
+
+        if installed == Some(false) {
+            return None;
+        }
+        let percentage = match (current, max) {
+            (Some(current), Some(max)) if max > 0 => current as f32 * 100. / max as f32,
+            _ => return None,
+        };
+        let state = if fully_charged == Some(true) {
+            BatteryState::Full
+        } else if is_charging == Some(true) {
+            BatteryState::Charging
+        } else if external_connected == Some(false) {
+            BatteryState::Discharging
+        } else {
+            BatteryState::Unknown
+        };
+        Some(Battery {
+            percentage: percentage.min(100.),
+            state,
+            time_to_full: match state {
+                BatteryState::Charging => get_time(time_to_full),
+                _ => None,
+            },
+            time_to_empty: match state {
+                BatteryState::Discharging => get_time(time_to_empty),
+                _ => None,
+            },
+        })
+    }
+}
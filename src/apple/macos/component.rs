@@ -5,12 +5,16 @@
 //
 
 use crate::sys::ffi;
+#[cfg(feature = "component")]
 use crate::ComponentExt;
 
 use libc::{c_char, c_int, c_void};
 
 use std::mem;
+#[cfg(feature = "component")]
+use std::path::{Path, PathBuf};
 
+#[cfg(feature = "component")]
 pub(crate) const COMPONENTS_TEMPERATURE_IDS: &[(&str, &[i8])] = &[
     ("PECI CPU", &['T' as i8, 'C' as i8, 'X' as i8, 'C' as i8]), // PECI CPU "TCXC"
     ("PECI CPU", &['T' as i8, 'C' as i8, 'X' as i8, 'c' as i8]), // PECI CPU "TCXc"
@@ -22,11 +26,13 @@ pub(crate) const COMPONENTS_TEMPERATURE_IDS: &[(&str, &[i8])] = &[
     ("Battery", &['T' as i8, 'B' as i8, '0' as i8, 'T' as i8]),  // Battery "TB0T"
 ];
 
+#[cfg(feature = "component")]
 pub struct ComponentFFI {
     input_structure: ffi::KeyData_t,
     val: ffi::Val_t,
 }
 
+#[cfg(feature = "component")]
 impl ComponentFFI {
     fn new(key: &[i8], con: ffi::io_connect_t) -> Option<ComponentFFI> {
         unsafe { get_key_size(con, key) }
@@ -43,6 +49,7 @@ impl ComponentFFI {
 }
 
 /// Struct containing a component information (temperature and name for the moment).
+#[cfg(feature = "component")]
 pub struct Component {
     temperature: f32,
     max: f32,
@@ -50,8 +57,11 @@ pub struct Component {
     label: String,
     ffi_part: ComponentFFI,
     connection: ffi::io_connect_t,
+    // The SMC key.
+    device_path: PathBuf,
 }
 
+#[cfg(feature = "component")]
 impl Component {
     /// Creates a new `Component` with the given information.
     pub(crate) fn new(
@@ -71,10 +81,14 @@ impl Component {
                 critical,
                 ffi_part,
                 connection,
+                device_path: PathBuf::from(
+                    key.iter().map(|&c| c as u8 as char).collect::<String>(),
+                ),
             })
     }
 }
 
+#[cfg(feature = "component")]
 impl ComponentExt for Component {
     fn get_temperature(&self) -> f32 {
         self.temperature
@@ -92,6 +106,10 @@ impl ComponentExt for Component {
         &self.label
     }
 
+    fn get_device_path(&self) -> Option<&Path> {
+        Some(&self.device_path)
+    }
+
     fn refresh(&mut self) {
         if let Some(temp) = self.ffi_part.get_temperature(self.connection) {
             self.temperature = temp;
@@ -140,7 +158,7 @@ unsafe fn ultostr(s: *mut c_char, val: u32) {
     *s.offset(4) = 0;
 }
 
-unsafe fn get_key_size(
+pub(crate) unsafe fn get_key_size(
     con: ffi::io_connect_t,
     key: &[i8],
 ) -> Result<(ffi::KeyData_t, ffi::Val_t), i32> {
@@ -171,7 +189,7 @@ unsafe fn get_key_size(
     Ok((input_structure, val))
 }
 
-unsafe fn read_key(
+pub(crate) unsafe fn read_key(
     con: ffi::io_connect_t,
     input_structure: &ffi::KeyData_t,
     mut val: ffi::Val_t,
@@ -4,18 +4,20 @@
 // Copyright (c) 2017 Guillaume Gomez
 //
 
-use crate::sys::{ffi, utils};
+use crate::sys::ffi;
+use crate::sys::inner::gpu::{get_string, get_u64, search_property};
+use crate::sys::utils::{self, get_dict_value};
 use crate::utils::to_cpath;
-use crate::{Disk, DiskType};
+use crate::{Disk, DiskType, Mount};
 
-use core_foundation_sys::base::{kCFAllocatorDefault, kCFAllocatorNull, CFRelease};
-use core_foundation_sys::dictionary::{CFDictionaryGetValueIfPresent, CFDictionaryRef};
+use core_foundation_sys::base::{kCFAllocatorDefault, CFGetTypeID, CFRelease};
+use core_foundation_sys::dictionary::{CFDictionaryGetTypeID, CFDictionaryRef};
 use core_foundation_sys::number::{kCFBooleanTrue, CFBooleanRef};
 use core_foundation_sys::string as cfs;
 
-use libc::{c_char, c_int, c_void, statfs};
+use libc::{c_char, c_int, statfs};
 
-use std::ffi::{OsStr, OsString};
+use std::ffi::{CString, OsStr, OsString};
 use std::mem;
 use std::os::unix::ffi::OsStrExt;
 use std::path::PathBuf;
@@ -37,27 +39,109 @@ fn to_path(mount_path: &[c_char]) -> Option<PathBuf> {
     }
 }
 
-pub(crate) fn get_disks(session: ffi::DASessionRef) -> Vec<Disk> {
-    if session.is_null() {
-        return Vec::new();
+/// Reads the "Medium Type" from the "Device Characteristics" of the IOKit storage device
+/// the disk belongs to.
+unsafe fn get_medium_type(disk: ffi::DADiskRef) -> Option<DiskType> {
+    let media = ffi::DADiskCopyIOMedia(disk);
+    if media == 0 {
+        return None;
+    }
+    let medium_type = search_property(media, b"Device Characteristics\0", |value| {
+        if CFGetTypeID(value) == CFDictionaryGetTypeID() {
+            get_dict_value(value as CFDictionaryRef, b"Medium Type\0", |v| {
+                get_string(v)
+            })
+        } else {
+            None
+        }
+    });
+    ffi::IOObjectRelease(media);
+    match medium_type.as_deref() {
+        Some("Solid State") => Some(DiskType::SSD),
+        Some("Rotational") => Some(DiskType::HDD),
+        _ => None,
+    }
+}
+
+/// Returns the number of read and write operations of the drive the device `device_name`
+/// (like `/dev/disk1s1`) belongs to, read from the "Statistics" of its IOKit block storage driver.
+pub(crate) fn get_operation_counts(device_name: &OsStr) -> Option<(u64, u64)> {
+    let bsd_name = device_name.as_bytes();
+    let bsd_name = if bsd_name.starts_with(b"/dev/") {
+        &bsd_name[5..]
+    } else {
+        bsd_name
+    };
+    let bsd_name = CString::new(bsd_name).ok()?;
+    let mut master_port = 0;
+
+    unsafe {
+        ffi::IOMasterPort(ffi::MACH_PORT_NULL, &mut master_port);
+        // The matching dictionary is consumed by `IOServiceGetMatchingService`.
+        let matching_dictionary = ffi::IOBSDNameMatching(master_port, 0, bsd_name.as_ptr());
+        if matching_dictionary.is_null() {
+            return None;
+        }
+        let media = ffi::IOServiceGetMatchingService(master_port, matching_dictionary);
+        if media == 0 {
+            return None;
+        }
+        let counts = search_property(media, b"Statistics\0", |value| {
+            if CFGetTypeID(value) != CFDictionaryGetTypeID() {
+                return None;
+            }
+            let dict = value as CFDictionaryRef;
+            Some((
+                get_dict_value(dict, b"Operations (Read)\0", |v| get_u64(v))?,
+                get_dict_value(dict, b"Operations (Write)\0", |v| get_u64(v))?,
+            ))
+        });
+        ffi::IOObjectRelease(media);
+        counts
     }
+}
+
+/// Returns the `statfs` of every mounted file system.
+fn get_mounted_file_systems() -> Vec<statfs> {
     let count = unsafe { libc::getfsstat(ptr::null_mut(), 0, libc::MNT_NOWAIT) };
     if count < 1 {
         return Vec::new();
     }
     let bufsize = count * mem::size_of::<libc::statfs>() as c_int;
-    let mut disks = Vec::with_capacity(count as _);
-    let count = unsafe { libc::getfsstat(disks.as_mut_ptr(), bufsize, libc::MNT_NOWAIT) };
+    let mut file_systems = Vec::with_capacity(count as _);
+    let count = unsafe { libc::getfsstat(file_systems.as_mut_ptr(), bufsize, libc::MNT_NOWAIT) };
     if count < 1 {
         return Vec::new();
     }
     unsafe {
-        disks.set_len(count as _);
+        file_systems.set_len(count as _);
     }
-    disks
+    file_systems
+}
+
+pub(crate) fn get_all_mounts() -> Vec<Mount> {
+    get_mounted_file_systems()
+        .into_iter()
+        .filter_map(|c_disk| {
+            Some(Mount {
+                device: utils::cstr_to_rust(c_disk.f_mntfromname.as_ptr()).unwrap_or_default(),
+                mount_point: to_path(&c_disk.f_mntonname)?,
+                file_system: utils::cstr_to_rust(c_disk.f_fstypename.as_ptr()).unwrap_or_default(),
+                options: flags_to_mount_options(c_disk.f_flags),
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn get_disks(session: ffi::DASessionRef) -> Vec<Disk> {
+    if session.is_null() {
+        return Vec::new();
+    }
+    get_mounted_file_systems()
         .into_iter()
         .filter_map(|c_disk| {
             let mount_point = to_path(&c_disk.f_mntonname)?;
+            let device_name = to_path(&c_disk.f_mntfromname)?.into_os_string();
             unsafe {
                 let disk = ffi::DADiskCreateFromBSDName(
                     kCFAllocatorDefault as _,
@@ -77,8 +161,12 @@ pub(crate) fn get_disks(session: ffi::DASessionRef) -> Vec<Disk> {
                 };
                 let removable = get_bool_value(dict, b"DAMediaRemovable\0").unwrap_or(false);
                 let ejectable = get_bool_value(dict, b"DAMediaEjectable\0").unwrap_or(false);
-                // This is very hackish but still better than nothing...
-                let type_ = if let Some(model) = get_str_value(dict, b"DADeviceModel\0") {
+                let type_ = if let Some(type_) =
+                    get_medium_type(disk).filter(|_| !removable && !ejectable)
+                {
+                    type_
+                } else if let Some(model) = get_str_value(dict, b"DADeviceModel\0") {
+                    // This is very hackish but still better than nothing...
                     if model.contains("SSD") {
                         DiskType::SSD
                     } else if removable || ejectable {
@@ -94,33 +182,18 @@ pub(crate) fn get_disks(session: ffi::DASessionRef) -> Vec<Disk> {
                 };
 
                 CFRelease(dict as _);
-                new_disk(name, mount_point, type_)
+                new_disk(
+                    name,
+                    device_name,
+                    mount_point,
+                    type_,
+                    removable || ejectable,
+                )
             }
         })
         .collect::<Vec<_>>()
 }
 
-unsafe fn get_dict_value<T, F: FnOnce(*const c_void) -> Option<T>>(
-    dict: CFDictionaryRef,
-    key: &[u8],
-    callback: F,
-) -> Option<T> {
-    let key = ffi::CFStringCreateWithCStringNoCopy(
-        ptr::null_mut(),
-        key.as_ptr() as *const c_char,
-        cfs::kCFStringEncodingUTF8,
-        kCFAllocatorNull as _,
-    );
-    let mut value = std::ptr::null();
-    let ret = if CFDictionaryGetValueIfPresent(dict, key as _, &mut value) != 0 {
-        callback(value)
-    } else {
-        None
-    };
-    CFRelease(key as _);
-    ret
-}
-
 unsafe fn get_str_value(dict: CFDictionaryRef, key: &[u8]) -> Option<String> {
     get_dict_value(dict, key, |v| {
         let v = v as cfs::CFStringRef;
@@ -136,11 +209,18 @@ unsafe fn get_bool_value(dict: CFDictionaryRef, key: &[u8]) -> Option<bool> {
     get_dict_value(dict, key, |v| Some(v as CFBooleanRef == kCFBooleanTrue))
 }
 
-fn new_disk(name: OsString, mount_point: PathBuf, type_: DiskType) -> Option<Disk> {
+fn new_disk(
+    name: OsString,
+    device_name: OsString,
+    mount_point: PathBuf,
+    type_: DiskType,
+    is_removable: bool,
+) -> Option<Disk> {
     let mount_point_cpath = to_cpath(&mount_point);
     let mut total_space = 0;
     let mut available_space = 0;
     let mut file_system = None;
+    let mut mount_options = Vec::new();
     unsafe {
         let mut stat: statfs = mem::zeroed();
         if statfs(mount_point_cpath.as_ptr() as *const i8, &mut stat) == 0 {
@@ -154,17 +234,51 @@ fn new_disk(name: OsString, mount_point: PathBuf, type_: DiskType) -> Option<Dis
                 vec.push(*x as u8);
             }
             file_system = Some(vec);
+            mount_options = flags_to_mount_options(stat.f_flags);
         }
     }
     if total_space == 0 {
         return None;
     }
+    let (read_count, write_count) = get_operation_counts(&device_name).unwrap_or((0, 0));
     Some(Disk {
         type_,
+        is_removable,
         name,
+        device_name,
         file_system: file_system.unwrap_or_else(|| b"<Unknown>".to_vec()),
         mount_point,
         total_space,
         available_space,
+        mount_options,
+        read_count,
+        old_read_count: read_count,
+        write_count,
+        old_write_count: write_count,
     })
 }
+
+fn flags_to_mount_options(flags: u32) -> Vec<String> {
+    let mut options = vec![if flags & libc::MNT_RDONLY as u32 != 0 {
+        "ro".to_owned()
+    } else {
+        "rw".to_owned()
+    }];
+    for &(flag, name) in &[
+        (libc::MNT_SYNCHRONOUS, "sync"),
+        (libc::MNT_NOEXEC, "noexec"),
+        (libc::MNT_NOSUID, "nosuid"),
+        (libc::MNT_NODEV, "nodev"),
+        (libc::MNT_ASYNC, "async"),
+        (libc::MNT_LOCAL, "local"),
+        (libc::MNT_QUOTA, "quota"),
+        (libc::MNT_ROOTFS, "rootfs"),
+        (libc::MNT_DONTBROWSE, "nobrowse"),
+        (libc::MNT_NOATIME, "noatime"),
+    ] {
+        if flags & flag as u32 != 0 {
+            options.push(name.to_owned());
+        }
+    }
+    options
+}
@@ -0,0 +1,94 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::ffi;
+use crate::sys::inner::wifi::create_string;
+
+use core_foundation_sys::array::{
+    CFArrayGetCount, CFArrayGetTypeID, CFArrayGetValueAtIndex, CFArrayRef,
+};
+use core_foundation_sys::base::{kCFAllocatorDefault, CFGetTypeID, CFIndex, CFRelease, CFTypeRef};
+use core_foundation_sys::dictionary::{
+    CFDictionaryGetTypeID, CFDictionaryGetValue, CFDictionaryRef,
+};
+use core_foundation_sys::string::{
+    kCFStringEncodingUTF8, CFStringGetCString, CFStringGetTypeID, CFStringRef,
+};
+
+use libc::c_char;
+use std::ffi::CStr;
+use std::net::IpAddr;
+use std::ptr::{null, null_mut};
+
+unsafe fn get_ip_address(value: CFTypeRef) -> Option<IpAddr> {
+    if value.is_null() || CFGetTypeID(value) != CFStringGetTypeID() {
+        return None;
+    }
+    // Long enough for any IPv6 address and its scope.
+    let mut buffer = [0 as c_char; 128];
+    if CFStringGetCString(
+        value as CFStringRef,
+        buffer.as_mut_ptr(),
+        buffer.len() as CFIndex,
+        kCFStringEncodingUTF8,
+    ) == 0
+    {
+        return None;
+    }
+    // Link-local IPv6 addresses can be followed by the interface (`fe80::1%en0`).
+    CStr::from_ptr(buffer.as_ptr())
+        .to_str()
+        .ok()?
+        .split('%')
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Reads the `ServerAddresses` array of the global DNS configuration.
+unsafe fn get_server_addresses(store: ffi::SCDynamicStoreRef) -> Vec<IpAddr> {
+    let mut servers = Vec::new();
+    let key = match create_string("State:/Network/Global/DNS") {
+        Some(key) => key,
+        None => return servers,
+    };
+    let dns = ffi::SCDynamicStoreCopyValue(store, key);
+    CFRelease(key as _);
+    if dns.is_null() {
+        return servers;
+    }
+    if CFGetTypeID(dns) == CFDictionaryGetTypeID() {
+        if let Some(key) = create_string("ServerAddresses") {
+            let addresses = CFDictionaryGetValue(dns as CFDictionaryRef, key as _);
+            CFRelease(key as _);
+            if !addresses.is_null() && CFGetTypeID(addresses) == CFArrayGetTypeID() {
+                let addresses = addresses as CFArrayRef;
+                for i in 0..CFArrayGetCount(addresses) {
+                    servers.extend(get_ip_address(CFArrayGetValueAtIndex(addresses, i)));
+                }
+            }
+        }
+    }
+    CFRelease(dns);
+    servers
+}
+
+pub(crate) fn get_dns_servers() -> Vec<IpAddr> {
+    unsafe {
+        let name = match create_string("sysinfo") {
+            Some(name) => name,
+            None => return Vec::new(),
+        };
+        let store = ffi::SCDynamicStoreCreate(kCFAllocatorDefault, name, null(), null_mut());
+        CFRelease(name as _);
+        if store.is_null() {
+            return Vec::new();
+        }
+        let servers = get_server_addresses(store);
+        CFRelease(store);
+        servers
+    }
+}
@@ -0,0 +1,95 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::component::{get_key_size, read_key};
+use crate::sys::ffi;
+use crate::FanExt;
+
+/// Struct containing a fan information (speed and name for the moment).
+pub struct Fan {
+    rpm: u32,
+    label: String,
+    input_structure: ffi::KeyData_t,
+    val: ffi::Val_t,
+    connection: ffi::io_connect_t,
+}
+
+impl Fan {
+    /// Creates a new `Fan` with the given information.
+    pub(crate) fn new(label: String, key: &[i8], connection: ffi::io_connect_t) -> Option<Fan> {
+        let (input_structure, val) = unsafe { get_key_size(connection, key) }.ok()?;
+        get_rpm_inner(connection, &input_structure, &val).map(|rpm| Fan {
+            rpm,
+            label,
+            input_structure,
+            val,
+            connection,
+        })
+    }
+}
+
+impl FanExt for Fan {
+    fn get_rpm(&self) -> u32 {
+        self.rpm
+    }
+
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    fn refresh(&mut self) {
+        if let Some(rpm) = get_rpm_inner(self.connection, &self.input_structure, &self.val) {
+            self.rpm = rpm;
+        }
+    }
+}
+
+fn is_data_type(val: &ffi::Val_t, data_type: &[u8]) -> bool {
+    unsafe { libc::strcmp(val.data_type.as_ptr(), data_type.as_ptr() as *const i8) == 0 }
+}
+
+fn get_rpm_inner(
+    con: ffi::io_connect_t,
+    input_structure: &ffi::KeyData_t,
+    original_val: &ffi::Val_t,
+) -> Option<u32> {
+    let val = unsafe { read_key(con, input_structure, (*original_val).clone()) }.ok()?;
+    if val.data_size == 0 {
+        return None;
+    }
+    if is_data_type(&val, b"fpe2\0") {
+        // Intel machines use an unsigned fixed point value with 2 fraction bits.
+        Some((u32::from(val.bytes[0] as u8) << 6) + (u32::from(val.bytes[1] as u8) >> 2))
+    } else if is_data_type(&val, b"flt \0") {
+        // Apple Silicon machines use a float.
+        let bytes = [
+            val.bytes[0] as u8,
+            val.bytes[1] as u8,
+            val.bytes[2] as u8,
+            val.bytes[3] as u8,
+        ];
+        Some(f32::from_le_bytes(bytes) as u32)
+    } else {
+        None
+    }
+}
+
+pub(crate) fn get_fans(con: ffi::io_connect_t) -> Vec<Fan> {
+    // "FNum" contains the number of fans.
+    let nb_fans = unsafe { get_key_size(con, &['F' as i8, 'N' as i8, 'u' as i8, 'm' as i8]) }
+        .ok()
+        .and_then(|(input_structure, val)| unsafe { read_key(con, &input_structure, val) }.ok())
+        .map(|val| val.bytes[0] as u8)
+        .unwrap_or(0);
+
+    (0..nb_fans.min(10))
+        .filter_map(|i| {
+            // "F{i}Ac" contains the current speed of the fan.
+            let key = ['F' as i8, '0' as i8 + i as i8, 'A' as i8, 'c' as i8];
+            Fan::new(format!("Fan {}", i + 1), &key, con)
+        })
+        .collect()
+}
@@ -4,7 +4,18 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
-use core_foundation_sys::base::CFAllocatorRef;
+// Each binding is only used when the features of its module are enabled.
+#![cfg_attr(
+    not(all(
+        feature = "process",
+        feature = "cpu",
+        feature = "disk",
+        feature = "component"
+    )),
+    allow(dead_code, unused_imports)
+)]
+
+use core_foundation_sys::base::{CFAllocatorRef, CFTypeRef};
 use core_foundation_sys::dictionary::CFMutableDictionaryRef;
 use core_foundation_sys::string::{CFStringEncoding, CFStringRef};
 
@@ -14,7 +25,17 @@ use libc::{c_char, c_void, mach_port_t, size_t};
 
 pub(crate) use crate::sys::ffi::*;
 
+pub type SCDynamicStoreRef = CFTypeRef;
+
 extern "C" {
+    pub fn SCDynamicStoreCreate(
+        allocator: CFAllocatorRef,
+        name: CFStringRef,
+        callout: *const c_void,
+        context: *mut c_void,
+    ) -> SCDynamicStoreRef;
+    pub fn SCDynamicStoreCopyValue(store: SCDynamicStoreRef, key: CFStringRef) -> CFTypeRef;
+
     #[cfg(not(feature = "apple-app-store"))]
     pub fn mach_absolute_time() -> u64;
 
@@ -43,6 +64,15 @@ extern "C" {
     #[cfg(not(feature = "apple-app-store"))]
     pub fn proc_pid_rusage(pid: c_int, flavor: c_int, buffer: *mut c_void) -> c_int;
 
+    #[cfg(not(feature = "apple-app-store"))]
+    pub fn proc_pidfdinfo(
+        pid: c_int,
+        fd: c_int,
+        flavor: c_int,
+        buffer: *mut c_void,
+        buffersize: c_int,
+    ) -> c_int;
+
     // IOKit is only available on MacOS: https://developer.apple.com/documentation/iokit
 
     pub fn IOMasterPort(a: i32, b: *mut mach_port_t) -> i32;
@@ -55,6 +85,10 @@ extern "C" {
         c: *mut io_iterator_t,
     ) -> i32;
 
+    pub fn IOServiceGetMatchingService(a: mach_port_t, b: *mut c_void) -> io_object_t;
+
+    pub fn IOBSDNameMatching(a: mach_port_t, options: u32, bsd_name: *const c_char) -> *mut c_void;
+
     pub fn IOIteratorNext(iterator: io_iterator_t) -> io_object_t;
 
     pub fn IOObjectRelease(obj: io_object_t) -> i32;
@@ -71,14 +105,34 @@ extern "C" {
         outputStruct: *mut KeyData_t,
         outputStructCnt: *mut size_t,
     ) -> i32;
-    // pub fn IORegistryEntryCreateCFProperties(
-    //     entry: io_registry_entry_t,
-    //     properties: *mut CFMutableDictionaryRef,
-    //     allocator: CFAllocatorRef,
-    //     options: IOOptionBits,
-    // ) -> kern_return_t;
+    pub fn IORegistryEntryCreateCFProperties(
+        entry: io_registry_entry_t,
+        properties: *mut CFMutableDictionaryRef,
+        allocator: CFAllocatorRef,
+        options: u32,
+    ) -> kern_return_t;
+
+    pub fn IORegistryEntrySearchCFProperty(
+        entry: io_registry_entry_t,
+        plane: *const c_char,
+        key: CFStringRef,
+        allocator: CFAllocatorRef,
+        options: u32,
+    ) -> CFTypeRef;
     // pub fn IORegistryEntryGetName(entry: io_registry_entry_t, name: *mut c_char) -> kern_return_t;
 
+    // Objective-C runtime functions, used to communicate with `CoreWLAN`.
+
+    pub fn objc_getClass(name: *const c_char) -> id;
+
+    pub fn sel_registerName(name: *const c_char) -> SEL;
+
+    pub fn objc_msgSend();
+
+    pub fn objc_autoreleasePoolPush() -> *mut c_void;
+
+    pub fn objc_autoreleasePoolPop(pool: *mut c_void);
+
     pub fn CFStringCreateWithCStringNoCopy(
         alloc: *mut c_void,
         cStr: *const c_char,
@@ -105,6 +159,8 @@ extern "C" {
     // pub fn DADiskGetBSDName(disk: DADiskRef) -> *const c_char;
 
     pub fn DADiskCopyDescription(disk: DADiskRef) -> CFMutableDictionaryRef;
+
+    pub fn DADiskCopyIOMedia(disk: DADiskRef) -> io_registry_entry_t;
 }
 
 pub type DADiskRef = *const __DADisk;
@@ -115,6 +171,11 @@ pub type io_object_t = mach_port_t;
 pub type io_connect_t = io_object_t;
 #[allow(non_camel_case_types)]
 pub type io_iterator_t = io_object_t;
+#[allow(non_camel_case_types)]
+pub type io_registry_entry_t = io_object_t;
+#[allow(non_camel_case_types)]
+pub type id = *mut c_void;
+pub type SEL = *const c_void;
 
 pub type DASessionRef = *const __DASession;
 
@@ -174,6 +235,9 @@ pub const SMC_CMD_READ_BYTES: u8 = 5;
 
 pub const KIO_RETURN_SUCCESS: i32 = 0;
 
+pub const KIO_REGISTRY_ITERATE_RECURSIVELY: u32 = 0x00000001;
+pub const KIO_REGISTRY_ITERATE_PARENTS: u32 = 0x00000002;
+
 //pub const PROC_ALL_PIDS: c_uint = 1;
 #[cfg(not(feature = "apple-app-store"))]
 pub const PROC_PIDTBSDINFO: c_int = 3;
@@ -0,0 +1,217 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::ffi;
+use crate::sys::utils::{self, create_key, get_dict_value};
+use crate::GpuExt;
+
+use core_foundation_sys::base::CFTypeRef;
+use core_foundation_sys::base::{kCFAllocatorDefault, CFGetTypeID, CFRelease};
+use core_foundation_sys::data::{CFDataGetBytePtr, CFDataGetLength, CFDataGetTypeID, CFDataRef};
+use core_foundation_sys::dictionary::{CFDictionaryGetTypeID, CFDictionaryRef};
+use core_foundation_sys::number::{
+    kCFNumberSInt64Type, CFNumberGetTypeID, CFNumberGetValue, CFNumberRef,
+};
+use core_foundation_sys::string::{self as cfs, CFStringGetTypeID, CFStringRef};
+
+use libc::{c_char, c_void};
+
+/// Struct containing a GPU information.
+///
+/// GPUs are read from the `IOAccelerator` IOKit services.
+pub struct Gpu {
+    name: String,
+    total_memory: u64,
+    used_memory: u64,
+    usage: f32,
+    entry: ffi::io_registry_entry_t,
+}
+
+impl Gpu {
+    fn new(entry: ffi::io_registry_entry_t) -> Gpu {
+        let name = unsafe { search_property(entry, b"model\0", |v| get_string(v)) }
+            .unwrap_or_else(|| "Unknown GPU".to_owned());
+        // Only available on GPUs with dedicated memory.
+        let total_memory = unsafe { search_property(entry, b"VRAM,totalMB\0", |v| get_u64(v)) }
+            .map(|mb| mb * 1_000)
+            .unwrap_or(0);
+        let mut gpu = Gpu {
+            name,
+            total_memory,
+            used_memory: 0,
+            usage: 0.,
+            entry,
+        };
+        gpu.refresh();
+        gpu
+    }
+}
+
+impl Drop for Gpu {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::IOObjectRelease(self.entry);
+        }
+    }
+}
+
+impl GpuExt for Gpu {
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_total_memory(&self) -> u64 {
+        self.total_memory
+    }
+
+    fn get_used_memory(&self) -> u64 {
+        self.used_memory
+    }
+
+    fn get_usage(&self) -> f32 {
+        self.usage
+    }
+
+    fn refresh(&mut self) {
+        unsafe {
+            let mut properties = std::ptr::null_mut();
+            if ffi::IORegistryEntryCreateCFProperties(
+                self.entry,
+                &mut properties,
+                kCFAllocatorDefault,
+                0,
+            ) != ffi::KIO_RETURN_SUCCESS
+                || properties.is_null()
+            {
+                return;
+            }
+            if let Some(stats) = get_dict_value(properties, b"PerformanceStatistics\0", |v| {
+                if CFGetTypeID(v) == CFDictionaryGetTypeID() {
+                    Some(v as CFDictionaryRef)
+                } else {
+                    None
+                }
+            }) {
+                if let Some(usage) =
+                    get_dict_value(stats, b"Device Utilization %\0", |v| get_u64(v))
+                {
+                    self.usage = usage as f32;
+                }
+                // GPUs with dedicated memory provide "vramUsedBytes" whereas the others (like
+                // Apple Silicon ones) only provide "In use system memory".
+                if let Some(used) = get_dict_value(stats, b"vramUsedBytes\0", |v| get_u64(v))
+                    .or_else(|| get_dict_value(stats, b"In use system memory\0", |v| get_u64(v)))
+                {
+                    self.used_memory = used / 1_000;
+                }
+            }
+            CFRelease(properties as _);
+        }
+    }
+}
+
+/// Looks for the given property in the entry and its parents.
+pub(crate) unsafe fn search_property<T, F: FnOnce(CFTypeRef) -> Option<T>>(
+    entry: ffi::io_registry_entry_t,
+    key: &[u8],
+    callback: F,
+) -> Option<T> {
+    let key = create_key(key);
+    let value = ffi::IORegistryEntrySearchCFProperty(
+        entry,
+        b"IOService\0".as_ptr() as *const c_char,
+        key,
+        kCFAllocatorDefault,
+        ffi::KIO_REGISTRY_ITERATE_RECURSIVELY | ffi::KIO_REGISTRY_ITERATE_PARENTS,
+    );
+    CFRelease(key as _);
+    if value.is_null() {
+        return None;
+    }
+    let ret = callback(value);
+    CFRelease(value);
+    ret
+}
+
+pub(crate) unsafe fn get_u64(value: CFTypeRef) -> Option<u64> {
+    let type_id = CFGetTypeID(value);
+    if type_id == CFNumberGetTypeID() {
+        let mut n: i64 = 0;
+        if CFNumberGetValue(
+            value as CFNumberRef,
+            kCFNumberSInt64Type,
+            &mut n as *mut i64 as *mut c_void,
+        ) {
+            return Some(n as u64);
+        }
+    } else if type_id == CFDataGetTypeID() {
+        // Some older GPUs store numbers as raw little-endian data.
+        let len = CFDataGetLength(value as CFDataRef) as usize;
+        let ptr = CFDataGetBytePtr(value as CFDataRef);
+        if !ptr.is_null() && len > 0 && len <= 8 {
+            let mut bytes = [0u8; 8];
+            bytes[..len].copy_from_slice(std::slice::from_raw_parts(ptr, len));
+            return Some(u64::from_le_bytes(bytes));
+        }
+    }
+    None
+}
+
+pub(crate) unsafe fn get_string(value: CFTypeRef) -> Option<String> {
+    let type_id = CFGetTypeID(value);
+    if type_id == CFStringGetTypeID() {
+        let value = value as CFStringRef;
+        let len = cfs::CFStringGetLength(value);
+        // `CFStringGetCStringPtr` can return NULL, so we copy the string instead.
+        let mut buffer = vec![0 as c_char; len as usize * 4 + 1];
+        if cfs::CFStringGetCString(
+            value,
+            buffer.as_mut_ptr(),
+            buffer.len() as _,
+            cfs::kCFStringEncodingUTF8,
+        ) != 0
+        {
+            return utils::cstr_to_rust(buffer.as_ptr());
+        }
+    } else if type_id == CFDataGetTypeID() {
+        // PCI devices store their model as NUL-terminated data.
+        let len = CFDataGetLength(value as CFDataRef) as usize;
+        let ptr = CFDataGetBytePtr(value as CFDataRef);
+        if !ptr.is_null() {
+            let bytes = std::slice::from_raw_parts(ptr, len);
+            let end = bytes.iter().position(|c| *c == 0).unwrap_or(len);
+            return String::from_utf8(bytes[..end].to_vec()).ok();
+        }
+    }
+    None
+}
+
+pub(crate) fn get_gpus() -> Vec<Gpu> {
+    let mut gpus = Vec::new();
+    let mut master_port = 0;
+    let mut iterator: ffi::io_iterator_t = 0;
+
+    unsafe {
+        ffi::IOMasterPort(ffi::MACH_PORT_NULL, &mut master_port);
+
+        let matching_dictionary = ffi::IOServiceMatching(b"IOAccelerator\0".as_ptr() as *const i8);
+        let result =
+            ffi::IOServiceGetMatchingServices(master_port, matching_dictionary, &mut iterator);
+        if result != ffi::KIO_RETURN_SUCCESS {
+            sysinfo_debug!("Error: IOServiceGetMatchingServices() = {}", result);
+            return gpus;
+        }
+        loop {
+            let entry = ffi::IOIteratorNext(iterator);
+            if entry == 0 {
+                break;
+            }
+            gpus.push(Gpu::new(entry));
+        }
+        ffi::IOObjectRelease(iterator);
+    }
+    gpus
+}
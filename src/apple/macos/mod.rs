@@ -4,13 +4,31 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
+#[cfg(feature = "system")]
+pub mod battery;
+#[cfg(any(feature = "cpu", feature = "component"))]
 pub mod component;
+#[cfg(feature = "disk")]
 pub mod disk;
+#[cfg(feature = "network")]
+pub mod dns;
+#[cfg(feature = "component")]
+pub mod fan;
 pub mod ffi;
+#[cfg(feature = "system")]
+pub mod gpu;
+#[cfg(feature = "process")]
 pub mod system;
+#[cfg(feature = "network")]
+pub mod wifi;
 
+#[cfg(feature = "process")]
 #[cfg(not(feature = "apple-app-store"))]
 pub mod process;
+#[cfg(feature = "process")]
+#[cfg(not(feature = "apple-app-store"))]
+pub mod socket;
 
+#[cfg(feature = "process")]
 #[cfg(feature = "apple-app-store")]
 pub use crate::sys::app_store::process;
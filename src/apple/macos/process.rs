@@ -4,19 +4,28 @@
 // Copyright (c) 2021 Guillaume Gomez
 //
 
+use std::ffi::{CStr, OsStr};
 use std::mem::{self, MaybeUninit};
 use std::ops::Deref;
+use std::os::unix::ffi::OsStrExt;
 use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+use std::time::{Duration, Instant};
 
 use std::borrow::Borrow;
 
-use libc::{c_int, c_void, gid_t, kill, size_t, uid_t};
+use libc::{c_int, c_void, getpriority, gid_t, kill, setpriority, size_t, uid_t, PRIO_PROCESS};
 
-use crate::{DiskUsage, Pid, ProcessExt, ProcessStatus, Signal};
+use crate::{
+    AccessError, DiskUsage, Gid, NetworkUsage, Pid, PriorityError, ProcessExt, ProcessRefreshKind,
+    ProcessStatus, Signal, SocketInfo, ThreadInfo, Uid,
+};
 
 use crate::sys::ffi;
+use crate::sys::inner::socket::get_process_sockets;
 use crate::sys::process::ThreadStatus;
 use crate::sys::system::Wrap;
+use crate::utils::{get_secs_since_epoch, wait_process};
 
 /// Struct containing a process' information.
 #[derive(Clone)]
@@ -35,11 +44,22 @@ pub struct Process {
     stime: u64,
     old_utime: u64,
     old_stime: u64,
+    accumulated_cpu_time: u64,
     start_time: u64,
+    run_time: u64,
+    thread_count: u32,
+    open_files_count: Option<usize>,
+    priority: i32,
     updated: bool,
+    /// `false` if the process exited but is kept for one more refresh.
+    pub(crate) exists: bool,
+    last_error: Option<AccessError>,
     cpu_usage: f32,
-    /// User id of the process owner.
+    /// Real user id of the process owner.
     pub uid: uid_t,
+    pub(crate) effective_uid: uid_t,
+    session_id: Option<i32>,
+    tty: Option<String>,
     /// Group id of the process owner.
     pub gid: gid_t,
     pub(crate) process_status: ProcessStatus,
@@ -52,6 +72,10 @@ pub struct Process {
     pub(crate) old_written_bytes: u64,
     pub(crate) read_bytes: u64,
     pub(crate) written_bytes: u64,
+    old_minor_faults: u64,
+    old_major_faults: u64,
+    minor_faults: u64,
+    major_faults: u64,
 }
 
 impl Process {
@@ -72,9 +96,19 @@ impl Process {
             stime: 0,
             old_utime: 0,
             old_stime: 0,
+            accumulated_cpu_time: 0,
             updated: true,
+            exists: true,
+            last_error: None,
             start_time: 0,
+            run_time: 0,
+            thread_count: 0,
+            open_files_count: None,
+            priority: 0,
             uid: 0,
+            effective_uid: 0,
+            session_id: None,
+            tty: None,
             gid: 0,
             process_status: ProcessStatus::Unknown(0),
             status: None,
@@ -82,6 +116,10 @@ impl Process {
             old_written_bytes: 0,
             read_bytes: 0,
             written_bytes: 0,
+            old_minor_faults: 0,
+            old_major_faults: 0,
+            minor_faults: 0,
+            major_faults: 0,
         }
     }
 
@@ -112,9 +150,19 @@ impl Process {
             stime: 0,
             old_utime: 0,
             old_stime: 0,
+            accumulated_cpu_time: 0,
             updated: true,
+            exists: true,
+            last_error: None,
             start_time,
+            run_time: 0,
+            thread_count: 0,
+            open_files_count: None,
+            priority: 0,
             uid: 0,
+            effective_uid: 0,
+            session_id: None,
+            tty: None,
             gid: 0,
             process_status: ProcessStatus::Unknown(0),
             status: None,
@@ -122,6 +170,10 @@ impl Process {
             old_written_bytes: 0,
             read_bytes: 0,
             written_bytes: 0,
+            old_minor_faults: 0,
+            old_major_faults: 0,
+            minor_faults: 0,
+            major_faults: 0,
         }
     }
 }
@@ -144,9 +196,19 @@ impl ProcessExt for Process {
             stime: 0,
             old_utime: 0,
             old_stime: 0,
+            accumulated_cpu_time: 0,
             updated: true,
+            exists: true,
+            last_error: None,
             start_time,
+            run_time: 0,
+            thread_count: 0,
+            open_files_count: None,
+            priority: 0,
             uid: 0,
+            effective_uid: 0,
+            session_id: None,
+            tty: None,
             gid: 0,
             process_status: ProcessStatus::Unknown(0),
             status: None,
@@ -154,11 +216,24 @@ impl ProcessExt for Process {
             old_written_bytes: 0,
             read_bytes: 0,
             written_bytes: 0,
+            old_minor_faults: 0,
+            old_major_faults: 0,
+            minor_faults: 0,
+            major_faults: 0,
         }
     }
 
     fn kill(&self, signal: Signal) -> bool {
-        unsafe { kill(self.pid, signal as c_int) == 0 }
+        unsafe { kill(self.pid.0, signal as c_int) == 0 }
+    }
+
+    fn wait(&self) -> Option<ExitStatus> {
+        wait_pid(self.pid, || false)
+    }
+
+    fn wait_timeout(&self, timeout: Duration) -> Option<ExitStatus> {
+        let start = Instant::now();
+        wait_pid(self.pid, || start.elapsed() >= timeout)
     }
 
     fn name(&self) -> &str {
@@ -197,10 +272,38 @@ impl ProcessExt for Process {
         self.virtual_memory
     }
 
+    fn shared_memory(&self) -> u64 {
+        0
+    }
+
+    fn swap_memory(&self) -> u64 {
+        0
+    }
+
     fn parent(&self) -> Option<Pid> {
         self.parent
     }
 
+    fn is_kernel_thread(&self) -> bool {
+        false
+    }
+
+    fn is_thread(&self) -> bool {
+        false
+    }
+
+    fn tgid(&self) -> Pid {
+        self.pid()
+    }
+
+    fn session_id(&self) -> Option<i32> {
+        self.session_id
+    }
+
+    fn tty(&self) -> Option<&str> {
+        self.tty.as_deref()
+    }
+
     fn status(&self) -> ProcessStatus {
         self.process_status
     }
@@ -209,10 +312,89 @@ impl ProcessExt for Process {
         self.start_time
     }
 
+    fn run_time(&self) -> u64 {
+        self.run_time
+    }
+
+    fn thread_count(&self) -> u32 {
+        self.thread_count
+    }
+
+    fn threads(&self) -> &[ThreadInfo] {
+        &[]
+    }
+
+    fn open_files_count(&self) -> Option<usize> {
+        self.open_files_count
+    }
+
+    fn open_sockets(&self) -> Vec<SocketInfo> {
+        get_process_sockets(self.pid)
+    }
+
+    fn network_usage(&self) -> Option<NetworkUsage> {
+        None
+    }
+
+    fn user_id(&self) -> Option<Uid> {
+        Some(Uid(self.uid))
+    }
+
+    fn effective_user_id(&self) -> Option<Uid> {
+        Some(Uid(self.effective_uid))
+    }
+
+    fn group_id(&self) -> Option<Gid> {
+        Some(Gid(self.gid))
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn set_nice(&self, nice: i32) -> Result<(), PriorityError> {
+        if unsafe { setpriority(PRIO_PROCESS as _, self.pid.0 as _, nice) } != 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        Ok(())
+    }
+
+    fn oom_score(&self) -> Option<u32> {
+        None
+    }
+
+    fn oom_score_adj(&self) -> Option<i32> {
+        None
+    }
+
+    fn cgroup(&self) -> Option<String> {
+        None
+    }
+
+    fn exists(&self) -> bool {
+        self.exists
+    }
+
+    fn last_error(&self) -> Option<AccessError> {
+        self.last_error
+    }
+
+    fn io_wait_time(&self) -> Option<u64> {
+        None
+    }
+
+    fn sched_delay(&self) -> Option<u64> {
+        None
+    }
+
     fn cpu_usage(&self) -> f32 {
         self.cpu_usage
     }
 
+    fn accumulated_cpu_time(&self) -> u64 {
+        self.accumulated_cpu_time
+    }
+
     fn disk_usage(&self) -> DiskUsage {
         DiskUsage {
             read_bytes: self.read_bytes - self.old_read_bytes,
@@ -221,6 +403,22 @@ impl ProcessExt for Process {
             total_written_bytes: self.written_bytes,
         }
     }
+
+    fn minor_faults(&self) -> u64 {
+        self.minor_faults.saturating_sub(self.old_minor_faults)
+    }
+
+    fn major_faults(&self) -> u64 {
+        self.major_faults.saturating_sub(self.old_major_faults)
+    }
+
+    fn total_minor_faults(&self) -> u64 {
+        self.minor_faults
+    }
+
+    fn total_major_faults(&self) -> u64 {
+        self.major_faults
+    }
 }
 
 pub(crate) fn compute_cpu_usage(
@@ -230,6 +428,8 @@ pub(crate) fn compute_cpu_usage(
     user_time: u64,
     time_interval: Option<f64>,
 ) {
+    // `pti_total_user` and `pti_total_system` are expressed in nanoseconds.
+    p.accumulated_cpu_time = (task_info.pti_total_user + task_info.pti_total_system) / 1_000_000;
     if let Some(time_interval) = time_interval {
         let total_existing_time = p.old_stime + p.old_utime;
         if time_interval > 0.000001 && total_existing_time > 0 {
@@ -292,7 +492,7 @@ unsafe fn get_task_info(pid: Pid) -> libc::proc_taskinfo {
     // If it doesn't work, we just don't have memory information for this process
     // so it's "fine".
     ffi::proc_pidinfo(
-        pid,
+        pid.0,
         libc::PROC_PIDTASKINFO,
         0,
         &mut task_info as *mut libc::proc_taskinfo as *mut c_void,
@@ -306,9 +506,11 @@ pub(crate) fn update_process(
     pid: Pid,
     mut size: size_t,
     time_interval: Option<f64>,
+    refresh_kind: ProcessRefreshKind,
 ) -> Result<Option<Process>, ()> {
     let mut mib: [c_int; 3] = [libc::CTL_KERN, libc::KERN_ARGMAX, 0];
     let mut proc_args = Vec::with_capacity(size as usize);
+    let now = get_secs_since_epoch();
 
     unsafe {
         if let Some(ref mut p) = (*wrap.0.get()).get_mut(&pid) {
@@ -320,7 +522,7 @@ pub(crate) fn update_process(
             let task_info = get_task_info(pid);
             let mut thread_info = mem::zeroed::<libc::proc_threadinfo>();
             let (user_time, system_time, thread_status) = if ffi::proc_pidinfo(
-                pid,
+                pid.0,
                 libc::PROC_PIDTHREADINFO,
                 0,
                 &mut thread_info as *mut libc::proc_threadinfo as *mut c_void,
@@ -336,53 +538,56 @@ pub(crate) fn update_process(
                 (0, 0, None)
             };
             p.status = thread_status;
-            compute_cpu_usage(p, task_info, system_time, user_time, time_interval);
+            if refresh_kind.cpu() {
+                compute_cpu_usage(p, task_info, system_time, user_time, time_interval);
+            }
 
-            p.memory = task_info.pti_resident_size / 1_000;
-            p.virtual_memory = task_info.pti_virtual_size / 1_000;
-            update_proc_disk_activity(p);
+            if refresh_kind.memory() {
+                p.memory = task_info.pti_resident_size / 1_000;
+                p.virtual_memory = task_info.pti_virtual_size / 1_000;
+                update_faults(p, &task_info);
+            }
+            p.run_time = now.saturating_sub(p.start_time);
+            p.thread_count = task_info.pti_threadnum as u32;
+            p.open_files_count = get_open_files_count(pid);
+            p.priority = getpriority(PRIO_PROCESS, pid.0 as _);
+            if refresh_kind.disk_usage() {
+                update_proc_disk_activity(p);
+            }
             return Ok(None);
         }
 
         let mut info = mem::zeroed::<libc::proc_bsdinfo>();
         if ffi::proc_pidinfo(
-            pid,
+            pid.0,
             ffi::PROC_PIDTBSDINFO,
             0,
             &mut info as *mut _ as *mut _,
             mem::size_of::<libc::proc_bsdinfo>() as _,
         ) != mem::size_of::<libc::proc_bsdinfo>() as _
         {
-            let mut buffer: Vec<u8> = Vec::with_capacity(ffi::PROC_PIDPATHINFO_MAXSIZE as _);
-            match ffi::proc_pidpath(
-                pid,
-                buffer.as_mut_ptr() as *mut _,
-                ffi::PROC_PIDPATHINFO_MAXSIZE,
-            ) {
-                x if x > 0 => {
-                    buffer.set_len(x as _);
-                    let tmp = String::from_utf8_unchecked(buffer);
-                    let exe = PathBuf::from(tmp);
-                    let name = exe
-                        .file_name()
-                        .and_then(|x| x.to_str())
-                        .unwrap_or("")
-                        .to_owned();
-                    return Ok(Some(Process::new_empty(pid, exe, name)));
-                }
-                _ => {}
+            let err = AccessError::from(std::io::Error::last_os_error());
+            if let Some(exe) = get_exe(pid) {
+                let name = exe
+                    .file_name()
+                    .and_then(|x| x.to_str())
+                    .unwrap_or("")
+                    .to_owned();
+                let mut p = Process::new_empty(pid, exe, name);
+                p.last_error = Some(err);
+                return Ok(Some(p));
             }
             return Err(());
         }
         let parent = match info.pbi_ppid as i32 {
             0 => None,
-            p => Some(p),
+            p => Some(Pid(p)),
         };
 
         let ptr: *mut u8 = proc_args.as_mut_slice().as_mut_ptr();
         mib[0] = libc::CTL_KERN;
         mib[1] = libc::KERN_PROCARGS2;
-        mib[2] = pid as c_int;
+        mib[2] = pid.0 as c_int;
         /*
          * /---------------\ 0x00000000
          * | ::::::::::::: |
@@ -413,7 +618,7 @@ pub(crate) fn update_process(
          * :               :
          * \---------------/ 0xffffffff
          */
-        if libc::sysctl(
+        let args_error = if libc::sysctl(
             mib.as_mut_ptr(),
             3,
             ptr as *mut c_void,
@@ -422,8 +627,18 @@ pub(crate) fn update_process(
             0,
         ) == -1
         {
-            return Err(()); // not enough rights I assume?
-        }
+            // The kernel returns `EINVAL` when we're not allowed to read the arguments of a
+            // process owned by another user.
+            let err = std::io::Error::last_os_error();
+            size = 0;
+            Some(if err.raw_os_error() == Some(libc::EINVAL) {
+                AccessError::PermissionDenied
+            } else {
+                AccessError::from(err)
+            })
+        } else {
+            None
+        };
         let mut n_args: c_int = 0;
         libc::memcpy(
             (&mut n_args) as *mut c_int as *mut c_void,
@@ -438,7 +653,13 @@ pub(crate) fn update_process(
             while cp < ptr.add(size) && *cp != 0 {
                 cp = cp.offset(1);
             }
-            let exe = Path::new(get_unchecked_str(cp, start).as_str()).to_path_buf();
+            let mut exe = Path::new(get_unchecked_str(cp, start).as_str()).to_path_buf();
+            if !exe.is_absolute() {
+                // `exec_path` is the path given to `execve`, so it can be relative.
+                if let Some(full_exe) = get_exe(pid) {
+                    exe = full_exe;
+                }
+            }
             let name = exe
                 .file_name()
                 .and_then(|x| x.to_str())
@@ -511,33 +732,181 @@ pub(crate) fn update_process(
                 info.pbi_start_tvsec,
                 exe,
                 name,
-                parse_command_line(&cmd),
-                environ,
+                if refresh_kind.cmd() {
+                    parse_command_line(&cmd)
+                } else {
+                    Vec::new()
+                },
+                if refresh_kind.environ() {
+                    environ
+                } else {
+                    Vec::new()
+                },
                 root,
             )
         } else {
             Process::new(pid, parent, info.pbi_start_tvsec)
         };
+        p.last_error = args_error;
 
         let task_info = get_task_info(pid);
 
-        p.memory = task_info.pti_resident_size / 1_000;
-        p.virtual_memory = task_info.pti_virtual_size / 1_000;
+        if refresh_kind.memory() {
+            p.memory = task_info.pti_resident_size / 1_000;
+            p.virtual_memory = task_info.pti_virtual_size / 1_000;
+            update_faults(&mut p, &task_info);
+        }
+
+        p.run_time = now.saturating_sub(p.start_time);
+        p.thread_count = task_info.pti_threadnum as u32;
+        p.open_files_count = get_open_files_count(pid);
+        p.priority = getpriority(PRIO_PROCESS, pid.0 as _);
+        p.cwd = get_cwd(pid);
+        p.session_id = match libc::getsid(pid.0) {
+            -1 => None,
+            sid => Some(sid),
+        };
+        p.tty = get_tty(info.e_tdev);
 
-        p.uid = info.pbi_uid;
-        p.gid = info.pbi_gid;
+        if refresh_kind.user() {
+            p.uid = info.pbi_ruid;
+            p.effective_uid = info.pbi_uid;
+            p.gid = info.pbi_gid;
+        }
         p.process_status = ProcessStatus::from(info.pbi_status);
-        update_proc_disk_activity(&mut p);
+        if refresh_kind.disk_usage() {
+            update_proc_disk_activity(&mut p);
+        }
         Ok(Some(p))
     }
 }
 
+unsafe fn get_tty(dev: u32) -> Option<String> {
+    // `NODEV` means that the process has no controlling terminal.
+    if dev as libc::dev_t == -1 {
+        return None;
+    }
+    let name = libc::devname(dev as libc::dev_t, libc::S_IFCHR);
+    if name.is_null() {
+        return None;
+    }
+    Some(format!("/dev/{}", CStr::from_ptr(name).to_string_lossy()))
+}
+
+unsafe fn get_exe(pid: Pid) -> Option<PathBuf> {
+    let mut buffer: Vec<u8> = Vec::with_capacity(ffi::PROC_PIDPATHINFO_MAXSIZE as _);
+    match ffi::proc_pidpath(
+        pid.0,
+        buffer.as_mut_ptr() as *mut _,
+        ffi::PROC_PIDPATHINFO_MAXSIZE,
+    ) {
+        x if x > 0 => {
+            buffer.set_len(x as _);
+            Some(PathBuf::from(String::from_utf8_unchecked(buffer)))
+        }
+        _ => None,
+    }
+}
+
+fn get_cwd(pid: Pid) -> PathBuf {
+    unsafe {
+        let mut vnodepathinfo = mem::zeroed::<libc::proc_vnodepathinfo>();
+        // If we don't have enough rights, we just return an empty path.
+        if ffi::proc_pidinfo(
+            pid.0,
+            libc::PROC_PIDVNODEPATHINFO,
+            0,
+            &mut vnodepathinfo as *mut libc::proc_vnodepathinfo as *mut c_void,
+            mem::size_of::<libc::proc_vnodepathinfo>() as _,
+        ) <= 0
+        {
+            return PathBuf::new();
+        }
+        let ptr = vnodepathinfo.pvi_cdir.vip_path.as_ptr() as *const libc::c_char;
+        PathBuf::from(OsStr::from_bytes(CStr::from_ptr(ptr).to_bytes()))
+    }
+}
+
+/// Returns the file descriptors opened by the process.
+pub(crate) fn get_fds(pid: Pid) -> Option<Vec<libc::proc_fdinfo>> {
+    unsafe {
+        // First we get the size of the buffer we need.
+        let size = ffi::proc_pidinfo(pid.0, libc::PROC_PIDLISTFDS, 0, std::ptr::null_mut(), 0);
+        if size <= 0 {
+            return None;
+        }
+        let mut fds: Vec<libc::proc_fdinfo> =
+            Vec::with_capacity(size as usize / mem::size_of::<libc::proc_fdinfo>());
+        let size = ffi::proc_pidinfo(
+            pid.0,
+            libc::PROC_PIDLISTFDS,
+            0,
+            fds.as_mut_ptr() as *mut c_void,
+            (fds.capacity() * mem::size_of::<libc::proc_fdinfo>()) as _,
+        );
+        if size <= 0 {
+            return None;
+        }
+        fds.set_len(size as usize / mem::size_of::<libc::proc_fdinfo>());
+        Some(fds)
+    }
+}
+
+fn get_open_files_count(pid: Pid) -> Option<usize> {
+    get_fds(pid).map(|fds| fds.len())
+}
+
+/// Blocks until the process `pid` ends or until `should_stop` returns `true`.
+pub(crate) fn wait_pid<S: Fn() -> bool>(pid: Pid, should_stop: S) -> Option<ExitStatus> {
+    wait_process(
+        pid,
+        || unsafe {
+            let mut info = mem::zeroed::<libc::proc_bsdinfo>();
+            if ffi::proc_pidinfo(
+                pid.0,
+                ffi::PROC_PIDTBSDINFO,
+                0,
+                &mut info as *mut _ as *mut _,
+                mem::size_of::<libc::proc_bsdinfo>() as _,
+            ) == mem::size_of::<libc::proc_bsdinfo>() as _
+            {
+                // A zombie process is already dead, it's only waiting for its parent to reap it.
+                if info.pbi_status == libc::SZOMB {
+                    None
+                } else {
+                    Some(info.pbi_start_tvsec)
+                }
+            } else if kill(pid.0, 0) == 0
+                || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+            {
+                // `EPERM` means that the process exists but belongs to another user, so its start
+                // time isn't available.
+                Some(0)
+            } else {
+                None
+            }
+        },
+        should_stop,
+    )
+}
+
+fn update_faults(p: &mut Process, task_info: &libc::proc_taskinfo) {
+    // `pti_faults` counts every page fault, the ones which needed a page-in are the major ones.
+    let faults = task_info.pti_faults as u64;
+    let pageins = task_info.pti_pageins as u64;
+    p.old_minor_faults = p.minor_faults;
+    p.old_major_faults = p.major_faults;
+    p.minor_faults = faults.saturating_sub(pageins);
+    p.major_faults = pageins;
+}
+
 fn update_proc_disk_activity(p: &mut Process) {
     p.old_read_bytes = p.read_bytes;
     p.old_written_bytes = p.written_bytes;
 
     let mut pidrusage = MaybeUninit::<ffi::RUsageInfoV2>::uninit();
-    let retval = unsafe { ffi::proc_pid_rusage(p.pid() as c_int, 2, pidrusage.as_mut_ptr() as _) };
+    let retval =
+        unsafe { ffi::proc_pid_rusage(p.pid().0 as c_int, 2, pidrusage.as_mut_ptr() as _) };
 
     if retval < 0 {
         sysinfo_debug!("proc_pid_rusage failed: {:?}", retval);
@@ -0,0 +1,233 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+// The structures below mirror the C ones, including their names and the fields we don't read.
+#![allow(non_camel_case_types, dead_code)]
+
+use crate::sys::ffi;
+use crate::sys::inner::process::{get_fds, get_proc_list};
+use crate::{Pid, SocketInfo, SocketProtocol, SocketState};
+
+use libc::{c_int, c_void};
+
+use std::mem;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+// The following constants and structures come from `sys/proc_info.h`.
+
+const PROX_FDTYPE_SOCKET: u32 = 2;
+const PROC_PIDFDSOCKETINFO: c_int = 3;
+
+const SOCKINFO_IN: c_int = 1;
+const SOCKINFO_TCP: c_int = 2;
+
+const INI_IPV4: u8 = 0x1;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct vinfo_stat {
+    vst_dev: u32,
+    vst_mode: u16,
+    vst_nlink: u16,
+    vst_ino: u64,
+    vst_uid: u32,
+    vst_gid: u32,
+    vst_atime: i64,
+    vst_atimensec: i64,
+    vst_mtime: i64,
+    vst_mtimensec: i64,
+    vst_ctime: i64,
+    vst_ctimensec: i64,
+    vst_birthtime: i64,
+    vst_birthtimensec: i64,
+    vst_size: i64,
+    vst_blocks: i64,
+    vst_blksize: i32,
+    vst_flags: u32,
+    vst_gen: u32,
+    vst_rdev: u32,
+    vst_qspare: [i64; 2],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct sockbuf_info {
+    sbi_cc: u32,
+    sbi_hiwat: u32,
+    sbi_mbcnt: u32,
+    sbi_mbmax: u32,
+    sbi_lowat: u32,
+    sbi_flags: i16,
+    sbi_timeo: i16,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct in_sockinfo {
+    insi_fport: c_int,
+    insi_lport: c_int,
+    insi_gencnt: u64,
+    insi_flags: u32,
+    insi_flow: u32,
+    insi_vflag: u8,
+    insi_ip_ttl: u8,
+    rfu_1: u32,
+    // Either an `in6_addr` or an `in4in6_addr`, which stores the IPv4 address in its last 4
+    // bytes.
+    insi_faddr: [u8; 16],
+    insi_laddr: [u8; 16],
+    insi_v4: u8,
+    insi_v6: [u32; 3],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct tcp_sockinfo {
+    tcpsi_ini: in_sockinfo,
+    tcpsi_state: c_int,
+    tcpsi_timer: [c_int; 4],
+    tcpsi_mss: c_int,
+    tcpsi_flags: u32,
+    rfu_1: u32,
+    tcpsi_tp: u64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+union soi_proto {
+    pri_in: in_sockinfo,
+    pri_tcp: tcp_sockinfo,
+    // The biggest member of the union is `un_sockinfo`, which we don't need.
+    pri_pad: [u64; 66],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct socket_info {
+    soi_stat: vinfo_stat,
+    soi_so: u64,
+    soi_pcb: u64,
+    soi_type: c_int,
+    soi_protocol: c_int,
+    soi_family: c_int,
+    soi_options: i16,
+    soi_linger: i16,
+    soi_state: i16,
+    soi_qlen: i16,
+    soi_incqlen: i16,
+    soi_qlimit: i16,
+    soi_timeo: i16,
+    soi_error: u16,
+    soi_oobmark: u32,
+    soi_rcv: sockbuf_info,
+    soi_snd: sockbuf_info,
+    soi_kind: c_int,
+    rfu_1: u32,
+    soi_proto: soi_proto,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct proc_fileinfo {
+    fi_openflags: u32,
+    fi_status: u32,
+    fi_offset: i64,
+    fi_type: i32,
+    fi_guardflags: u32,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct socket_fdinfo {
+    pfi: proc_fileinfo,
+    psi: socket_info,
+}
+
+fn get_address(info: &in_sockinfo, addr: [u8; 16], port: c_int) -> SocketAddr {
+    // Ports are stored in network byte order.
+    let port = u16::from_be(port as u16);
+    if info.insi_vflag & INI_IPV4 != 0 {
+        let ip = Ipv4Addr::new(addr[12], addr[13], addr[14], addr[15]);
+        SocketAddr::new(IpAddr::V4(ip), port)
+    } else {
+        SocketAddr::new(IpAddr::V6(Ipv6Addr::from(addr)), port)
+    }
+}
+
+/// Converts a `TCPS_*` state from `netinet/tcp_fsm.h`.
+fn get_tcp_state(state: c_int) -> SocketState {
+    match state {
+        0 => SocketState::Closed,
+        1 => SocketState::Listen,
+        2 => SocketState::SynSent,
+        3 => SocketState::SynReceived,
+        4 => SocketState::Established,
+        5 => SocketState::CloseWait,
+        6 => SocketState::FinWait1,
+        7 => SocketState::Closing,
+        8 => SocketState::LastAck,
+        9 => SocketState::FinWait2,
+        10 => SocketState::TimeWait,
+        x => SocketState::Unknown(x as u32),
+    }
+}
+
+fn get_socket_info(pid: Pid, fd: c_int) -> Option<SocketInfo> {
+    let info = unsafe {
+        let mut info = mem::zeroed::<socket_fdinfo>();
+        if ffi::proc_pidfdinfo(
+            pid.0,
+            fd,
+            PROC_PIDFDSOCKETINFO,
+            &mut info as *mut socket_fdinfo as *mut c_void,
+            mem::size_of::<socket_fdinfo>() as _,
+        ) <= 0
+        {
+            return None;
+        }
+        info.psi
+    };
+    let (protocol, in_info, state) = match info.soi_kind {
+        SOCKINFO_TCP => {
+            let tcp_info = unsafe { info.soi_proto.pri_tcp };
+            (
+                SocketProtocol::Tcp,
+                tcp_info.tcpsi_ini,
+                Some(get_tcp_state(tcp_info.tcpsi_state)),
+            )
+        }
+        SOCKINFO_IN if info.soi_protocol == libc::IPPROTO_UDP => {
+            (SocketProtocol::Udp, unsafe { info.soi_proto.pri_in }, None)
+        }
+        _ => return None,
+    };
+    Some(SocketInfo {
+        protocol,
+        local_address: get_address(&in_info, in_info.insi_laddr, in_info.insi_lport),
+        remote_address: get_address(&in_info, in_info.insi_faddr, in_info.insi_fport),
+        state,
+        pid: Some(pid),
+    })
+}
+
+/// Returns the TCP and UDP sockets opened by the process.
+pub(crate) fn get_process_sockets(pid: Pid) -> Vec<SocketInfo> {
+    get_fds(pid)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|fd| fd.proc_fdtype == PROX_FDTYPE_SOCKET)
+        .filter_map(|fd| get_socket_info(pid, fd.proc_fd))
+        .collect()
+}
+
+/// Returns the TCP and UDP sockets opened by all the processes.
+pub(crate) fn get_all_sockets() -> Vec<SocketInfo> {
+    get_proc_list()
+        .unwrap_or_default()
+        .into_iter()
+        .flat_map(get_process_sockets)
+        .collect()
+}
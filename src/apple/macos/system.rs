@@ -29,6 +29,8 @@ pub struct SystemTimeInfo {
     old_cpu_count: natural_t,
 }
 
+// `old_cpu_load` is a buffer returned by `host_processor_info` which is only owned by this
+// `SystemTimeInfo` (it's unmapped when it's dropped or replaced).
 unsafe impl Send for SystemTimeInfo {}
 unsafe impl Sync for SystemTimeInfo {}
 
@@ -0,0 +1,119 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::ffi::{self, id, SEL};
+
+use core_foundation_sys::base::{kCFAllocatorDefault, Boolean, CFIndex, CFRelease};
+use core_foundation_sys::string::{kCFStringEncodingUTF8, CFStringCreateWithBytes, CFStringRef};
+
+use libc::{c_char, c_long, c_void};
+
+unsafe fn send(receiver: id, selector: &[u8]) -> id {
+    let f: unsafe extern "C" fn(id, SEL) -> id =
+        std::mem::transmute(ffi::objc_msgSend as *const c_void);
+    f(receiver, ffi::sel_registerName(selector.as_ptr() as _))
+}
+
+unsafe fn send_with_arg(receiver: id, selector: &[u8], arg: id) -> id {
+    let f: unsafe extern "C" fn(id, SEL, id) -> id =
+        std::mem::transmute(ffi::objc_msgSend as *const c_void);
+    f(receiver, ffi::sel_registerName(selector.as_ptr() as _), arg)
+}
+
+unsafe fn send_long(receiver: id, selector: &[u8]) -> c_long {
+    let f: unsafe extern "C" fn(id, SEL) -> c_long =
+        std::mem::transmute(ffi::objc_msgSend as *const c_void);
+    f(receiver, ffi::sel_registerName(selector.as_ptr() as _))
+}
+
+unsafe fn send_bool(receiver: id, selector: &[u8], arg: id) -> bool {
+    let f: unsafe extern "C" fn(id, SEL, id) -> c_char =
+        std::mem::transmute(ffi::objc_msgSend as *const c_void);
+    f(receiver, ffi::sel_registerName(selector.as_ptr() as _), arg) != 0
+}
+
+unsafe fn get_client() -> Option<id> {
+    let class = ffi::objc_getClass(b"CWWiFiClient\0".as_ptr() as _);
+    if class.is_null() {
+        return None;
+    }
+    let client = send(class, b"sharedWiFiClient\0");
+    if client.is_null() {
+        None
+    } else {
+        Some(client)
+    }
+}
+
+// `CFString` is toll-free bridged with `NSString`.
+pub(crate) unsafe fn create_string(s: &str) -> Option<CFStringRef> {
+    let s = CFStringCreateWithBytes(
+        kCFAllocatorDefault,
+        s.as_ptr(),
+        s.len() as CFIndex,
+        kCFStringEncodingUTF8,
+        false as Boolean,
+    );
+    if s.is_null() {
+        None
+    } else {
+        Some(s)
+    }
+}
+
+unsafe fn get_rssi(interface_name: &str) -> Option<i32> {
+    let client = get_client()?;
+    let name = create_string(interface_name)?;
+    let interface = send_with_arg(client, b"interfaceWithName:\0", name as id);
+    CFRelease(name as _);
+    if interface.is_null() {
+        return None;
+    }
+    // 0 is returned if this isn't a wireless interface or if it isn't associated.
+    match send_long(interface, b"rssiValue\0") {
+        0 => None,
+        rssi => Some(rssi as i32),
+    }
+}
+
+/// Returns the signal strength (in dBm) of the given interface using `CoreWLAN`.
+pub(crate) fn get_wifi_signal(interface_name: &str) -> Option<i32> {
+    unsafe {
+        // The objects returned by `CoreWLAN` are autoreleased.
+        let pool = ffi::objc_autoreleasePoolPush();
+        let signal = get_rssi(interface_name);
+        ffi::objc_autoreleasePoolPop(pool);
+        signal
+    }
+}
+
+unsafe fn has_interface(interface_name: &str) -> bool {
+    let client = match get_client() {
+        Some(client) => client,
+        None => return false,
+    };
+    let names = send(client, b"interfaceNames\0");
+    if names.is_null() {
+        return false;
+    }
+    let name = match create_string(interface_name) {
+        Some(name) => name,
+        None => return false,
+    };
+    let ret = send_bool(names, b"containsObject:\0", name as id);
+    CFRelease(name as _);
+    ret
+}
+
+/// Returns `true` if `CoreWLAN` knows the given interface, meaning it's a wireless one.
+pub(crate) fn is_wifi_interface(interface_name: &str) -> bool {
+    unsafe {
+        let pool = ffi::objc_autoreleasePoolPush();
+        let ret = has_interface(interface_name);
+        ffi::objc_autoreleasePoolPop(pool);
+        ret
+    }
+}
@@ -6,12 +6,20 @@
 
 use crate::sys::ffi;
 
-use libc::{self, c_char, CTL_NET, NET_RT_IFLIST2, PF_ROUTE, RTM_IFINFO2};
+use libc::{self, c_int, CTL_NET, NET_RT_FLAGS, NET_RT_IFLIST2, PF_ROUTE, RTM_IFINFO2};
 
-use std::collections::{hash_map, HashMap};
+use std::collections::{hash_map, HashMap, VecDeque};
+use std::mem::size_of;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::ptr::null_mut;
+use std::time::{Duration, Instant};
 
-use crate::{NetworkExt, NetworksExt, NetworksIter};
+use crate::common::NetworkHistory;
+use crate::utils::{get_rate, RefreshInterval};
+use crate::{
+    CountingBasis, InterfaceStatus, InterfaceType, NetworkExt, NetworksExt, NetworksIter,
+    RefreshError,
+};
 
 macro_rules! old_and_new {
     ($ty_:expr, $name:ident, $old:ident, $new_val:expr) => {{
@@ -20,119 +28,354 @@ macro_rules! old_and_new {
     }};
 }
 
+#[cfg(target_os = "macos")]
+fn get_wifi_signal(name: &str) -> Option<i32> {
+    crate::sys::inner::wifi::get_wifi_signal(name)
+}
+
+#[cfg(target_os = "ios")]
+fn get_wifi_signal(_name: &str) -> Option<i32> {
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn get_dns_servers() -> Vec<IpAddr> {
+    crate::sys::inner::dns::get_dns_servers()
+}
+
+#[cfg(target_os = "ios")]
+fn get_dns_servers() -> Vec<IpAddr> {
+    Vec::new()
+}
+
+#[cfg(target_os = "macos")]
+fn is_wifi_interface(name: &str) -> bool {
+    crate::sys::inner::wifi::is_wifi_interface(name)
+}
+
+#[cfg(target_os = "ios")]
+fn is_wifi_interface(_name: &str) -> bool {
+    false
+}
+
+/// The type values are the `IFT_*` constants from `net/if_types.h`.
+fn get_interface_type(name: &str, ifi_type: u8) -> InterfaceType {
+    match ifi_type {
+        // Wireless interfaces are also reported as ethernet ones.
+        0x06 if is_wifi_interface(name) => InterfaceType::WiFi,
+        0x06 => InterfaceType::Ethernet,
+        0x47 => InterfaceType::WiFi,
+        0x18 => InterfaceType::Loopback,
+        // IFT_GIF and IFT_STF.
+        0x37 | 0x39 => InterfaceType::Tunnel,
+        // `utun` and `ipsec` interfaces don't have a dedicated type.
+        _ if name.starts_with("utun") || name.starts_with("ipsec") => InterfaceType::Tunnel,
+        _ => InterfaceType::Other,
+    }
+}
+
+/// There is no flag telling if an interface is backed by a hardware adapter, so only the
+/// ethernet and wireless ones are considered, minus the virtual interfaces sharing these types.
+fn is_physical_interface(name: &str, ifi_type: u8) -> bool {
+    const VIRTUAL_PREFIXES: &[&str] = &[
+        "ap", "awdl", "bond", "bridge", "feth", "llw", "vlan", "vmenet",
+    ];
+
+    match ifi_type {
+        0x06 | 0x47 => !VIRTUAL_PREFIXES
+            .iter()
+            .any(|prefix| name.starts_with(prefix)),
+        _ => false,
+    }
+}
+
+/// Returns the administrative and operational statuses from the `IFF_*` flags of an interface.
+fn get_interface_statuses(flags: c_int) -> (InterfaceStatus, InterfaceStatus) {
+    if flags & libc::IFF_UP == 0 {
+        (InterfaceStatus::Down, InterfaceStatus::Down)
+    } else if flags & libc::IFF_RUNNING == 0 {
+        (InterfaceStatus::Up, InterfaceStatus::Down)
+    } else {
+        (InterfaceStatus::Up, InterfaceStatus::Up)
+    }
+}
+
+// From `net/route.h`.
+const RTF_GATEWAY: c_int = 0x2;
+const RTF_IFSCOPE: c_int = 0x100_0000;
+const RTAX_DST: usize = 0;
+const RTAX_GATEWAY: usize = 1;
+const RTAX_NETMASK: usize = 2;
+const RTAX_MAX: usize = 8;
+
+/// Reads the address of a `sockaddr_in` or `sockaddr_in6`.
+fn get_sockaddr_ip(sockaddr: &[u8]) -> Option<IpAddr> {
+    match *sockaddr.get(1)? as c_int {
+        libc::AF_INET if sockaddr.len() >= 8 => {
+            let mut ip = [0; 4];
+            ip.copy_from_slice(&sockaddr[4..8]);
+            Some(IpAddr::V4(Ipv4Addr::from(ip)))
+        }
+        libc::AF_INET6 if sockaddr.len() >= 24 => {
+            let mut ip = [0; 16];
+            ip.copy_from_slice(&sockaddr[8..24]);
+            // The kernel stores the interface index in the link-local addresses.
+            if ip[0] == 0xfe && ip[1] & 0xc0 == 0x80 {
+                ip[2] = 0;
+                ip[3] = 0;
+            }
+            Some(IpAddr::V6(Ipv6Addr::from(ip)))
+        }
+        _ => None,
+    }
+}
+
+/// Splits the socket addresses following a `rt_msghdr`. `addrs` tells which ones are present.
+fn get_route_sockaddrs(data: &[u8], addrs: c_int) -> [Option<&[u8]>; RTAX_MAX] {
+    let mut sockaddrs = [None; RTAX_MAX];
+    let mut pos = 0;
+    for (i, sockaddr) in sockaddrs.iter_mut().enumerate() {
+        if addrs & (1 << i) == 0 {
+            continue;
+        }
+        if pos >= data.len() {
+            break;
+        }
+        let len = data[pos] as usize;
+        *sockaddr = Some(&data[pos..data.len().min(pos + len)]);
+        // The addresses are aligned on 4 bytes, an empty one still takes 4 bytes.
+        pos += if len == 0 { 4 } else { (len + 3) & !3 };
+    }
+    sockaddrs
+}
+
+/// Returns the gateway of the default route of the `family` addresses, read from the routing
+/// table.
+#[allow(clippy::cast_ptr_alignment)]
+fn get_default_gateway(family: c_int) -> Option<IpAddr> {
+    let mib = &mut [CTL_NET, PF_ROUTE, 0, family, NET_RT_FLAGS, RTF_GATEWAY];
+    let mut len = 0;
+    if unsafe { libc::sysctl(mib.as_mut_ptr(), 6, null_mut(), &mut len, null_mut(), 0) } < 0 {
+        return None;
+    }
+    let mut buf = vec![0u8; len];
+    if unsafe {
+        libc::sysctl(
+            mib.as_mut_ptr(),
+            6,
+            buf.as_mut_ptr() as _,
+            &mut len,
+            null_mut(),
+            0,
+        )
+    } < 0
+    {
+        return None;
+    }
+    buf.truncate(len);
+
+    let mut pos = 0;
+    while pos + size_of::<libc::rt_msghdr>() <= buf.len() {
+        let (msg_len, flags, addrs) = unsafe {
+            let rtm = buf.as_ptr().add(pos) as *const libc::rt_msghdr;
+            (
+                (*rtm).rtm_msglen as usize,
+                (*rtm).rtm_flags,
+                (*rtm).rtm_addrs,
+            )
+        };
+        if msg_len == 0 || pos + msg_len > buf.len() {
+            break;
+        }
+        // Each interface can have its own scoped default route, they're skipped to get the
+        // primary one.
+        if flags & RTF_IFSCOPE == 0 {
+            let sockaddrs = get_route_sockaddrs(
+                &buf[pos + size_of::<libc::rt_msghdr>()..pos + msg_len],
+                addrs,
+            );
+            let is_default = sockaddrs[RTAX_DST]
+                .and_then(get_sockaddr_ip)
+                .map(|dst| dst.is_unspecified())
+                .unwrap_or(false)
+                // An empty netmask means `0.0.0.0`.
+                && sockaddrs[RTAX_NETMASK]
+                    .map(|mask| mask.iter().skip(2).all(|b| *b == 0))
+                    .unwrap_or(true);
+            if is_default {
+                if let Some(gateway) = sockaddrs[RTAX_GATEWAY].and_then(get_sockaddr_ip) {
+                    return Some(gateway);
+                }
+            }
+        }
+        pos += msg_len;
+    }
+    None
+}
+
 /// Network interfaces.
 ///
 /// ```no_run
-/// use sysinfo::{NetworksExt, System, SystemExt};
+/// use sysinfo::{Networks, NetworksExt};
 ///
-/// let s = System::new_all();
-/// let networks = s.get_networks();
+/// let mut networks = Networks::new();
+/// networks.refresh_networks_list();
 /// ```
+#[derive(Clone)]
 pub struct Networks {
     interfaces: HashMap<String, NetworkData>,
+    history_len: usize,
+    include_loopback: bool,
+    refresh_interval: RefreshInterval,
+}
+
+impl Default for Networks {
+    fn default() -> Self {
+        Networks::new()
+    }
 }
 
 impl Networks {
-    pub(crate) fn new() -> Self {
+    /// Creates an empty `Networks`, which can be used on its own. Call
+    /// [`NetworksExt::refresh_networks_list`][crate::NetworksExt::refresh_networks_list] to fill
+    /// it.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// ```
+    pub fn new() -> Self {
         Networks {
             interfaces: HashMap::new(),
+            history_len: 0,
+            include_loopback: false,
+            refresh_interval: RefreshInterval::default(),
         }
     }
 
-    #[allow(clippy::cast_ptr_alignment)]
-    fn update_networks(&mut self) {
+    fn get_interfaces_list() -> Result<Vec<u8>, RefreshError> {
         let mib = &mut [CTL_NET, PF_ROUTE, 0, 0, NET_RT_IFLIST2, 0];
         let mut len = 0;
         if unsafe { libc::sysctl(mib.as_mut_ptr(), 6, null_mut(), &mut len, null_mut(), 0) } < 0 {
-            // TODO: might be nice to put an error in here...
-            return;
+            return Err(std::io::Error::last_os_error().into());
         }
-        let mut buf = Vec::with_capacity(len);
-        unsafe {
-            buf.set_len(len);
-            if libc::sysctl(
+        let mut buf = vec![0u8; len];
+        if unsafe {
+            libc::sysctl(
                 mib.as_mut_ptr(),
                 6,
-                buf.as_mut_ptr(),
+                buf.as_mut_ptr() as _,
                 &mut len,
                 null_mut(),
                 0,
-            ) < 0
-            {
-                // TODO: might be nice to put an error in here...
-                return;
-            }
+            )
+        } < 0
+        {
+            return Err(std::io::Error::last_os_error().into());
         }
-        let buf = buf.as_ptr() as *const c_char;
-        let lim = unsafe { buf.add(len) };
-        let mut next = buf;
-        while next < lim {
+        // `len` now contains the number of bytes which were actually written.
+        buf.truncate(len);
+        Ok(buf)
+    }
+
+    /// If `add_new` is `false`, only the interfaces which are already known are updated.
+    fn update_from_buffer(&mut self, buf: &[u8], add_new: bool) {
+        let mut pos = 0;
+        while pos + size_of::<libc::if_msghdr>() <= buf.len() {
             unsafe {
-                let ifm = next as *const libc::if_msghdr;
-                next = next.offset((*ifm).ifm_msglen as isize);
-                if (*ifm).ifm_type == RTM_IFINFO2 as u8 {
+                // The messages aren't necessarily aligned in the buffer.
+                let msg = buf.as_ptr().add(pos);
+                let ifm = std::ptr::read_unaligned(msg as *const libc::if_msghdr);
+                let msg_len = ifm.ifm_msglen as usize;
+                if msg_len == 0 || pos + msg_len > buf.len() {
+                    // The message is invalid or truncated, no need to go any further.
+                    break;
+                }
+                pos += msg_len;
+                if ifm.ifm_type == RTM_IFINFO2 as u8 && msg_len >= size_of::<ffi::if_msghdr2>() {
                     // The interface (line description) name stored at ifname will be returned in
                     // the default coded character set identifier (CCSID) currently in effect for
                     // the job. If this is not a single byte CCSID, then storage greater than
                     // IFNAMSIZ (16) bytes may be needed. 22 bytes is large enough for all CCSIDs.
                     let mut name = vec![0u8; libc::IFNAMSIZ + 6];
 
-                    let if2m: *const ffi::if_msghdr2 = ifm as *const ffi::if_msghdr2;
-                    let pname =
-                        libc::if_indextoname((*if2m).ifm_index as _, name.as_mut_ptr() as _);
+                    let if2m = std::ptr::read_unaligned(msg as *const ffi::if_msghdr2);
+                    let pname = libc::if_indextoname(if2m.ifm_index as _, name.as_mut_ptr() as _);
                     if pname.is_null() {
                         continue;
                     }
                     name.set_len(libc::strlen(pname));
                     let name = String::from_utf8_unchecked(name);
+                    let wifi_signal = get_wifi_signal(&name);
+                    let (admin_status, oper_status) = get_interface_statuses(if2m.ifm_flags);
                     match self.interfaces.entry(name) {
                         hash_map::Entry::Occupied(mut e) => {
                             let mut interface = e.get_mut();
-                            old_and_new!(
-                                interface,
-                                current_out,
-                                old_out,
-                                (*if2m).ifm_data.ifi_obytes
-                            );
-                            old_and_new!(
-                                interface,
-                                current_in,
-                                old_in,
-                                (*if2m).ifm_data.ifi_ibytes
-                            );
+                            old_and_new!(interface, current_out, old_out, if2m.ifm_data.ifi_obytes);
+                            old_and_new!(interface, current_in, old_in, if2m.ifm_data.ifi_ibytes);
                             old_and_new!(
                                 interface,
                                 packets_in,
                                 old_packets_in,
-                                (*if2m).ifm_data.ifi_ipackets
+                                if2m.ifm_data.ifi_ipackets
                             );
                             old_and_new!(
                                 interface,
                                 packets_out,
                                 old_packets_out,
-                                (*if2m).ifm_data.ifi_opackets
+                                if2m.ifm_data.ifi_opackets
                             );
                             old_and_new!(
                                 interface,
                                 errors_in,
                                 old_errors_in,
-                                (*if2m).ifm_data.ifi_ierrors
+                                if2m.ifm_data.ifi_ierrors
                             );
                             old_and_new!(
                                 interface,
                                 errors_out,
                                 old_errors_out,
-                                (*if2m).ifm_data.ifi_oerrors
+                                if2m.ifm_data.ifi_oerrors
+                            );
+                            old_and_new!(
+                                interface,
+                                drops_in,
+                                old_drops_in,
+                                if2m.ifm_data.ifi_iqdrops
+                            );
+                            old_and_new!(
+                                interface,
+                                multicast_in,
+                                old_multicast_in,
+                                if2m.ifm_data.ifi_imcasts
+                            );
+                            old_and_new!(
+                                interface,
+                                last_refreshed,
+                                old_last_refreshed,
+                                Instant::now()
                             );
+                            interface.wifi_signal = wifi_signal;
+                            interface.admin_status = admin_status;
+                            interface.oper_status = oper_status;
                             interface.updated = true;
                         }
+                        hash_map::Entry::Vacant(_) if !add_new => {}
                         hash_map::Entry::Vacant(e) => {
-                            let current_in = (*if2m).ifm_data.ifi_ibytes;
-                            let current_out = (*if2m).ifm_data.ifi_obytes;
-                            let packets_in = (*if2m).ifm_data.ifi_ipackets;
-                            let packets_out = (*if2m).ifm_data.ifi_opackets;
-                            let errors_in = (*if2m).ifm_data.ifi_ierrors;
-                            let errors_out = (*if2m).ifm_data.ifi_oerrors;
+                            let current_in = if2m.ifm_data.ifi_ibytes;
+                            let current_out = if2m.ifm_data.ifi_obytes;
+                            let packets_in = if2m.ifm_data.ifi_ipackets;
+                            let packets_out = if2m.ifm_data.ifi_opackets;
+                            let errors_in = if2m.ifm_data.ifi_ierrors;
+                            let errors_out = if2m.ifm_data.ifi_oerrors;
+                            let drops_in = if2m.ifm_data.ifi_iqdrops;
+                            let multicast_in = if2m.ifm_data.ifi_imcasts;
+                            let interface_type =
+                                get_interface_type(e.key(), if2m.ifm_data.ifi_type);
+                            let is_physical =
+                                is_physical_interface(e.key(), if2m.ifm_data.ifi_type);
 
                             e.insert(NetworkData {
                                 current_in,
@@ -147,6 +390,18 @@ impl Networks {
                                 old_errors_in: errors_in,
                                 errors_out,
                                 old_errors_out: errors_out,
+                                drops_in,
+                                old_drops_in: drops_in,
+                                multicast_in,
+                                old_multicast_in: multicast_in,
+                                wifi_signal,
+                                interface_type,
+                                is_physical,
+                                admin_status,
+                                oper_status,
+                                history: NetworkHistory::new(self.history_len),
+                                last_refreshed: Instant::now(),
+                                old_last_refreshed: Instant::now(),
                                 updated: true,
                             });
                         }
@@ -163,21 +418,77 @@ impl NetworksExt for Networks {
         NetworksIter::new(self.interfaces.iter())
     }
 
-    fn refresh_networks_list(&mut self) {
+    fn try_refresh_networks_list(&mut self) -> Result<(), RefreshError> {
+        // The interfaces are only marked as not updated once the list was retrieved so they're
+        // kept if it fails.
+        let buf = Self::get_interfaces_list()?;
         for (_, data) in self.interfaces.iter_mut() {
             data.updated = false;
         }
-        self.update_networks();
+        self.update_from_buffer(&buf, true);
         self.interfaces.retain(|_, data| data.updated);
+        Ok(())
+    }
+
+    fn refresh_networks_list_no_prune(&mut self) {
+        if let Ok(buf) = Self::get_interfaces_list() {
+            self.update_from_buffer(&buf, true);
+        }
     }
 
     fn refresh(&mut self) {
-        self.update_networks();
+        if !self.refresh_interval.should_refresh() {
+            return;
+        }
+        if let Ok(buf) = Self::get_interfaces_list() {
+            self.update_from_buffer(&buf, false);
+        }
+        for data in self.interfaces.values_mut() {
+            data.history
+                .update(data.get_received(), data.get_transmitted());
+        }
+    }
+
+    fn set_history_len(&mut self, len: usize) {
+        self.history_len = len;
+        for data in self.interfaces.values_mut() {
+            data.history.set_len(len);
+        }
+    }
+
+    fn set_include_loopback(&mut self, include_loopback: bool) {
+        self.include_loopback = include_loopback;
+    }
+
+    fn is_loopback_included(&self) -> bool {
+        self.include_loopback
+    }
+
+    fn set_min_refresh_interval(&mut self, interval: Duration) {
+        self.refresh_interval.set_min_interval(interval);
+    }
+
+    fn get_default_gateway_v4(&self) -> Option<Ipv4Addr> {
+        match get_default_gateway(libc::AF_INET)? {
+            IpAddr::V4(gateway) => Some(gateway),
+            IpAddr::V6(_) => None,
+        }
+    }
+
+    fn get_default_gateway_v6(&self) -> Option<Ipv6Addr> {
+        match get_default_gateway(libc::AF_INET6)? {
+            IpAddr::V6(gateway) => Some(gateway),
+            IpAddr::V4(_) => None,
+        }
+    }
+
+    fn get_dns_servers(&self) -> Vec<IpAddr> {
+        get_dns_servers()
     }
 }
 
 /// Contains network information.
-#[derive(PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct NetworkData {
     current_in: u64,
     old_in: u64,
@@ -191,6 +502,18 @@ pub struct NetworkData {
     old_errors_in: u64,
     errors_out: u64,
     old_errors_out: u64,
+    drops_in: u64,
+    old_drops_in: u64,
+    multicast_in: u64,
+    old_multicast_in: u64,
+    wifi_signal: Option<i32>,
+    interface_type: InterfaceType,
+    is_physical: bool,
+    admin_status: InterfaceStatus,
+    oper_status: InterfaceStatus,
+    history: NetworkHistory,
+    last_refreshed: Instant,
+    old_last_refreshed: Instant,
     updated: bool,
 }
 
@@ -242,4 +565,114 @@ impl NetworkExt for NetworkData {
     fn get_total_errors_on_transmitted(&self) -> u64 {
         self.errors_out
     }
+
+    fn get_drops_on_received(&self) -> u64 {
+        self.drops_in.saturating_sub(self.old_drops_in)
+    }
+
+    fn get_total_drops_on_received(&self) -> u64 {
+        self.drops_in
+    }
+
+    fn get_drops_on_transmitted(&self) -> u64 {
+        0
+    }
+
+    fn get_total_drops_on_transmitted(&self) -> u64 {
+        0
+    }
+
+    fn get_packets_received_multicast(&self) -> u64 {
+        self.multicast_in.saturating_sub(self.old_multicast_in)
+    }
+
+    fn get_total_packets_received_multicast(&self) -> u64 {
+        self.multicast_in
+    }
+
+    fn get_received_ipv4(&self) -> u64 {
+        self.get_received()
+    }
+
+    fn get_received_ipv6(&self) -> u64 {
+        0
+    }
+
+    fn get_wifi_signal(&self) -> Option<i32> {
+        self.wifi_signal
+    }
+
+    fn get_interface_type(&self) -> InterfaceType {
+        self.interface_type
+    }
+
+    fn is_physical(&self) -> bool {
+        self.is_physical
+    }
+
+    fn get_counting_basis(&self) -> CountingBasis {
+        // `ifi_ibytes` and `ifi_obytes` include the link-layer header, `utun` and `gif`
+        // interfaces don't have one.
+        match self.interface_type {
+            InterfaceType::Tunnel => CountingBasis::Network,
+            _ => CountingBasis::LinkLayer,
+        }
+    }
+
+    fn get_admin_status(&self) -> InterfaceStatus {
+        self.admin_status
+    }
+
+    fn get_oper_status(&self) -> InterfaceStatus {
+        self.oper_status
+    }
+
+    fn get_received_history(&self) -> &VecDeque<u64> {
+        &self.history.received
+    }
+
+    fn get_transmitted_history(&self) -> &VecDeque<u64> {
+        &self.history.transmitted
+    }
+
+    fn get_last_refreshed(&self) -> Instant {
+        self.last_refreshed
+    }
+
+    fn get_received_per_second(&self) -> f64 {
+        get_rate(
+            self.get_received(),
+            self.old_last_refreshed,
+            self.last_refreshed,
+        )
+    }
+
+    fn get_transmitted_per_second(&self) -> f64 {
+        get_rate(
+            self.get_transmitted(),
+            self.old_last_refreshed,
+            self.last_refreshed,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Networks;
+    use crate::NetworksExt;
+
+    #[test]
+    fn check_empty_or_truncated_buffer() {
+        let mut networks = Networks::new();
+        networks.update_from_buffer(&[], true);
+        assert_eq!(networks.iter().count(), 0);
+        // Not even a full message header.
+        networks.update_from_buffer(&[0u8; 3], true);
+        assert_eq!(networks.iter().count(), 0);
+        // A header announcing a message larger than the buffer.
+        let mut buf = vec![0u8; 64];
+        buf[0] = 0xff;
+        networks.update_from_buffer(&buf, true);
+        assert_eq!(networks.iter().count(), 0);
+    }
 }
@@ -50,6 +50,11 @@ impl ProcessStatus {
             ProcessStatus::Unknown(_) => "Unknown",
         }
     }
+
+    /// Returns `true` if the process is currently runnable.
+    pub(crate) fn is_running(&self) -> bool {
+        matches!(*self, ProcessStatus::Run)
+    }
 }
 
 impl fmt::Display for ProcessStatus {
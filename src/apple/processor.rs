@@ -7,7 +7,7 @@
 use crate::sys::ffi;
 use crate::sys::system::get_sys_value;
 
-use crate::ProcessorExt;
+use crate::{CpuTimes, ProcessorExt};
 
 use libc::c_char;
 use std::mem;
@@ -16,6 +16,8 @@ use std::sync::Arc;
 
 pub struct UnsafePtr<T>(*mut T);
 
+// The only pointer wrapped is the `cpu_info` buffer allocated by `host_processor_info`. It's
+// owned by `ProcessorData`, which deallocates it when it's dropped, and is only read.
 unsafe impl<T> Send for UnsafePtr<T> {}
 unsafe impl<T> Sync for UnsafePtr<T> {}
 
@@ -61,6 +63,8 @@ pub struct Processor {
     frequency: u64,
     vendor_id: String,
     brand: String,
+    temperature: Option<f32>,
+    times: CpuTimes,
 }
 
 impl Processor {
@@ -78,13 +82,24 @@ impl Processor {
             frequency,
             vendor_id,
             brand,
+            temperature: None,
+            times: CpuTimes::default(),
         }
     }
 
+    #[cfg(target_os = "macos")]
+    pub(crate) fn set_temperature(&mut self, temperature: Option<f32>) {
+        self.temperature = temperature;
+    }
+
     pub(crate) fn set_cpu_usage(&mut self, cpu_usage: f32) {
         self.cpu_usage = cpu_usage;
     }
 
+    pub(crate) fn set_times(&mut self, times: CpuTimes) {
+        self.times = times;
+    }
+
     pub(crate) fn update(&mut self, cpu_usage: f32, processor_data: Arc<ProcessorData>) {
         self.cpu_usage = cpu_usage;
         self.processor_data = processor_data;
@@ -116,6 +131,27 @@ impl ProcessorExt for Processor {
     fn get_brand(&self) -> &str {
         &self.brand
     }
+
+    fn get_temperature(&self) -> Option<f32> {
+        self.temperature
+    }
+
+    fn get_times(&self) -> CpuTimes {
+        self.times
+    }
+}
+
+/// Reads the ticks of the processor whose information starts at `offset` in `cpu_info`.
+pub(crate) unsafe fn get_processor_times(cpu_info: *const i32, offset: isize) -> CpuTimes {
+    // The ticks are unsigned integers.
+    let get = |state: i32| *cpu_info.offset(offset + state as isize) as u32 as u64;
+    CpuTimes {
+        user: get(libc::CPU_STATE_USER),
+        nice: get(libc::CPU_STATE_NICE),
+        system: get(libc::CPU_STATE_SYSTEM),
+        idle: get(libc::CPU_STATE_IDLE),
+        ..CpuTimes::default()
+    }
 }
 
 pub fn get_cpu_frequency() -> u64 {
@@ -185,6 +221,10 @@ pub fn init_processors(port: libc::mach_port_t) -> (Processor, Vec<Processor>) {
                     + *cpu_info
                         .offset((libc::CPU_STATE_MAX * i) as isize + libc::CPU_STATE_IDLE as isize);
                 p.set_cpu_usage(in_use as f32 / total as f32 * 100.);
+                p.set_times(get_processor_times(
+                    cpu_info,
+                    (libc::CPU_STATE_MAX * i) as isize,
+                ));
                 pourcent += p.get_cpu_usage();
                 processors.push(p);
             }
@@ -198,10 +238,20 @@ pub fn init_processors(port: libc::mach_port_t) -> (Processor, Vec<Processor>) {
         brand,
     );
     global_processor.set_cpu_usage(pourcent / processors.len() as f32);
+    global_processor.set_times(sum_times(&processors));
 
     (global_processor, processors)
 }
 
+/// Returns the sum of the processors' ticks.
+pub(crate) fn sum_times(processors: &[Processor]) -> CpuTimes {
+    let mut times = CpuTimes::default();
+    for processor in processors {
+        times += processor.times;
+    }
+    times
+}
+
 fn get_sysctl_str(s: &[u8]) -> String {
     let mut len = 0;
 
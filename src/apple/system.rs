@@ -4,26 +4,59 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
+#[cfg(feature = "component")]
 use crate::sys::component::Component;
+#[cfg(feature = "disk")]
 use crate::sys::disk::*;
+#[cfg(feature = "component")]
+use crate::sys::fan::Fan;
 use crate::sys::ffi;
+use crate::sys::gpu::Gpu;
+#[cfg(feature = "network")]
 use crate::sys::network::Networks;
+#[cfg(feature = "process")]
 use crate::sys::process::*;
+#[cfg(feature = "cpu")]
 use crate::sys::processor::*;
-#[cfg(target_os = "macos")]
+#[cfg(all(target_os = "macos", feature = "disk"))]
 use core_foundation_sys::base::{kCFAllocatorDefault, CFRelease};
 
-use crate::{LoadAvg, Pid, ProcessorExt, RefreshKind, SystemExt, User};
-
-#[cfg(all(target_os = "macos", not(feature = "apple-app-store")))]
+#[cfg(feature = "cpu")]
+use crate::utils::RefreshInterval;
+#[cfg(feature = "disk")]
+use crate::Mount;
+use crate::{Battery, LoadAvg, RefreshKind, SwapDevice, SwapType, SystemExt, User, Virtualization};
+#[cfg(feature = "cpu")]
+use crate::{CacheInfo, CacheType, ProcessorExt};
+#[cfg(feature = "process")]
+use crate::{Pid, ProcessRefreshKind, ProcessWatcher, SocketInfo};
+
+#[cfg(all(
+    target_os = "macos",
+    feature = "process",
+    not(feature = "apple-app-store")
+))]
 use crate::ProcessExt;
 
+#[cfg(feature = "process")]
 use std::cell::UnsafeCell;
+#[cfg(feature = "process")]
 use std::collections::HashMap;
 use std::mem;
+#[cfg(feature = "disk")]
+use std::path::Path;
+#[cfg(feature = "process")]
+use std::process::ExitStatus;
+#[cfg(feature = "cpu")]
 use std::sync::Arc;
-
-#[cfg(all(target_os = "macos", not(feature = "apple-app-store")))]
+#[cfg(feature = "cpu")]
+use std::time::Duration;
+
+#[cfg(all(
+    target_os = "macos",
+    feature = "process",
+    not(feature = "apple-app-store")
+))]
 use libc::size_t;
 
 use libc::{
@@ -32,42 +65,64 @@ use libc::{
 
 /// Structs containing system's information.
 pub struct System {
+    #[cfg(feature = "process")]
     process_list: HashMap<Pid, Process>,
     mem_total: u64,
     mem_free: u64,
     mem_available: u64,
     swap_total: u64,
     swap_free: u64,
+    swap_devices: Vec<SwapDevice>,
+    #[cfg(feature = "cpu")]
     global_processor: Processor,
+    #[cfg(feature = "cpu")]
     processors: Vec<Processor>,
+    #[cfg(feature = "cpu")]
+    cpu_refresh_interval: RefreshInterval,
     page_size_kb: u64,
+    #[cfg(feature = "component")]
     components: Vec<Component>,
+    #[cfg(feature = "component")]
+    fans: Vec<Fan>,
+    gpus: Vec<Gpu>,
     // Used to get CPU information, not supported on iOS.
-    #[cfg(target_os = "macos")]
+    #[cfg(all(target_os = "macos", any(feature = "cpu", feature = "component")))]
     connection: Option<ffi::io_connect_t>,
+    #[cfg(feature = "disk")]
     disks: Vec<Disk>,
+    #[cfg(target_os = "macos")]
+    #[cfg(feature = "disk")]
+    disk_filter: Option<crate::utils::DiskFilter>,
+    #[cfg(all(target_os = "macos", not(feature = "apple-app-store")))]
+    #[cfg(feature = "process")]
+    keep_exited_processes: bool,
+    #[cfg(feature = "network")]
     networks: Networks,
     port: mach_port_t,
     users: Vec<User>,
+    #[cfg(feature = "process")]
+    sockets: Vec<SocketInfo>,
     boot_time: u64,
     // Used to get disk information, to be more specific, it's needed by the
     // DADiskCreateFromVolumePath function. Not supported on iOS.
     #[cfg(target_os = "macos")]
+    #[cfg(feature = "disk")]
     session: ffi::SessionWrap,
     #[cfg(target_os = "macos")]
+    #[cfg(feature = "process")]
     clock_info: Option<crate::sys::macos::system::SystemTimeInfo>,
 }
 
 impl Drop for System {
     fn drop(&mut self) {
-        #[cfg(target_os = "macos")]
+        #[cfg(all(target_os = "macos", any(feature = "cpu", feature = "component")))]
         if let Some(conn) = self.connection {
             unsafe {
                 ffi::IOServiceClose(conn);
             }
         }
 
-        #[cfg(target_os = "macos")]
+        #[cfg(all(target_os = "macos", feature = "disk"))]
         if !self.session.0.is_null() {
             unsafe {
                 CFRelease(self.session.0 as _);
@@ -76,12 +131,43 @@ impl Drop for System {
     }
 }
 
+#[cfg(all(target_os = "macos", feature = "cpu"))]
+impl System {
+    fn refresh_processors_temperature(&mut self) {
+        let con = match self.connection {
+            Some(con) => con,
+            None => return,
+        };
+        // The logical processors of a same core are next to each other.
+        let physical_core_count = self.get_physical_core_count().unwrap_or(0).max(1);
+        let threads_per_core = (self.processors.len() / physical_core_count).max(1);
+        for (pos, proc_) in self.processors.iter_mut().enumerate() {
+            // The per-core keys go from "TC0C" to "TC9C".
+            let temperature =
+                std::char::from_digit((pos / threads_per_core) as u32, 10).and_then(|core| {
+                    crate::sys::inner::component::get_temperature(
+                        con,
+                        &['T' as i8, 'C' as i8, core as i8, 'C' as i8, 0],
+                    )
+                });
+            proc_.set_temperature(temperature);
+        }
+    }
+}
+
+#[cfg(feature = "process")]
 pub(crate) struct Wrap<'a>(pub UnsafeCell<&'a mut HashMap<Pid, Process>>);
 
+#[cfg(feature = "process")]
 unsafe impl<'a> Send for Wrap<'a> {}
+#[cfg(feature = "process")]
 unsafe impl<'a> Sync for Wrap<'a> {}
 
-#[cfg(all(target_os = "macos", not(feature = "apple-app-store")))]
+#[cfg(all(
+    target_os = "macos",
+    feature = "process",
+    not(feature = "apple-app-store")
+))]
 impl System {
     fn clear_procs(&mut self) {
         use crate::sys::macos::process;
@@ -89,7 +175,12 @@ impl System {
         let mut to_delete = Vec::new();
 
         for (pid, mut proc_) in &mut self.process_list {
-            if !process::has_been_updated(&mut proc_) {
+            if process::has_been_updated(&mut proc_) {
+                proc_.exists = true;
+            } else if self.keep_exited_processes && proc_.exists {
+                // It's removed at the next refresh.
+                proc_.exists = false;
+            } else {
                 to_delete.push(*pid);
             }
         }
@@ -126,35 +217,102 @@ fn boot_time() -> u64 {
 impl SystemExt for System {
     fn new_with_specifics(refreshes: RefreshKind) -> System {
         let port = unsafe { ffi::mach_host_self() };
+        #[cfg(feature = "cpu")]
         let (global_processor, processors) = init_processors(port);
 
         let mut s = System {
+            #[cfg(feature = "process")]
             process_list: HashMap::with_capacity(200),
             mem_total: 0,
             mem_free: 0,
             mem_available: 0,
             swap_total: 0,
             swap_free: 0,
+            swap_devices: Vec::new(),
+            #[cfg(feature = "cpu")]
             global_processor,
+            #[cfg(feature = "cpu")]
             processors,
+            #[cfg(feature = "cpu")]
+            cpu_refresh_interval: RefreshInterval::default(),
             page_size_kb: unsafe { sysconf(_SC_PAGESIZE) as u64 / 1_000 },
+            #[cfg(feature = "component")]
             components: Vec::with_capacity(2),
-            #[cfg(target_os = "macos")]
+            #[cfg(feature = "component")]
+            fans: Vec::new(),
+            gpus: Vec::new(),
+            #[cfg(all(target_os = "macos", any(feature = "cpu", feature = "component")))]
             connection: get_io_service_connection(),
+            #[cfg(feature = "disk")]
             disks: Vec::with_capacity(1),
+            #[cfg(target_os = "macos")]
+            #[cfg(feature = "disk")]
+            disk_filter: None,
+            #[cfg(all(target_os = "macos", not(feature = "apple-app-store")))]
+            #[cfg(feature = "process")]
+            keep_exited_processes: false,
+            #[cfg(feature = "network")]
             networks: Networks::new(),
             port,
             users: Vec::new(),
+            #[cfg(feature = "process")]
+            sockets: Vec::new(),
             boot_time: boot_time(),
             #[cfg(target_os = "macos")]
+            #[cfg(feature = "disk")]
             session: ffi::SessionWrap(::std::ptr::null_mut()),
             #[cfg(target_os = "macos")]
+            #[cfg(feature = "process")]
             clock_info: crate::sys::macos::system::SystemTimeInfo::new(port),
         };
         s.refresh_specifics(refreshes);
         s
     }
 
+    #[cfg(feature = "cpu")]
+    fn refresh_cpu_over(&mut self, window: Duration) {
+        self.cpu_refresh_interval.reset();
+        self.refresh_cpu();
+        std::thread::sleep(window);
+        self.cpu_refresh_interval.reset();
+        self.refresh_cpu();
+    }
+
+    #[cfg(feature = "cpu")]
+    fn set_min_refresh_interval(&mut self, interval: Duration) {
+        self.cpu_refresh_interval.set_min_interval(interval);
+    }
+
+    #[cfg(feature = "process")]
+    fn set_include_threads(&mut self, _include: bool) {}
+
+    #[cfg(any(target_os = "ios", feature = "apple-app-store"))]
+    #[cfg(feature = "process")]
+    fn set_keep_exited_processes(&mut self, _keep: bool) {}
+
+    #[cfg(all(target_os = "macos", not(feature = "apple-app-store")))]
+    #[cfg(feature = "process")]
+    fn set_keep_exited_processes(&mut self, keep: bool) {
+        self.keep_exited_processes = keep;
+    }
+
+    #[cfg(target_os = "ios")]
+    #[cfg(feature = "disk")]
+    fn set_disk_filter<F>(&mut self, _filter: F)
+    where
+        F: Fn(&Path, &str) -> bool + Send + Sync + 'static,
+    {
+    }
+
+    #[cfg(target_os = "macos")]
+    #[cfg(feature = "disk")]
+    fn set_disk_filter<F>(&mut self, filter: F)
+    where
+        F: Fn(&Path, &str) -> bool + Send + Sync + 'static,
+    {
+        self.disk_filter = Some(Box::new(filter));
+    }
+
     fn refresh_memory(&mut self) {
         let mut mib = [0, 0];
 
@@ -172,6 +330,7 @@ impl SystemExt for System {
                 self.swap_total = xs.xsu_total / 1_000;
                 self.swap_free = xs.xsu_avail / 1_000;
             }
+            self.swap_devices = get_swap_devices(xs.xsu_used);
             // get ram info
             if self.mem_total < 1 {
                 get_sys_value(
@@ -213,9 +372,11 @@ impl SystemExt for System {
     }
 
     #[cfg(target_os = "ios")]
+    #[cfg(feature = "component")]
     fn refresh_components_list(&mut self) {}
 
     #[cfg(target_os = "macos")]
+    #[cfg(feature = "component")]
     fn refresh_components_list(&mut self) {
         if let Some(con) = self.connection {
             self.components.clear();
@@ -230,10 +391,23 @@ impl SystemExt for System {
                     self.components.push(c);
                 }
             }
+            self.fans = crate::apple::fan::get_fans(con);
         }
     }
 
+    #[cfg(target_os = "ios")]
+    fn refresh_gpus_list(&mut self) {}
+
+    #[cfg(target_os = "macos")]
+    fn refresh_gpus_list(&mut self) {
+        self.gpus = crate::apple::gpu::get_gpus();
+    }
+
+    #[cfg(feature = "cpu")]
     fn refresh_cpu(&mut self) {
+        if !self.cpu_refresh_interval.should_refresh() {
+            return;
+        }
         // get processor values
         let mut num_cpu_u = 0u32;
         let mut cpu_info: *mut i32 = std::ptr::null_mut();
@@ -272,6 +446,7 @@ impl SystemExt for System {
                                 .cpu_info
                                 .offset(add as isize + libc::CPU_STATE_IDLE as isize));
                     proc_.update(in_use as f32 / total as f32 * 100., Arc::clone(&proc_data));
+                    proc_.set_times(get_processor_times(cpu_info, add as isize));
                     pourcent += proc_.get_cpu_usage();
 
                     add += libc::CPU_STATE_MAX;
@@ -280,13 +455,18 @@ impl SystemExt for System {
         }
         self.global_processor
             .set_cpu_usage(pourcent / self.processors.len() as f32);
+        self.global_processor.set_times(sum_times(&self.processors));
+        #[cfg(target_os = "macos")]
+        self.refresh_processors_temperature();
     }
 
     #[cfg(any(target_os = "ios", feature = "apple-app-store"))]
-    fn refresh_processes(&mut self) {}
+    #[cfg(feature = "process")]
+    fn refresh_processes_specifics(&mut self, _refresh_kind: ProcessRefreshKind) {}
 
     #[cfg(all(target_os = "macos", not(feature = "apple-app-store")))]
-    fn refresh_processes(&mut self) {
+    #[cfg(feature = "process")]
+    fn refresh_processes_specifics(&mut self, refresh_kind: ProcessRefreshKind) {
         use crate::utils::into_iter;
 
         let count = unsafe { ffi::proc_listallpids(::std::ptr::null_mut(), 0) };
@@ -305,7 +485,13 @@ impl SystemExt for System {
 
                 into_iter(pids)
                     .flat_map(|pid| {
-                        match update_process(wrap, pid, arg_max as size_t, time_interval) {
+                        match update_process(
+                            wrap,
+                            pid,
+                            arg_max as size_t,
+                            time_interval,
+                            refresh_kind,
+                        ) {
                             Ok(x) => x,
                             Err(_) => None,
                         }
@@ -320,18 +506,33 @@ impl SystemExt for System {
     }
 
     #[cfg(any(target_os = "ios", feature = "apple-app-store"))]
+    #[cfg(feature = "process")]
     fn refresh_process(&mut self, _: Pid) -> bool {
         false
     }
 
     #[cfg(all(target_os = "macos", not(feature = "apple-app-store")))]
+    #[cfg(feature = "process")]
     fn refresh_process(&mut self, pid: Pid) -> bool {
+        // Signal 0 only checks that the process exists.
+        if unsafe { libc::kill(pid.0, 0) } != 0
+            && std::io::Error::last_os_error().raw_os_error() == Some(libc::ESRCH)
+        {
+            self.process_list.remove(&pid);
+            return false;
+        }
         let arg_max = get_arg_max();
         let port = self.port;
         let time_interval = self.clock_info.as_mut().map(|c| c.get_time_interval(port));
         match {
             let wrap = Wrap(UnsafeCell::new(&mut self.process_list));
-            update_process(&wrap, pid, arg_max as size_t, time_interval)
+            update_process(
+                &wrap,
+                pid,
+                arg_max as size_t,
+                time_interval,
+                ProcessRefreshKind::everything(),
+            )
         } {
             Ok(Some(p)) => {
                 self.process_list.insert(p.pid(), p);
@@ -343,40 +544,66 @@ impl SystemExt for System {
     }
 
     #[cfg(target_os = "ios")]
+    #[cfg(feature = "disk")]
     fn refresh_disks_list(&mut self) {}
 
     #[cfg(target_os = "macos")]
+    #[cfg(feature = "disk")]
     fn refresh_disks_list(&mut self) {
         if self.session.0.is_null() {
             self.session.0 = unsafe { ffi::DASessionCreate(kCFAllocatorDefault as _) };
         }
         self.disks = get_disks(self.session.0);
+        crate::utils::filter_disks(&mut self.disks, &self.disk_filter);
     }
 
     fn refresh_users_list(&mut self) {
         self.users = crate::apple::users::get_users_list();
     }
 
+    #[cfg(any(target_os = "ios", feature = "apple-app-store"))]
+    #[cfg(feature = "process")]
+    fn refresh_sockets(&mut self) {}
+
+    #[cfg(all(target_os = "macos", not(feature = "apple-app-store")))]
+    #[cfg(feature = "process")]
+    fn refresh_sockets(&mut self) {
+        self.sockets = crate::sys::inner::socket::get_all_sockets();
+    }
+
     // COMMON PART
     //
     // Need to be moved into a "common" file to avoid duplication.
 
+    #[cfg(feature = "process")]
     fn get_processes(&self) -> &HashMap<Pid, Process> {
         &self.process_list
     }
 
+    #[cfg(feature = "process")]
     fn get_process(&self, pid: Pid) -> Option<&Process> {
         self.process_list.get(&pid)
     }
 
+    #[cfg(feature = "process")]
+    fn watch_process_exit<F>(&self, pid: Pid, f: F) -> ProcessWatcher
+    where
+        F: FnOnce(Option<ExitStatus>) + Send + 'static,
+    {
+        ProcessWatcher::new(move |should_stop| wait_pid(pid, should_stop), f)
+    }
+
+    #[cfg(feature = "cpu")]
     fn get_global_processor_info(&self) -> &Processor {
         &self.global_processor
     }
 
+    #[cfg(feature = "cpu")]
     fn get_processors(&self) -> &[Processor] {
         &self.processors
     }
 
+    #[cfg(feature = "cpu")]
     fn get_physical_core_count(&self) -> Option<usize> {
         let mut physical_core_count = 0;
 
@@ -393,10 +620,22 @@ impl SystemExt for System {
         }
     }
 
+    #[cfg(feature = "cpu")]
+    fn get_context_switches(&self) -> u64 {
+        0
+    }
+
+    #[cfg(feature = "cpu")]
+    fn get_interrupts(&self) -> u64 {
+        0
+    }
+
+    #[cfg(feature = "network")]
     fn get_networks(&self) -> &Networks {
         &self.networks
     }
 
+    #[cfg(feature = "network")]
     fn get_networks_mut(&mut self) -> &mut Networks {
         &mut self.networks
     }
@@ -430,22 +669,60 @@ impl SystemExt for System {
         self.swap_total - self.swap_free
     }
 
+    fn get_swap_devices(&self) -> &[SwapDevice] {
+        &self.swap_devices
+    }
+
+    #[cfg(feature = "component")]
     fn get_components(&self) -> &[Component] {
         &self.components
     }
 
+    #[cfg(feature = "component")]
     fn get_components_mut(&mut self) -> &mut [Component] {
         &mut self.components
     }
 
+    #[cfg(feature = "component")]
+    fn get_fans(&self) -> &[Fan] {
+        &self.fans
+    }
+
+    #[cfg(feature = "component")]
+    fn get_fans_mut(&mut self) -> &mut [Fan] {
+        &mut self.fans
+    }
+
+    fn get_gpus(&self) -> &[Gpu] {
+        &self.gpus
+    }
+
+    fn get_gpus_mut(&mut self) -> &mut [Gpu] {
+        &mut self.gpus
+    }
+
+    #[cfg(feature = "disk")]
     fn get_disks(&self) -> &[Disk] {
         &self.disks
     }
 
+    #[cfg(feature = "disk")]
     fn get_disks_mut(&mut self) -> &mut [Disk] {
         &mut self.disks
     }
 
+    #[cfg(target_os = "ios")]
+    #[cfg(feature = "disk")]
+    fn get_all_mounts(&self) -> Vec<Mount> {
+        Vec::new()
+    }
+
+    #[cfg(target_os = "macos")]
+    #[cfg(feature = "disk")]
+    fn get_all_mounts(&self) -> Vec<Mount> {
+        get_all_mounts()
+    }
+
     fn get_uptime(&self) -> u64 {
         let csec = unsafe { libc::time(::std::ptr::null_mut()) };
 
@@ -464,10 +741,25 @@ impl SystemExt for System {
         }
     }
 
+    #[cfg(target_os = "ios")]
+    fn get_battery(&self) -> Option<Battery> {
+        None
+    }
+
+    #[cfg(target_os = "macos")]
+    fn get_battery(&self) -> Option<Battery> {
+        crate::sys::inner::battery::get_battery()
+    }
+
     fn get_users(&self) -> &[User] {
         &self.users
     }
 
+    #[cfg(feature = "process")]
+    fn get_sockets(&self) -> &[SocketInfo] {
+        &self.sockets
+    }
+
     fn get_boot_time(&self) -> u64 {
         self.boot_time
     }
@@ -479,13 +771,8 @@ impl SystemExt for System {
     fn get_long_os_version(&self) -> Option<String> {
         #[cfg(target_os = "macos")]
         let friendly_name = match self.get_os_version().unwrap_or_default() {
-            f_n if f_n.starts_with("10.16")
-                | f_n.starts_with("11.0")
-                | f_n.starts_with("11.1")
-                | f_n.starts_with("11.2") =>
-            {
-                "Big Sur"
-            }
+            f_n if f_n.starts_with("12.") => "Monterey",
+            f_n if f_n.starts_with("10.16") | f_n.starts_with("11.") => "Big Sur",
             f_n if f_n.starts_with("10.15") => "Catalina",
             f_n if f_n.starts_with("10.14") => "Mojave",
             f_n if f_n.starts_with("10.13") => "High Sierra",
@@ -527,34 +814,57 @@ impl SystemExt for System {
     }
 
     fn get_os_version(&self) -> Option<String> {
-        unsafe {
-            // get the size for the buffer first
-            let mut size = 0;
-            if get_sys_value_by_name(b"kern.osproductversion\0", &mut size, std::ptr::null_mut())
-                && size > 0
-            {
-                // now create a buffer with the size and get the real value
-                let mut buf = vec![0_u8; size as usize];
-
-                if get_sys_value_by_name(
-                    b"kern.osproductversion\0",
-                    &mut size,
-                    buf.as_mut_ptr() as *mut c_void,
-                ) {
-                    if let Some(pos) = buf.iter().position(|x| *x == 0) {
-                        // Shrink buffer to terminate the null bytes
-                        buf.resize(pos, 0);
-                    }
-
-                    String::from_utf8(buf).ok()
-                } else {
-                    // getting the system value failed
-                    None
-                }
-            } else {
-                // getting the system value failed, or did not return a buffer size
-                None
-            }
+        let version = get_os_product_version();
+        // `kern.osproductversion` is only available starting macOS 10.13.4.
+        #[cfg(target_os = "macos")]
+        let version = version.or_else(get_os_version_from_plist);
+        version
+    }
+
+    #[cfg(feature = "cpu")]
+    fn get_cpu_arch(&self) -> Option<String> {
+        let mut raw = mem::MaybeUninit::<libc::utsname>::zeroed();
+
+        if unsafe { libc::uname(raw.as_mut_ptr()) } == 0 {
+            let info = unsafe { raw.assume_init() };
+
+            let machine = info
+                .machine
+                .iter()
+                .filter(|c| **c != 0)
+                .map(|c| *c as u8 as char)
+                .collect::<String>();
+
+            Some(machine)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "cpu")]
+    fn get_cpu_features(&self) -> Vec<String> {
+        get_cpu_features()
+    }
+
+    #[cfg(feature = "cpu")]
+    fn get_cpu_caches(&self) -> Vec<CacheInfo> {
+        get_cpu_caches()
+    }
+
+    fn get_virtualization(&self) -> Virtualization {
+        let mut value: c_int = 0;
+        let mut len = mem::size_of::<c_int>();
+        if unsafe {
+            get_sys_value_by_name(
+                b"kern.hv_vmm_present\0",
+                &mut len,
+                &mut value as *mut c_int as *mut c_void,
+            )
+        } && value != 0
+        {
+            get_cpuid_virtualization().unwrap_or(Virtualization::Other)
+        } else {
+            Virtualization::None
         }
     }
 }
@@ -565,9 +875,52 @@ impl Default for System {
     }
 }
 
+fn get_os_product_version() -> Option<String> {
+    unsafe {
+        // get the size for the buffer first
+        let mut size = 0;
+        if get_sys_value_by_name(b"kern.osproductversion\0", &mut size, std::ptr::null_mut())
+            && size > 0
+        {
+            // now create a buffer with the size and get the real value
+            let mut buf = vec![0_u8; size as usize];
+
+            if get_sys_value_by_name(
+                b"kern.osproductversion\0",
+                &mut size,
+                buf.as_mut_ptr() as *mut c_void,
+            ) {
+                if let Some(pos) = buf.iter().position(|x| *x == 0) {
+                    // Shrink buffer to terminate the null bytes
+                    buf.resize(pos, 0);
+                }
+
+                String::from_utf8(buf).ok()
+            } else {
+                // getting the system value failed
+                None
+            }
+        } else {
+            // getting the system value failed, or did not return a buffer size
+            None
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn get_os_version_from_plist() -> Option<String> {
+    let content =
+        std::fs::read_to_string("/System/Library/CoreServices/SystemVersion.plist").ok()?;
+    let key = "<key>ProductVersion</key>";
+    let content = &content[content.find(key)? + key.len()..];
+    let start = content.find("<string>")? + "<string>".len();
+    let end = start + content[start..].find("</string>")?;
+    Some(content[start..end].trim().to_owned())
+}
+
 // code from https://github.com/Chris911/iStats
 // Not supported on iOS
-#[cfg(target_os = "macos")]
+#[cfg(all(target_os = "macos", any(feature = "cpu", feature = "component")))]
 fn get_io_service_connection() -> Option<ffi::io_connect_t> {
     let mut master_port: mach_port_t = 0;
     let mut iterator: ffi::io_iterator_t = 0;
@@ -602,7 +955,11 @@ fn get_io_service_connection() -> Option<ffi::io_connect_t> {
     }
 }
 
-#[cfg(all(target_os = "macos", not(feature = "apple-app-store")))]
+#[cfg(all(
+    target_os = "macos",
+    feature = "process",
+    not(feature = "apple-app-store")
+))]
 fn get_arg_max() -> usize {
     let mut mib: [c_int; 3] = [libc::CTL_KERN, libc::KERN_ARGMAX, 0];
     let mut arg_max = 0i32;
@@ -653,6 +1010,118 @@ unsafe fn get_sys_value_by_name(name: &[u8], len: &mut usize, value: *mut c_void
     ) == 0
 }
 
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[cfg(feature = "cpu")]
+fn get_cpu_features() -> Vec<String> {
+    crate::utils::get_x86_cpu_features()
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn get_cpuid_virtualization() -> Option<Virtualization> {
+    crate::utils::get_cpuid_virtualization()
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+fn get_cpuid_virtualization() -> Option<Virtualization> {
+    None
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+#[cfg(feature = "cpu")]
+fn get_cpu_features() -> Vec<String> {
+    const FEATURES: &[(&str, &[u8])] = &[
+        ("neon", b"hw.optional.neon\0"),
+        ("fp16", b"hw.optional.neon_fp16\0"),
+        ("crc32", b"hw.optional.armv8_crc32\0"),
+        ("atomics", b"hw.optional.armv8_1_atomics\0"),
+        ("aes", b"hw.optional.arm.FEAT_AES\0"),
+        ("sha2", b"hw.optional.arm.FEAT_SHA256\0"),
+    ];
+
+    FEATURES
+        .iter()
+        .filter(|(_, key)| {
+            let mut value: c_int = 0;
+            let mut len = mem::size_of::<c_int>();
+            let found = unsafe {
+                get_sys_value_by_name(key, &mut len, &mut value as *mut c_int as *mut c_void)
+            };
+            found && value != 0
+        })
+        .map(|(name, _)| (*name).to_owned())
+        .collect()
+}
+
+/// Returns the dynamic swap files. Their individual usage isn't available so they're considered
+/// to be filled one after the other with the `used` bytes.
+fn get_swap_devices(mut used: u64) -> Vec<SwapDevice> {
+    let mut files = match std::fs::read_dir("/private/var/vm") {
+        Ok(entries) => entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let index = entry
+                    .file_name()
+                    .to_str()?
+                    .strip_prefix("swapfile")?
+                    .parse::<u32>()
+                    .ok()?;
+                let size = entry.metadata().ok()?.len();
+                Some((index, entry.path(), size))
+            })
+            .collect::<Vec<_>>(),
+        Err(_) => return Vec::new(),
+    };
+    files.sort_unstable_by_key(|(index, _, _)| *index);
+    files
+        .into_iter()
+        .map(|(_, path, size)| {
+            let file_used = used.min(size);
+            used -= file_used;
+            SwapDevice {
+                name: path.to_string_lossy().into_owned(),
+                type_: SwapType::File,
+                size,
+                used: file_used,
+            }
+        })
+        .collect()
+}
+
+#[cfg(feature = "cpu")]
+fn get_cpu_caches() -> Vec<CacheInfo> {
+    const CACHES: &[(u8, CacheType, &[u8])] = &[
+        (1, CacheType::Data, b"hw.l1dcachesize\0"),
+        (1, CacheType::Instruction, b"hw.l1icachesize\0"),
+        (2, CacheType::Unified, b"hw.l2cachesize\0"),
+        (3, CacheType::Unified, b"hw.l3cachesize\0"),
+    ];
+
+    let get_value = |key: &[u8]| -> Option<u64> {
+        let mut value: u64 = 0;
+        let mut len = mem::size_of::<u64>();
+        if unsafe { get_sys_value_by_name(key, &mut len, &mut value as *mut u64 as *mut c_void) }
+            && value != 0
+        {
+            Some(value)
+        } else {
+            None
+        }
+    };
+    let line_size = get_value(b"hw.cachelinesize\0").unwrap_or(0);
+    // Keys of missing cache levels either don't exist or are set to 0.
+    CACHES
+        .iter()
+        .filter_map(|&(level, type_, key)| {
+            Some(CacheInfo {
+                level,
+                type_,
+                size: get_value(key)?,
+                line_size,
+            })
+        })
+        .collect()
+}
+
 fn get_system_info(value: c_int, default: Option<&str>) -> Option<String> {
     let mut mib: [c_int; 2] = [libc::CTL_KERN, value];
     let mut size = 0;
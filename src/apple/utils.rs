@@ -4,6 +4,16 @@
 // Copyright (c) 2020 Guillaume Gomez
 //
 
+#[cfg(target_os = "macos")]
+use crate::sys::ffi;
+
+#[cfg(target_os = "macos")]
+use core_foundation_sys::base::{kCFAllocatorNull, CFRelease, CFTypeRef};
+#[cfg(target_os = "macos")]
+use core_foundation_sys::dictionary::{CFDictionaryGetValueIfPresent, CFDictionaryRef};
+#[cfg(target_os = "macos")]
+use core_foundation_sys::string::{self as cfs, CFStringRef};
+
 use libc::c_char;
 
 pub fn cstr_to_rust(c: *const c_char) -> Option<String> {
@@ -29,3 +39,30 @@ pub fn cstr_to_rust_with_size(c: *const c_char, size: Option<usize>) -> Option<S
     }
     String::from_utf8(s).ok()
 }
+
+#[cfg(target_os = "macos")]
+pub unsafe fn create_key(key: &[u8]) -> CFStringRef {
+    ffi::CFStringCreateWithCStringNoCopy(
+        std::ptr::null_mut(),
+        key.as_ptr() as *const c_char,
+        cfs::kCFStringEncodingUTF8,
+        kCFAllocatorNull as _,
+    )
+}
+
+#[cfg(target_os = "macos")]
+pub unsafe fn get_dict_value<T, F: FnOnce(CFTypeRef) -> Option<T>>(
+    dict: CFDictionaryRef,
+    key: &[u8],
+    callback: F,
+) -> Option<T> {
+    let key = create_key(key);
+    let mut value = std::ptr::null();
+    let ret = if CFDictionaryGetValueIfPresent(dict, key as _, &mut value) != 0 {
+        callback(value)
+    } else {
+        None
+    };
+    CFRelease(key as _);
+    ret
+}
@@ -4,10 +4,13 @@
 // Copyright (c) 2017 Guillaume Gomez
 //
 
-use crate::{NetworkExt, NetworksExt, Process, ProcessExt, ProcessorExt, System, SystemExt};
+use crate::{
+    NetworkData, NetworkExt, Networks, NetworksExt, Pid, Process, ProcessExt, ProcessorExt, System,
+    SystemExt,
+};
 use libc::{self, c_char, c_float, c_uint, c_void, pid_t, size_t};
 use std::borrow::BorrowMut;
-use std::ffi::CString;
+use std::ffi::{CStr, CString};
 
 /// Equivalent of [`System`][crate::System] struct.
 pub type CSystem = *mut c_void;
@@ -17,6 +20,10 @@ pub type CProcess = *const c_void;
 pub type RString = *const c_char;
 /// Callback used by [`get_processes`][crate::System#method.get_processes].
 pub type ProcessLoop = extern "C" fn(pid: pid_t, process: CProcess, data: *mut c_void) -> bool;
+/// Equivalent of [`Networks`][crate::Networks] struct.
+pub type CNetworks = *mut c_void;
+/// Callback used by [`sysinfo_networks_iter`]. `interface_name` is only valid during the call.
+pub type NetworkLoop = extern "C" fn(interface_name: *const c_char, data: *mut c_void) -> bool;
 
 /// Equivalent of [`System::new()`][crate::System#method.new].
 #[no_mangle]
@@ -114,7 +121,7 @@ pub extern "C" fn sysinfo_refresh_process(system: CSystem, pid: pid_t) {
     let mut system: Box<System> = unsafe { Box::from_raw(system as *mut System) };
     {
         let system: &mut System = system.borrow_mut();
-        system.refresh_process(pid);
+        system.refresh_process(Pid(pid));
     }
     Box::into_raw(system);
 }
@@ -231,6 +238,147 @@ pub extern "C" fn sysinfo_get_networks_transmitted(system: CSystem) -> size_t {
     ret
 }
 
+/// Creates a new [`Networks`][crate::Networks] with its interfaces list already filled. Must be
+/// freed with [`sysinfo_networks_free`].
+#[no_mangle]
+pub extern "C" fn sysinfo_networks_new() -> CNetworks {
+    let mut networks = Box::new(Networks::new());
+    networks.refresh_networks_list();
+    Box::into_raw(networks) as CNetworks
+}
+
+/// Equivalent of `Networks::drop()`. Important in C to cleanup memory.
+#[no_mangle]
+pub extern "C" fn sysinfo_networks_free(networks: CNetworks) {
+    assert!(!networks.is_null());
+    unsafe {
+        Box::from_raw(networks as *mut Networks);
+    }
+}
+
+/// Equivalent of [`Networks::refresh()`][crate::Networks#method.refresh].
+#[no_mangle]
+pub extern "C" fn sysinfo_networks_refresh(networks: CNetworks) {
+    assert!(!networks.is_null());
+    let mut networks: Box<Networks> = unsafe { Box::from_raw(networks as *mut Networks) };
+    {
+        let networks: &mut Networks = networks.borrow_mut();
+        networks.refresh();
+    }
+    Box::into_raw(networks);
+}
+
+/// Equivalent of
+/// [`Networks::refresh_networks_list()`][crate::Networks#method.refresh_networks_list].
+#[no_mangle]
+pub extern "C" fn sysinfo_networks_refresh_list(networks: CNetworks) {
+    assert!(!networks.is_null());
+    let mut networks: Box<Networks> = unsafe { Box::from_raw(networks as *mut Networks) };
+    {
+        let networks: &mut Networks = networks.borrow_mut();
+        networks.refresh_networks_list();
+    }
+    Box::into_raw(networks);
+}
+
+/// Equivalent of iterating over [`Networks::iter()`][crate::Networks#method.iter]. The callback
+/// is called with the name of each interface until it returns `false`. Returns the number of
+/// interfaces.
+#[no_mangle]
+pub extern "C" fn sysinfo_networks_iter(
+    networks: CNetworks,
+    fn_pointer: Option<NetworkLoop>,
+    data: *mut c_void,
+) -> size_t {
+    assert!(!networks.is_null());
+    if let Some(fn_pointer) = fn_pointer {
+        let networks: Box<Networks> = unsafe { Box::from_raw(networks as *mut Networks) };
+        let len = {
+            let mut len = 0;
+            for (interface_name, _) in networks.iter() {
+                len += 1;
+                let interface_name = match CString::new(interface_name.as_bytes()) {
+                    Ok(s) => s,
+                    Err(_) => continue,
+                };
+                if !fn_pointer(interface_name.as_ptr(), data) {
+                    break;
+                }
+            }
+            len
+        };
+        Box::into_raw(networks);
+        len
+    } else {
+        0
+    }
+}
+
+fn get_network_value(
+    networks: CNetworks,
+    interface_name: *const c_char,
+    getter: fn(&NetworkData) -> u64,
+) -> u64 {
+    assert!(!networks.is_null());
+    if interface_name.is_null() {
+        return 0;
+    }
+    let interface_name = match unsafe { CStr::from_ptr(interface_name) }.to_str() {
+        Ok(s) => s,
+        Err(_) => return 0,
+    };
+    let networks: Box<Networks> = unsafe { Box::from_raw(networks as *mut Networks) };
+    let ret = networks
+        .iter()
+        .find(|(name, _)| name.as_str() == interface_name)
+        .map(|(_, data)| getter(data))
+        .unwrap_or(0);
+    Box::into_raw(networks);
+    ret
+}
+
+/// Equivalent of [`NetworkData::get_received()`][crate::NetworkData#method.get_received] for the
+/// interface named `interface_name`. Returns 0 if there is no such interface.
+#[no_mangle]
+pub extern "C" fn sysinfo_networks_get_received(
+    networks: CNetworks,
+    interface_name: *const c_char,
+) -> u64 {
+    get_network_value(networks, interface_name, NetworkExt::get_received)
+}
+
+/// Equivalent of
+/// [`NetworkData::get_total_received()`][crate::NetworkData#method.get_total_received] for the
+/// interface named `interface_name`. Returns 0 if there is no such interface.
+#[no_mangle]
+pub extern "C" fn sysinfo_networks_get_total_received(
+    networks: CNetworks,
+    interface_name: *const c_char,
+) -> u64 {
+    get_network_value(networks, interface_name, NetworkExt::get_total_received)
+}
+
+/// Equivalent of [`NetworkData::get_transmitted()`][crate::NetworkData#method.get_transmitted] for
+/// the interface named `interface_name`. Returns 0 if there is no such interface.
+#[no_mangle]
+pub extern "C" fn sysinfo_networks_get_transmitted(
+    networks: CNetworks,
+    interface_name: *const c_char,
+) -> u64 {
+    get_network_value(networks, interface_name, NetworkExt::get_transmitted)
+}
+
+/// Equivalent of
+/// [`NetworkData::get_total_transmitted()`][crate::NetworkData#method.get_total_transmitted] for
+/// the interface named `interface_name`. Returns 0 if there is no such interface.
+#[no_mangle]
+pub extern "C" fn sysinfo_networks_get_total_transmitted(
+    networks: CNetworks,
+    interface_name: *const c_char,
+) -> u64 {
+    get_network_value(networks, interface_name, NetworkExt::get_total_transmitted)
+}
+
 /// Equivalent of [`System::get_processors_usage()`][crate::System#method.get_processors_usage].
 ///
 /// * `length` will contain the number of cpu usage added into `procs`.
@@ -280,7 +428,7 @@ pub extern "C" fn sysinfo_get_processes(
         let len = {
             let entries = system.get_processes();
             for (pid, process) in entries {
-                if !fn_pointer(*pid, process as *const Process as CProcess, data) {
+                if !fn_pointer(pid.0, process as *const Process as CProcess, data) {
                     break;
                 }
             }
@@ -303,7 +451,7 @@ pub extern "C" fn sysinfo_get_processes(
 pub extern "C" fn sysinfo_get_process_by_pid(system: CSystem, pid: pid_t) -> CProcess {
     assert!(!system.is_null());
     let system: Box<System> = unsafe { Box::from_raw(system as *mut System) };
-    let ret = if let Some(process) = system.get_process(pid) {
+    let ret = if let Some(process) = system.get_process(Pid(pid)) {
         process as *const Process as CProcess
     } else {
         std::ptr::null()
@@ -328,7 +476,7 @@ pub extern "C" fn sysinfo_process_get_tasks(
     if let Some(fn_pointer) = fn_pointer {
         let process = process as *const Process;
         for (pid, process) in unsafe { (*process).tasks.iter() } {
-            if !fn_pointer(*pid, process as *const Process as CProcess, data) {
+            if !fn_pointer(pid.0, process as *const Process as CProcess, data) {
                 break;
             }
         }
@@ -343,7 +491,7 @@ pub extern "C" fn sysinfo_process_get_tasks(
 pub extern "C" fn sysinfo_process_get_pid(process: CProcess) -> pid_t {
     assert!(!process.is_null());
     let process = process as *const Process;
-    unsafe { (*process).pid() }
+    unsafe { (*process).pid().0 }
 }
 
 /// Equivalent of [`Process::parent()`][crate::Process#method.parent].
@@ -353,7 +501,7 @@ pub extern "C" fn sysinfo_process_get_pid(process: CProcess) -> pid_t {
 pub extern "C" fn sysinfo_process_get_parent_pid(process: CProcess) -> pid_t {
     assert!(!process.is_null());
     let process = process as *const Process;
-    unsafe { (*process).parent().unwrap_or(0) }
+    unsafe { (*process).parent().map(|p| p.0).unwrap_or(0) }
 }
 
 /// Equivalent of [`Process::cpu_usage()`][crate::Process#method.cpu_usage].
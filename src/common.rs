@@ -4,7 +4,28 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
-use crate::{NetworkData, Networks, NetworksExt, UserExt};
+#[cfg(feature = "system")]
+use crate::UserExt;
+#[cfg(feature = "network")]
+use crate::{NetworkData, Networks, NetworksExt};
+
+#[cfg(feature = "network")]
+use std::collections::VecDeque;
+use std::convert::TryFrom;
+use std::fmt;
+#[cfg(feature = "process")]
+use std::net::SocketAddr;
+use std::num::TryFromIntError;
+#[cfg(feature = "disk")]
+use std::path::PathBuf;
+#[cfg(feature = "process")]
+use std::process::ExitStatus;
+#[cfg(feature = "process")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "process")]
+use std::sync::Arc;
+#[cfg(feature = "process")]
+use std::thread::JoinHandle;
 
 /// Trait to have a common fallback for the [`Pid`][crate::Pid] type.
 pub trait AsU32 {
@@ -14,37 +35,125 @@ pub trait AsU32 {
 
 cfg_if::cfg_if! {
     if #[cfg(any(windows, target_os = "unknown", target_arch = "wasm32"))] {
-        /// Process id.
-        pub type Pid = usize;
+        type PidInner = usize;
+    } else {
+        type PidInner = libc::pid_t;
+    }
+}
+
+/// Process id.
+///
+/// Its underlying integer type depends on the platform (`pid_t` on unix systems, `usize` on
+/// Windows). Like for the primitive integer types, [`From`] is only implemented for the
+/// conversions which can't lose information on the current platform, use [`TryFrom`] to convert
+/// from and to an integer in a portable way.
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use sysinfo::{AsU32, Pid};
+///
+/// let pid = Pid::try_from(1337u32).unwrap();
+/// assert_eq!(pid.as_u32(), 1337);
+/// assert_eq!(u32::try_from(pid), Ok(1337));
+/// assert_eq!(pid, Pid::try_from(1337i32).unwrap());
+/// assert_eq!(pid.to_string(), "1337");
+/// ```
+///
+/// [`TryFrom`]: std::convert::TryFrom
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Pid(pub(crate) PidInner);
+
+impl AsU32 for Pid {
+    fn as_u32(&self) -> u32 {
+        self.0 as u32
+    }
+}
+
+cfg_if::cfg_if! {
+    if #[cfg(any(windows, target_os = "unknown", target_arch = "wasm32"))] {
+        impl From<u32> for Pid {
+            fn from(pid: u32) -> Self {
+                Pid(pid as _)
+            }
+        }
 
-        impl AsU32 for Pid {
-            fn as_u32(&self) -> u32 {
-                *self as u32
+        impl TryFrom<i32> for Pid {
+            type Error = TryFromIntError;
+
+            fn try_from(pid: i32) -> Result<Self, Self::Error> {
+                PidInner::try_from(pid).map(Pid)
+            }
+        }
+
+        impl TryFrom<Pid> for i32 {
+            type Error = TryFromIntError;
+
+            fn try_from(pid: Pid) -> Result<Self, Self::Error> {
+                i32::try_from(pid.0)
             }
         }
     } else {
-        use libc::pid_t;
+        impl TryFrom<u32> for Pid {
+            type Error = TryFromIntError;
 
-        /// Process id.
-        pub type Pid = pid_t;
+            fn try_from(pid: u32) -> Result<Self, Self::Error> {
+                PidInner::try_from(pid).map(Pid)
+            }
+        }
+
+        impl From<i32> for Pid {
+            fn from(pid: i32) -> Self {
+                Pid(pid)
+            }
+        }
 
-        impl AsU32 for Pid {
-            fn as_u32(&self) -> u32 {
-                *self as u32
+        impl From<Pid> for i32 {
+            fn from(pid: Pid) -> Self {
+                pid.0
             }
         }
     }
 }
 
+impl TryFrom<Pid> for u32 {
+    type Error = TryFromIntError;
+
+    fn try_from(pid: Pid) -> Result<Self, Self::Error> {
+        u32::try_from(pid.0)
+    }
+}
+
+impl std::str::FromStr for Pid {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PidInner::from_str(s).map(Pid)
+    }
+}
+
+impl fmt::Display for Pid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Debug for Pid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(feature = "system")]
 macro_rules! impl_get_set {
-    ($name:ident, $with:ident, $without:ident) => {
+    ($ty_name:ident, $name:ident, $with:ident, $without:ident) => {
         doc_comment::doc_comment! {
         concat!("Returns the value of the \"", stringify!($name), "\" refresh kind.
 
 ```
-use sysinfo::RefreshKind;
+use sysinfo::", stringify!($ty_name), ";
 
-let r = RefreshKind::new();
+let r = ", stringify!($ty_name), "::new();
 assert_eq!(r.", stringify!($name), "(), false);
 
 let r = r.with_", stringify!($name), "();
@@ -62,15 +171,15 @@ assert_eq!(r.", stringify!($name), "(), false);
         concat!("Sets the value of the \"", stringify!($name), "\" refresh kind to `true`.
 
 ```
-use sysinfo::RefreshKind;
+use sysinfo::", stringify!($ty_name), ";
 
-let r = RefreshKind::new();
+let r = ", stringify!($ty_name), "::new();
 assert_eq!(r.", stringify!($name), "(), false);
 
 let r = r.with_", stringify!($name), "();
 assert_eq!(r.", stringify!($name), "(), true);
 ```"),
-                    pub fn $with(mut self) -> RefreshKind {
+                    pub fn $with(mut self) -> $ty_name {
                         self.$name = true;
                         self
                     }
@@ -80,15 +189,15 @@ assert_eq!(r.", stringify!($name), "(), true);
         concat!("Sets the value of the \"", stringify!($name), "\" refresh kind to `false`.
 
 ```
-use sysinfo::RefreshKind;
+use sysinfo::", stringify!($ty_name), ";
 
-let r = RefreshKind::everything();
+let r = ", stringify!($ty_name), "::everything();
 assert_eq!(r.", stringify!($name), "(), true);
 
 let r = r.without_", stringify!($name), "();
 assert_eq!(r.", stringify!($name), "(), false);
 ```"),
-                    pub fn $without(mut self) -> RefreshKind {
+                    pub fn $without(mut self) -> $ty_name {
                         self.$name = false;
                         self
                     }
@@ -99,6 +208,7 @@ assert_eq!(r.", stringify!($name), "(), false);
 /// Used to determine what you want to refresh specifically on [`System`] type.
 ///
 /// ```
+/// # #[cfg(all(feature = "process", feature = "disk"))] {
 /// use sysinfo::{RefreshKind, System, SystemExt};
 ///
 /// // We want everything except disks.
@@ -106,9 +216,11 @@ assert_eq!(r.", stringify!($name), "(), false);
 ///
 /// assert_eq!(system.get_disks().len(), 0);
 /// assert!(system.get_processes().len() > 0);
+/// # }
 /// ```
 ///
 /// [`System`]: crate::System
+#[cfg(feature = "system")]
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct RefreshKind {
     networks: bool,
@@ -121,8 +233,11 @@ pub struct RefreshKind {
     components: bool,
     components_list: bool,
     users_list: bool,
+    gpus: bool,
+    gpus_list: bool,
 }
 
+#[cfg(feature = "system")]
 impl RefreshKind {
     /// Creates a new `RefreshKind` with every refresh set to `false`.
     ///
@@ -141,6 +256,8 @@ impl RefreshKind {
     /// assert_eq!(r.components(), false);
     /// assert_eq!(r.components_list(), false);
     /// assert_eq!(r.users_list(), false);
+    /// assert_eq!(r.gpus(), false);
+    /// assert_eq!(r.gpus_list(), false);
     /// ```
     pub fn new() -> RefreshKind {
         RefreshKind::default()
@@ -163,6 +280,8 @@ impl RefreshKind {
     /// assert_eq!(r.components(), true);
     /// assert_eq!(r.components_list(), true);
     /// assert_eq!(r.users_list(), true);
+    /// assert_eq!(r.gpus(), true);
+    /// assert_eq!(r.gpus_list(), true);
     /// ```
     pub fn everything() -> RefreshKind {
         RefreshKind {
@@ -176,23 +295,165 @@ impl RefreshKind {
             components: true,
             components_list: true,
             users_list: true,
+            gpus: true,
+            gpus_list: true,
         }
     }
 
-    impl_get_set!(networks, with_networks, without_networks);
-    impl_get_set!(networks_list, with_networks_list, without_networks_list);
-    impl_get_set!(processes, with_processes, without_processes);
-    impl_get_set!(disks, with_disks, without_disks);
-    impl_get_set!(disks_list, with_disks_list, without_disks_list);
-    impl_get_set!(memory, with_memory, without_memory);
-    impl_get_set!(cpu, with_cpu, without_cpu);
-    impl_get_set!(components, with_components, without_components);
+    impl_get_set!(RefreshKind, networks, with_networks, without_networks);
     impl_get_set!(
+        RefreshKind,
+        networks_list,
+        with_networks_list,
+        without_networks_list
+    );
+    impl_get_set!(RefreshKind, processes, with_processes, without_processes);
+    impl_get_set!(RefreshKind, disks, with_disks, without_disks);
+    impl_get_set!(RefreshKind, disks_list, with_disks_list, without_disks_list);
+    impl_get_set!(RefreshKind, memory, with_memory, without_memory);
+    impl_get_set!(RefreshKind, cpu, with_cpu, without_cpu);
+    impl_get_set!(RefreshKind, components, with_components, without_components);
+    impl_get_set!(
+        RefreshKind,
         components_list,
         with_components_list,
         without_components_list
     );
-    impl_get_set!(users_list, with_users_list, without_users_list);
+    impl_get_set!(RefreshKind, users_list, with_users_list, without_users_list);
+    impl_get_set!(RefreshKind, gpus, with_gpus, without_gpus);
+    impl_get_set!(RefreshKind, gpus_list, with_gpus_list, without_gpus_list);
+}
+
+/// Used to determine which processes information you want to refresh with
+/// [`SystemExt::refresh_processes_specifics`][crate::SystemExt::refresh_processes_specifics].
+///
+/// Some information (like the command line or the environment) is only read when a process is
+/// seen for the first time, so disabling it skips the matching (and costly) system calls.
+///
+/// ```
+/// use sysinfo::{ProcessExt, ProcessRefreshKind, System, SystemExt};
+///
+/// let mut system = System::new();
+/// // We don't want the command line and the environment of the processes.
+/// system.refresh_processes_specifics(
+///     ProcessRefreshKind::everything().without_cmd().without_environ(),
+/// );
+///
+/// for process in system.get_processes().values() {
+///     assert!(process.cmd().is_empty());
+///     assert!(process.environ().is_empty());
+/// }
+/// ```
+#[cfg(feature = "process")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ProcessRefreshKind {
+    cpu: bool,
+    memory: bool,
+    disk_usage: bool,
+    user: bool,
+    cmd: bool,
+    environ: bool,
+}
+
+#[cfg(feature = "process")]
+impl ProcessRefreshKind {
+    /// Creates a new `ProcessRefreshKind` with every refresh set to `false`.
+    ///
+    /// ```
+    /// use sysinfo::ProcessRefreshKind;
+    ///
+    /// let r = ProcessRefreshKind::new();
+    ///
+    /// assert_eq!(r.cpu(), false);
+    /// assert_eq!(r.memory(), false);
+    /// assert_eq!(r.disk_usage(), false);
+    /// assert_eq!(r.user(), false);
+    /// assert_eq!(r.cmd(), false);
+    /// assert_eq!(r.environ(), false);
+    /// ```
+    pub fn new() -> ProcessRefreshKind {
+        ProcessRefreshKind::default()
+    }
+
+    /// Creates a new `ProcessRefreshKind` with every refresh set to `true`.
+    ///
+    /// ```
+    /// use sysinfo::ProcessRefreshKind;
+    ///
+    /// let r = ProcessRefreshKind::everything();
+    ///
+    /// assert_eq!(r.cpu(), true);
+    /// assert_eq!(r.memory(), true);
+    /// assert_eq!(r.disk_usage(), true);
+    /// assert_eq!(r.user(), true);
+    /// assert_eq!(r.cmd(), true);
+    /// assert_eq!(r.environ(), true);
+    /// ```
+    pub fn everything() -> ProcessRefreshKind {
+        ProcessRefreshKind {
+            cpu: true,
+            memory: true,
+            disk_usage: true,
+            user: true,
+            cmd: true,
+            environ: true,
+        }
+    }
+
+    impl_get_set!(ProcessRefreshKind, cpu, with_cpu, without_cpu);
+    impl_get_set!(ProcessRefreshKind, memory, with_memory, without_memory);
+    impl_get_set!(
+        ProcessRefreshKind,
+        disk_usage,
+        with_disk_usage,
+        without_disk_usage
+    );
+    impl_get_set!(ProcessRefreshKind, user, with_user, without_user);
+    impl_get_set!(ProcessRefreshKind, cmd, with_cmd, without_cmd);
+    impl_get_set!(ProcessRefreshKind, environ, with_environ, without_environ);
+}
+
+/// History of the received and transmitted bytes of a network interface.
+#[cfg(feature = "network")]
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct NetworkHistory {
+    len: usize,
+    pub(crate) received: VecDeque<u64>,
+    pub(crate) transmitted: VecDeque<u64>,
+}
+
+#[cfg(feature = "network")]
+impl NetworkHistory {
+    pub(crate) fn new(len: usize) -> Self {
+        NetworkHistory {
+            len,
+            received: VecDeque::with_capacity(len),
+            transmitted: VecDeque::with_capacity(len),
+        }
+    }
+
+    pub(crate) fn set_len(&mut self, len: usize) {
+        self.len = len;
+        Self::truncate(&mut self.received, len);
+        Self::truncate(&mut self.transmitted, len);
+    }
+
+    pub(crate) fn update(&mut self, received: u64, transmitted: u64) {
+        if self.len == 0 {
+            return;
+        }
+        Self::truncate(&mut self.received, self.len - 1);
+        Self::truncate(&mut self.transmitted, self.len - 1);
+        self.received.push_back(received);
+        self.transmitted.push_back(transmitted);
+    }
+
+    // Removes the oldest samples so at most `len` are kept.
+    fn truncate(samples: &mut VecDeque<u64>, len: usize) {
+        while samples.len() > len {
+            samples.pop_front();
+        }
+    }
 }
 
 /// Iterator over network interfaces.
@@ -200,21 +461,24 @@ impl RefreshKind {
 /// It is returned by [`Networks::iter`][crate::Networks#method.iter].
 ///
 /// ```no_run
-/// use sysinfo::{System, SystemExt, NetworksExt};
+/// use sysinfo::{Networks, NetworksExt};
 ///
-/// let system = System::new_all();
-/// let networks_iter = system.get_networks().iter();
+/// let networks = Networks::new();
+/// let networks_iter = networks.iter();
 /// ```
+#[cfg(feature = "network")]
 pub struct NetworksIter<'a> {
     inner: std::collections::hash_map::Iter<'a, String, NetworkData>,
 }
 
+#[cfg(feature = "network")]
 impl<'a> NetworksIter<'a> {
     pub(crate) fn new(v: std::collections::hash_map::Iter<'a, String, NetworkData>) -> Self {
         NetworksIter { inner: v }
     }
 }
 
+#[cfg(feature = "network")]
 impl<'a> Iterator for NetworksIter<'a> {
     type Item = (&'a String, &'a NetworkData);
 
@@ -223,6 +487,7 @@ impl<'a> Iterator for NetworksIter<'a> {
     }
 }
 
+#[cfg(feature = "network")]
 impl<'a> IntoIterator for &'a Networks {
     type Item = (&'a String, &'a NetworkData);
     type IntoIter = NetworksIter<'a>;
@@ -232,6 +497,101 @@ impl<'a> IntoIterator for &'a Networks {
     }
 }
 
+/// Snapshot of the counters of a network interface.
+///
+/// It is returned by [`NetworkExt::get_stats`][crate::NetworkExt#method.get_stats].
+///
+/// ```no_run
+/// use std::collections::HashSet;
+/// use sysinfo::{NetworkExt, Networks, NetworksExt};
+///
+/// let mut networks = Networks::new();
+/// networks.refresh_networks_list();
+/// let stats = networks
+///     .iter()
+///     .map(|(_, network)| network.get_stats())
+///     .collect::<HashSet<_>>();
+/// ```
+#[cfg(feature = "network")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NetworkStats {
+    /// Number of bytes received since the last refresh.
+    pub received: u64,
+    /// Total number of bytes received.
+    pub total_received: u64,
+    /// Number of bytes transmitted since the last refresh.
+    pub transmitted: u64,
+    /// Total number of bytes transmitted.
+    pub total_transmitted: u64,
+    /// Number of packets received since the last refresh.
+    pub packets_received: u64,
+    /// Total number of packets received.
+    pub total_packets_received: u64,
+    /// Number of packets transmitted since the last refresh.
+    pub packets_transmitted: u64,
+    /// Total number of packets transmitted.
+    pub total_packets_transmitted: u64,
+    /// Number of errors on incoming packets since the last refresh.
+    pub errors_on_received: u64,
+    /// Total number of errors on incoming packets.
+    pub total_errors_on_received: u64,
+    /// Number of errors on outgoing packets since the last refresh.
+    pub errors_on_transmitted: u64,
+    /// Total number of errors on outgoing packets.
+    pub total_errors_on_transmitted: u64,
+    /// Number of incoming packets dropped since the last refresh.
+    pub drops_on_received: u64,
+    /// Total number of incoming packets dropped.
+    pub total_drops_on_received: u64,
+    /// Number of outgoing packets dropped since the last refresh.
+    pub drops_on_transmitted: u64,
+    /// Total number of outgoing packets dropped.
+    pub total_drops_on_transmitted: u64,
+    /// Number of incoming multicast packets since the last refresh.
+    pub packets_received_multicast: u64,
+    /// Total number of incoming multicast packets.
+    pub total_packets_received_multicast: u64,
+}
+
+/// A change between two snapshots of the network interfaces.
+///
+/// It is returned by [`NetworksExt::diff`][crate::NetworksExt#method.diff].
+///
+/// ```no_run
+/// use sysinfo::{NetworkDelta, Networks, NetworksExt};
+///
+/// let mut networks = Networks::new();
+/// networks.refresh_networks_list();
+/// let previous = networks.clone();
+/// networks.refresh_networks_list();
+/// for delta in networks.diff(&previous) {
+///     if let NetworkDelta::Added(name) = delta {
+///         println!("new interface: {}", name);
+///     }
+/// }
+/// ```
+#[cfg(feature = "network")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkDelta {
+    /// The interface with this name appeared.
+    Added(String),
+    /// The interface with this name disappeared.
+    Removed(String),
+    /// The counters of the interface changed.
+    Changed {
+        /// Name of the interface.
+        name: String,
+        /// Number of bytes received since the previous snapshot.
+        received: u64,
+        /// Number of bytes transmitted since the previous snapshot.
+        transmitted: u64,
+        /// Number of packets received since the previous snapshot.
+        packets_received: u64,
+        /// Number of packets transmitted since the previous snapshot.
+        packets_transmitted: u64,
+    },
+}
+
 /// Enum containing the different supported disks types.
 ///
 /// This type is returned by [`Disk::get_type`][crate::Disk#method.get_type].
@@ -244,6 +604,7 @@ impl<'a> IntoIterator for &'a Networks {
 ///     println!("{:?}: {:?}", disk.get_name(), disk.get_type());
 /// }
 /// ```
+#[cfg(feature = "disk")]
 #[derive(Debug, PartialEq, Clone, Copy)]
 pub enum DiskType {
     /// HDD type.
@@ -256,7 +617,399 @@ pub enum DiskType {
     Unknown(isize),
 }
 
+/// Enum containing the different network interfaces types.
+///
+/// This type is returned by
+/// [`NetworkData::get_interface_type`][crate::NetworkData#method.get_interface_type].
+///
+/// ```no_run
+/// use sysinfo::{NetworkExt, Networks, NetworksExt};
+///
+/// let mut networks = Networks::new();
+/// networks.refresh_networks_list();
+/// for (name, network) in &networks {
+///     println!("{}: {:?}", name, network.get_interface_type());
+/// }
+/// ```
+#[cfg(feature = "network")]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum InterfaceType {
+    /// Wired ethernet interface.
+    Ethernet,
+    /// Wireless interface.
+    WiFi,
+    /// Loopback interface.
+    Loopback,
+    /// Tunnel interface (like VPNs).
+    Tunnel,
+    /// Any other kind of interface.
+    Other,
+}
+
+/// Enum describing which bytes are counted by the received and transmitted counters of a network
+/// interface.
+///
+/// This type is returned by
+/// [`NetworkData::get_counting_basis`][crate::NetworkData#method.get_counting_basis].
+///
+/// ```no_run
+/// use sysinfo::{NetworkExt, Networks, NetworksExt};
+///
+/// let mut networks = Networks::new();
+/// networks.refresh_networks_list();
+/// for (name, network) in &networks {
+///     println!("{}: {:?}", name, network.get_counting_basis());
+/// }
+/// ```
+#[cfg(feature = "network")]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CountingBasis {
+    /// Whole link-layer (L2) frames, including their header (like the ethernet one) but not the
+    /// preamble nor the checksum.
+    LinkLayer,
+    /// Network-layer (L3) packets only, for interfaces without any link-layer header like most
+    /// tunnels.
+    Network,
+}
+
+/// Enum describing the administrative or operational status of a network interface, with the
+/// values defined by the `ifOperStatus` object of RFC 2863.
+///
+/// An interface can be administratively up (enabled by the user) while being operationally down
+/// (if its cable is unplugged for example).
+///
+/// This type is returned by
+/// [`NetworkData::get_admin_status`][crate::NetworkData#method.get_admin_status] and
+/// [`NetworkData::get_oper_status`][crate::NetworkData#method.get_oper_status].
+///
+/// ```no_run
+/// use sysinfo::{NetworkExt, Networks, NetworksExt};
+///
+/// let mut networks = Networks::new();
+/// networks.refresh_networks_list();
+/// for (name, network) in &networks {
+///     println!(
+///         "{}: {:?}/{:?}",
+///         name,
+///         network.get_admin_status(),
+///         network.get_oper_status(),
+///     );
+/// }
+/// ```
+#[cfg(feature = "network")]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum InterfaceStatus {
+    /// The interface is up.
+    Up,
+    /// The interface is down.
+    Down,
+    /// The interface is in test mode.
+    Testing,
+    /// The interface is waiting for an external event (like a connection to be established).
+    Dormant,
+    /// A component of the interface (typically its hardware) is missing.
+    NotPresent,
+    /// The interface is down because of one of the interfaces it relies on.
+    LowerLayerDown,
+    /// The status couldn't be retrieved.
+    Unknown,
+}
+
+/// Error returned by the fallible refresh methods like
+/// [`NetworksExt::try_refresh_networks_list`][crate::NetworksExt#method.try_refresh_networks_list].
+///
+/// When such an error is returned, the previously retrieved information is kept as is.
+///
+/// ```no_run
+/// use sysinfo::{Networks, NetworksExt};
+///
+/// let mut networks = Networks::new();
+/// networks.refresh_networks_list();
+/// if let Err(e) = networks.try_refresh_networks_list() {
+///     eprintln!("failed to refresh networks list: {}", e);
+/// }
+/// ```
+#[cfg(feature = "network")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RefreshError {
+    /// The system call used to retrieve the information failed with this OS error code.
+    Os(i32),
+}
+
+#[cfg(feature = "network")]
+impl fmt::Display for RefreshError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            RefreshError::Os(code) => write!(
+                f,
+                "system call failed: {}",
+                std::io::Error::from_raw_os_error(code)
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "network")]
+impl std::error::Error for RefreshError {}
+
+#[cfg(feature = "network")]
+impl From<std::io::Error> for RefreshError {
+    fn from(e: std::io::Error) -> Self {
+        RefreshError::Os(e.raw_os_error().unwrap_or(0))
+    }
+}
+
+/// Error which prevented some information of a process from being retrieved.
+///
+/// It is returned by [`ProcessExt::last_error`][crate::ProcessExt#method.last_error].
+///
+/// ```no_run
+/// use sysinfo::{AccessError, ProcessExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for process in s.get_processes().values() {
+///     if process.last_error() == Some(AccessError::PermissionDenied) {
+///         println!("not allowed to read all information of {}", process.name());
+///     }
+/// }
+/// ```
+#[cfg(feature = "process")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessError {
+    /// The current user isn't allowed to read this information.
+    PermissionDenied,
+    /// The system call used to retrieve the information failed with this OS error code.
+    Os(i32),
+}
+
+#[cfg(feature = "process")]
+impl fmt::Display for AccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            AccessError::PermissionDenied => f.write_str("permission denied"),
+            AccessError::Os(code) => write!(
+                f,
+                "system call failed: {}",
+                std::io::Error::from_raw_os_error(code)
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "process")]
+impl std::error::Error for AccessError {}
+
+#[cfg(feature = "process")]
+impl From<std::io::Error> for AccessError {
+    fn from(e: std::io::Error) -> Self {
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            AccessError::PermissionDenied
+        } else {
+            AccessError::Os(e.raw_os_error().unwrap_or(0))
+        }
+    }
+}
+
+/// Error returned when the priority of a process couldn't be changed.
+///
+/// It is returned by [`ProcessExt::set_nice`][crate::ProcessExt#method.set_nice].
+///
+/// ```no_run
+/// use sysinfo::{Pid, PriorityError, ProcessExt, System, SystemExt};
+///
+/// let s = System::new();
+/// if let Some(process) = s.get_process(Pid::from(1337)) {
+///     if let Err(PriorityError::PermissionDenied) = process.set_nice(-10) {
+///         eprintln!("not allowed to raise the priority of {}", process.name());
+///     }
+/// }
+/// ```
+#[cfg(feature = "process")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriorityError {
+    /// The current user isn't allowed to change the priority of this process, or to raise it.
+    PermissionDenied,
+    /// Changing the priority of a process isn't supported on this platform.
+    Unsupported,
+    /// The system call used to change the priority failed with this OS error code.
+    Os(i32),
+}
+
+#[cfg(feature = "process")]
+impl fmt::Display for PriorityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            PriorityError::PermissionDenied => f.write_str("permission denied"),
+            PriorityError::Unsupported => f.write_str("not supported on this platform"),
+            PriorityError::Os(code) => write!(
+                f,
+                "system call failed: {}",
+                std::io::Error::from_raw_os_error(code)
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "process")]
+impl std::error::Error for PriorityError {}
+
+#[cfg(feature = "process")]
+impl From<std::io::Error> for PriorityError {
+    fn from(e: std::io::Error) -> Self {
+        // Both `EPERM` and `EACCES` (returned when raising the priority on Linux) are mapped.
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            PriorityError::PermissionDenied
+        } else {
+            PriorityError::Os(e.raw_os_error().unwrap_or(0))
+        }
+    }
+}
+
+/// Transport protocol of a [`SocketInfo`].
+#[cfg(feature = "process")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SocketProtocol {
+    /// TCP socket.
+    Tcp,
+    /// UDP socket.
+    Udp,
+}
+
+/// State of a TCP socket.
+#[cfg(feature = "process")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SocketState {
+    /// The connection is open.
+    Established,
+    /// A connection request was sent.
+    SynSent,
+    /// A connection request was received and answered.
+    SynReceived,
+    /// The socket is closed and waits for the end of the connection to be acknowledged.
+    FinWait1,
+    /// The socket is closed and waits for the remote end to close the connection.
+    FinWait2,
+    /// The connection is closed and waits for the remaining packets.
+    TimeWait,
+    /// The socket isn't used.
+    Closed,
+    /// The remote end closed the connection, waiting for the socket to be closed.
+    CloseWait,
+    /// Both ends closed the connection, waiting for the last acknowledgement.
+    LastAck,
+    /// The socket waits for incoming connections.
+    Listen,
+    /// Both ends are closing the connection at the same time.
+    Closing,
+    /// Unknown state.
+    Unknown(u32),
+}
+
+/// Information about a TCP or UDP socket.
+///
+/// ```no_run
+/// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// if let Some(process) = s.get_process(Pid::from(1337)) {
+///     for socket in process.open_sockets() {
+///         println!("{:?} {} -> {}", socket.protocol, socket.local_address, socket.remote_address);
+///     }
+/// }
+/// ```
+#[cfg(feature = "process")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SocketInfo {
+    /// Transport protocol of the socket.
+    pub protocol: SocketProtocol,
+    /// Local address of the socket.
+    pub local_address: SocketAddr,
+    /// Address of the remote end. It is unspecified (`0.0.0.0:0` or `[::]:0`) if the socket
+    /// isn't connected.
+    pub remote_address: SocketAddr,
+    /// State of the socket, `None` for UDP sockets.
+    pub state: Option<SocketState>,
+    /// PID of the process owning the socket, if it could be found.
+    pub pid: Option<Pid>,
+}
+
+/// Information about a thread of a process.
+///
+/// ```no_run
+/// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// if let Some(process) = s.get_process(Pid::from(1337)) {
+///     for thread in process.threads() {
+///         println!("{} {}: {}%", thread.tid, thread.name, thread.cpu_usage);
+///     }
+/// }
+/// ```
+#[cfg(feature = "process")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThreadInfo {
+    /// Id of the thread.
+    pub tid: Pid,
+    /// Name of the thread.
+    pub name: String,
+    /// CPU usage of the thread (in %), computed like [`ProcessExt::cpu_usage`].
+    ///
+    /// [`ProcessExt::cpu_usage`]: crate::ProcessExt::cpu_usage
+    pub cpu_usage: f32,
+}
+
+/// Handle returned by [`SystemExt::watch_process_exit`][crate::SystemExt::watch_process_exit].
+///
+/// Dropping it doesn't stop the watch, use [`ProcessWatcher::cancel`] for that.
+///
+/// ```no_run
+/// use sysinfo::{Pid, System, SystemExt};
+///
+/// let s = System::new();
+/// let watcher = s.watch_process_exit(Pid::from(1337), |status| {
+///     println!("process ended: {:?}", status);
+/// });
+/// // We're not interested anymore.
+/// watcher.cancel();
+/// ```
+#[cfg(feature = "process")]
+#[derive(Debug)]
+pub struct ProcessWatcher {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+#[cfg(feature = "process")]
+impl ProcessWatcher {
+    /// Spawns a thread calling `f` with the result of `wait`, unless the watch is cancelled in
+    /// the meantime. `wait` is given a predicate returning `true` once it's the case.
+    pub(crate) fn new<W, F>(wait: W, f: F) -> ProcessWatcher
+    where
+        W: FnOnce(&dyn Fn() -> bool) -> Option<ExitStatus> + Send + 'static,
+        F: FnOnce(Option<ExitStatus>) + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = Arc::clone(&stop);
+        let thread = std::thread::spawn(move || {
+            let should_stop = || thread_stop.load(Ordering::Relaxed);
+            let status = wait(&should_stop);
+            if !should_stop() {
+                f(status);
+            }
+        });
+        ProcessWatcher { stop, thread }
+    }
+
+    /// Stops watching the process: the callback won't be called if it wasn't already. Blocks
+    /// until the watching thread is done, so if the callback is running, until it returns.
+    pub fn cancel(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let _ = self.thread.join();
+    }
+}
+
 /// An enum representing signal on UNIX-like systems.
+#[cfg(feature = "process")]
 #[repr(C)]
 #[derive(Clone, PartialEq, PartialOrd, Debug, Copy)]
 pub enum Signal {
@@ -344,6 +1097,7 @@ pub enum Signal {
 ///     load_avg.fifteen,
 /// );
 /// ```
+#[cfg(feature = "system")]
 #[repr(C)]
 #[derive(Default, Debug, Clone)]
 pub struct LoadAvg {
@@ -355,6 +1109,47 @@ pub struct LoadAvg {
     pub fifteen: f64,
 }
 
+/// An enum representing the charging state of a [`Battery`].
+#[cfg(feature = "system")]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BatteryState {
+    /// The battery is charging.
+    Charging,
+    /// The battery is discharging.
+    Discharging,
+    /// The battery is fully charged.
+    Full,
+    /// The state couldn't be determined.
+    Unknown,
+}
+
+/// A struct representing the battery information.
+///
+/// It is returned by [`SystemExt::get_battery`][crate::SystemExt::get_battery]. If the system
+/// has more than one battery, their values are aggregated.
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// if let Some(battery) = s.get_battery() {
+///     println!("{}% ({:?})", battery.percentage, battery.state);
+/// }
+/// ```
+#[cfg(feature = "system")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Battery {
+    /// Charge level of the battery (between 0 and 100).
+    pub percentage: f32,
+    /// Charging state of the battery.
+    pub state: BatteryState,
+    /// Estimated time (in seconds) before the battery is fully charged, if available.
+    pub time_to_full: Option<u64>,
+    /// Estimated time (in seconds) before the battery is empty, if available.
+    pub time_to_empty: Option<u64>,
+}
+
+#[cfg(feature = "system")]
 macro_rules! xid {
     ($(#[$outer:meta])+ $name:ident, $type:ty) => {
         $(#[$outer])+
@@ -371,6 +1166,7 @@ macro_rules! xid {
     };
 }
 
+#[cfg(feature = "system")]
 #[cfg(not(target_os = "windows"))]
 xid!(
     /// A user id wrapping a platform specific type
@@ -378,6 +1174,7 @@ xid!(
     libc::uid_t
 );
 
+#[cfg(feature = "system")]
 #[cfg(target_os = "windows")]
 xid!(
     /// A user id wrapping a platform specific type
@@ -385,6 +1182,7 @@ xid!(
     u32
 );
 
+#[cfg(feature = "system")]
 #[cfg(not(target_os = "windows"))]
 xid!(
     /// A group id wrapping a platform specific type
@@ -392,6 +1190,7 @@ xid!(
     libc::gid_t
 );
 
+#[cfg(feature = "system")]
 #[cfg(target_os = "windows")]
 xid!(
     /// A group id wrapping a platform specific type
@@ -409,6 +1208,7 @@ xid!(
 /// let s = System::new_all();
 /// println!("users: {:?}", s.get_users());
 /// ```
+#[cfg(feature = "system")]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
 pub struct User {
     pub(crate) uid: Uid,
@@ -417,6 +1217,7 @@ pub struct User {
     pub(crate) groups: Vec<String>,
 }
 
+#[cfg(feature = "system")]
 impl UserExt for User {
     fn get_uid(&self) -> Uid {
         self.uid
@@ -435,6 +1236,230 @@ impl UserExt for User {
     }
 }
 
+/// Time spent by a processor in its different states since boot, as raw ticks.
+///
+/// It is returned by [`ProcessorExt::get_times`][crate::ProcessorExt::get_times]. The unit
+/// depends on the platform: `USER_HZ` (usually 1/100th of second) on Linux, `CLK_TCK` on macOS
+/// and 100 nanoseconds on Windows. The states which don't exist on a platform are always 0.
+///
+/// ```no_run
+/// use sysinfo::{ProcessorExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// let times = s.get_global_processor_info().get_times();
+/// println!("idle: {}, user: {}", times.idle, times.user);
+/// ```
+#[cfg(feature = "cpu")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CpuTimes {
+    /// Time spent in user mode.
+    pub user: u64,
+    /// Time spent in user mode with a low priority.
+    pub nice: u64,
+    /// Time spent in kernel mode, without the interrupts.
+    pub system: u64,
+    /// Time spent doing nothing.
+    pub idle: u64,
+    /// Time spent waiting for I/O to complete. Linux only.
+    pub iowait: u64,
+    /// Time spent servicing hardware interrupts.
+    pub irq: u64,
+    /// Time spent servicing software interrupts (deferred procedure calls on Windows).
+    pub softirq: u64,
+    /// Time stolen by other operating systems running in a virtual environment. Linux only.
+    pub steal: u64,
+}
+
+#[cfg(feature = "cpu")]
+impl std::ops::AddAssign for CpuTimes {
+    fn add_assign(&mut self, other: CpuTimes) {
+        self.user += other.user;
+        self.nice += other.nice;
+        self.system += other.system;
+        self.idle += other.idle;
+        self.iowait += other.iowait;
+        self.irq += other.irq;
+        self.softirq += other.softirq;
+        self.steal += other.steal;
+    }
+}
+
+/// Virtualization technology or container runtime the system runs in.
+///
+/// It is returned by [`SystemExt::get_virtualization`][crate::SystemExt::get_virtualization].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt, Virtualization};
+///
+/// let s = System::new();
+/// if s.get_virtualization() != Virtualization::None {
+///     println!("running in {:?}", s.get_virtualization());
+/// }
+/// ```
+#[cfg(feature = "system")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Virtualization {
+    /// No virtualization was detected.
+    None,
+    /// KVM hypervisor.
+    Kvm,
+    /// QEMU without hardware acceleration.
+    Qemu,
+    /// VMware hypervisor.
+    VMware,
+    /// Oracle VirtualBox hypervisor.
+    VirtualBox,
+    /// Xen hypervisor.
+    Xen,
+    /// Microsoft Hyper-V hypervisor.
+    HyperV,
+    /// Parallels hypervisor.
+    Parallels,
+    /// Docker container.
+    Docker,
+    /// Podman container.
+    Podman,
+    /// LXC container.
+    Lxc,
+    /// Windows Subsystem for Linux.
+    Wsl,
+    /// Hypervisor or container which isn't recognized.
+    Other,
+}
+
+/// Kind of data stored in a CPU cache.
+///
+/// ```no_run
+/// use sysinfo::{CacheType, System, SystemExt};
+///
+/// let s = System::new();
+/// for cache in s.get_cpu_caches() {
+///     if cache.type_ == CacheType::Data {
+///         println!("L{} data cache: {} bytes", cache.level, cache.size);
+///     }
+/// }
+/// ```
+#[cfg(feature = "cpu")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheType {
+    /// Cache only holding data.
+    Data,
+    /// Cache only holding instructions.
+    Instruction,
+    /// Cache holding both data and instructions.
+    Unified,
+}
+
+/// Description of a CPU cache.
+///
+/// It is returned by [`SystemExt::get_cpu_caches`][crate::SystemExt::get_cpu_caches].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// for cache in s.get_cpu_caches() {
+///     println!("L{} {:?}: {} bytes", cache.level, cache.type_, cache.size);
+/// }
+/// ```
+#[cfg(feature = "cpu")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheInfo {
+    /// Level of the cache (1 for L1, 2 for L2...).
+    pub level: u8,
+    /// Kind of data stored in the cache.
+    pub type_: CacheType,
+    /// Size of the cache (in bytes).
+    pub size: u64,
+    /// Size of a cache line (in bytes), or 0 if it isn't known.
+    pub line_size: u64,
+}
+
+/// Kind of [`SwapDevice`].
+#[cfg(feature = "system")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SwapType {
+    /// A disk partition (or a block device like `zram`).
+    Partition,
+    /// A file (like the Windows pagefiles or the macOS dynamic swap files).
+    File,
+}
+
+/// Description of a swap partition or file.
+///
+/// It is returned by [`SystemExt::get_swap_devices`][crate::SystemExt::get_swap_devices].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new_all();
+/// for device in s.get_swap_devices() {
+///     println!("{} ({:?}): {}/{} bytes", device.name, device.type_, device.used, device.size);
+/// }
+/// ```
+#[cfg(feature = "system")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapDevice {
+    /// Path of the partition or of the file.
+    pub name: String,
+    /// Whether it's a partition or a file.
+    pub type_: SwapType,
+    /// Size of the swap space (in bytes).
+    pub size: u64,
+    /// Amount of swap space in use (in bytes).
+    pub used: u64,
+}
+
+/// Description of a mounted file system, pseudo ones (like `proc` or `tmpfs`) included.
+///
+/// It is returned by [`SystemExt::get_all_mounts`][crate::SystemExt::get_all_mounts].
+///
+/// ```no_run
+/// use sysinfo::{System, SystemExt};
+///
+/// let s = System::new();
+/// for mount in s.get_all_mounts() {
+///     println!("{} on {:?} ({})", mount.device, mount.mount_point, mount.file_system);
+/// }
+/// ```
+#[cfg(feature = "disk")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mount {
+    /// Device or source of the file system, like `/dev/sda1`. For pseudo file systems, it's
+    /// usually the name of the file system (like `proc`). On Windows, it's the volume GUID path.
+    pub device: String,
+    /// Path the file system is mounted on.
+    pub mount_point: PathBuf,
+    /// Type of the file system, like `ext4` or `proc`.
+    pub file_system: String,
+    /// Mount options, like `ro` or `nosuid`.
+    pub options: Vec<String>,
+}
+
+/// Type containing the bytes transferred over the sockets of a process.
+///
+/// It is returned by [`ProcessExt::network_usage`][crate::ProcessExt::network_usage].
+///
+/// ```no_run
+/// use sysinfo::{ProcessExt, System, SystemExt};
+///
+/// let s = System::new_all();
+/// for (pid, process) in s.get_processes() {
+///     if let Some(usage) = process.network_usage() {
+///         println!("[{}] received bytes   : {} B", pid, usage.total_received_bytes);
+///         println!("[{}] transmitted bytes: {} B", pid, usage.total_transmitted_bytes);
+///     }
+/// }
+/// ```
+#[cfg(feature = "process")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkUsage {
+    /// Total number of bytes received.
+    pub total_received_bytes: u64,
+    /// Total number of bytes transmitted (and acknowledged by the remote end).
+    pub total_transmitted_bytes: u64,
+}
+
 /// Type containing read and written bytes.
 ///
 /// It is returned by [`ProcessExt::disk_usage`][crate::ProcessExt::disk_usage].
@@ -457,6 +1482,7 @@ impl UserExt for User {
 ///     );
 /// }
 /// ```
+#[cfg(feature = "process")]
 #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
 pub struct DiskUsage {
     /// Total number of written bytes.
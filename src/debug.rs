@@ -4,13 +4,23 @@
 // Copyright (c) 2020 Guillaume Gomez
 //
 
-use crate::{
-    Component, ComponentExt, Disk, DiskExt, NetworkData, NetworkExt, Networks, NetworksExt,
-    Process, ProcessExt, Processor, ProcessorExt, System, SystemExt,
-};
+#[cfg(feature = "component")]
+use crate::{Component, ComponentExt, Fan, FanExt};
+#[cfg(feature = "disk")]
+use crate::{Disk, DiskExt};
+#[cfg(feature = "system")]
+use crate::{Gpu, GpuExt, System, SystemExt};
+#[cfg(feature = "network")]
+use crate::{NetworkData, NetworkExt, Networks, NetworksExt};
+#[cfg(feature = "process")]
+use crate::{Process, ProcessExt};
+#[cfg(feature = "cpu")]
+use crate::{Processor, ProcessorExt};
 
+#[cfg(any(feature = "network", feature = "system"))]
 use std::fmt;
 
+#[cfg(feature = "cpu")]
 impl fmt::Debug for Processor {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Processor")
@@ -20,27 +30,36 @@ impl fmt::Debug for Processor {
     }
 }
 
+#[cfg(feature = "system")]
 impl fmt::Debug for System {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("System")
-            .field(
-                "global CPU usage",
-                &self.get_global_processor_info().get_cpu_usage(),
-            )
-            .field("load average", &self.get_load_average())
+        let mut d = f.debug_struct("System");
+        #[cfg(feature = "cpu")]
+        d.field(
+            "global CPU usage",
+            &self.get_global_processor_info().get_cpu_usage(),
+        );
+        d.field("load average", &self.get_load_average())
             .field("total memory", &self.get_total_memory())
             .field("free memory", &self.get_free_memory())
             .field("total swap", &self.get_total_swap())
-            .field("free swap", &self.get_free_swap())
-            .field("nb CPUs", &self.get_processors().len())
-            .field("nb network interfaces", &self.get_networks().iter().count())
-            .field("nb processes", &self.get_processes().len())
-            .field("nb disks", &self.get_disks().len())
-            .field("nb components", &self.get_components().len())
-            .finish()
+            .field("free swap", &self.get_free_swap());
+        #[cfg(feature = "cpu")]
+        d.field("nb CPUs", &self.get_processors().len());
+        #[cfg(feature = "network")]
+        d.field("nb network interfaces", &self.get_networks().iter().count());
+        #[cfg(feature = "process")]
+        d.field("nb processes", &self.get_processes().len());
+        #[cfg(feature = "disk")]
+        d.field("nb disks", &self.get_disks().len());
+        #[cfg(feature = "component")]
+        d.field("nb components", &self.get_components().len())
+            .field("nb fans", &self.get_fans().len());
+        d.field("nb GPUs", &self.get_gpus().len()).finish()
     }
 }
 
+#[cfg(feature = "disk")]
 impl fmt::Debug for Disk {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -59,6 +78,7 @@ impl fmt::Debug for Disk {
     }
 }
 
+#[cfg(feature = "process")]
 impl fmt::Debug for Process {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Process")
@@ -79,6 +99,7 @@ impl fmt::Debug for Process {
     }
 }
 
+#[cfg(feature = "component")]
 impl fmt::Debug for Component {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if let Some(critical) = self.get_critical() {
@@ -102,19 +123,35 @@ impl fmt::Debug for Component {
     }
 }
 
-impl fmt::Debug for Networks {
+#[cfg(feature = "component")]
+impl fmt::Debug for Fan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} RPM", self.get_label(), self.get_rpm())
+    }
+}
+
+#[cfg(feature = "system")]
+impl fmt::Debug for Gpu {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "Networks {{ {} }}",
-            self.iter()
-                .map(|x| format!("{:?}", x))
-                .collect::<Vec<_>>()
-                .join(", ")
+            "{}: {}% ({}/{} kB)",
+            self.get_name(),
+            self.get_usage(),
+            self.get_used_memory(),
+            self.get_total_memory()
         )
     }
 }
 
+#[cfg(feature = "network")]
+impl fmt::Debug for Networks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "network")]
 impl fmt::Debug for NetworkData {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("NetworkData")
@@ -136,6 +173,22 @@ impl fmt::Debug for NetworkData {
                 "total errors outcome",
                 &self.get_total_errors_on_transmitted(),
             )
+            .field("drops income", &self.get_drops_on_received())
+            .field("total drops income", &self.get_total_drops_on_received())
+            .field("drops outcome", &self.get_drops_on_transmitted())
+            .field(
+                "total drops outcome",
+                &self.get_total_drops_on_transmitted(),
+            )
+            .field("multicast income", &self.get_packets_received_multicast())
+            .field(
+                "total multicast income",
+                &self.get_total_packets_received_multicast(),
+            )
+            .field("wifi signal", &self.get_wifi_signal())
+            .field("interface type", &self.get_interface_type())
+            .field("admin status", &self.get_admin_status())
+            .field("oper status", &self.get_oper_status())
             .finish()
     }
 }
@@ -12,6 +12,7 @@
 //! # Examples
 //!
 //! ```
+//! # #[cfg(all(feature = "process", feature = "component", feature = "disk"))] {
 //! use sysinfo::{ProcessExt, SystemExt};
 //!
 //! let mut system = sysinfo::System::new_all();
@@ -45,6 +46,7 @@
 //! println!("System kernel version:   {:?}", system.get_kernel_version());
 //! println!("System OS version:       {:?}", system.get_os_version());
 //! println!("System host name:        {:?}", system.get_host_name());
+//! # }
 //! ```
 
 #![crate_name = "sysinfo"]
@@ -56,7 +58,13 @@
 #![allow(clippy::upper_case_acronyms)]
 #![allow(renamed_and_removed_lints)]
 
-#[cfg(doctest)]
+#[cfg(all(
+    doctest,
+    feature = "network",
+    feature = "process",
+    feature = "disk",
+    feature = "component"
+))]
 doc_comment::doctest!("../README.md");
 
 #[cfg(feature = "debug")]
@@ -77,51 +85,97 @@ macro_rules! sysinfo_debug {
 
 cfg_if::cfg_if! {
     if #[cfg(any(target_os = "macos", target_os = "ios"))] {
+        #[cfg(any(feature = "network", feature = "system"))]
         mod apple;
+        #[cfg(any(feature = "network", feature = "system"))]
         use apple as sys;
         extern crate core_foundation_sys;
 
-        #[cfg(test)]
+        #[cfg(all(test, feature = "process"))]
         pub(crate) const MIN_USERS: usize = 1;
     } else if #[cfg(windows)] {
+        #[cfg(any(feature = "network", feature = "system"))]
         mod windows;
+        #[cfg(any(feature = "network", feature = "system"))]
         use windows as sys;
         extern crate winapi;
         extern crate ntapi;
 
-        #[cfg(test)]
+        #[cfg(all(test, feature = "process"))]
         pub(crate) const MIN_USERS: usize = 1;
     } else if #[cfg(any(target_os = "linux", target_os = "android"))] {
+        #[cfg(any(feature = "network", feature = "system"))]
         mod linux;
+        #[cfg(any(feature = "network", feature = "system"))]
         use linux as sys;
 
-        #[cfg(test)]
+        #[cfg(all(test, feature = "process"))]
         pub(crate) const MIN_USERS: usize = 1;
     } else {
+        #[cfg(any(feature = "network", feature = "system"))]
         mod unknown;
+        #[cfg(any(feature = "network", feature = "system"))]
         use unknown as sys;
 
-        #[cfg(test)]
+        #[cfg(all(test, feature = "process"))]
         pub(crate) const MIN_USERS: usize = 0;
     }
 }
 
+#[cfg(feature = "process")]
 pub use common::{
-    AsU32, DiskType, DiskUsage, Gid, LoadAvg, NetworksIter, Pid, RefreshKind, Signal, Uid, User,
+    AccessError, DiskUsage, NetworkUsage, PriorityError, ProcessRefreshKind, ProcessWatcher,
+    Signal, SocketInfo, SocketProtocol, SocketState, ThreadInfo,
 };
-pub use sys::{Component, Disk, NetworkData, Networks, Process, ProcessStatus, Processor, System};
-pub use traits::{
-    ComponentExt, DiskExt, NetworkExt, NetworksExt, ProcessExt, ProcessorExt, SystemExt, UserExt,
+pub use common::{AsU32, Pid};
+#[cfg(feature = "system")]
+pub use common::{
+    Battery, BatteryState, Gid, LoadAvg, RefreshKind, SwapDevice, SwapType, Uid, User,
+    Virtualization,
+};
+#[cfg(feature = "cpu")]
+pub use common::{CacheInfo, CacheType, CpuTimes};
+#[cfg(feature = "network")]
+pub use common::{
+    CountingBasis, InterfaceStatus, InterfaceType, NetworkDelta, NetworkStats, NetworksIter,
+    RefreshError,
 };
+#[cfg(feature = "disk")]
+pub use common::{DiskType, Mount};
+#[cfg(feature = "disk")]
+pub use sys::Disk;
+#[cfg(feature = "cpu")]
+pub use sys::Processor;
+#[cfg(feature = "component")]
+pub use sys::{Component, Fan};
+#[cfg(feature = "system")]
+pub use sys::{Gpu, System};
+#[cfg(feature = "network")]
+pub use sys::{NetworkData, Networks};
+#[cfg(feature = "process")]
+pub use sys::{Process, ProcessStatus};
+#[cfg(feature = "disk")]
+pub use traits::DiskExt;
+#[cfg(feature = "process")]
+pub use traits::ProcessExt;
+#[cfg(feature = "cpu")]
+pub use traits::ProcessorExt;
+#[cfg(feature = "component")]
+pub use traits::{ComponentExt, FanExt};
+#[cfg(feature = "system")]
+pub use traits::{GpuExt, SystemExt, UserExt};
+#[cfg(feature = "network")]
+pub use traits::{NetworkExt, NetworksExt};
 
 #[cfg(feature = "c-interface")]
 pub use c_interface::*;
-pub use utils::get_current_pid;
+pub use utils::{format_bytes, format_bytes_with, get_current_pid, UnitSystem};
 
 #[cfg(feature = "c-interface")]
 mod c_interface;
 mod common;
 mod debug;
+#[cfg(feature = "system")]
 mod system;
 mod traits;
 mod utils;
@@ -149,6 +203,7 @@ mod utils;
 /// }
 /// let s = System::new_all();
 /// ```
+#[cfg(feature = "process")]
 pub fn set_open_files_limit(mut _new_limit: isize) -> bool {
     #[cfg(any(target_os = "linux", target_os = "android"))]
     {
@@ -175,7 +230,7 @@ pub fn set_open_files_limit(mut _new_limit: isize) -> bool {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "process"))]
 mod test {
     use crate::*;
 
@@ -221,6 +276,47 @@ mod test {
         );
     }
 
+    #[test]
+    fn check_refresh_specifics() {
+        let mut s = System::new_with_specifics(RefreshKind::new().with_cpu());
+        assert!(s.get_processes().is_empty());
+        // We don't want to test on unsupported systems.
+        if MIN_USERS > 0 {
+            assert!(!s.get_processors().is_empty());
+        }
+        s.refresh_specifics(RefreshKind::new().with_cpu().with_memory());
+        assert!(s.get_processes().is_empty());
+        s.refresh_specifics(RefreshKind::new().with_networks_list());
+        assert!(s.get_processes().is_empty());
+
+        s.refresh_specifics(RefreshKind::new().with_processes());
+        if MIN_USERS > 0 {
+            assert!(!s.get_processes().is_empty());
+        }
+    }
+
+    #[test]
+    fn check_min_refresh_interval() {
+        let mut s = System::new();
+        s.set_min_refresh_interval(std::time::Duration::from_secs(60));
+        s.refresh_cpu();
+        let usages = s
+            .get_processors()
+            .iter()
+            .map(|p| p.get_cpu_usage())
+            .collect::<Vec<_>>();
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        // Called before the interval elapsed so nothing should be updated.
+        s.refresh_cpu();
+        assert_eq!(
+            usages,
+            s.get_processors()
+                .iter()
+                .map(|p| p.get_cpu_usage())
+                .collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn check_users() {
         let mut s = System::new();
@@ -260,6 +356,24 @@ mod test {
         }
     }
 
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn check_current_user_in_list() {
+        let pw = unsafe { libc::getpwuid(libc::getuid()) };
+        if pw.is_null() {
+            return;
+        }
+        let name = unsafe { std::ffi::CStr::from_ptr((*pw).pw_name) }.to_string_lossy();
+        let shell = unsafe { std::ffi::CStr::from_ptr((*pw).pw_shell) }.to_string_lossy();
+        // Users without a real shell are filtered out.
+        if shell.is_empty() || shell.ends_with("/false") || shell.ends_with("/nologin") {
+            return;
+        }
+        let mut s = System::new();
+        s.refresh_users_list();
+        assert!(s.get_users().iter().any(|user| user.get_name() == name));
+    }
+
     #[test]
     fn check_system_info() {
         // We don't want to test on unsupported systems.
@@ -296,6 +410,56 @@ mod test {
         }
     }
 
+    #[test]
+    fn check_memory_bytes() {
+        let mut s = System::new();
+        s.refresh_memory();
+        assert_eq!(s.get_total_memory_bytes(), s.get_total_memory() * 1_000);
+        assert_eq!(s.get_free_memory_bytes(), s.get_free_memory() * 1_000);
+        assert_eq!(
+            s.get_available_memory_bytes(),
+            s.get_available_memory() * 1_000
+        );
+        assert_eq!(s.get_used_memory_bytes(), s.get_used_memory() * 1_000);
+        assert_eq!(s.get_total_swap_bytes(), s.get_total_swap() * 1_000);
+        assert_eq!(s.get_free_swap_bytes(), s.get_free_swap() * 1_000);
+        assert_eq!(s.get_used_swap_bytes(), s.get_used_swap() * 1_000);
+    }
+
+    #[test]
+    fn check_cpu_arch() {
+        // We don't want to test on unsupported systems.
+        if MIN_USERS > 0 {
+            let s = System::new();
+            assert!(!s
+                .get_cpu_arch()
+                .expect("Failed to get CPU architecture")
+                .is_empty());
+        }
+    }
+
+    #[test]
+    fn check_cpu_features() {
+        // We don't want to test on unsupported systems.
+        if MIN_USERS > 0 {
+            let s = System::new();
+            let features = s.get_cpu_features();
+            // SSE2 is part of the x86_64 baseline.
+            if cfg!(target_arch = "x86_64") {
+                assert!(features.iter().any(|f| f == "sse2"));
+            }
+        }
+    }
+
+    #[test]
+    fn check_virtualization() {
+        let s = System::new();
+        let virt = s.get_virtualization();
+        if cfg!(target_os = "linux") && std::path::Path::new("/.dockerenv").exists() {
+            assert_eq!(virt, Virtualization::Docker);
+        }
+    }
+
     #[test]
     fn check_refresh_process_return_value() {
         // We don't want to test on unsupported systems.
@@ -309,10 +473,28 @@ mod test {
             assert!(s.refresh_process(pid));
         }
     }
+
+    #[test]
+    fn check_process_run_time() {
+        // We don't want to test on unsupported systems.
+        if MIN_USERS > 0 {
+            let pid = get_current_pid().expect("Failed to get current PID");
+            let mut s = System::new();
+            assert!(s.refresh_process(pid));
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .expect("Failed to get current time")
+                .as_secs();
+            let p = s.get_process(pid).expect("Failed to get current process");
+            assert!(p.start_time() > 0);
+            assert!(p.start_time() <= now);
+            assert!(p.run_time() <= now - p.start_time());
+        }
+    }
 }
 
 // Used to check that System is Send and Sync.
-#[cfg(doctest)]
+#[cfg(all(doctest, feature = "system"))]
 doc_comment::doc_comment!(
     "
 ```
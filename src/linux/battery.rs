@@ -0,0 +1,156 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::utils::get_file_line;
+use crate::{Battery, BatteryState};
+
+use std::fs::read_dir;
+use std::path::Path;
+
+fn read_u64(path: &Path) -> Option<u64> {
+    get_file_line(path, 20).and_then(|content| content.trim().parse::<u64>().ok())
+}
+
+#[derive(Default)]
+struct Values {
+    /// Energy (in µWh) or charge (in µAh) currently stored.
+    now: u64,
+    /// Energy (in µWh) or charge (in µAh) stored when full.
+    full: u64,
+    /// Power (in µW) or current (in µA).
+    rate: u64,
+}
+
+fn read_values(folder: &Path) -> Option<Values> {
+    // Depending on the driver, values are either provided as energy or as charge.
+    for (now, full, rate) in &[
+        ("energy_now", "energy_full", "power_now"),
+        ("charge_now", "charge_full", "current_now"),
+    ] {
+        if let (Some(now), Some(full)) = (read_u64(&folder.join(now)), read_u64(&folder.join(full)))
+        {
+            return Some(Values {
+                now,
+                full,
+                rate: read_u64(&folder.join(rate)).unwrap_or(0),
+            });
+        }
+    }
+    None
+}
+
+pub(crate) fn get_battery_from(folder: &Path) -> Option<Battery> {
+    let mut values = Values::default();
+    let mut capacities = Vec::new();
+    let mut states = Vec::new();
+
+    let dir = read_dir(folder).ok()?;
+    for entry in dir.flatten() {
+        let file_name = entry.file_name();
+        if !file_name.to_str().unwrap_or("").starts_with("BAT") {
+            continue;
+        }
+        let path = entry.path();
+        let capacity = read_u64(&path.join("capacity"));
+        let v = read_values(&path);
+        if capacity.is_none() && v.is_none() {
+            continue;
+        }
+        if let Some(v) = v {
+            values.now += v.now;
+            values.full += v.full;
+            values.rate += v.rate;
+        }
+        if let Some(capacity) = capacity {
+            capacities.push(capacity);
+        }
+        states.push(
+            match get_file_line(&path.join("status"), 16)
+                .as_deref()
+                .map(|s| s.trim())
+            {
+                Some("Charging") => BatteryState::Charging,
+                Some("Discharging") => BatteryState::Discharging,
+                Some("Full") => BatteryState::Full,
+                _ => BatteryState::Unknown,
+            },
+        );
+    }
+    if states.is_empty() {
+        return None;
+    }
+
+    let percentage = if values.full > 0 {
+        values.now as f32 * 100. / values.full as f32
+    } else if !capacities.is_empty() {
+        capacities.iter().sum::<u64>() as f32 / capacities.len() as f32
+    } else {
+        0.
+    };
+    let state = if states.contains(&BatteryState::Charging) {
+        BatteryState::Charging
+    } else if states.contains(&BatteryState::Discharging) {
+        BatteryState::Discharging
+    } else if states.iter().all(|s| *s == BatteryState::Full) {
+        BatteryState::Full
+    } else {
+        BatteryState::Unknown
+    };
+    let get_time = |amount: u64| (amount * 3_600).checked_div(values.rate);
+    Some(Battery {
+        percentage: percentage.min(100.),
+        state,
+        time_to_full: match state {
+            BatteryState::Charging => get_time(values.full.saturating_sub(values.now)),
+            _ => None,
+        },
+        time_to_empty: match state {
+            BatteryState::Discharging => get_time(values.now),
+            _ => None,
+        },
+    })
+}
+
+pub(crate) fn get_battery() -> Option<Battery> {
+    get_battery_from(Path::new("/sys/class/power_supply/"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::get_battery_from;
+    use crate::BatteryState;
+    use std::fs;
+
+    #[test]
+    fn check_battery() {
+        let folder = tempfile::tempdir().expect("failed to create temporary directory");
+        let folder = folder.path();
+        // Power supplies which aren't batteries are ignored.
+        fs::create_dir_all(folder.join("AC")).expect("failed to create AC folder");
+        fs::write(folder.join("AC/online"), "0\n").expect("failed to write online");
+        assert!(get_battery_from(folder).is_none());
+
+        let bat = folder.join("BAT0");
+        fs::create_dir_all(&bat).expect("failed to create battery folder");
+        fs::write(bat.join("status"), "Discharging\n").expect("failed to write status");
+        fs::write(bat.join("capacity"), "50\n").expect("failed to write capacity");
+        fs::write(bat.join("energy_now"), "20000000\n").expect("failed to write energy_now");
+        fs::write(bat.join("energy_full"), "40000000\n").expect("failed to write energy_full");
+        fs::write(bat.join("power_now"), "10000000\n").expect("failed to write power_now");
+
+        let battery = get_battery_from(folder).expect("failed to get battery");
+        assert_eq!(battery.percentage, 50.);
+        assert_eq!(battery.state, BatteryState::Discharging);
+        assert_eq!(battery.time_to_empty, Some(7_200));
+        assert_eq!(battery.time_to_full, None);
+
+        fs::write(bat.join("status"), "Charging\n").expect("failed to write status");
+        let battery = get_battery_from(folder).expect("failed to get battery");
+        assert_eq!(battery.state, BatteryState::Charging);
+        assert_eq!(battery.time_to_empty, None);
+        assert_eq!(battery.time_to_full, Some(7_200));
+    }
+}
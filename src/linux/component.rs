@@ -4,11 +4,11 @@
 // Copyright (c) 2018 Guillaume Gomez
 //
 
+use crate::utils::{get_file_line, is_file};
 use crate::ComponentExt;
 
 use std::collections::HashMap;
-use std::fs::{metadata, read_dir, File};
-use std::io::Read;
+use std::fs::read_dir;
 use std::path::{Path, PathBuf};
 
 /// More information can be found at [kernel.org][k].
@@ -26,23 +26,6 @@ pub struct Component {
     input_file: PathBuf,
 }
 
-fn get_file_line(file: &Path, capacity: usize) -> Option<String> {
-    let mut reader = String::with_capacity(capacity);
-    if let Ok(mut f) = File::open(file) {
-        if f.read_to_string(&mut reader).is_ok() {
-            Some(reader)
-        } else {
-            None
-        }
-    } else {
-        None
-    }
-}
-
-fn is_file<T: AsRef<Path>>(path: T) -> bool {
-    metadata(path).ok().map(|m| m.is_file()).unwrap_or(false)
-}
-
 fn append_files(components: &mut Vec<Component>, folder: &Path) {
     let mut matchings: HashMap<u32, Vec<String>> = HashMap::with_capacity(10);
 
@@ -154,6 +137,10 @@ impl ComponentExt for Component {
         &self.label
     }
 
+    fn get_device_path(&self) -> Option<&Path> {
+        Some(&self.input_file)
+    }
+
     fn refresh(&mut self) {
         if let Some(content) = get_file_line(self.input_file.as_path(), 10) {
             self.temperature = content
@@ -197,3 +184,62 @@ pub fn get_components() -> Vec<Component> {
     }
     components
 }
+
+#[cfg(test)]
+mod test {
+    use super::append_files;
+    use crate::ComponentExt;
+    use std::fs;
+
+    #[test]
+    fn check_critical_and_max() {
+        let hwmon = tempfile::tempdir().expect("failed to create temporary directory");
+        let hwmon = hwmon.path();
+        fs::write(hwmon.join("temp1_label"), "Core 0\n").expect("failed to write label");
+        fs::write(hwmon.join("temp1_input"), "40000\n").expect("failed to write input");
+        fs::write(hwmon.join("temp1_crit"), "95000\n").expect("failed to write crit");
+
+        let mut components = Vec::new();
+        append_files(&mut components, hwmon);
+        assert_eq!(components.len(), 1);
+        let component = &mut components[0];
+        assert_eq!(component.get_label(), "Core 0");
+        assert_eq!(component.get_temperature(), 40.);
+        assert_eq!(component.get_max(), 40.);
+        assert_eq!(component.get_critical(), Some(95.));
+
+        fs::write(hwmon.join("temp1_input"), "52000\n").expect("failed to write input");
+        component.refresh();
+        assert_eq!(component.get_temperature(), 52.);
+        assert_eq!(component.get_max(), 52.);
+
+        fs::write(hwmon.join("temp1_input"), "45000\n").expect("failed to write input");
+        component.refresh();
+        assert_eq!(component.get_temperature(), 45.);
+        assert_eq!(component.get_max(), 52.);
+    }
+
+    #[test]
+    fn check_device_path() {
+        let hwmon0 = tempfile::tempdir().expect("failed to create temporary directory");
+        let hwmon1 = tempfile::tempdir().expect("failed to create temporary directory");
+        for hwmon in &[hwmon0.path(), hwmon1.path()] {
+            fs::write(hwmon.join("temp1_label"), "Composite\n").expect("failed to write label");
+            fs::write(hwmon.join("temp1_input"), "40000\n").expect("failed to write input");
+        }
+
+        let mut components = Vec::new();
+        append_files(&mut components, hwmon0.path());
+        append_files(&mut components, hwmon1.path());
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].get_label(), components[1].get_label());
+        assert_eq!(
+            components[0].get_device_path(),
+            Some(hwmon0.path().join("temp1_input").as_path())
+        );
+        assert_eq!(
+            components[1].get_device_path(),
+            Some(hwmon1.path().join("temp1_input").as_path())
+        );
+    }
+}
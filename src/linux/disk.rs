@@ -5,14 +5,16 @@
 //
 
 use crate::sys::system::get_all_data;
-use crate::{utils, DiskExt, DiskType};
+use crate::{utils, DiskExt, DiskType, Mount};
 
 use libc::statvfs;
+use std::collections::HashMap;
 use std::ffi::{OsStr, OsString};
 use std::fs;
 use std::mem;
 use std::os::unix::ffi::OsStrExt;
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
 macro_rules! cast {
     ($x:expr) => {
@@ -24,11 +26,22 @@ macro_rules! cast {
 #[derive(PartialEq)]
 pub struct Disk {
     type_: DiskType,
+    is_removable: bool,
     name: OsString,
     file_system: Vec<u8>,
     mount_point: PathBuf,
     total_space: u64,
     available_space: u64,
+    mount_options: Vec<String>,
+    diskstats_name: Option<String>,
+    read_count: u64,
+    old_read_count: u64,
+    write_count: u64,
+    old_write_count: u64,
+    queue_depth: u64,
+    io_time: u64,
+    io_utilization: f32,
+    last_refresh: Instant,
 }
 
 impl DiskExt for Disk {
@@ -40,6 +53,11 @@ impl DiskExt for Disk {
         &self.name
     }
 
+    fn get_device_name(&self) -> &OsStr {
+        // On Linux, the disk name already is the device it was mounted from.
+        &self.name
+    }
+
     fn get_file_system(&self) -> &[u8] {
         &self.file_system
     }
@@ -56,6 +74,42 @@ impl DiskExt for Disk {
         self.available_space
     }
 
+    fn is_read_only(&self) -> bool {
+        self.mount_options.iter().any(|o| o == "ro")
+    }
+
+    fn is_removable(&self) -> bool {
+        self.is_removable
+    }
+
+    fn get_mount_options(&self) -> &[String] {
+        &self.mount_options
+    }
+
+    fn get_read_count(&self) -> u64 {
+        self.read_count.saturating_sub(self.old_read_count)
+    }
+
+    fn get_total_read_count(&self) -> u64 {
+        self.read_count
+    }
+
+    fn get_write_count(&self) -> u64 {
+        self.write_count.saturating_sub(self.old_write_count)
+    }
+
+    fn get_total_write_count(&self) -> u64 {
+        self.write_count
+    }
+
+    fn get_io_utilization(&self) -> f32 {
+        self.io_utilization
+    }
+
+    fn get_queue_depth(&self) -> u64 {
+        self.queue_depth
+    }
+
     fn refresh(&mut self) -> bool {
         unsafe {
             let mut stat: statvfs = mem::zeroed();
@@ -63,17 +117,91 @@ impl DiskExt for Disk {
             if statvfs(mount_point_cpath.as_ptr() as *const _, &mut stat) == 0 {
                 let tmp = cast!(stat.f_bsize) * cast!(stat.f_bavail);
                 self.available_space = cast!(tmp);
-                true
             } else {
-                false
+                return false;
             }
         }
+        if let Some(ref diskstats_name) = self.diskstats_name {
+            let diskstats =
+                get_disk_stats(&get_all_data("/proc/diskstats", 16_385).unwrap_or_default());
+            if let Some(stats) = diskstats.get(diskstats_name) {
+                let now = Instant::now();
+                let elapsed = now.duration_since(self.last_refresh).as_millis() as f32;
+                self.old_read_count = self.read_count;
+                self.read_count = stats.read_count;
+                self.old_write_count = self.write_count;
+                self.write_count = stats.write_count;
+                self.queue_depth = stats.in_flight;
+                if elapsed > 0. {
+                    let busy = stats.io_time.saturating_sub(self.io_time) as f32;
+                    self.io_utilization = (busy * 100. / elapsed).min(100.);
+                }
+                self.io_time = stats.io_time;
+                self.last_refresh = now;
+            }
+        }
+        true
+    }
+}
+
+/// Returns the name of the device `name` in `/proc/diskstats`, like `sda1` for `/dev/sda1` or
+/// `dm-0` for `/dev/mapper/root`.
+fn get_diskstats_name(name: &OsStr) -> Option<String> {
+    let name = Path::new(name);
+    if !name.starts_with("/dev/") {
+        return None;
     }
+    fs::canonicalize(name)
+        .ok()?
+        .file_name()?
+        .to_str()
+        .map(|s| s.to_owned())
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct DiskStats {
+    read_count: u64,
+    write_count: u64,
+    in_flight: u64,
+    /// Time spent doing I/O, in milliseconds.
+    io_time: u64,
+}
+
+/// Parses `/proc/diskstats` and returns the number of completed reads and writes (the fourth
+/// and eighth fields), the number of I/O in progress and the time spent doing I/O (the twelfth
+/// and thirteenth fields) of each device.
+fn get_disk_stats(diskstats: &str) -> HashMap<String, DiskStats> {
+    diskstats
+        .lines()
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            if fields.len() < 13 {
+                return None;
+            }
+            Some((
+                fields[2].to_owned(),
+                DiskStats {
+                    read_count: fields[3].parse().ok()?,
+                    write_count: fields[7].parse().ok()?,
+                    in_flight: fields[11].parse().ok()?,
+                    io_time: fields[12].parse().ok()?,
+                },
+            ))
+        })
+        .collect()
 }
 
-fn new_disk(name: &OsStr, mount_point: &Path, file_system: &[u8]) -> Option<Disk> {
+fn new_disk(
+    name: &OsStr,
+    mount_point: &Path,
+    file_system: &[u8],
+    mount_options: &str,
+    disk_stats: &HashMap<String, DiskStats>,
+) -> Option<Disk> {
     let mount_point_cpath = utils::to_cpath(mount_point);
-    let type_ = find_type_for_name(name);
+    let block_device = find_block_device_for_name(name);
+    let type_ = find_type_for_block_device(&block_device);
+    let is_removable = is_block_device_removable(&block_device);
     let mut total = 0;
     let mut available = 0;
     unsafe {
@@ -86,18 +214,52 @@ fn new_disk(name: &OsStr, mount_point: &Path, file_system: &[u8]) -> Option<Disk
     if total == 0 {
         return None;
     }
+    let diskstats_name = get_diskstats_name(name);
+    let stats = diskstats_name
+        .as_ref()
+        .and_then(|n| disk_stats.get(n))
+        .copied()
+        .unwrap_or_default();
     Some(Disk {
         type_,
+        is_removable,
         name: name.to_owned(),
         file_system: file_system.to_owned(),
         mount_point: mount_point.to_owned(),
         total_space: cast!(total),
         available_space: cast!(available),
+        mount_options: mount_options.split(',').map(|o| o.to_owned()).collect(),
+        diskstats_name,
+        read_count: stats.read_count,
+        old_read_count: stats.read_count,
+        write_count: stats.write_count,
+        old_write_count: stats.write_count,
+        queue_depth: stats.in_flight,
+        io_time: stats.io_time,
+        io_utilization: 0.,
+        last_refresh: Instant::now(),
     })
 }
 
+/// If `name` is a partition, returns the name of the block device it belongs to. In
+/// `/sys/class/block/`, partitions are symbolic links to a sub-folder of their block device
+/// (for example `.../block/vda/vda1`) and contain a `partition` file.
+fn get_parent_block_device(sys_class_block: &Path, name: &str) -> Option<String> {
+    let path = sys_class_block.join(name);
+    if !path.join("partition").exists() {
+        return None;
+    }
+    fs::canonicalize(path)
+        .ok()?
+        .parent()?
+        .file_name()?
+        .to_str()
+        .map(|s| s.to_owned())
+}
+
+/// Returns the `/sys/block/` folder of the block device the given device belongs to.
 #[allow(clippy::manual_range_contains)]
-fn find_type_for_name(name: &OsStr) -> DiskType {
+fn find_block_device_for_name(name: &OsStr) -> PathBuf {
     // The format of devices are as follows:
     //  - name_path is symbolic link in the case of /dev/mapper/
     //     and /dev/root, and the target is corresponding device under
@@ -114,7 +276,7 @@ fn find_type_for_name(name: &OsStr) -> DiskType {
     if name_path.starts_with("/dev/mapper/") {
         // Recursively solve, for example /dev/dm-0
         if real_path != name_path {
-            return find_type_for_name(OsStr::new(&real_path));
+            return find_block_device_for_name(OsStr::new(&real_path));
         }
     } else if name_path.starts_with("/dev/sd") {
         // Turn "sda1" into "sda"
@@ -128,7 +290,7 @@ fn find_type_for_name(name: &OsStr) -> DiskType {
     } else if name_path.starts_with("/dev/root") {
         // Recursively solve, for example /dev/mmcblk0p1
         if real_path != name_path {
-            return find_type_for_name(OsStr::new(&real_path));
+            return find_block_device_for_name(OsStr::new(&real_path));
         }
     } else if name_path.starts_with("/dev/mmcblk") {
         // Turn "mmcblk0p1" into "mmcblk0"
@@ -141,12 +303,23 @@ fn find_type_for_name(name: &OsStr) -> DiskType {
         real_path = real_path.trim_start_matches("/dev/");
     }
 
+    // The partition naming isn't always handled above (like with "vda1" or "xvda1") so if there
+    // is no block device with this name, we look for the device this partition belongs to.
+    let parent;
+    if !Path::new("/sys/block/").join(real_path).exists() {
+        if let Some(p) = get_parent_block_device(Path::new("/sys/class/block/"), real_path) {
+            parent = p;
+            real_path = &parent;
+        }
+    }
+
     let trimmed: &OsStr = OsStrExt::from_bytes(real_path.as_bytes());
 
-    let path = Path::new("/sys/block/")
-        .to_owned()
-        .join(trimmed)
-        .join("queue/rotational");
+    Path::new("/sys/block/").join(trimmed)
+}
+
+fn find_type_for_block_device(block_device: &Path) -> DiskType {
+    let path = block_device.join("queue/rotational");
     // Normally, this file only contains '0' or '1' but just in case, we get 8 bytes...
     match get_all_data(path, 8)
         .unwrap_or_default()
@@ -165,27 +338,39 @@ fn find_type_for_name(name: &OsStr) -> DiskType {
     }
 }
 
-fn get_all_disks_inner(content: &str) -> Vec<Disk> {
+fn is_block_device_removable(block_device: &Path) -> bool {
+    get_all_data(block_device.join("removable"), 8)
+        .map(|content| content.trim() == "1")
+        .unwrap_or(false)
+}
+
+/// Returns the `fs_spec`, `fs_file`, `fs_vfstype` and `fs_mntops` fields of a `/proc/mounts`
+/// line.
+fn parse_mounts_line(line: &str) -> (&str, String, &str, &str) {
+    let line = line.trim_start();
+    // mounts format
+    // http://man7.org/linux/man-pages/man5/fstab.5.html
+    // fs_spec<tab>fs_file<tab>fs_vfstype<tab>fs_mntops<tab>other fields
+    let mut fields = line.split_whitespace();
+    let fs_spec = fields.next().unwrap_or("");
+    let fs_file = fields
+        .next()
+        .unwrap_or("")
+        .replace("\\134", "\\")
+        .replace("\\040", " ")
+        .replace("\\011", "\t")
+        .replace("\\012", "\n");
+    let fs_vfstype = fields.next().unwrap_or("");
+    let fs_mntops = fields.next().unwrap_or("");
+    (fs_spec, fs_file, fs_vfstype, fs_mntops)
+}
+
+fn get_all_disks_inner(content: &str, diskstats: &str) -> Vec<Disk> {
+    let disk_stats = get_disk_stats(diskstats);
     content
         .lines()
-        .map(|line| {
-            let line = line.trim_start();
-            // mounts format
-            // http://man7.org/linux/man-pages/man5/fstab.5.html
-            // fs_spec<tab>fs_file<tab>fs_vfstype<tab>other fields
-            let mut fields = line.split_whitespace();
-            let fs_spec = fields.next().unwrap_or("");
-            let fs_file = fields
-                .next()
-                .unwrap_or("")
-                .replace("\\134", "\\")
-                .replace("\\040", " ")
-                .replace("\\011", "\t")
-                .replace("\\012", "\n");
-            let fs_vfstype = fields.next().unwrap_or("");
-            (fs_spec, fs_file, fs_vfstype)
-        })
-        .filter(|(fs_spec, fs_file, fs_vfstype)| {
+        .map(parse_mounts_line)
+        .filter(|(fs_spec, fs_file, fs_vfstype, _)| {
             // Check if fs_vfstype is one of our 'ignored' file systems.
             let filtered = matches!(
                 *fs_vfstype,
@@ -207,14 +392,45 @@ fn get_all_disks_inner(content: &str) -> Vec<Disk> {
                (fs_file.starts_with("/run") && !fs_file.starts_with("/run/media")) ||
                fs_spec.starts_with("sunrpc"))
         })
-        .filter_map(|(fs_spec, fs_file, fs_vfstype)| {
-            new_disk(fs_spec.as_ref(), Path::new(&fs_file), fs_vfstype.as_bytes())
+        .filter_map(|(fs_spec, fs_file, fs_vfstype, fs_mntops)| {
+            new_disk(
+                fs_spec.as_ref(),
+                Path::new(&fs_file),
+                fs_vfstype.as_bytes(),
+                fs_mntops,
+                &disk_stats,
+            )
         })
         .collect()
 }
 
 pub fn get_all_disks() -> Vec<Disk> {
-    get_all_disks_inner(&get_all_data("/proc/mounts", 16_385).unwrap_or_default())
+    get_all_disks_inner(
+        &get_all_data("/proc/mounts", 16_385).unwrap_or_default(),
+        &get_all_data("/proc/diskstats", 16_385).unwrap_or_default(),
+    )
+}
+
+/// Parses the `/proc/mounts` file, without filtering out anything.
+fn parse_mounts(content: &str) -> Vec<Mount> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (fs_spec, fs_file, fs_vfstype, fs_mntops) = parse_mounts_line(line);
+            Mount {
+                device: fs_spec.to_owned(),
+                mount_point: PathBuf::from(fs_file),
+                file_system: fs_vfstype.to_owned(),
+                options: fs_mntops.split(',').map(|o| o.to_owned()).collect(),
+            }
+        })
+        .collect()
+}
+
+/// `mounts` is the `/proc/mounts` file.
+pub(crate) fn get_all_mounts(mounts: &Path) -> Vec<Mount> {
+    parse_mounts(&get_all_data(mounts, 16_385).unwrap_or_default())
 }
 
 // #[test]
@@ -248,6 +464,172 @@ pub fn get_all_disks() -> Vec<Disk> {
 //             mount_point: PathBuf::from("/dev/pts"),
 //             total_space: 0,
 //             available_space: 0,
+//             mount_options: Vec::new(),
 //         }
 //     );
 // }
+
+#[cfg(test)]
+mod test {
+    use super::{
+        get_all_disks_inner, get_disk_stats, get_parent_block_device, parse_mounts, Disk, DiskStats,
+    };
+    use crate::traits::sum_disks;
+    use crate::{DiskExt, DiskType, Mount};
+    use std::fs;
+    use std::path::PathBuf;
+    use std::time::Instant;
+
+    fn new_fake_disk(name: &str, mount_point: &str, total_space: u64) -> Disk {
+        Disk {
+            type_: DiskType::SSD,
+            is_removable: false,
+            name: name.into(),
+            file_system: b"ext4".to_vec(),
+            mount_point: PathBuf::from(mount_point),
+            total_space,
+            available_space: total_space / 2,
+            mount_options: Vec::new(),
+            diskstats_name: None,
+            read_count: 0,
+            old_read_count: 0,
+            write_count: 0,
+            old_write_count: 0,
+            queue_depth: 0,
+            io_time: 0,
+            io_utilization: 0.,
+            last_refresh: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn check_read_only_mount() {
+        let disks = get_all_disks_inner(
+            "/dev/sda1 / ext4 ro,relatime 0 0\n/dev/sda1 / ext4 rw,nosuid,noexec 0 0\n",
+            "",
+        );
+        assert_eq!(disks.len(), 2);
+        assert!(disks[0].is_read_only());
+        assert_eq!(disks[0].get_mount_options(), &["ro", "relatime"]);
+        assert!(!disks[1].is_read_only());
+        assert_eq!(disks[1].get_mount_options(), &["rw", "nosuid", "noexec"]);
+    }
+
+    #[test]
+    fn check_parse_mounts() {
+        let mounts = parse_mounts(
+            "proc /proc proc rw,nosuid,nodev,noexec,relatime 0 0\n\
+             sysfs /sys sysfs rw,nosuid 0 0\n\
+             /dev/sda1 /media/my\\040disk ext4 ro,relatime 0 0\n",
+        );
+        assert_eq!(
+            mounts,
+            [
+                Mount {
+                    device: "proc".to_owned(),
+                    mount_point: PathBuf::from("/proc"),
+                    file_system: "proc".to_owned(),
+                    options: vec![
+                        "rw".to_owned(),
+                        "nosuid".to_owned(),
+                        "nodev".to_owned(),
+                        "noexec".to_owned(),
+                        "relatime".to_owned(),
+                    ],
+                },
+                Mount {
+                    device: "sysfs".to_owned(),
+                    mount_point: PathBuf::from("/sys"),
+                    file_system: "sysfs".to_owned(),
+                    options: vec!["rw".to_owned(), "nosuid".to_owned()],
+                },
+                Mount {
+                    device: "/dev/sda1".to_owned(),
+                    mount_point: PathBuf::from("/media/my disk"),
+                    file_system: "ext4".to_owned(),
+                    options: vec!["ro".to_owned(), "relatime".to_owned()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn check_sum_disks_deduplicates_devices() {
+        let disks = get_all_disks_inner(
+            "/dev/sda1 / ext4 rw,relatime 0 0\n/dev/sda1 / ext4 rw,relatime 0 0\n",
+            "",
+        );
+        assert_eq!(disks.len(), 2);
+        assert_eq!(
+            sum_disks(&disks, DiskExt::get_total_space),
+            disks[0].get_total_space()
+        );
+        assert_eq!(
+            sum_disks(&disks[..1], DiskExt::get_total_space),
+            disks[0].get_total_space()
+        );
+        assert_eq!(sum_disks(&[], DiskExt::get_total_space), 0);
+    }
+
+    #[test]
+    fn check_sum_disks_shared_device() {
+        // `/dev/sda1` is mounted twice, its space must only be counted once.
+        let disks = [
+            new_fake_disk("/dev/sda1", "/", 1_000),
+            new_fake_disk("/dev/sdb1", "/data", 300),
+            new_fake_disk("/dev/sda1", "/mnt/bind", 1_000),
+        ];
+        assert_eq!(sum_disks(&disks, DiskExt::get_total_space), 1_300);
+        assert_eq!(sum_disks(&disks, DiskExt::get_available_space), 650);
+    }
+
+    #[test]
+    fn check_parent_block_device() {
+        let dir = tempfile::tempdir().expect("failed to create temporary directory");
+        let devices = dir.path().join("devices");
+        let class = dir.path().join("class");
+        fs::create_dir_all(devices.join("vda/vda1")).expect("failed to create vda1 folder");
+        fs::create_dir_all(&class).expect("failed to create class folder");
+        fs::write(devices.join("vda/vda1/partition"), "1\n").expect("failed to write partition");
+        std::os::unix::fs::symlink(devices.join("vda"), class.join("vda"))
+            .expect("failed to create vda link");
+        std::os::unix::fs::symlink(devices.join("vda/vda1"), class.join("vda1"))
+            .expect("failed to create vda1 link");
+
+        assert_eq!(
+            get_parent_block_device(&class, "vda1"),
+            Some("vda".to_owned())
+        );
+        // Not a partition.
+        assert_eq!(get_parent_block_device(&class, "vda"), None);
+        assert_eq!(get_parent_block_device(&class, "unknown"), None);
+    }
+
+    #[test]
+    fn check_disk_stats() {
+        let stats = get_disk_stats(
+            "   8       0 sda 51420 13722 3790254 27328 86190 86925 4392224 99264 0 84116 135600\n\
+                8       1 sda1 51300 13722 3784894 27288 86172 86925 4392224 99248 0 84088 126536\n\
+                7       0 loop0 0 0\n",
+        );
+        assert_eq!(stats.len(), 2);
+        assert_eq!(
+            stats["sda"],
+            DiskStats {
+                read_count: 51420,
+                write_count: 86190,
+                in_flight: 0,
+                io_time: 84116,
+            }
+        );
+        assert_eq!(
+            stats["sda1"],
+            DiskStats {
+                read_count: 51300,
+                write_count: 86172,
+                in_flight: 0,
+                io_time: 84088,
+            }
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::utils::{get_file_line, is_file};
+use crate::FanExt;
+
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
+
+/// Struct containing a fan information (speed and name for the moment).
+///
+/// Fans are read from the `fan*_input` files of the [hwmon][k] interface.
+///
+/// [k]: https://www.kernel.org/doc/Documentation/hwmon/sysfs-interface
+pub struct Fan {
+    rpm: u32,
+    label: String,
+    input_file: PathBuf,
+}
+
+impl Fan {
+    /// Creates a new fan with the given information.
+    pub(crate) fn new(label: String, input_path: &Path) -> Fan {
+        let mut f = Fan {
+            rpm: 0,
+            label,
+            input_file: input_path.to_path_buf(),
+        };
+        f.refresh();
+        f
+    }
+}
+
+impl FanExt for Fan {
+    fn get_rpm(&self) -> u32 {
+        self.rpm
+    }
+
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    fn refresh(&mut self) {
+        if let Some(content) = get_file_line(self.input_file.as_path(), 10) {
+            self.rpm = content.trim().parse::<u32>().unwrap_or(0);
+        }
+    }
+}
+
+fn append_files(fans: &mut Vec<Fan>, folder: &Path) {
+    let mut ids = Vec::new();
+
+    if let Ok(dir) = read_dir(folder) {
+        for entry in dir.flatten() {
+            let file_name = entry.file_name();
+            let file_name = file_name.to_str().unwrap_or("");
+            if !file_name.starts_with("fan") || !file_name.ends_with("_input") {
+                continue;
+            }
+            if let Ok(id) = file_name[3..file_name.len() - 6].parse::<u32>() {
+                ids.push(id);
+            }
+        }
+    }
+    ids.sort_unstable();
+    for id in ids {
+        let mut p_label = folder.to_path_buf();
+        let mut p_input = folder.to_path_buf();
+
+        p_label.push(format!("fan{}_label", id));
+        p_input.push(format!("fan{}_input", id));
+        if is_file(&p_input) {
+            let label = get_file_line(p_label.as_path(), 10)
+                .map(|label| label.trim().to_owned())
+                .unwrap_or_else(|| format!("Fan {}", id));
+            fans.push(Fan::new(label, p_input.as_path()));
+        }
+    }
+}
+
+pub fn get_fans() -> Vec<Fan> {
+    let mut fans = Vec::new();
+    if let Ok(dir) = read_dir(Path::new("/sys/class/hwmon/")) {
+        for entry in dir.flatten() {
+            let entry = entry.path();
+            if !entry.is_dir()
+                || !entry
+                    .file_name()
+                    .and_then(|x| x.to_str())
+                    .unwrap_or("")
+                    .starts_with("hwmon")
+            {
+                continue;
+            }
+            append_files(&mut fans, &entry);
+        }
+    }
+    fans
+}
+
+#[cfg(test)]
+mod test {
+    use super::append_files;
+    use crate::FanExt;
+    use std::fs;
+
+    #[test]
+    fn check_fans() {
+        let hwmon = tempfile::tempdir().expect("failed to create temporary directory");
+        let hwmon = hwmon.path();
+        fs::write(hwmon.join("fan1_input"), "1200\n").expect("failed to write input");
+        fs::write(hwmon.join("fan1_label"), "CPU fan\n").expect("failed to write label");
+        fs::write(hwmon.join("fan2_input"), "0\n").expect("failed to write input");
+        fs::write(hwmon.join("temp1_input"), "40000\n").expect("failed to write input");
+
+        let mut fans = Vec::new();
+        append_files(&mut fans, hwmon);
+        assert_eq!(fans.len(), 2);
+        assert_eq!(fans[0].get_label(), "CPU fan");
+        assert_eq!(fans[0].get_rpm(), 1200);
+        assert_eq!(fans[1].get_label(), "Fan 2");
+        assert_eq!(fans[1].get_rpm(), 0);
+
+        fs::write(hwmon.join("fan1_input"), "1800\n").expect("failed to write input");
+        fans[0].refresh();
+        assert_eq!(fans[0].get_rpm(), 1800);
+    }
+}
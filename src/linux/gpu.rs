@@ -0,0 +1,339 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::utils::get_file_line;
+use crate::GpuExt;
+
+use std::fs::{read_dir, read_link};
+use std::path::{Path, PathBuf};
+
+enum Source {
+    /// Path to the `device` folder of the DRM card.
+    Drm(PathBuf),
+    /// Index of the device in NVML.
+    #[cfg(feature = "nvml")]
+    Nvml(u32),
+}
+
+/// Struct containing a GPU information.
+///
+/// GPUs are read from the `/sys/class/drm` interface. Only the `amdgpu` driver exposes memory
+/// and usage information this way. If the `nvml` feature is enabled, NVIDIA GPUs are read using
+/// the NVML library (if available) instead.
+pub struct Gpu {
+    name: String,
+    total_memory: u64,
+    used_memory: u64,
+    usage: f32,
+    source: Source,
+}
+
+impl Gpu {
+    fn new(name: String, source: Source) -> Gpu {
+        let mut gpu = Gpu {
+            name,
+            total_memory: 0,
+            used_memory: 0,
+            usage: 0.,
+            source,
+        };
+        gpu.refresh();
+        gpu
+    }
+}
+
+fn read_u64(path: &Path) -> Option<u64> {
+    get_file_line(path, 20).and_then(|content| content.trim().parse::<u64>().ok())
+}
+
+impl GpuExt for Gpu {
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_total_memory(&self) -> u64 {
+        self.total_memory
+    }
+
+    fn get_used_memory(&self) -> u64 {
+        self.used_memory
+    }
+
+    fn get_usage(&self) -> f32 {
+        self.usage
+    }
+
+    fn refresh(&mut self) {
+        match self.source {
+            Source::Drm(ref device) => {
+                if let Some(total) = read_u64(&device.join("mem_info_vram_total")) {
+                    self.total_memory = total / 1_000;
+                }
+                if let Some(used) = read_u64(&device.join("mem_info_vram_used")) {
+                    self.used_memory = used / 1_000;
+                }
+                if let Some(usage) = read_u64(&device.join("gpu_busy_percent")) {
+                    self.usage = usage as f32;
+                }
+            }
+            #[cfg(feature = "nvml")]
+            Source::Nvml(index) => {
+                if let Some(info) = nvml::get_device_info(index) {
+                    self.total_memory = info.total_memory / 1_000;
+                    self.used_memory = info.used_memory / 1_000;
+                    self.usage = info.usage as f32;
+                }
+            }
+        }
+    }
+}
+
+fn get_drm_gpu_name(device: &Path, driver: &str) -> String {
+    if let Some(name) = get_file_line(&device.join("product_name"), 64) {
+        let name = name.trim();
+        if !name.is_empty() {
+            return name.to_owned();
+        }
+    }
+    let get_id = |file: &str| {
+        get_file_line(&device.join(file), 10)
+            .map(|id| id.trim().trim_start_matches("0x").to_owned())
+            .unwrap_or_default()
+    };
+    format!(
+        "{} ({}:{})",
+        if driver.is_empty() { "unknown" } else { driver },
+        get_id("vendor"),
+        get_id("device")
+    )
+}
+
+fn append_drm_gpus(gpus: &mut Vec<Gpu>, folder: &Path) {
+    let mut cards = Vec::new();
+
+    if let Ok(dir) = read_dir(folder) {
+        for entry in dir.flatten() {
+            let file_name = entry.file_name();
+            let file_name = file_name.to_str().unwrap_or("");
+            // We want to skip connectors (like "card0-HDMI-A-1") and render nodes.
+            if let Some(id) = file_name
+                .strip_prefix("card")
+                .and_then(|id| id.parse::<u32>().ok())
+            {
+                cards.push((id, entry.path()));
+            }
+        }
+    }
+    cards.sort_unstable_by_key(|(id, _)| *id);
+    for (_, card) in cards {
+        let device = card.join("device");
+        let driver = read_link(device.join("driver"))
+            .ok()
+            .and_then(|driver| driver.file_name()?.to_str().map(|d| d.to_owned()))
+            .unwrap_or_default();
+        // NVIDIA GPUs are handled through NVML.
+        #[cfg(feature = "nvml")]
+        {
+            if driver == "nvidia" && nvml::is_available() {
+                continue;
+            }
+        }
+        let name = get_drm_gpu_name(&device, &driver);
+        gpus.push(Gpu::new(name, Source::Drm(device)));
+    }
+}
+
+pub fn get_gpus() -> Vec<Gpu> {
+    let mut gpus = Vec::new();
+    append_drm_gpus(&mut gpus, Path::new("/sys/class/drm/"));
+    #[cfg(feature = "nvml")]
+    {
+        for (index, name) in nvml::get_devices() {
+            gpus.push(Gpu::new(name, Source::Nvml(index)));
+        }
+    }
+    gpus
+}
+
+/// Minimal NVML bindings. The library is loaded at runtime so `sysinfo` can still be used on
+/// systems without the NVIDIA driver.
+#[cfg(feature = "nvml")]
+mod nvml {
+    use libc::{c_char, c_int, c_uint, c_void};
+    use once_cell::sync::Lazy;
+
+    use std::ffi::CStr;
+
+    type Device = *mut c_void;
+
+    #[repr(C)]
+    struct Memory {
+        total: u64,
+        free: u64,
+        used: u64,
+    }
+
+    #[repr(C)]
+    struct Utilization {
+        gpu: c_uint,
+        memory: c_uint,
+    }
+
+    const SUCCESS: c_int = 0;
+    const NAME_BUFFER_SIZE: usize = 96;
+
+    struct Library {
+        device_get_count: unsafe extern "C" fn(*mut c_uint) -> c_int,
+        device_get_handle_by_index: unsafe extern "C" fn(c_uint, *mut Device) -> c_int,
+        device_get_name: unsafe extern "C" fn(Device, *mut c_char, c_uint) -> c_int,
+        device_get_memory_info: unsafe extern "C" fn(Device, *mut Memory) -> c_int,
+        device_get_utilization_rates: unsafe extern "C" fn(Device, *mut Utilization) -> c_int,
+    }
+
+    /// `T` must be the function pointer type matching the symbol.
+    unsafe fn get_symbol<T>(lib: *mut c_void, name: &[u8]) -> Option<T> {
+        let symbol = libc::dlsym(lib, name.as_ptr() as *const c_char);
+        if symbol.is_null() {
+            None
+        } else {
+            Some(std::mem::transmute_copy(&symbol))
+        }
+    }
+
+    impl Library {
+        unsafe fn load() -> Option<Library> {
+            let lib = libc::dlopen(
+                b"libnvidia-ml.so.1\0".as_ptr() as *const c_char,
+                libc::RTLD_NOW,
+            );
+            if lib.is_null() {
+                return None;
+            }
+            let init: unsafe extern "C" fn() -> c_int = get_symbol(lib, b"nvmlInit_v2\0")?;
+            if init() != SUCCESS {
+                sysinfo_debug!("nvmlInit_v2 failed");
+                libc::dlclose(lib);
+                return None;
+            }
+            // The library is never unloaded so the function pointers remain valid.
+            Some(Library {
+                device_get_count: get_symbol(lib, b"nvmlDeviceGetCount_v2\0")?,
+                device_get_handle_by_index: get_symbol(lib, b"nvmlDeviceGetHandleByIndex_v2\0")?,
+                device_get_name: get_symbol(lib, b"nvmlDeviceGetName\0")?,
+                device_get_memory_info: get_symbol(lib, b"nvmlDeviceGetMemoryInfo\0")?,
+                device_get_utilization_rates: get_symbol(lib, b"nvmlDeviceGetUtilizationRates\0")?,
+            })
+        }
+
+        unsafe fn get_device(&self, index: u32) -> Option<Device> {
+            let mut device = std::ptr::null_mut();
+            if (self.device_get_handle_by_index)(index, &mut device) == SUCCESS {
+                Some(device)
+            } else {
+                None
+            }
+        }
+    }
+
+    static LIBRARY: Lazy<Option<Library>> = Lazy::new(|| unsafe { Library::load() });
+
+    pub(super) struct DeviceInfo {
+        pub(super) total_memory: u64,
+        pub(super) used_memory: u64,
+        pub(super) usage: u32,
+    }
+
+    pub(super) fn is_available() -> bool {
+        LIBRARY.is_some()
+    }
+
+    pub(super) fn get_devices() -> Vec<(u32, String)> {
+        let lib = match *LIBRARY {
+            Some(ref lib) => lib,
+            None => return Vec::new(),
+        };
+        unsafe {
+            let mut count = 0;
+            if (lib.device_get_count)(&mut count) != SUCCESS {
+                return Vec::new();
+            }
+            (0..count)
+                .filter_map(|index| {
+                    let device = lib.get_device(index)?;
+                    let mut name = [0 as c_char; NAME_BUFFER_SIZE];
+                    if (lib.device_get_name)(device, name.as_mut_ptr(), NAME_BUFFER_SIZE as _)
+                        != SUCCESS
+                    {
+                        return None;
+                    }
+                    let name = CStr::from_ptr(name.as_ptr()).to_string_lossy().into_owned();
+                    Some((index, name))
+                })
+                .collect()
+        }
+    }
+
+    pub(super) fn get_device_info(index: u32) -> Option<DeviceInfo> {
+        let lib = LIBRARY.as_ref()?;
+        unsafe {
+            let device = lib.get_device(index)?;
+            let mut memory = std::mem::zeroed::<Memory>();
+            if (lib.device_get_memory_info)(device, &mut memory) != SUCCESS {
+                return None;
+            }
+            let mut utilization = std::mem::zeroed::<Utilization>();
+            if (lib.device_get_utilization_rates)(device, &mut utilization) != SUCCESS {
+                return None;
+            }
+            Some(DeviceInfo {
+                total_memory: memory.total,
+                used_memory: memory.used,
+                usage: utilization.gpu,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::append_drm_gpus;
+    use crate::GpuExt;
+    use std::fs;
+
+    #[test]
+    fn check_drm_gpus() {
+        let drm = tempfile::tempdir().expect("failed to create temporary directory");
+        let drm = drm.path();
+        let device = drm.join("card0/device");
+        fs::create_dir_all(&device).expect("failed to create device folder");
+        fs::create_dir_all(drm.join("card0-HDMI-A-1")).expect("failed to create connector");
+        fs::create_dir_all(drm.join("renderD128")).expect("failed to create render node");
+        fs::write(device.join("product_name"), "Some GPU\n").expect("failed to write name");
+        fs::write(device.join("mem_info_vram_total"), "8000000000\n")
+            .expect("failed to write total memory");
+        fs::write(device.join("mem_info_vram_used"), "1000000\n")
+            .expect("failed to write used memory");
+        fs::write(device.join("gpu_busy_percent"), "12\n").expect("failed to write usage");
+        let other = drm.join("card1/device");
+        fs::create_dir_all(&other).expect("failed to create device folder");
+        fs::write(other.join("vendor"), "0x8086\n").expect("failed to write vendor");
+        fs::write(other.join("device"), "0x9a49\n").expect("failed to write device");
+
+        let mut gpus = Vec::new();
+        append_drm_gpus(&mut gpus, drm);
+        assert_eq!(gpus.len(), 2);
+        assert_eq!(gpus[0].get_name(), "Some GPU");
+        assert_eq!(gpus[0].get_total_memory(), 8_000_000);
+        assert_eq!(gpus[0].get_used_memory(), 1_000);
+        assert_eq!(gpus[0].get_usage(), 12.);
+        assert_eq!(gpus[1].get_name(), "unknown (8086:9a49)");
+        assert_eq!(gpus[1].get_total_memory(), 0);
+
+        fs::write(device.join("gpu_busy_percent"), "50\n").expect("failed to write usage");
+        gpus[0].refresh();
+        assert_eq!(gpus[0].get_usage(), 50.);
+    }
+}
@@ -4,17 +4,42 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
+#[cfg(feature = "system")]
+pub mod battery;
+#[cfg(feature = "component")]
 pub mod component;
+#[cfg(feature = "disk")]
 pub mod disk;
+#[cfg(feature = "component")]
+pub mod fan;
+#[cfg(feature = "system")]
+pub mod gpu;
+#[cfg(feature = "network")]
 pub mod network;
+#[cfg(feature = "process")]
 pub mod process;
+#[cfg(feature = "cpu")]
 pub mod processor;
+#[cfg(feature = "process")]
+pub mod socket;
+#[cfg(feature = "system")]
 pub mod system;
+#[cfg(feature = "system")]
 pub mod users;
 
+#[cfg(feature = "component")]
 pub use self::component::Component;
+#[cfg(feature = "disk")]
 pub use self::disk::Disk;
+#[cfg(feature = "component")]
+pub use self::fan::Fan;
+#[cfg(feature = "system")]
+pub use self::gpu::Gpu;
+#[cfg(feature = "network")]
 pub use self::network::{NetworkData, Networks};
+#[cfg(feature = "process")]
 pub use self::process::{Process, ProcessStatus};
+#[cfg(feature = "cpu")]
 pub use self::processor::Processor;
+#[cfg(feature = "system")]
 pub use self::system::System;
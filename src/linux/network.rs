@@ -5,29 +5,40 @@
 //
 
 use std::fs::File;
-use std::io::Read;
-use std::path::Path;
+use std::io::{self, Read};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::os::unix::io::AsRawFd;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
-use crate::{NetworkExt, NetworksExt, NetworksIter};
-use std::collections::{hash_map, HashMap};
+use crate::common::NetworkHistory;
+use crate::utils::{get_rate, RefreshInterval};
+use crate::{
+    CountingBasis, InterfaceStatus, InterfaceType, NetworkExt, NetworksExt, NetworksIter,
+    RefreshError,
+};
+use std::collections::{hash_map, HashMap, VecDeque};
 
 /// Network interfaces.
 ///
 /// ```no_run
-/// use sysinfo::{NetworksExt, System, SystemExt};
+/// use sysinfo::{Networks, NetworksExt};
 ///
-/// let s = System::new_all();
-/// let networks = s.get_networks();
+/// let mut networks = Networks::new();
+/// networks.refresh_networks_list();
 /// ```
+#[derive(Clone)]
 pub struct Networks {
     interfaces: HashMap<String, NetworkData>,
+    history_len: usize,
+    include_loopback: bool,
+    refresh_interval: RefreshInterval,
+    netns: Option<PathBuf>,
+    /// Directory containing the `proc` and `sys` directories to read.
+    sysroot: PathBuf,
 }
 
 macro_rules! old_and_new {
-    ($ty_:expr, $name:ident, $old:ident) => {{
-        $ty_.$old = $ty_.$name;
-        $ty_.$name = $name;
-    }};
     ($ty_:expr, $name:ident, $old:ident, $path:expr) => {{
         let _tmp = $path;
         $ty_.$old = $ty_.$name;
@@ -52,78 +63,524 @@ fn read<P: AsRef<Path>>(parent: P, path: &str, data: &mut Vec<u8>) -> u64 {
     0
 }
 
+/// The type values are the `ARPHRD_*` constants from `include/uapi/linux/if_arp.h`.
+fn get_interface_type(path: &Path, data: &mut Vec<u8>) -> InterfaceType {
+    match read(path, "type", data) {
+        // Wireless interfaces are also reported as ethernet ones.
+        1 if path.join("wireless").exists() || path.join("phy80211").exists() => {
+            InterfaceType::WiFi
+        }
+        1 => InterfaceType::Ethernet,
+        772 => InterfaceType::Loopback,
+        801 => InterfaceType::WiFi,
+        // IPIP, IPv6 in IPv6, SIT, GRE, IPv6 GRE and "none" (used by TUN devices).
+        768 | 769 | 776 | 778 | 823 | 65534 => InterfaceType::Tunnel,
+        _ => InterfaceType::Other,
+    }
+}
+
+/// Returns the administrative and operational statuses of the interface whose sysfs directory is
+/// `path`.
+fn get_interface_statuses(path: &Path) -> (InterfaceStatus, InterfaceStatus) {
+    let read = |name: &str| std::fs::read_to_string(path.join(name)).ok();
+    // The flags are written in hexadecimal, like `0x1003`.
+    let flags = read("flags")
+        .and_then(|flags| u32::from_str_radix(flags.trim().trim_start_matches("0x"), 16).ok());
+    let admin_status = match flags {
+        Some(flags) if flags & libc::IFF_UP as u32 != 0 => InterfaceStatus::Up,
+        Some(_) => InterfaceStatus::Down,
+        None => InterfaceStatus::Unknown,
+    };
+    let oper_status = match read("operstate").as_deref().map(str::trim) {
+        Some("up") => InterfaceStatus::Up,
+        Some("down") => InterfaceStatus::Down,
+        Some("testing") => InterfaceStatus::Testing,
+        Some("dormant") => InterfaceStatus::Dormant,
+        Some("notpresent") => InterfaceStatus::NotPresent,
+        Some("lowerlayerdown") => InterfaceStatus::LowerLayerDown,
+        // Drivers which don't report the operational state (like the loopback one) are
+        // considered up by the kernel as long as they have a carrier.
+        _ if admin_status == InterfaceStatus::Up
+            && read("carrier").as_deref().map(str::trim) == Some("1") =>
+        {
+            InterfaceStatus::Up
+        }
+        _ => InterfaceStatus::Unknown,
+    };
+    (admin_status, oper_status)
+}
+
+impl Default for Networks {
+    fn default() -> Self {
+        Networks::new()
+    }
+}
+
 impl Networks {
-    pub(crate) fn new() -> Self {
+    /// Creates an empty `Networks`, which can be used on its own. Call
+    /// [`NetworksExt::refresh_networks_list`][crate::NetworksExt::refresh_networks_list] to fill
+    /// it.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// ```
+    pub fn new() -> Self {
         Networks {
             interfaces: HashMap::new(),
+            history_len: 0,
+            include_loopback: false,
+            refresh_interval: RefreshInterval::default(),
+            netns: None,
+            sysroot: PathBuf::from("/"),
+        }
+    }
+
+    /// Creates an empty `Networks` reading the files of the `proc` and `sys` directories of
+    /// `sysroot` (used by [`System::with_sysroot`][crate::System]).
+    #[cfg(feature = "system")]
+    pub(crate) fn new_with_sysroot(sysroot: &Path) -> Networks {
+        Networks {
+            sysroot: sysroot.to_owned(),
+            ..Networks::new()
+        }
+    }
+
+    /// Creates a `Networks` listing the interfaces of the network namespace referred to by
+    /// `path` (like `/proc/[pid]/ns/net` or `/var/run/netns/[name]`) instead of the ones of the
+    /// current namespace. It allows a host process to monitor the interfaces of a container.
+    ///
+    /// On each refresh, the calling thread joins the namespace with `setns` to read
+    /// `/proc/net/dev` and then goes back to its original namespace. It requires the
+    /// `CAP_SYS_ADMIN` capability in the current user namespace as well as in the one owning the
+    /// target namespace, otherwise refreshing the list fails. The interface type is only known
+    /// for the loopback interface and the Wi-Fi signal level isn't available.
+    ///
+    /// This function is only available on Linux.
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use sysinfo::{Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new_in_namespace(Path::new("/var/run/netns/container"));
+    /// networks.refresh_networks_list();
+    /// for (interface_name, _) in &networks {
+    ///     println!("{}", interface_name);
+    /// }
+    /// ```
+    pub fn new_in_namespace(path: &Path) -> Networks {
+        Networks {
+            netns: Some(path.to_owned()),
+            ..Networks::new()
+        }
+    }
+
+    /// Reads the `/proc/net/[name]` file of the network namespace of these interfaces.
+    fn read_proc_net(&self, name: &str) -> Result<String, RefreshError> {
+        match self.netns {
+            Some(ref netns) => read_proc_net_in_namespace(netns, name),
+            None => Ok(std::fs::read_to_string(
+                self.sysroot.join("proc/net").join(name),
+            )?),
+        }
+    }
+
+    fn update_networks_list(&mut self, prune: bool) -> Result<(), RefreshError> {
+        match self.netns {
+            Some(ref netns) => {
+                let content = read_proc_net_in_namespace(netns, "dev")?;
+                refresh_networks_list_from_net_dev(&mut self.interfaces, &content, prune);
+            }
+            None => {
+                refresh_networks_list_from_sysfs(
+                    &mut self.interfaces,
+                    &self.sysroot.join("sys/class/net"),
+                    &self.sysroot.join("proc/net/dev_snmp6"),
+                    prune,
+                )?;
+                refresh_wifi_signals(
+                    &mut self.interfaces,
+                    &self.sysroot.join("proc/net/wireless"),
+                );
+                refresh_interface_statuses(
+                    &mut self.interfaces,
+                    &self.sysroot.join("sys/class/net"),
+                );
+            }
+        }
+        // Interfaces which were just added don't know the history length yet.
+        for data in self.interfaces.values_mut() {
+            data.history.set_len(self.history_len);
         }
+        Ok(())
     }
 }
 
+/// Counters of an interface, read either from sysfs or from `/proc/net/dev`.
+struct Counters {
+    rx_bytes: u64,
+    tx_bytes: u64,
+    rx_packets: u64,
+    tx_packets: u64,
+    rx_errors: u64,
+    tx_errors: u64,
+    rx_dropped: u64,
+    tx_dropped: u64,
+    multicast: u64,
+    rx_ipv6_bytes: u64,
+}
+
+impl Counters {
+    /// `dev_snmp6` is the `/proc/net/dev_snmp6/[interface]` file of the interface.
+    fn from_sysfs(parent: &Path, dev_snmp6: &Path, data: &mut Vec<u8>) -> Counters {
+        Counters {
+            rx_bytes: read(parent, "rx_bytes", data),
+            tx_bytes: read(parent, "tx_bytes", data),
+            rx_packets: read(parent, "rx_packets", data),
+            tx_packets: read(parent, "tx_packets", data),
+            rx_errors: read(parent, "rx_errors", data),
+            tx_errors: read(parent, "tx_errors", data),
+            rx_dropped: read(parent, "rx_dropped", data),
+            tx_dropped: read(parent, "tx_dropped", data),
+            multicast: read(parent, "multicast", data),
+            rx_ipv6_bytes: read_snmp6_value(dev_snmp6, "Ip6InOctets"),
+            // rx_compressed: read(parent, "rx_compressed", data),
+            // tx_compressed: read(parent, "tx_compressed", data),
+        }
+    }
+}
+
+/// Reads the `key` counter from an interface file of `/proc/net/dev_snmp6`. It looks like this:
+///
+/// ```text
+/// ifIndex                             2
+/// Ip6InReceives                       1262
+/// Ip6InOctets                         163512
+/// ```
+///
+/// Returns 0 if the file or the counter doesn't exist, like when IPv6 is disabled.
+fn read_snmp6_value(path: &Path, key: &str) -> u64 {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| {
+            content.lines().find_map(|line| {
+                let mut parts = line.split_whitespace();
+                if parts.next() != Some(key) {
+                    return None;
+                }
+                parts.next()?.parse().ok()
+            })
+        })
+        .unwrap_or(0)
+}
+
+/// Updates the counters of the `name` interface or adds it if it isn't known yet.
+/// `get_interface_kind` returns the interface type and whether it's a physical one.
+fn update_interface<F: FnOnce() -> (InterfaceType, bool)>(
+    interfaces: &mut HashMap<String, NetworkData>,
+    name: String,
+    counters: Counters,
+    get_interface_kind: F,
+) {
+    match interfaces.entry(name) {
+        hash_map::Entry::Occupied(mut e) => {
+            let interface = e.get_mut();
+            interface.set_counters(counters);
+            interface.updated = true;
+        }
+        hash_map::Entry::Vacant(e) => {
+            let (interface_type, is_physical) = get_interface_kind();
+            e.insert(NetworkData {
+                rx_bytes: counters.rx_bytes,
+                old_rx_bytes: counters.rx_bytes,
+                tx_bytes: counters.tx_bytes,
+                old_tx_bytes: counters.tx_bytes,
+                rx_packets: counters.rx_packets,
+                old_rx_packets: counters.rx_packets,
+                tx_packets: counters.tx_packets,
+                old_tx_packets: counters.tx_packets,
+                rx_errors: counters.rx_errors,
+                old_rx_errors: counters.rx_errors,
+                tx_errors: counters.tx_errors,
+                old_tx_errors: counters.tx_errors,
+                rx_dropped: counters.rx_dropped,
+                old_rx_dropped: counters.rx_dropped,
+                tx_dropped: counters.tx_dropped,
+                old_tx_dropped: counters.tx_dropped,
+                multicast: counters.multicast,
+                old_multicast: counters.multicast,
+                rx_ipv6_bytes: counters.rx_ipv6_bytes,
+                old_rx_ipv6_bytes: counters.rx_ipv6_bytes,
+                // rx_compressed,
+                // old_rx_compressed: rx_compressed,
+                // tx_compressed,
+                // old_tx_compressed: tx_compressed,
+                wifi_signal: None,
+                interface_type,
+                is_physical,
+                admin_status: InterfaceStatus::Unknown,
+                oper_status: InterfaceStatus::Unknown,
+                history: NetworkHistory::new(0),
+                last_refreshed: Instant::now(),
+                old_last_refreshed: Instant::now(),
+                updated: true,
+            });
+        }
+    }
+}
+
+/// If `prune` is `true`, the interfaces which aren't listed anymore are removed.
 fn refresh_networks_list_from_sysfs(
     interfaces: &mut HashMap<String, NetworkData>,
     sysfs_net: &Path,
+    dev_snmp6: &Path,
+    prune: bool,
+) -> Result<(), RefreshError> {
+    let dir = std::fs::read_dir(sysfs_net)?;
+    let mut data = vec![0; 30];
+
+    for stats in interfaces.values_mut() {
+        stats.updated = false;
+    }
+
+    for entry in dir.flatten() {
+        let path = entry.path();
+        let name = match entry.file_name().into_string() {
+            Ok(name) => name,
+            Err(_) => continue,
+        };
+        let counters =
+            Counters::from_sysfs(&path.join("statistics"), &dev_snmp6.join(&name), &mut data);
+        update_interface(interfaces, name, counters, || {
+            // Virtual interfaces don't have an underlying device.
+            (
+                get_interface_type(&path, &mut data),
+                path.join("device").exists(),
+            )
+        });
+    }
+
+    if prune {
+        // Remove interfaces which are gone.
+        interfaces.retain(|_, d| d.updated);
+    }
+    Ok(())
+}
+
+/// Parses the `/proc/net/dev` file. It looks like this:
+///
+/// ```text
+/// Inter-|   Receive                                                |  Transmit
+///  face |bytes    packets errs drop fifo frame compressed multicast|bytes    packets errs drop fifo colls carrier compressed
+///     lo:   53616     612    0    0    0     0          0         0    53616     612    0    0    0     0       0          0
+/// ```
+fn parse_net_dev(content: &str) -> Vec<(String, Counters)> {
+    // The first two lines are the headers.
+    content
+        .lines()
+        .skip(2)
+        .filter_map(|line| {
+            let pos = line.find(':')?;
+            let fields = line[pos + 1..]
+                .split_whitespace()
+                .map(|field| field.parse::<u64>().unwrap_or(0))
+                .collect::<Vec<_>>();
+            if fields.len() < 12 {
+                return None;
+            }
+            Some((
+                line[..pos].trim().to_owned(),
+                Counters {
+                    rx_bytes: fields[0],
+                    rx_packets: fields[1],
+                    rx_errors: fields[2],
+                    rx_dropped: fields[3],
+                    multicast: fields[7],
+                    tx_bytes: fields[8],
+                    tx_packets: fields[9],
+                    tx_errors: fields[10],
+                    tx_dropped: fields[11],
+                    // The IPv6 counters of the namespace aren't read.
+                    rx_ipv6_bytes: 0,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// If `prune` is `true`, the interfaces which aren't listed anymore are removed.
+fn refresh_networks_list_from_net_dev(
+    interfaces: &mut HashMap<String, NetworkData>,
+    content: &str,
+    prune: bool,
 ) {
-    if let Ok(dir) = std::fs::read_dir(sysfs_net) {
-        let mut data = vec![0; 30];
+    for stats in interfaces.values_mut() {
+        stats.updated = false;
+    }
 
-        for stats in interfaces.values_mut() {
-            stats.updated = false;
+    for (name, counters) in parse_net_dev(content) {
+        // Without sysfs, we can only recognize the loopback interface.
+        let interface_type = if name == "lo" {
+            InterfaceType::Loopback
+        } else {
+            InterfaceType::Other
+        };
+        update_interface(interfaces, name, counters, || (interface_type, false));
+    }
+
+    if prune {
+        // Remove interfaces which are gone.
+        interfaces.retain(|_, d| d.updated);
+    }
+}
+
+/// Reads the `/proc/net/[name]` file from inside the network namespace referred to by `netns`.
+///
+/// The read is done from a short-lived thread which joins the namespace and then exits, so the
+/// calling thread never leaves its own namespace, even if the read fails.
+fn read_proc_net_in_namespace(netns: &Path, name: &str) -> Result<String, RefreshError> {
+    let target = File::open(netns)?;
+    let path = Path::new("/proc/thread-self/net").join(name);
+    let reader = std::thread::spawn(move || -> io::Result<String> {
+        if unsafe { libc::setns(target.as_raw_fd(), libc::CLONE_NEWNET) } != 0 {
+            return Err(io::Error::last_os_error());
         }
+        std::fs::read_to_string(path)
+    });
+    // The thread can only panic if something went very wrong, so we forward the panic.
+    let content = reader
+        .join()
+        .unwrap_or_else(|panic| std::panic::resume_unwind(panic))?;
+    Ok(content)
+}
 
-        for entry in dir.flatten() {
-            let parent = &entry.path().join("statistics");
-            let entry = match entry.file_name().into_string() {
-                Ok(entry) => entry,
-                Err(_) => continue,
-            };
-            let rx_bytes = read(parent, "rx_bytes", &mut data);
-            let tx_bytes = read(parent, "tx_bytes", &mut data);
-            let rx_packets = read(parent, "rx_packets", &mut data);
-            let tx_packets = read(parent, "tx_packets", &mut data);
-            let rx_errors = read(parent, "rx_errors", &mut data);
-            let tx_errors = read(parent, "tx_errors", &mut data);
-            // let rx_compressed = read(parent, "rx_compressed", &mut data);
-            // let tx_compressed = read(parent, "tx_compressed", &mut data);
-            match interfaces.entry(entry) {
-                hash_map::Entry::Occupied(mut e) => {
-                    let mut interface = e.get_mut();
-                    old_and_new!(interface, rx_bytes, old_rx_bytes);
-                    old_and_new!(interface, tx_bytes, old_tx_bytes);
-                    old_and_new!(interface, rx_packets, old_rx_packets);
-                    old_and_new!(interface, tx_packets, old_tx_packets);
-                    old_and_new!(interface, rx_errors, old_rx_errors);
-                    old_and_new!(interface, tx_errors, old_tx_errors);
-                    // old_and_new!(e, rx_compressed, old_rx_compressed);
-                    // old_and_new!(e, tx_compressed, old_tx_compressed);
-                    interface.updated = true;
-                }
-                hash_map::Entry::Vacant(e) => {
-                    e.insert(NetworkData {
-                        rx_bytes,
-                        old_rx_bytes: rx_bytes,
-                        tx_bytes,
-                        old_tx_bytes: tx_bytes,
-                        rx_packets,
-                        old_rx_packets: rx_packets,
-                        tx_packets,
-                        old_tx_packets: tx_packets,
-                        rx_errors,
-                        old_rx_errors: rx_errors,
-                        tx_errors,
-                        old_tx_errors: tx_errors,
-                        // rx_compressed,
-                        // old_rx_compressed: rx_compressed,
-                        // tx_compressed,
-                        // old_tx_compressed: tx_compressed,
-                        updated: true,
-                    });
-                }
-            };
+/// Parses the `/proc/net/route` file and returns the gateway of the default route with the
+/// lowest metric. It looks like this:
+///
+/// ```text
+/// Iface Destination Gateway  Flags RefCnt Use Metric Mask     MTU Window IRTT
+/// eth0  00000000    0102A8C0 0003  0      0   100    00000000 0   0      0
+/// ```
+fn parse_default_gateway_v4(content: &str) -> Option<Ipv4Addr> {
+    // The first line is the header.
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            if fields.len() < 8 {
+                return None;
+            }
+            let destination = u32::from_str_radix(fields[1], 16).ok()?;
+            let gateway = u32::from_str_radix(fields[2], 16).ok()?;
+            let flags = u32::from_str_radix(fields[3], 16).ok()?;
+            let metric = fields[6].parse::<u32>().ok()?;
+            let mask = u32::from_str_radix(fields[7], 16).ok()?;
+            // `RTF_UP | RTF_GATEWAY`
+            if destination != 0 || mask != 0 || flags & 0x3 != 0x3 {
+                return None;
+            }
+            // The addresses are written as they're stored, in network byte order.
+            Some((metric, Ipv4Addr::from(gateway.to_ne_bytes())))
+        })
+        .min_by_key(|(metric, _)| *metric)
+        .map(|(_, gateway)| gateway)
+}
+
+/// Parses the `/proc/net/ipv6_route` file and returns the gateway of the default route with the
+/// lowest metric. The fields are the destination, its prefix length, the source, its prefix
+/// length, the next hop, the metric, the reference count, the use count, the flags and the
+/// interface:
+///
+/// ```text
+/// 00000000000000000000000000000000 00 00000000000000000000000000000000 00 fe800000000000000000000000000001 00000400 00000001 00000000 00000003 eth0
+/// ```
+fn parse_default_gateway_v6(content: &str) -> Option<Ipv6Addr> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            if fields.len() < 9 {
+                return None;
+            }
+            let destination = u128::from_str_radix(fields[0], 16).ok()?;
+            let prefix_len = u8::from_str_radix(fields[1], 16).ok()?;
+            let next_hop = u128::from_str_radix(fields[4], 16).ok()?;
+            let metric = u32::from_str_radix(fields[5], 16).ok()?;
+            let flags = u32::from_str_radix(fields[8], 16).ok()?;
+            // `RTF_UP | RTF_GATEWAY`
+            if destination != 0 || prefix_len != 0 || next_hop == 0 || flags & 0x3 != 0x3 {
+                return None;
+            }
+            Some((metric, Ipv6Addr::from(next_hop)))
+        })
+        .min_by_key(|(metric, _)| *metric)
+        .map(|(_, gateway)| gateway)
+}
+
+/// Parses the `nameserver` lines of the `/etc/resolv.conf` file.
+fn parse_resolv_conf(content: &str) -> Vec<IpAddr> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            if parts.next() != Some("nameserver") {
+                return None;
+            }
+            // Link-local IPv6 addresses can be followed by the interface (`fe80::1%eth0`).
+            parts.next()?.split('%').next()?.parse().ok()
+        })
+        .collect()
+}
+
+/// Parses the `/proc/net/wireless` file and returns the signal level (in dBm) of each wireless
+/// interface. It looks like this:
+///
+/// ```text
+/// Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE
+///  face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22
+///  wlan0: 0000   54.  -56.  -256        0      0      0      0      0        0
+/// ```
+fn get_wireless_signals(path: &Path) -> HashMap<String, i32> {
+    let mut signals = HashMap::new();
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) => return signals,
+    };
+    // The first two lines are the headers.
+    for line in content.lines().skip(2) {
+        let pos = match line.find(':') {
+            Some(pos) => pos,
+            None => continue,
+        };
+        let name = line[..pos].trim();
+        // The level is the fourth field, after the status and the link quality.
+        if let Some(level) = line[pos + 1..]
+            .split_whitespace()
+            .nth(2)
+            .and_then(|level| level.trim_end_matches('.').parse::<i32>().ok())
+        {
+            signals.insert(name.to_owned(), level);
         }
+    }
+    signals
+}
 
-        // Remove interfaces which are gone.
-        interfaces.retain(|_, d| d.updated);
+fn refresh_wifi_signals(interfaces: &mut HashMap<String, NetworkData>, path: &Path) {
+    let signals = get_wireless_signals(path);
+    for (name, data) in interfaces.iter_mut() {
+        data.wifi_signal = signals.get(name).copied();
+    }
+}
+
+/// `sysfs_net` is the `/sys/class/net` directory.
+fn refresh_interface_statuses(interfaces: &mut HashMap<String, NetworkData>, sysfs_net: &Path) {
+    for (name, data) in interfaces.iter_mut() {
+        let (admin_status, oper_status) = get_interface_statuses(&sysfs_net.join(name));
+        data.admin_status = admin_status;
+        data.oper_status = oper_status;
     }
 }
 
@@ -133,19 +590,79 @@ impl NetworksExt for Networks {
     }
 
     fn refresh(&mut self) {
+        if !self.refresh_interval.should_refresh() {
+            return;
+        }
+        if let Some(ref netns) = self.netns {
+            if let Ok(content) = read_proc_net_in_namespace(netns, "dev") {
+                for (interface_name, counters) in parse_net_dev(&content) {
+                    if let Some(data) = self.interfaces.get_mut(&interface_name) {
+                        data.update(counters);
+                    }
+                }
+            }
+            return;
+        }
         let mut v = vec![0; 30];
+        let sys_class_net = self.sysroot.join("sys/class/net");
+        let dev_snmp6 = self.sysroot.join("proc/net/dev_snmp6");
 
         for (interface_name, data) in self.interfaces.iter_mut() {
-            data.update(interface_name, &mut v);
+            let path = sys_class_net.join(interface_name).join("statistics");
+            let dev_snmp6 = dev_snmp6.join(interface_name);
+            data.update(Counters::from_sysfs(&path, &dev_snmp6, &mut v));
+        }
+        refresh_wifi_signals(
+            &mut self.interfaces,
+            &self.sysroot.join("proc/net/wireless"),
+        );
+        refresh_interface_statuses(&mut self.interfaces, &sys_class_net);
+    }
+
+    fn try_refresh_networks_list(&mut self) -> Result<(), RefreshError> {
+        self.update_networks_list(true)
+    }
+
+    fn refresh_networks_list_no_prune(&mut self) {
+        let _ = self.update_networks_list(false);
+    }
+
+    fn set_history_len(&mut self, len: usize) {
+        self.history_len = len;
+        for data in self.interfaces.values_mut() {
+            data.history.set_len(len);
         }
     }
 
-    fn refresh_networks_list(&mut self) {
-        refresh_networks_list_from_sysfs(&mut self.interfaces, Path::new("/sys/class/net/"));
+    fn set_include_loopback(&mut self, include_loopback: bool) {
+        self.include_loopback = include_loopback;
+    }
+
+    fn is_loopback_included(&self) -> bool {
+        self.include_loopback
+    }
+
+    fn set_min_refresh_interval(&mut self, interval: Duration) {
+        self.refresh_interval.set_min_interval(interval);
+    }
+
+    fn get_default_gateway_v4(&self) -> Option<Ipv4Addr> {
+        parse_default_gateway_v4(&self.read_proc_net("route").ok()?)
+    }
+
+    fn get_default_gateway_v6(&self) -> Option<Ipv6Addr> {
+        parse_default_gateway_v6(&self.read_proc_net("ipv6_route").ok()?)
+    }
+
+    fn get_dns_servers(&self) -> Vec<IpAddr> {
+        std::fs::read_to_string("/etc/resolv.conf")
+            .map(|content| parse_resolv_conf(&content))
+            .unwrap_or_default()
     }
 }
 
 /// Contains network information.
+#[derive(Clone)]
 pub struct NetworkData {
     /// Total number of bytes received over interface.
     rx_bytes: u64,
@@ -167,6 +684,19 @@ pub struct NetworkData {
     /// similar to `rx_errors`
     tx_errors: u64,
     old_tx_errors: u64,
+    /// Total number of packets received but dropped by the kernel (because of a lack of buffer
+    /// space for example).
+    rx_dropped: u64,
+    old_rx_dropped: u64,
+    /// Total number of packets dropped before being transmitted.
+    tx_dropped: u64,
+    old_tx_dropped: u64,
+    /// Total number of multicast packets received.
+    multicast: u64,
+    old_multicast: u64,
+    /// Total number of bytes received in IPv6 packets, from `Ip6InOctets`.
+    rx_ipv6_bytes: u64,
+    old_rx_ipv6_bytes: u64,
     // /// Indicates the number of compressed packets received by this
     // /// network device. This value might only be relevant for interfaces
     // /// that support packet compression (e.g: PPP).
@@ -177,51 +707,47 @@ pub struct NetworkData {
     // /// compression (e.g: PPP).
     // tx_compressed: usize,
     // old_tx_compressed: usize,
+    /// Signal level (in dBm) if this is a wireless interface.
+    wifi_signal: Option<i32>,
+    interface_type: InterfaceType,
+    is_physical: bool,
+    admin_status: InterfaceStatus,
+    oper_status: InterfaceStatus,
+    history: NetworkHistory,
+    last_refreshed: Instant,
+    old_last_refreshed: Instant,
     /// Whether or not the above data has been updated during refresh
     updated: bool,
 }
 
 impl NetworkData {
-    fn update(&mut self, path: &str, data: &mut Vec<u8>) {
-        let path = &Path::new("/sys/class/net/").join(path).join("statistics");
-        old_and_new!(self, rx_bytes, old_rx_bytes, read(path, "rx_bytes", data));
-        old_and_new!(self, tx_bytes, old_tx_bytes, read(path, "tx_bytes", data));
-        old_and_new!(
-            self,
-            rx_packets,
-            old_rx_packets,
-            read(path, "rx_packets", data)
-        );
-        old_and_new!(
-            self,
-            tx_packets,
-            old_tx_packets,
-            read(path, "tx_packets", data)
-        );
+    fn set_counters(&mut self, counters: Counters) {
+        old_and_new!(self, rx_bytes, old_rx_bytes, counters.rx_bytes);
+        old_and_new!(self, tx_bytes, old_tx_bytes, counters.tx_bytes);
+        old_and_new!(self, rx_packets, old_rx_packets, counters.rx_packets);
+        old_and_new!(self, tx_packets, old_tx_packets, counters.tx_packets);
+        old_and_new!(self, rx_errors, old_rx_errors, counters.rx_errors);
+        old_and_new!(self, tx_errors, old_tx_errors, counters.tx_errors);
+        old_and_new!(self, rx_dropped, old_rx_dropped, counters.rx_dropped);
+        old_and_new!(self, tx_dropped, old_tx_dropped, counters.tx_dropped);
+        old_and_new!(self, multicast, old_multicast, counters.multicast);
         old_and_new!(
             self,
-            rx_errors,
-            old_rx_errors,
-            read(path, "rx_errors", data)
+            rx_ipv6_bytes,
+            old_rx_ipv6_bytes,
+            counters.rx_ipv6_bytes
         );
-        old_and_new!(
-            self,
-            tx_errors,
-            old_tx_errors,
-            read(path, "tx_errors", data)
+        old_and_new!(self, last_refreshed, old_last_refreshed, Instant::now());
+        // old_and_new!(self, rx_compressed, old_rx_compressed, counters.rx_compressed);
+        // old_and_new!(self, tx_compressed, old_tx_compressed, counters.tx_compressed);
+    }
+
+    fn update(&mut self, counters: Counters) {
+        self.set_counters(counters);
+        self.history.update(
+            self.rx_bytes.saturating_sub(self.old_rx_bytes),
+            self.tx_bytes.saturating_sub(self.old_tx_bytes),
         );
-        // old_and_new!(
-        //     self,
-        //     rx_compressed,
-        //     old_rx_compressed,
-        //     read(path, "rx_compressed", data)
-        // );
-        // old_and_new!(
-        //     self,
-        //     tx_compressed,
-        //     old_tx_compressed,
-        //     read(path, "tx_compressed", data)
-        // );
     }
 }
 
@@ -273,11 +799,107 @@ impl NetworkExt for NetworkData {
     fn get_total_errors_on_transmitted(&self) -> u64 {
         self.tx_errors
     }
+
+    fn get_drops_on_received(&self) -> u64 {
+        self.rx_dropped.saturating_sub(self.old_rx_dropped)
+    }
+
+    fn get_total_drops_on_received(&self) -> u64 {
+        self.rx_dropped
+    }
+
+    fn get_drops_on_transmitted(&self) -> u64 {
+        self.tx_dropped.saturating_sub(self.old_tx_dropped)
+    }
+
+    fn get_total_drops_on_transmitted(&self) -> u64 {
+        self.tx_dropped
+    }
+
+    fn get_packets_received_multicast(&self) -> u64 {
+        self.multicast.saturating_sub(self.old_multicast)
+    }
+
+    fn get_total_packets_received_multicast(&self) -> u64 {
+        self.multicast
+    }
+
+    fn get_received_ipv4(&self) -> u64 {
+        self.get_received().saturating_sub(self.get_received_ipv6())
+    }
+
+    fn get_received_ipv6(&self) -> u64 {
+        self.rx_ipv6_bytes.saturating_sub(self.old_rx_ipv6_bytes)
+    }
+
+    fn get_wifi_signal(&self) -> Option<i32> {
+        self.wifi_signal
+    }
+
+    fn get_interface_type(&self) -> InterfaceType {
+        self.interface_type
+    }
+
+    fn is_physical(&self) -> bool {
+        self.is_physical
+    }
+
+    fn get_counting_basis(&self) -> CountingBasis {
+        // The kernel counts the bytes of the frames given to or by the driver, tunnels (like TUN
+        // devices) only handle IP packets.
+        match self.interface_type {
+            InterfaceType::Tunnel => CountingBasis::Network,
+            _ => CountingBasis::LinkLayer,
+        }
+    }
+
+    fn get_admin_status(&self) -> InterfaceStatus {
+        self.admin_status
+    }
+
+    fn get_oper_status(&self) -> InterfaceStatus {
+        self.oper_status
+    }
+
+    fn get_received_history(&self) -> &VecDeque<u64> {
+        &self.history.received
+    }
+
+    fn get_transmitted_history(&self) -> &VecDeque<u64> {
+        &self.history.transmitted
+    }
+
+    fn get_last_refreshed(&self) -> Instant {
+        self.last_refreshed
+    }
+
+    fn get_received_per_second(&self) -> f64 {
+        get_rate(
+            self.get_received(),
+            self.old_last_refreshed,
+            self.last_refreshed,
+        )
+    }
+
+    fn get_transmitted_per_second(&self) -> f64 {
+        get_rate(
+            self.get_transmitted(),
+            self.old_last_refreshed,
+            self.last_refreshed,
+        )
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use super::refresh_networks_list_from_sysfs;
+    use super::{
+        parse_default_gateway_v4, parse_default_gateway_v6, parse_resolv_conf,
+        refresh_interface_statuses, refresh_networks_list_from_net_dev,
+        refresh_networks_list_from_sysfs, refresh_wifi_signals, Networks,
+    };
+    use crate::{
+        CountingBasis, InterfaceStatus, InterfaceType, NetworkDelta, NetworkExt, NetworksExt,
+    };
     use std::collections::HashMap;
     use std::fs;
 
@@ -289,12 +911,24 @@ mod test {
 
         let mut interfaces = HashMap::new();
 
-        refresh_networks_list_from_sysfs(&mut interfaces, sys_net_dir.path());
+        refresh_networks_list_from_sysfs(
+            &mut interfaces,
+            sys_net_dir.path(),
+            &sys_net_dir.path().join("dev_snmp6"),
+            true,
+        )
+        .expect("failed to read sysfs");
         assert_eq!(interfaces.keys().collect::<Vec<_>>(), ["itf1"]);
 
         fs::create_dir(sys_net_dir.path().join("itf2")).expect("failed to create subdirectory");
 
-        refresh_networks_list_from_sysfs(&mut interfaces, sys_net_dir.path());
+        refresh_networks_list_from_sysfs(
+            &mut interfaces,
+            sys_net_dir.path(),
+            &sys_net_dir.path().join("dev_snmp6"),
+            true,
+        )
+        .expect("failed to read sysfs");
         let mut itf_names: Vec<String> = interfaces.keys().map(|n| n.to_owned()).collect();
         itf_names.sort();
         assert_eq!(itf_names, ["itf1", "itf2"]);
@@ -311,14 +945,466 @@ mod test {
 
         let mut interfaces = HashMap::new();
 
-        refresh_networks_list_from_sysfs(&mut interfaces, sys_net_dir.path());
+        refresh_networks_list_from_sysfs(
+            &mut interfaces,
+            sys_net_dir.path(),
+            &sys_net_dir.path().join("dev_snmp6"),
+            true,
+        )
+        .expect("failed to read sysfs");
         let mut itf_names: Vec<String> = interfaces.keys().map(|n| n.to_owned()).collect();
         itf_names.sort();
         assert_eq!(itf_names, ["itf1", "itf2"]);
 
         fs::remove_dir(&itf1_dir).expect("failed to remove subdirectory");
 
-        refresh_networks_list_from_sysfs(&mut interfaces, sys_net_dir.path());
+        refresh_networks_list_from_sysfs(
+            &mut interfaces,
+            sys_net_dir.path(),
+            &sys_net_dir.path().join("dev_snmp6"),
+            true,
+        )
+        .expect("failed to read sysfs");
         assert_eq!(interfaces.keys().collect::<Vec<_>>(), ["itf2"]);
     }
+
+    #[test]
+    fn check_wifi_signal() {
+        let sys_net_dir = tempfile::tempdir().expect("failed to create temporary directory");
+        fs::create_dir(sys_net_dir.path().join("eth0")).expect("failed to create subdirectory");
+        fs::create_dir(sys_net_dir.path().join("wlan0")).expect("failed to create subdirectory");
+        let wireless = sys_net_dir.path().join("wireless");
+        fs::write(
+            &wireless,
+            "Inter-| sta-|   Quality        |   Discarded packets               | Missed | WE\n \
+              face | tus | link level noise |  nwid  crypt   frag  retry   misc | beacon | 22\n \
+             wlan0: 0000   54.  -56.  -256        0      0      0      0      0        0\n",
+        )
+        .expect("failed to write wireless file");
+
+        let mut interfaces = HashMap::new();
+        refresh_networks_list_from_sysfs(
+            &mut interfaces,
+            sys_net_dir.path(),
+            &sys_net_dir.path().join("dev_snmp6"),
+            true,
+        )
+        .expect("failed to read sysfs");
+        refresh_wifi_signals(&mut interfaces, &wireless);
+        assert_eq!(interfaces["eth0"].wifi_signal, None);
+        assert_eq!(interfaces["wlan0"].wifi_signal, Some(-56));
+    }
+
+    #[test]
+    fn check_interface_statuses() {
+        let sys_net_dir = tempfile::tempdir().expect("failed to create temporary directory");
+        for (name, flags, operstate, carrier) in &[
+            ("eth0", "0x1003", "up", Some("1")),
+            ("eth1", "0x1003", "down", Some("0")),
+            ("eth2", "0x1002", "down", None),
+            ("lo", "0x9", "unknown", Some("1")),
+            ("tun0", "0x1091", "unknown", Some("0")),
+        ] {
+            let dir = sys_net_dir.path().join(name);
+            fs::create_dir(&dir).expect("failed to create subdirectory");
+            fs::write(dir.join("flags"), format!("{}\n", flags)).expect("failed to write flags");
+            fs::write(dir.join("operstate"), format!("{}\n", operstate))
+                .expect("failed to write operstate");
+            if let Some(carrier) = carrier {
+                fs::write(dir.join("carrier"), format!("{}\n", carrier))
+                    .expect("failed to write carrier");
+            }
+        }
+        fs::create_dir(sys_net_dir.path().join("other")).expect("failed to create subdirectory");
+
+        let mut interfaces = HashMap::new();
+        refresh_networks_list_from_sysfs(
+            &mut interfaces,
+            sys_net_dir.path(),
+            &sys_net_dir.path().join("dev_snmp6"),
+            true,
+        )
+        .expect("failed to read sysfs");
+        refresh_interface_statuses(&mut interfaces, sys_net_dir.path());
+        let statuses = |name: &str| {
+            (
+                interfaces[name].get_admin_status(),
+                interfaces[name].get_oper_status(),
+            )
+        };
+        assert_eq!(statuses("eth0"), (InterfaceStatus::Up, InterfaceStatus::Up));
+        assert_eq!(
+            statuses("eth1"),
+            (InterfaceStatus::Up, InterfaceStatus::Down)
+        );
+        assert_eq!(
+            statuses("eth2"),
+            (InterfaceStatus::Down, InterfaceStatus::Down)
+        );
+        assert_eq!(statuses("lo"), (InterfaceStatus::Up, InterfaceStatus::Up));
+        assert_eq!(
+            statuses("tun0"),
+            (InterfaceStatus::Up, InterfaceStatus::Unknown)
+        );
+        assert_eq!(
+            statuses("other"),
+            (InterfaceStatus::Unknown, InterfaceStatus::Unknown)
+        );
+    }
+
+    #[test]
+    fn check_interface_type() {
+        let sys_net_dir = tempfile::tempdir().expect("failed to create temporary directory");
+        for (name, type_) in &[
+            ("eth0", "1"),
+            ("lo", "772"),
+            ("tun0", "65534"),
+            ("wlan0", "1"),
+        ] {
+            let dir = sys_net_dir.path().join(name);
+            fs::create_dir(&dir).expect("failed to create subdirectory");
+            fs::write(dir.join("type"), format!("{}\n", type_)).expect("failed to write type");
+        }
+        fs::create_dir(sys_net_dir.path().join("wlan0/wireless"))
+            .expect("failed to create subdirectory");
+        fs::create_dir(sys_net_dir.path().join("other")).expect("failed to create subdirectory");
+        for name in &["eth0", "wlan0"] {
+            fs::create_dir(sys_net_dir.path().join(name).join("device"))
+                .expect("failed to create subdirectory");
+        }
+
+        let mut interfaces = HashMap::new();
+        refresh_networks_list_from_sysfs(
+            &mut interfaces,
+            sys_net_dir.path(),
+            &sys_net_dir.path().join("dev_snmp6"),
+            true,
+        )
+        .expect("failed to read sysfs");
+        assert_eq!(interfaces["eth0"].interface_type, InterfaceType::Ethernet);
+        assert_eq!(interfaces["lo"].interface_type, InterfaceType::Loopback);
+        assert_eq!(interfaces["tun0"].interface_type, InterfaceType::Tunnel);
+        assert_eq!(interfaces["wlan0"].interface_type, InterfaceType::WiFi);
+        assert_eq!(interfaces["other"].interface_type, InterfaceType::Other);
+        assert!(interfaces["eth0"].is_physical());
+        assert!(interfaces["wlan0"].is_physical());
+        assert!(!interfaces["lo"].is_physical());
+        assert!(!interfaces["tun0"].is_physical());
+        assert_eq!(
+            interfaces["eth0"].get_counting_basis(),
+            CountingBasis::LinkLayer
+        );
+        assert_eq!(
+            interfaces["tun0"].get_counting_basis(),
+            CountingBasis::Network
+        );
+    }
+
+    #[test]
+    fn refresh_networks_list_failure_keeps_interfaces() {
+        let sys_net_dir = tempfile::tempdir().expect("failed to create temporary directory");
+        fs::create_dir(sys_net_dir.path().join("itf1")).expect("failed to create subdirectory");
+
+        let mut interfaces = HashMap::new();
+        refresh_networks_list_from_sysfs(
+            &mut interfaces,
+            sys_net_dir.path(),
+            &sys_net_dir.path().join("dev_snmp6"),
+            true,
+        )
+        .expect("failed to read sysfs");
+        assert_eq!(interfaces.keys().collect::<Vec<_>>(), ["itf1"]);
+
+        let missing = sys_net_dir.path().join("missing");
+        assert!(
+            refresh_networks_list_from_sysfs(&mut interfaces, &missing, &missing, true).is_err()
+        );
+        assert_eq!(interfaces.keys().collect::<Vec<_>>(), ["itf1"]);
+    }
+
+    #[test]
+    fn check_networks_errors_and_drops() {
+        let header = "Inter-|   Receive\n face |bytes\n";
+        let mut networks = Networks::new();
+        let content = format!(
+            "{}    lo: 0 0 7 7 0 0 0 0 0 0 7 7 0 0 0 0\n  \
+             eth0: 1000 10 1 4 0 0 0 0 2000 20 2 5 0 0 0 0\n  \
+             eth1: 1000 10 3 0 0 0 0 0 2000 20 0 6 0 0 0 0\n",
+            header
+        );
+        refresh_networks_list_from_net_dev(&mut networks.interfaces, &content, true);
+        // The loopback interface is skipped.
+        assert_eq!(networks.get_total_errors(), 6);
+        assert_eq!(networks.get_total_drops(), 15);
+
+        let content = format!(
+            "{}    lo: 0 0 9 9 0 0 0 0 0 0 9 9 0 0 0 0\n  \
+             eth0: 1000 10 2 4 0 0 0 0 2000 20 2 6 0 0 0 0\n  \
+             eth1: 1000 10 3 0 0 0 0 0 2000 20 1 8 0 0 0 0\n",
+            header
+        );
+        refresh_networks_list_from_net_dev(&mut networks.interfaces, &content, true);
+        assert_eq!(networks.get_errors(), 2);
+        assert_eq!(networks.get_total_errors(), 8);
+        assert_eq!(networks.get_drops(), 3);
+        assert_eq!(networks.get_total_drops(), 18);
+
+        networks.set_include_loopback(true);
+        assert_eq!(networks.get_errors(), 6);
+        assert_eq!(networks.get_total_drops(), 36);
+    }
+
+    #[test]
+    fn check_networks_diff() {
+        let sys_net_dir = tempfile::tempdir().expect("failed to create temporary directory");
+        for name in &["itf1", "itf2"] {
+            let stats = sys_net_dir.path().join(name).join("statistics");
+            fs::create_dir_all(&stats).expect("failed to create subdirectory");
+            fs::write(stats.join("rx_bytes"), "100\n").expect("failed to write rx_bytes");
+        }
+
+        let mut networks = Networks::new();
+        refresh_networks_list_from_sysfs(
+            &mut networks.interfaces,
+            sys_net_dir.path(),
+            &sys_net_dir.path().join("dev_snmp6"),
+            true,
+        )
+        .expect("failed to read sysfs");
+        let previous = networks.clone();
+        assert!(networks.diff(&previous).is_empty());
+
+        fs::write(sys_net_dir.path().join("itf1/statistics/rx_bytes"), "150\n")
+            .expect("failed to write rx_bytes");
+        fs::remove_dir_all(sys_net_dir.path().join("itf2")).expect("failed to remove itf2");
+        fs::create_dir(sys_net_dir.path().join("itf3")).expect("failed to create subdirectory");
+        refresh_networks_list_from_sysfs(
+            &mut networks.interfaces,
+            sys_net_dir.path(),
+            &sys_net_dir.path().join("dev_snmp6"),
+            true,
+        )
+        .expect("failed to read sysfs");
+
+        let mut deltas = networks.diff(&previous);
+        deltas.sort_by_key(|d| format!("{:?}", d));
+        assert_eq!(
+            deltas,
+            vec![
+                NetworkDelta::Added("itf3".to_owned()),
+                NetworkDelta::Changed {
+                    name: "itf1".to_owned(),
+                    received: 50,
+                    transmitted: 0,
+                    packets_received: 0,
+                    packets_transmitted: 0,
+                },
+                NetworkDelta::Removed("itf2".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn check_multicast_statistics() {
+        let sys_net_dir = tempfile::tempdir().expect("failed to create temporary directory");
+        let stats = sys_net_dir.path().join("itf1").join("statistics");
+        fs::create_dir_all(&stats).expect("failed to create subdirectory");
+        fs::write(stats.join("multicast"), "12\n").expect("failed to write multicast");
+
+        let mut interfaces = HashMap::new();
+        refresh_networks_list_from_sysfs(
+            &mut interfaces,
+            sys_net_dir.path(),
+            &sys_net_dir.path().join("dev_snmp6"),
+            true,
+        )
+        .expect("failed to read sysfs");
+        let itf1 = &interfaces["itf1"];
+        assert_eq!(itf1.get_total_packets_received_multicast(), 12);
+        assert_eq!(itf1.get_packets_received_multicast(), 0);
+
+        fs::write(stats.join("multicast"), "20\n").expect("failed to write multicast");
+        refresh_networks_list_from_sysfs(
+            &mut interfaces,
+            sys_net_dir.path(),
+            &sys_net_dir.path().join("dev_snmp6"),
+            true,
+        )
+        .expect("failed to read sysfs");
+        let itf1 = &interfaces["itf1"];
+        assert_eq!(itf1.get_total_packets_received_multicast(), 20);
+        assert_eq!(itf1.get_packets_received_multicast(), 8);
+    }
+
+    #[test]
+    fn check_ipv6_received() {
+        let sys_net_dir = tempfile::tempdir().expect("failed to create temporary directory");
+        let stats = sys_net_dir.path().join("itf1").join("statistics");
+        fs::create_dir_all(&stats).expect("failed to create subdirectory");
+        fs::write(stats.join("rx_bytes"), "1000\n").expect("failed to write rx_bytes");
+        let dev_snmp6 = sys_net_dir.path().join("dev_snmp6");
+        fs::create_dir(&dev_snmp6).expect("failed to create subdirectory");
+        let write_snmp6 = |octets: u64| {
+            fs::write(
+                dev_snmp6.join("itf1"),
+                format!(
+                    "ifIndex                         \t2\n\
+                     Ip6InReceives                   \t12\n\
+                     Ip6InOctets                     \t{}\n\
+                     Ip6OutOctets                    \t600\n",
+                    octets
+                ),
+            )
+            .expect("failed to write snmp6 file");
+        };
+        write_snmp6(400);
+
+        let mut interfaces = HashMap::new();
+        refresh_networks_list_from_sysfs(&mut interfaces, sys_net_dir.path(), &dev_snmp6, true)
+            .expect("failed to read sysfs");
+        let itf1 = &interfaces["itf1"];
+        assert_eq!(itf1.get_received_ipv4(), 0);
+        assert_eq!(itf1.get_received_ipv6(), 0);
+
+        fs::write(stats.join("rx_bytes"), "1500\n").expect("failed to write rx_bytes");
+        write_snmp6(700);
+        refresh_networks_list_from_sysfs(&mut interfaces, sys_net_dir.path(), &dev_snmp6, true)
+            .expect("failed to read sysfs");
+        let itf1 = &interfaces["itf1"];
+        assert_eq!(itf1.get_received(), 500);
+        assert_eq!(itf1.get_received_ipv6(), 300);
+        assert_eq!(itf1.get_received_ipv4(), 200);
+    }
+
+    #[test]
+    fn refresh_networks_list_without_pruning() {
+        let sys_net_dir = tempfile::tempdir().expect("failed to create temporary directory");
+        let itf1_dir = sys_net_dir.path().join("itf1");
+        fs::create_dir(&itf1_dir).expect("failed to create subdirectory");
+
+        let mut interfaces = HashMap::new();
+        refresh_networks_list_from_sysfs(
+            &mut interfaces,
+            sys_net_dir.path(),
+            &sys_net_dir.path().join("dev_snmp6"),
+            true,
+        )
+        .expect("failed to read sysfs");
+        assert_eq!(interfaces.keys().collect::<Vec<_>>(), ["itf1"]);
+
+        fs::remove_dir(&itf1_dir).expect("failed to remove subdirectory");
+        fs::create_dir(sys_net_dir.path().join("itf2")).expect("failed to create subdirectory");
+        refresh_networks_list_from_sysfs(
+            &mut interfaces,
+            sys_net_dir.path(),
+            &sys_net_dir.path().join("dev_snmp6"),
+            false,
+        )
+        .expect("failed to read sysfs");
+        let mut itf_names: Vec<String> = interfaces.keys().map(|n| n.to_owned()).collect();
+        itf_names.sort();
+        assert_eq!(itf_names, ["itf1", "itf2"]);
+    }
+
+    #[test]
+    fn refresh_networks_list_from_proc_net_dev() {
+        let header =
+            "Inter-|   Receive                                                |  Transmit\n \
+                      face |bytes    packets errs drop fifo frame compressed multicast|bytes    \
+                      packets errs drop fifo colls carrier compressed\n";
+        let content = format!(
+            "{}    lo:   53616     612    0    0    0     0          0         0    53616     612    0    0    0     0       0          0\n  \
+             eth0: 1000 10 1 4 0 0 0 3 2000 20 2 5 0 0 0 0\n",
+            header
+        );
+
+        let mut interfaces = HashMap::new();
+        refresh_networks_list_from_net_dev(&mut interfaces, &content, true);
+        let mut itf_names: Vec<String> = interfaces.keys().map(|n| n.to_owned()).collect();
+        itf_names.sort();
+        assert_eq!(itf_names, ["eth0", "lo"]);
+        assert_eq!(
+            interfaces["lo"].get_interface_type(),
+            InterfaceType::Loopback
+        );
+        let eth0 = &interfaces["eth0"];
+        assert_eq!(eth0.get_interface_type(), InterfaceType::Other);
+        assert!(!eth0.is_physical());
+        assert_eq!(eth0.get_total_received(), 1000);
+        assert_eq!(eth0.get_total_transmitted(), 2000);
+        assert_eq!(eth0.get_total_packets_received(), 10);
+        assert_eq!(eth0.get_total_packets_transmitted(), 20);
+        assert_eq!(eth0.get_total_errors_on_received(), 1);
+        assert_eq!(eth0.get_total_errors_on_transmitted(), 2);
+        assert_eq!(eth0.get_total_drops_on_received(), 4);
+        assert_eq!(eth0.get_total_drops_on_transmitted(), 5);
+        assert_eq!(eth0.get_total_packets_received_multicast(), 3);
+
+        let content = format!(
+            "{}  eth0: 1500 15 1 0 0 0 0 3 2000 20 2 0 0 0 0 0\n",
+            header
+        );
+        refresh_networks_list_from_net_dev(&mut interfaces, &content, true);
+        assert_eq!(interfaces.keys().collect::<Vec<_>>(), ["eth0"]);
+        assert_eq!(interfaces["eth0"].get_received(), 500);
+    }
+
+    #[test]
+    fn check_default_gateway_v4() {
+        let content =
+            "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+wlan0\t00000000\t0102A8C0\t0003\t0\t0\t600\t00000000\t0\t0\t0
+eth0\t00000000\t0100000A\t0003\t0\t0\t100\t00000000\t0\t0\t0
+eth0\t0000000A\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0
+";
+        // The route with the lowest metric is used.
+        assert_eq!(
+            parse_default_gateway_v4(content),
+            Some("10.0.0.1".parse().unwrap())
+        );
+        // Only the header and a non-default route.
+        let content = content
+            .lines()
+            .enumerate()
+            .filter(|(i, _)| *i == 0 || *i == 3)
+            .map(|(_, line)| line)
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(parse_default_gateway_v4(&content), None);
+        assert_eq!(parse_default_gateway_v4(""), None);
+    }
+
+    #[test]
+    fn check_default_gateway_v6() {
+        let content = "\
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 00000000000000000000000000000000 ffffffff 00000001 00000000 00200200 lo
+fe800000000000000000000000000000 40 00000000000000000000000000000000 00 00000000000000000000000000000000 00000100 00000001 00000000 00000001 eth0
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 fe800000000000000000000000000001 00000400 00000001 00000000 00000003 eth0
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 fe800000000000000000000000000002 00000258 00000001 00000000 00000003 wlan0
+";
+        assert_eq!(
+            parse_default_gateway_v6(content),
+            Some("fe80::2".parse().unwrap())
+        );
+        assert_eq!(parse_default_gateway_v6(""), None);
+    }
+
+    #[test]
+    fn check_resolv_conf() {
+        let content = "\
+# Generated by NetworkManager
+search example.com
+nameserver 192.168.1.1
+nameserver fe80::1%eth0
+nameserver invalid
+options edns0
+";
+        assert_eq!(
+            parse_resolv_conf(content),
+            vec![
+                "192.168.1.1".parse::<std::net::IpAddr>().unwrap(),
+                "fe80::1".parse().unwrap(),
+            ]
+        );
+    }
 }
@@ -6,12 +6,20 @@
 
 use std::collections::HashMap;
 use std::fmt;
-use std::fs::File;
+use std::fs::{self, File};
 use std::path::{Path, PathBuf};
+use std::process::ExitStatus;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use libc::{c_int, gid_t, kill, uid_t};
+use libc::{c_int, gid_t, kill, setpriority, uid_t, PRIO_PROCESS};
 
-use crate::{DiskUsage, Pid, ProcessExt, Signal};
+use crate::sys::socket::SocketTables;
+use crate::utils::wait_process;
+use crate::{
+    AccessError, DiskUsage, Gid, NetworkUsage, Pid, PriorityError, ProcessExt, Signal, SocketInfo,
+    ThreadInfo, Uid,
+};
 
 /// Enum describing the different status of a process.
 #[derive(Clone, Copy, Debug)]
@@ -88,6 +96,11 @@ impl ProcessStatus {
             ProcessStatus::Unknown(_) => "Unknown",
         }
     }
+
+    /// Returns `true` if the process is currently runnable.
+    pub(crate) fn is_running(&self) -> bool {
+        matches!(*self, ProcessStatus::Run)
+    }
 }
 
 impl fmt::Display for ProcessStatus {
@@ -96,83 +109,166 @@ impl fmt::Display for ProcessStatus {
     }
 }
 
+/// The `stat` file of a process, kept open to speed up the next refreshes. It isn't shared with
+/// the clones of the process, they open their own file if they're refreshed.
+#[derive(Default)]
+pub(crate) struct StatFile(pub(crate) Option<File>);
+
+impl Clone for StatFile {
+    fn clone(&self) -> StatFile {
+        StatFile(None)
+    }
+}
+
 /// Struct containing a process' information.
+#[derive(Clone)]
 pub struct Process {
     pub(crate) name: String,
+    /// Name from the `stat` file, truncated to 15 characters.
+    pub(crate) short_name: String,
     pub(crate) cmd: Vec<String>,
     pub(crate) exe: PathBuf,
     pub(crate) pid: Pid,
     parent: Option<Pid>,
+    pub(crate) tgid: Pid,
+    pub(crate) is_kernel_thread: bool,
+    pub(crate) session_id: Option<i32>,
+    pub(crate) tty_nr: u32,
+    pub(crate) tty: Option<String>,
     pub(crate) environ: Vec<String>,
     pub(crate) cwd: PathBuf,
     pub(crate) root: PathBuf,
+    pub(crate) cgroup: Option<String>,
     pub(crate) memory: u64,
     pub(crate) virtual_memory: u64,
+    pub(crate) shared_memory: u64,
+    pub(crate) swap_memory: u64,
     utime: u64,
     stime: u64,
     old_utime: u64,
     old_stime: u64,
     start_time: u64,
-    updated: bool,
+    pub(crate) run_time: u64,
+    pub(crate) thread_count: u32,
+    pub(crate) open_files_count: Option<usize>,
+    pub(crate) priority: i32,
+    pub(crate) oom_score: Option<u32>,
+    pub(crate) oom_score_adj: Option<i32>,
+    pub(crate) io_wait_time: Option<u64>,
+    pub(crate) sched_delay: Option<u64>,
+    pub(crate) updated: bool,
+    /// `false` if the process exited but is kept for one more refresh.
+    pub(crate) exists: bool,
+    pub(crate) last_error: Option<AccessError>,
     cpu_usage: f32,
-    /// User id of the process owner.
+    /// Real user id of the process owner.
     pub uid: uid_t,
+    pub(crate) effective_uid: uid_t,
     /// Group id of the process owner.
     pub gid: gid_t,
     pub(crate) status: ProcessStatus,
     /// Tasks run by this process.
     pub tasks: HashMap<Pid, Process>,
-    pub(crate) stat_file: Option<File>,
+    /// Name of the task, only set for the tasks of a process.
+    pub(crate) thread_name: String,
+    threads: Vec<ThreadInfo>,
+    pub(crate) stat_file: StatFile,
+    /// Socket tables of the last refresh of this process.
+    pub(crate) socket_tables: Option<Arc<SocketTables>>,
     old_read_bytes: u64,
     old_written_bytes: u64,
     read_bytes: u64,
     written_bytes: u64,
+    old_minor_faults: u64,
+    old_major_faults: u64,
+    minor_faults: u64,
+    major_faults: u64,
 }
 
 impl ProcessExt for Process {
     fn new(pid: Pid, parent: Option<Pid>, start_time: u64) -> Process {
         Process {
             name: String::with_capacity(20),
+            short_name: String::new(),
             pid,
             parent,
+            tgid: pid,
+            is_kernel_thread: false,
+            session_id: None,
+            tty_nr: 0,
+            tty: None,
             cmd: Vec::with_capacity(2),
             environ: Vec::with_capacity(10),
             exe: PathBuf::new(),
             cwd: PathBuf::new(),
             root: PathBuf::new(),
+            cgroup: None,
             memory: 0,
             virtual_memory: 0,
+            shared_memory: 0,
+            swap_memory: 0,
             cpu_usage: 0.,
             utime: 0,
             stime: 0,
             old_utime: 0,
             old_stime: 0,
             updated: true,
+            exists: true,
+            last_error: None,
             start_time,
+            run_time: 0,
+            thread_count: 0,
+            open_files_count: None,
+            priority: 0,
+            oom_score: None,
+            oom_score_adj: None,
+            io_wait_time: None,
+            sched_delay: None,
             uid: 0,
+            effective_uid: 0,
             gid: 0,
             status: ProcessStatus::Unknown(0),
-            tasks: if pid == 0 {
+            tasks: if pid.0 == 0 {
                 HashMap::with_capacity(1000)
             } else {
                 HashMap::new()
             },
-            stat_file: None,
+            thread_name: String::new(),
+            threads: Vec::new(),
+            stat_file: StatFile::default(),
+            socket_tables: None,
             old_read_bytes: 0,
             old_written_bytes: 0,
             read_bytes: 0,
             written_bytes: 0,
+            old_minor_faults: 0,
+            old_major_faults: 0,
+            minor_faults: 0,
+            major_faults: 0,
         }
     }
 
     fn kill(&self, signal: Signal) -> bool {
-        unsafe { kill(self.pid, signal as c_int) == 0 }
+        unsafe { kill(self.pid.0, signal as c_int) == 0 }
+    }
+
+    fn wait(&self) -> Option<ExitStatus> {
+        wait_pid(self.pid, || false)
+    }
+
+    fn wait_timeout(&self, timeout: Duration) -> Option<ExitStatus> {
+        let start = Instant::now();
+        wait_pid(self.pid, || start.elapsed() >= timeout)
     }
 
     fn name(&self) -> &str {
         &self.name
     }
 
+    fn short_name(&self) -> &str {
+        &self.short_name
+    }
+
     fn cmd(&self) -> &[String] {
         &self.cmd
     }
@@ -205,10 +301,38 @@ impl ProcessExt for Process {
         self.virtual_memory
     }
 
+    fn shared_memory(&self) -> u64 {
+        self.shared_memory
+    }
+
+    fn swap_memory(&self) -> u64 {
+        self.swap_memory
+    }
+
     fn parent(&self) -> Option<Pid> {
         self.parent
     }
 
+    fn is_kernel_thread(&self) -> bool {
+        self.is_kernel_thread
+    }
+
+    fn is_thread(&self) -> bool {
+        self.tgid != self.pid
+    }
+
+    fn tgid(&self) -> Pid {
+        self.tgid
+    }
+
+    fn session_id(&self) -> Option<i32> {
+        self.session_id
+    }
+
+    fn tty(&self) -> Option<&str> {
+        self.tty.as_deref()
+    }
+
     /// Returns the status of the processus (idle, run, zombie, etc). `None` means that
     /// `sysinfo` doesn't have enough rights to get this information.
     fn status(&self) -> ProcessStatus {
@@ -219,10 +343,95 @@ impl ProcessExt for Process {
         self.start_time
     }
 
+    fn run_time(&self) -> u64 {
+        self.run_time
+    }
+
+    fn thread_count(&self) -> u32 {
+        self.thread_count
+    }
+
+    fn threads(&self) -> &[ThreadInfo] {
+        &self.threads
+    }
+
+    fn open_files_count(&self) -> Option<usize> {
+        self.open_files_count
+    }
+
+    fn open_sockets(&self) -> Vec<SocketInfo> {
+        // Threads share the file descriptors of their process.
+        self.socket_tables().get_process_sockets(self.tgid)
+    }
+
+    fn network_usage(&self) -> Option<NetworkUsage> {
+        self.socket_tables().get_process_network_usage(self.tgid)
+    }
+
+    fn user_id(&self) -> Option<Uid> {
+        Some(Uid(self.uid))
+    }
+
+    fn effective_user_id(&self) -> Option<Uid> {
+        Some(Uid(self.effective_uid))
+    }
+
+    fn group_id(&self) -> Option<Gid> {
+        Some(Gid(self.gid))
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn set_nice(&self, nice: i32) -> Result<(), PriorityError> {
+        if unsafe { setpriority(PRIO_PROCESS as _, self.pid.0 as _, nice) } != 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        Ok(())
+    }
+
+    fn oom_score(&self) -> Option<u32> {
+        self.oom_score
+    }
+
+    fn oom_score_adj(&self) -> Option<i32> {
+        self.oom_score_adj
+    }
+
+    fn cgroup(&self) -> Option<String> {
+        self.cgroup.clone()
+    }
+
+    fn exists(&self) -> bool {
+        self.exists
+    }
+
+    fn last_error(&self) -> Option<AccessError> {
+        self.last_error
+    }
+
+    fn io_wait_time(&self) -> Option<u64> {
+        self.io_wait_time
+    }
+
+    fn sched_delay(&self) -> Option<u64> {
+        self.sched_delay
+    }
+
     fn cpu_usage(&self) -> f32 {
         self.cpu_usage
     }
 
+    fn accumulated_cpu_time(&self) -> u64 {
+        // `utime` and `stime` are expressed in clock ticks.
+        let clock_ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+        if clock_ticks <= 0 {
+            return 0;
+        }
+        (self.utime + self.stime) * 1_000 / clock_ticks as u64
+    }
+
     fn disk_usage(&self) -> DiskUsage {
         DiskUsage {
             written_bytes: self.written_bytes - self.old_written_bytes,
@@ -231,11 +440,37 @@ impl ProcessExt for Process {
             total_read_bytes: self.read_bytes,
         }
     }
+
+    fn minor_faults(&self) -> u64 {
+        self.minor_faults.saturating_sub(self.old_minor_faults)
+    }
+
+    fn major_faults(&self) -> u64 {
+        self.major_faults.saturating_sub(self.old_major_faults)
+    }
+
+    fn total_minor_faults(&self) -> u64 {
+        self.minor_faults
+    }
+
+    fn total_major_faults(&self) -> u64 {
+        self.major_faults
+    }
+}
+
+impl Process {
+    /// Returns the socket tables of the last refresh, or new ones if the process hasn't been
+    /// refreshed by a `System`.
+    fn socket_tables(&self) -> Arc<SocketTables> {
+        self.socket_tables
+            .clone()
+            .unwrap_or_else(|| Arc::new(SocketTables::new(Path::new("/proc"))))
+    }
 }
 
 impl Drop for Process {
     fn drop(&mut self) {
-        if self.stat_file.is_some() {
+        if self.stat_file.0.is_some() {
             if let Ok(ref mut x) = unsafe { crate::sys::system::REMAINING_FILES.lock() } {
                 **x += 1;
             }
@@ -254,16 +489,119 @@ pub fn compute_cpu_usage(p: &mut Process, nb_processors: u64, total_time: f32) {
     p.updated = false;
 }
 
+/// Blocks until the process `pid` ends or until `should_stop` returns `true`.
+pub(crate) fn wait_pid<S: Fn() -> bool>(pid: Pid, should_stop: S) -> Option<ExitStatus> {
+    let path = PathBuf::from(format!("/proc/{}/stat", pid));
+    wait_process(
+        pid,
+        || {
+            let data = fs::read_to_string(&path).ok()?;
+            let parts = super::system::parse_stat_file(&data).ok()?;
+            // A zombie process is already dead, it's only waiting for its parent to reap it.
+            if parts[2] == "Z" {
+                None
+            } else {
+                parts[21].parse().ok()
+            }
+        },
+        should_stop,
+    )
+}
+
+/// Parses the content of the `/proc/[pid]/schedstat` file and returns the time spent waiting on
+/// a run queue (in milliseconds). It contains the time spent on the CPU and waiting on a run
+/// queue (both in nanoseconds) and the number of timeslices run on this CPU:
+///
+/// ```text
+/// 1254623 54470 12
+/// ```
+pub(crate) fn parse_schedstat(content: &str) -> Option<u64> {
+    let delay = content.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+    Some(delay / 1_000_000)
+}
+
+/// Parses the content of the `/proc/[pid]/cgroup` file, which has a line for each hierarchy
+/// with its id, its controllers and the path of the process' group. The cgroup v2 hierarchy has
+/// the id 0 and no controllers:
+///
+/// ```text
+/// 4:memory:/docker/0123456789abcdef
+/// 1:cpu:/
+/// 0::/
+/// ```
+pub(crate) fn parse_cgroup_path(content: &str) -> Option<String> {
+    let entries = content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, ':');
+            Some((parts.next()?, parts.next()?, parts.next()?))
+        })
+        .collect::<Vec<_>>();
+    let unified = entries
+        .iter()
+        .find(|(id, controllers, _)| *id == "0" && controllers.is_empty());
+    let path = match unified {
+        // On hybrid systems, the v2 hierarchy might not be used by anything but systemd.
+        Some((_, _, path)) if *path != "/" || entries.len() == 1 => path,
+        _ => entries
+            .iter()
+            .find(|(_, _, path)| *path != "/")
+            .or_else(|| entries.first())
+            .map(|(_, _, path)| path)?,
+    };
+    Some((*path).to_owned())
+}
+
+/// Computes the CPU usage of the tasks of `p` and updates its threads list.
+pub(crate) fn update_threads(p: &mut Process, nb_processors: u64, total_time: f32) {
+    p.threads.clear();
+    for task in p.tasks.values_mut() {
+        compute_cpu_usage(task, nb_processors, total_time);
+        p.threads.push(ThreadInfo {
+            tid: task.pid,
+            name: task.thread_name.clone(),
+            cpu_usage: task.cpu_usage,
+        });
+    }
+    p.threads.sort_unstable_by_key(|thread| thread.tid);
+}
+
 pub fn set_time(p: &mut Process, utime: u64, stime: u64) {
     p.old_utime = p.utime;
     p.old_stime = p.stime;
     p.utime = utime;
     p.stime = stime;
-    p.updated = true;
 }
 
-pub fn has_been_updated(p: &Process) -> bool {
-    p.updated
+pub(crate) fn set_faults(p: &mut Process, minor_faults: u64, major_faults: u64) {
+    p.old_minor_faults = p.minor_faults;
+    p.old_major_faults = p.major_faults;
+    p.minor_faults = minor_faults;
+    p.major_faults = major_faults;
+}
+
+/// Makes `p` and its tasks use the socket tables of the current refresh.
+pub(crate) fn set_socket_tables(p: &mut Process, tables: &Arc<SocketTables>) {
+    p.socket_tables = Some(Arc::clone(tables));
+    for task in p.tasks.values_mut() {
+        task.socket_tables = Some(Arc::clone(tables));
+    }
+}
+
+pub fn has_been_updated(p: &mut Process) -> bool {
+    let old = p.updated;
+    p.updated = false;
+    old
+}
+
+/// Creates the processes list entry of the thread `task`.
+pub(crate) fn new_thread_entry(task: &Process) -> Process {
+    let mut entry = task.clone();
+    entry.tasks = HashMap::new();
+    entry.threads = Vec::new();
+    entry.updated = true;
+    entry.exists = true;
+    entry
 }
 
 pub(crate) fn update_process_disk_activity(p: &mut Process, path: &Path) {
@@ -300,3 +638,34 @@ pub(crate) fn update_process_disk_activity(p: &mut Process, path: &Path) {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{parse_cgroup_path, parse_schedstat};
+
+    #[test]
+    fn check_parse_cgroup_path() {
+        assert_eq!(
+            parse_cgroup_path("0::/system.slice/docker-0123.scope\n"),
+            Some("/system.slice/docker-0123.scope".to_owned())
+        );
+        assert_eq!(parse_cgroup_path("0::/\n"), Some("/".to_owned()));
+        let hybrid = "9:name=systemd:/\n4:memory:/docker/0123\n1:cpu:/\n0::/\n";
+        assert_eq!(parse_cgroup_path(hybrid), Some("/docker/0123".to_owned()));
+        let legacy = "4:memory:/\n1:cpu:/\n";
+        assert_eq!(parse_cgroup_path(legacy), Some("/".to_owned()));
+        assert_eq!(parse_cgroup_path(""), None);
+    }
+
+    #[test]
+    fn check_parse_schedstat() {
+        assert_eq!(parse_schedstat("1254623 54470000 12\n"), Some(54));
+        assert_eq!(parse_schedstat("1254623 999999 12\n"), Some(0));
+        assert_eq!(parse_schedstat("1254623\n"), None);
+        assert_eq!(parse_schedstat(""), None);
+
+        let content =
+            std::fs::read_to_string("/proc/self/schedstat").expect("failed to read schedstat");
+        assert!(parse_schedstat(&content).is_some());
+    }
+}
@@ -6,11 +6,14 @@
 
 #![allow(clippy::too_many_arguments)]
 
-use std::collections::HashSet;
-use std::fs::File;
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
 use std::io::Read;
+#[cfg(feature = "process")]
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
 
-use crate::ProcessorExt;
+use crate::{CacheInfo, CacheType, CpuTimes, ProcessorExt};
 
 /// Struct containing values to compute a CPU usage.
 #[derive(Clone, Copy)]
@@ -127,6 +130,8 @@ pub struct Processor {
     pub(crate) frequency: u64,
     pub(crate) vendor_id: String,
     pub(crate) brand: String,
+    pub(crate) temperature_file: Option<PathBuf>,
+    temperature: Option<f32>,
 }
 
 impl Processor {
@@ -158,9 +163,18 @@ impl Processor {
             frequency,
             vendor_id,
             brand,
+            temperature_file: None,
+            temperature: None,
         }
     }
 
+    pub(crate) fn refresh_temperature(&mut self) {
+        self.temperature = self
+            .temperature_file
+            .as_ref()
+            .and_then(|f| read_temperature(f));
+    }
+
     pub(crate) fn set(
         &mut self,
         user: u64,
@@ -219,18 +233,126 @@ impl ProcessorExt for Processor {
     fn get_brand(&self) -> &str {
         &self.brand
     }
+
+    fn get_temperature(&self) -> Option<f32> {
+        self.temperature
+    }
+
+    fn get_times(&self) -> CpuTimes {
+        let values = &self.new_values;
+        CpuTimes {
+            user: values.user,
+            nice: values.nice,
+            system: values.system,
+            idle: values.idle,
+            iowait: values.iowait,
+            irq: values.irq,
+            softirq: values.softirq,
+            steal: values.steal,
+        }
+    }
+}
+
+fn read_temperature(path: &Path) -> Option<f32> {
+    let content = fs::read_to_string(path).ok()?;
+    // The value is in millidegree Celsius.
+    content.trim().parse::<f32>().ok().map(|temp| temp / 1000.)
 }
 
-pub fn get_raw_times(p: &Processor) -> (u64, u64) {
-    (p.total_time, p.old_total_time)
+/// Returns the package and core ids of the given CPU.
+pub(crate) fn get_cpu_topology(sys_cpu: &Path, cpu_index: usize) -> Option<(u32, u32)> {
+    let topology = sys_cpu.join(format!("cpu{}/topology", cpu_index));
+    let read = |name: &str| -> Option<u32> {
+        fs::read_to_string(topology.join(name))
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+    };
+    Some((read("physical_package_id")?, read("core_id")?))
 }
 
-pub fn get_cpu_frequency(cpu_core_index: usize) -> u64 {
+/// Returns the temperature input files of the `coretemp` hwmon devices, indexed by their package
+/// and core ids. The cores are labeled "Core N" and each package has a "Package id N" label.
+pub(crate) fn get_core_temperature_files(sys_hwmon: &Path) -> HashMap<(u32, u32), PathBuf> {
+    let mut files = HashMap::new();
+    let dir = match fs::read_dir(sys_hwmon) {
+        Ok(dir) => dir,
+        Err(_) => return files,
+    };
+    for entry in dir.flatten() {
+        let hwmon = entry.path();
+        match fs::read_to_string(hwmon.join("name")) {
+            Ok(ref name) if name.trim() == "coretemp" => {}
+            _ => continue,
+        }
+        let labels = match fs::read_dir(&hwmon) {
+            Ok(labels) => labels,
+            Err(_) => continue,
+        };
+        let mut package = 0;
+        let mut cores = Vec::new();
+        for label in labels.flatten() {
+            let file_name = label.file_name();
+            let file_name = match file_name.to_str() {
+                Some(f) if f.starts_with("temp") && f.ends_with("_label") => f,
+                _ => continue,
+            };
+            let content = match fs::read_to_string(label.path()) {
+                Ok(content) => content,
+                Err(_) => continue,
+            };
+            let content = content.trim();
+            if let Some(id) = content.strip_prefix("Package id ") {
+                package = id.parse().unwrap_or(0);
+            } else if let Some(id) = content.strip_prefix("Core ") {
+                if let Ok(id) = id.parse::<u32>() {
+                    let input = file_name.replace("_label", "_input");
+                    cores.push((id, hwmon.join(input)));
+                }
+            }
+        }
+        for (core, input) in cores {
+            files.insert((package, core), input);
+        }
+    }
+    files
+}
+
+/// Parses the aggregated `cpu` line of `/proc/stat` and returns its total time.
+#[cfg(feature = "process")]
+pub(crate) fn parse_total_cpu_time(line: &str) -> Option<u64> {
+    let mut parts = line.split_whitespace();
+    if parts.next() != Some("cpu") {
+        return None;
+    }
+    let mut values = [0u64; 10];
+    for (value, part) in values.iter_mut().zip(parts) {
+        *value = part.parse().unwrap_or(0);
+    }
+    Some(
+        CpuValues::new_with_values(
+            values[0], values[1], values[2], values[3], values[4], values[5], values[6], values[7],
+            values[8], values[9],
+        )
+        .total_time(),
+    )
+}
+
+/// Returns the total time spent by all the processors, without updating any [`Processor`].
+#[cfg(feature = "process")]
+pub(crate) fn get_total_cpu_time(sysroot: &Path) -> Option<u64> {
+    let mut line = String::new();
+    File::open(sysroot.join("proc/stat"))
+        .and_then(|f| BufReader::new(f).read_line(&mut line))
+        .ok()?;
+    parse_total_cpu_time(&line)
+}
+
+pub fn get_cpu_frequency(sysroot: &Path, cpu_core_index: usize) -> u64 {
     let mut s = String::new();
-    if File::open(format!(
-        "/sys/devices/system/cpu/cpu{}/cpufreq/scaling_cur_freq",
+    if File::open(sysroot.join(format!(
+        "sys/devices/system/cpu/cpu{}/cpufreq/scaling_cur_freq",
         cpu_core_index
-    ))
+    )))
     .and_then(|mut f| f.read_to_string(&mut s))
     .is_ok()
     {
@@ -242,7 +364,7 @@ pub fn get_cpu_frequency(cpu_core_index: usize) -> u64 {
         }
     }
     s.clear();
-    if File::open("/proc/cpuinfo")
+    if File::open(sysroot.join("proc/cpuinfo"))
         .and_then(|mut f| f.read_to_string(&mut s))
         .is_err()
     {
@@ -298,9 +420,9 @@ pub fn get_physical_core_count() -> Option<usize> {
 }
 
 /// Returns the brand/vendor string for the first CPU (which should be the same for all CPUs).
-pub fn get_vendor_id_and_brand() -> (String, String) {
+pub fn get_vendor_id_and_brand(sysroot: &Path) -> (String, String) {
     let mut s = String::new();
-    if File::open("/proc/cpuinfo")
+    if File::open(sysroot.join("proc/cpuinfo"))
         .and_then(|mut f| f.read_to_string(&mut s))
         .is_err()
     {
@@ -331,3 +453,188 @@ pub fn get_vendor_id_and_brand() -> (String, String) {
     }
     (vendor_id.unwrap_or_default(), brand.unwrap_or_default())
 }
+
+/// Returns the instruction set extensions supported by the CPU.
+pub(crate) fn get_cpu_features() -> Vec<String> {
+    let mut s = String::new();
+    let features = match File::open("/proc/cpuinfo").and_then(|mut f| f.read_to_string(&mut s)) {
+        Ok(_) => parse_cpu_features(&s),
+        Err(_) => Vec::new(),
+    };
+    // Some kernels (or containers) don't expose the flags, so we ask the CPU directly.
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if features.is_empty() {
+            return crate::utils::get_x86_cpu_features();
+        }
+    }
+    features
+}
+
+/// Returns the caches listed in the `cache` directory of a CPU in sysfs, which contains an
+/// `index[N]` directory for each cache.
+pub(crate) fn get_cpu_caches(cache_dir: &Path) -> Vec<CacheInfo> {
+    let dir = match fs::read_dir(cache_dir) {
+        Ok(dir) => dir,
+        Err(_) => return Vec::new(),
+    };
+    let mut caches = dir
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("index"))
+        .filter_map(|entry| {
+            let path = entry.path();
+            let read = |name: &str| -> Option<String> {
+                fs::read_to_string(path.join(name))
+                    .ok()
+                    .map(|s| s.trim().to_owned())
+            };
+            let type_ = match read("type")?.as_str() {
+                "Data" => CacheType::Data,
+                "Instruction" => CacheType::Instruction,
+                "Unified" => CacheType::Unified,
+                _ => return None,
+            };
+            Some(CacheInfo {
+                level: read("level")?.parse().ok()?,
+                type_,
+                size: parse_cache_size(&read("size")?)?,
+                line_size: read("coherency_line_size")
+                    .and_then(|s| s.parse().ok())
+                    .unwrap_or(0),
+            })
+        })
+        .collect::<Vec<_>>();
+    // The directory entries aren't sorted.
+    caches.sort_by_key(|cache| (cache.level, cache.type_ as u8));
+    caches
+}
+
+/// Parses a cache size like "32K" or "8M".
+fn parse_cache_size(size: &str) -> Option<u64> {
+    let (value, multiplier) = match size.chars().last()? {
+        'K' => (&size[..size.len() - 1], 1024),
+        'M' => (&size[..size.len() - 1], 1024 * 1024),
+        'G' => (&size[..size.len() - 1], 1024 * 1024 * 1024),
+        _ => (size, 1),
+    };
+    value.parse::<u64>().ok().map(|value| value * multiplier)
+}
+
+/// Parses the `flags` (x86) or `Features` (ARM) line of the first CPU in `/proc/cpuinfo`.
+fn parse_cpu_features(cpuinfo: &str) -> Vec<String> {
+    for line in cpuinfo.lines() {
+        let mut parts = line.splitn(2, ':');
+        let key = parts.next().unwrap_or("").trim();
+        if key == "flags" || key == "Features" {
+            return parts
+                .next()
+                .unwrap_or("")
+                .split_whitespace()
+                .map(|f| f.to_owned())
+                .collect();
+        }
+    }
+    Vec::new()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        get_core_temperature_files, get_cpu_caches, get_cpu_topology, parse_cpu_features,
+        read_temperature,
+    };
+    use crate::{CacheInfo, CacheType};
+    use std::fs;
+
+    #[test]
+    fn check_core_temperature_files() {
+        let sys_hwmon = tempfile::tempdir().expect("failed to create temporary directory");
+        let coretemp = sys_hwmon.path().join("hwmon1");
+        fs::create_dir(&coretemp).expect("failed to create subdirectory");
+        fs::write(coretemp.join("name"), "coretemp\n").expect("failed to write name");
+        fs::write(coretemp.join("temp1_label"), "Package id 1\n").expect("failed to write label");
+        fs::write(coretemp.join("temp2_label"), "Core 0\n").expect("failed to write label");
+        fs::write(coretemp.join("temp2_input"), "42000\n").expect("failed to write input");
+        fs::write(coretemp.join("temp3_label"), "Core 4\n").expect("failed to write label");
+        let other = sys_hwmon.path().join("hwmon0");
+        fs::create_dir(&other).expect("failed to create subdirectory");
+        fs::write(other.join("name"), "acpitz\n").expect("failed to write name");
+        fs::write(other.join("temp1_label"), "Core 0\n").expect("failed to write label");
+
+        let files = get_core_temperature_files(sys_hwmon.path());
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[&(1, 0)], coretemp.join("temp2_input"));
+        assert_eq!(files[&(1, 4)], coretemp.join("temp3_input"));
+        assert_eq!(read_temperature(&files[&(1, 0)]), Some(42.));
+        assert_eq!(read_temperature(&files[&(1, 4)]), None);
+
+        let sys_cpu = tempfile::tempdir().expect("failed to create temporary directory");
+        let topology = sys_cpu.path().join("cpu3/topology");
+        fs::create_dir_all(&topology).expect("failed to create subdirectory");
+        fs::write(topology.join("physical_package_id"), "1\n").expect("failed to write id");
+        fs::write(topology.join("core_id"), "4\n").expect("failed to write id");
+        assert_eq!(get_cpu_topology(sys_cpu.path(), 3), Some((1, 4)));
+        assert_eq!(get_cpu_topology(sys_cpu.path(), 0), None);
+    }
+
+    #[test]
+    fn check_parse_cpu_features() {
+        let x86 = "processor\t: 0\nvendor_id\t: GenuineIntel\nflags\t\t: fpu sse sse2 avx2\n\n\
+                   processor\t: 1\nflags\t\t: fpu sse\n";
+        assert_eq!(parse_cpu_features(x86), vec!["fpu", "sse", "sse2", "avx2"]);
+        let arm = "processor\t: 0\nBogoMIPS\t: 48.00\nFeatures\t: fp asimd crc32\n";
+        assert_eq!(parse_cpu_features(arm), vec!["fp", "asimd", "crc32"]);
+        assert!(parse_cpu_features("processor\t: 0\n").is_empty());
+    }
+
+    #[test]
+    fn check_cpu_caches() {
+        let cache_dir = tempfile::tempdir().expect("failed to create temporary directory");
+        for (index, level, type_, size) in &[
+            ("index0", "1", "Data", "48K"),
+            ("index1", "1", "Instruction", "32K"),
+            ("index2", "2", "Unified", "2048K"),
+            ("index3", "3", "Unified", "260M"),
+        ] {
+            let dir = cache_dir.path().join(index);
+            fs::create_dir(&dir).expect("failed to create subdirectory");
+            fs::write(dir.join("level"), format!("{}\n", level)).expect("failed to write level");
+            fs::write(dir.join("type"), format!("{}\n", type_)).expect("failed to write type");
+            fs::write(dir.join("size"), format!("{}\n", size)).expect("failed to write size");
+            fs::write(dir.join("coherency_line_size"), "64\n")
+                .expect("failed to write coherency_line_size");
+        }
+        fs::write(cache_dir.path().join("uevent"), "").expect("failed to write uevent");
+
+        let cache = |level, type_, size| CacheInfo {
+            level,
+            type_,
+            size,
+            line_size: 64,
+        };
+        assert_eq!(
+            get_cpu_caches(cache_dir.path()),
+            vec![
+                cache(1, CacheType::Data, 48 * 1024),
+                cache(1, CacheType::Instruction, 32 * 1024),
+                cache(2, CacheType::Unified, 2048 * 1024),
+                cache(3, CacheType::Unified, 260 * 1024 * 1024),
+            ]
+        );
+        assert!(get_cpu_caches(&cache_dir.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "process")]
+    fn check_parse_total_cpu_time() {
+        use super::parse_total_cpu_time;
+
+        assert_eq!(
+            parse_total_cpu_time("cpu  10 2 30 400 5 6 7 8 9 1"),
+            Some(10 + 2 + 30 + 400 + 5 + 6 + 7 + 8)
+        );
+        assert_eq!(parse_total_cpu_time("cpu  10 2 30"), Some(42));
+        assert_eq!(parse_total_cpu_time("cpu0 10 2 30 400 5 6 7 8 9 1"), None);
+        assert_eq!(parse_total_cpu_time(""), None);
+    }
+}
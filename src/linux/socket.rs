@@ -0,0 +1,512 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::system::get_all_data;
+use crate::{NetworkUsage, Pid, SocketInfo, SocketProtocol, SocketState};
+
+use libc::c_int;
+
+use std::collections::{HashMap, HashSet};
+use std::convert::TryInto;
+use std::fs;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// The socket tables of `/proc/net`, with the protocol they list.
+const SOCKET_TABLES: &[(&str, SocketProtocol)] = &[
+    ("tcp", SocketProtocol::Tcp),
+    ("tcp6", SocketProtocol::Tcp),
+    ("udp", SocketProtocol::Udp),
+    ("udp6", SocketProtocol::Udp),
+];
+
+/// Returns the inodes of the sockets opened by the process whose folder is `proc_path` (like
+/// `/proc/[pid]`).
+fn get_socket_inodes(proc_path: &Path) -> HashSet<u64> {
+    let dir = match fs::read_dir(proc_path.join("fd")) {
+        Ok(dir) => dir,
+        Err(_) => return HashSet::new(),
+    };
+    dir.flatten()
+        .filter_map(|entry| {
+            // The link target looks like "socket:[12345]".
+            let target = fs::read_link(entry.path()).ok()?;
+            let target = target.to_str()?;
+            target
+                .strip_prefix("socket:[")?
+                .strip_suffix(']')?
+                .parse()
+                .ok()
+        })
+        .collect()
+}
+
+/// Returns the sockets listed in the tables of `net_path` (like `/proc/net` or
+/// `/proc/[pid]/net`), along with their inode.
+pub(crate) fn get_sockets(net_path: &Path) -> Vec<(u64, SocketInfo)> {
+    SOCKET_TABLES
+        .iter()
+        .flat_map(|(file, protocol)| {
+            parse_socket_table(
+                &get_all_data(net_path.join(file), 16_385).unwrap_or_default(),
+                *protocol,
+            )
+        })
+        .collect()
+}
+
+/// Returns all the sockets of the system, with the PID of the process owning them. `proc_path`
+/// is the procfs folder (like `/proc`).
+pub(crate) fn get_all_sockets(proc_path: &Path) -> Vec<SocketInfo> {
+    let mut owners = HashMap::new();
+    if let Ok(dir) = fs::read_dir(proc_path) {
+        for entry in dir.flatten() {
+            let pid = match entry
+                .file_name()
+                .to_str()
+                .and_then(|s| s.parse::<Pid>().ok())
+            {
+                Some(pid) => pid,
+                None => continue,
+            };
+            for inode in get_socket_inodes(&entry.path()) {
+                // A socket can be shared between processes (after a fork for example), we keep
+                // the first one we find.
+                owners.entry(inode).or_insert(pid);
+            }
+        }
+    }
+    get_sockets(&proc_path.join("net"))
+        .into_iter()
+        .map(|(inode, mut socket)| {
+            socket.pid = owners.get(&inode).copied();
+            socket
+        })
+        .collect()
+}
+
+/// Sockets listed in the tables of a network namespace, with their inode.
+type NamespaceSockets = Arc<Vec<(u64, SocketInfo)>>;
+
+/// System-wide socket tables shared by the processes refreshed at the same time. Each table is
+/// only read the first time it's needed, so it's read at most once per refresh.
+pub(crate) struct SocketTables {
+    /// The procfs folder (like `/proc`).
+    proc_path: PathBuf,
+    /// The sockets of each network namespace, with their inode.
+    sockets: Mutex<HashMap<PathBuf, NamespaceSockets>>,
+    /// The bytes transferred by the TCP sockets, `None` until the `sock_diag` dump is done.
+    tcp_usage: Mutex<Option<Option<HashMap<u64, NetworkUsage>>>>,
+}
+
+impl SocketTables {
+    pub(crate) fn new(proc_path: &Path) -> SocketTables {
+        SocketTables {
+            proc_path: proc_path.to_owned(),
+            sockets: Mutex::new(HashMap::new()),
+            tcp_usage: Mutex::new(None),
+        }
+    }
+
+    /// Returns the sockets opened by the process `pid`.
+    pub(crate) fn get_process_sockets(&self, pid: Pid) -> Vec<SocketInfo> {
+        let path = self.proc_path.join(pid.to_string());
+        let inodes = get_socket_inodes(&path);
+        if inodes.is_empty() {
+            return Vec::new();
+        }
+        // The tables of the process' folder are the ones of its network namespace.
+        let namespace = fs::read_link(path.join("ns/net")).unwrap_or_else(|_| path.clone());
+        let sockets = match self.sockets.lock() {
+            Ok(mut sockets) => sockets
+                .entry(namespace)
+                .or_insert_with(|| Arc::new(get_sockets(&path.join("net"))))
+                .clone(),
+            Err(_) => Arc::new(get_sockets(&path.join("net"))),
+        };
+        sockets
+            .iter()
+            .filter(|(inode, _)| inodes.contains(inode))
+            .map(|(_, socket)| SocketInfo {
+                pid: Some(pid),
+                ..*socket
+            })
+            .collect()
+    }
+
+    /// Returns the bytes transferred by the TCP sockets of the process `pid`.
+    pub(crate) fn get_process_network_usage(&self, pid: Pid) -> Option<NetworkUsage> {
+        let path = self.proc_path.join(pid.to_string());
+        let inodes = get_socket_inodes(&path);
+        if inodes.is_empty() && fs::read_dir(path.join("fd")).is_err() {
+            // We're not allowed to see the sockets of this process.
+            return None;
+        }
+        let mut tcp_usage = self.tcp_usage.lock().ok()?;
+        let usage = tcp_usage
+            .get_or_insert_with(get_tcp_sockets_usage)
+            .as_ref()?;
+        Some(inodes.iter().filter_map(|inode| usage.get(inode)).fold(
+            NetworkUsage::default(),
+            |total, usage| NetworkUsage {
+                total_received_bytes: total.total_received_bytes + usage.total_received_bytes,
+                total_transmitted_bytes: total.total_transmitted_bytes
+                    + usage.total_transmitted_bytes,
+            },
+        ))
+    }
+}
+
+// From `linux/netlink.h`, `linux/sock_diag.h` and `linux/inet_diag.h`.
+const NETLINK_SOCK_DIAG: c_int = 4;
+const SOCK_DIAG_BY_FAMILY: u16 = 20;
+const NLM_F_REQUEST: u16 = 0x1;
+const NLM_F_DUMP: u16 = 0x300;
+const NLMSG_ERROR: u16 = 2;
+const NLMSG_DONE: u16 = 3;
+const INET_DIAG_INFO: u16 = 2;
+/// Size of `struct nlmsghdr`.
+const NLMSG_HEADER_LEN: usize = 16;
+/// Size of `struct inet_diag_msg`, the inode is its last field.
+const INET_DIAG_MSG_LEN: usize = 72;
+/// Offsets of `tcpi_bytes_acked` and `tcpi_bytes_received` in `struct tcp_info`.
+const TCPI_BYTES_ACKED: usize = 120;
+const TCPI_BYTES_RECEIVED: usize = 128;
+
+/// Netlink socket, closed when dropped.
+struct NetlinkSocket(c_int);
+
+impl Drop for NetlinkSocket {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.0);
+        }
+    }
+}
+
+fn read_u16(data: &[u8], pos: usize) -> u16 {
+    u16::from_ne_bytes(data[pos..pos + 2].try_into().unwrap())
+}
+
+fn read_u32(data: &[u8], pos: usize) -> u32 {
+    u32::from_ne_bytes(data[pos..pos + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], pos: usize) -> u64 {
+    u64::from_ne_bytes(data[pos..pos + 8].try_into().unwrap())
+}
+
+/// Netlink messages and attributes are aligned on 4 bytes.
+fn align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+/// Builds a `sock_diag` request dumping the TCP sockets of the `family` with their `tcp_info`.
+fn build_sock_diag_request(family: c_int) -> Vec<u8> {
+    // `struct nlmsghdr` followed by `struct inet_diag_req_v2`.
+    let mut request = vec![0u8; NLMSG_HEADER_LEN + 56];
+    let len = request.len() as u32;
+    request[0..4].copy_from_slice(&len.to_ne_bytes());
+    request[4..6].copy_from_slice(&SOCK_DIAG_BY_FAMILY.to_ne_bytes());
+    request[6..8].copy_from_slice(&(NLM_F_REQUEST | NLM_F_DUMP).to_ne_bytes());
+    request[16] = family as u8;
+    request[17] = libc::IPPROTO_TCP as u8;
+    // The extensions are a bit field, `INET_DIAG_INFO` is the first one.
+    request[18] = 1 << (INET_DIAG_INFO - 1);
+    // All the states.
+    request[20..24].copy_from_slice(&u32::MAX.to_ne_bytes());
+    request
+}
+
+/// Parses the `sock_diag` messages of `data` and adds the bytes transferred by each socket to
+/// `usage`. Returns `Some(true)` once the end of the dump is reached and `None` on error.
+fn parse_sock_diag_messages(data: &[u8], usage: &mut HashMap<u64, NetworkUsage>) -> Option<bool> {
+    let mut pos = 0;
+    while pos + NLMSG_HEADER_LEN <= data.len() {
+        let len = read_u32(data, pos) as usize;
+        if len < NLMSG_HEADER_LEN || pos + len > data.len() {
+            return None;
+        }
+        match read_u16(data, pos + 4) {
+            NLMSG_DONE => return Some(true),
+            NLMSG_ERROR => return None,
+            SOCK_DIAG_BY_FAMILY if len >= NLMSG_HEADER_LEN + INET_DIAG_MSG_LEN => {
+                let msg = &data[pos + NLMSG_HEADER_LEN..pos + len];
+                let inode = read_u32(msg, INET_DIAG_MSG_LEN - 4) as u64;
+                let mut attr_pos = INET_DIAG_MSG_LEN;
+                while attr_pos + 4 <= msg.len() {
+                    let attr_len = read_u16(msg, attr_pos) as usize;
+                    if attr_len < 4 || attr_pos + attr_len > msg.len() {
+                        break;
+                    }
+                    // `tcpi_bytes_acked` and `tcpi_bytes_received` were added in Linux 4.1
+                    // and 4.2.
+                    if read_u16(msg, attr_pos + 2) == INET_DIAG_INFO
+                        && attr_len >= 4 + TCPI_BYTES_RECEIVED + 8
+                    {
+                        let info = &msg[attr_pos + 4..attr_pos + attr_len];
+                        usage.insert(
+                            inode,
+                            NetworkUsage {
+                                total_received_bytes: read_u64(info, TCPI_BYTES_RECEIVED),
+                                total_transmitted_bytes: read_u64(info, TCPI_BYTES_ACKED),
+                            },
+                        );
+                    }
+                    attr_pos += align(attr_len);
+                }
+            }
+            _ => {}
+        }
+        pos += align(len);
+    }
+    Some(false)
+}
+
+/// Returns the bytes transferred by the TCP sockets of the current network namespace, by
+/// inode. They're retrieved from the `sock_diag` netlink subsystem.
+fn get_tcp_sockets_usage() -> Option<HashMap<u64, NetworkUsage>> {
+    let mut usage = HashMap::new();
+    let mut buffer = vec![0u8; 32_768];
+    for family in &[libc::AF_INET, libc::AF_INET6] {
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_DGRAM | libc::SOCK_CLOEXEC,
+                NETLINK_SOCK_DIAG,
+            )
+        };
+        if fd < 0 {
+            return None;
+        }
+        let socket = NetlinkSocket(fd);
+        let request = build_sock_diag_request(*family);
+        // Without destination, the request is sent to the kernel.
+        if unsafe { libc::send(socket.0, request.as_ptr() as _, request.len(), 0) } < 0 {
+            return None;
+        }
+        loop {
+            let len = unsafe { libc::recv(socket.0, buffer.as_mut_ptr() as _, buffer.len(), 0) };
+            if len <= 0 {
+                return None;
+            }
+            if parse_sock_diag_messages(&buffer[..len as usize], &mut usage)? {
+                break;
+            }
+        }
+    }
+    Some(usage)
+}
+
+/// Parses one of the socket tables. It looks like this:
+///
+/// ```text
+///   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+///    0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 24531 1 ...
+/// ```
+fn parse_socket_table(content: &str, protocol: SocketProtocol) -> Vec<(u64, SocketInfo)> {
+    // The first line is the header.
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            if fields.len() < 10 {
+                return None;
+            }
+            let state = match protocol {
+                SocketProtocol::Tcp => {
+                    Some(get_tcp_state(u32::from_str_radix(fields[3], 16).ok()?))
+                }
+                SocketProtocol::Udp => None,
+            };
+            Some((
+                fields[9].parse().ok()?,
+                SocketInfo {
+                    protocol,
+                    local_address: parse_socket_address(fields[1])?,
+                    remote_address: parse_socket_address(fields[2])?,
+                    state,
+                    pid: None,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Parses an address like `0100007F:0277`. The IP address is written as 32-bit words in host
+/// byte order, whereas the port is in network byte order.
+fn parse_socket_address(address: &str) -> Option<SocketAddr> {
+    let mut parts = address.splitn(2, ':');
+    let ip = parts.next()?;
+    let port = u16::from_str_radix(parts.next()?, 16).ok()?;
+    let mut bytes = Vec::with_capacity(16);
+    for pos in (0..ip.len()).step_by(8) {
+        let word = u32::from_str_radix(ip.get(pos..pos + 8)?, 16).ok()?;
+        bytes.extend_from_slice(&word.to_ne_bytes());
+    }
+    let ip = match bytes.len() {
+        4 => IpAddr::V4(Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3])),
+        16 => {
+            let mut octets = [0; 16];
+            octets.copy_from_slice(&bytes);
+            IpAddr::V6(Ipv6Addr::from(octets))
+        }
+        _ => return None,
+    };
+    Some(SocketAddr::new(ip, port))
+}
+
+/// The values are the `TCP_*` states from `include/net/tcp_states.h`.
+fn get_tcp_state(state: u32) -> SocketState {
+    match state {
+        1 => SocketState::Established,
+        2 => SocketState::SynSent,
+        3 => SocketState::SynReceived,
+        4 => SocketState::FinWait1,
+        5 => SocketState::FinWait2,
+        6 => SocketState::TimeWait,
+        7 => SocketState::Closed,
+        8 => SocketState::CloseWait,
+        9 => SocketState::LastAck,
+        10 => SocketState::Listen,
+        11 => SocketState::Closing,
+        x => SocketState::Unknown(x),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        build_sock_diag_request, parse_sock_diag_messages, parse_socket_address,
+        parse_socket_table, SocketTables, INET_DIAG_INFO, INET_DIAG_MSG_LEN, NLMSG_DONE,
+        SOCK_DIAG_BY_FAMILY, TCPI_BYTES_ACKED, TCPI_BYTES_RECEIVED,
+    };
+    use crate::{NetworkUsage, Pid, SocketProtocol, SocketState};
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    fn new_message(type_: u16, payload: &[u8]) -> Vec<u8> {
+        let mut message = Vec::new();
+        message.extend_from_slice(&(16 + payload.len() as u32).to_ne_bytes());
+        message.extend_from_slice(&type_.to_ne_bytes());
+        message.extend_from_slice(&[0; 10]);
+        message.extend_from_slice(payload);
+        message
+    }
+
+    fn new_sock_diag_message(inode: u32, received: u64, acked: u64) -> Vec<u8> {
+        let mut payload = vec![0; INET_DIAG_MSG_LEN];
+        payload[INET_DIAG_MSG_LEN - 4..].copy_from_slice(&inode.to_ne_bytes());
+        let mut info = vec![0; TCPI_BYTES_RECEIVED + 16];
+        info[TCPI_BYTES_ACKED..TCPI_BYTES_ACKED + 8].copy_from_slice(&acked.to_ne_bytes());
+        info[TCPI_BYTES_RECEIVED..TCPI_BYTES_RECEIVED + 8].copy_from_slice(&received.to_ne_bytes());
+        payload.extend_from_slice(&(4 + info.len() as u16).to_ne_bytes());
+        payload.extend_from_slice(&INET_DIAG_INFO.to_ne_bytes());
+        payload.extend_from_slice(&info);
+        new_message(SOCK_DIAG_BY_FAMILY, &payload)
+    }
+
+    #[test]
+    fn check_parse_socket_table() {
+        let tcp = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   \
+                   uid  timeout inode\n   \
+                   0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000     \
+                   0        0 24531 1 0000000000000000 100 0 0 10 0\n   \
+                   1: 0F02000A:9C40 2D0FA8C0:01BB 01 00000000:00000000 02:00000A2B 00000000  \
+                   1000        0 891011 2 0000000000000000 20 4 30 10 -1\n";
+        let sockets = parse_socket_table(tcp, SocketProtocol::Tcp);
+        assert_eq!(sockets.len(), 2);
+        assert_eq!(sockets[0].0, 24531);
+        assert_eq!(sockets[0].1.protocol, SocketProtocol::Tcp);
+        assert_eq!(sockets[0].1.state, Some(SocketState::Listen));
+        assert_eq!(sockets[0].1.remote_address.port(), 0);
+        assert_eq!(sockets[1].0, 891011);
+        assert_eq!(sockets[1].1.local_address.port(), 40000);
+        assert_eq!(sockets[1].1.remote_address.port(), 443);
+        assert_eq!(sockets[1].1.state, Some(SocketState::Established));
+
+        let udp = "   sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   \
+                   uid  timeout inode ref pointer drops\n  \
+                   7: 00000000:0044 00000000:0000 07 00000000:00000000 00:00000000 00000000     \
+                   0        0 17356 2 0000000000000000 0\n";
+        let sockets = parse_socket_table(udp, SocketProtocol::Udp);
+        assert_eq!(sockets.len(), 1);
+        assert_eq!(sockets[0].1.local_address, "0.0.0.0:68".parse().unwrap());
+        assert_eq!(sockets[0].1.state, None);
+    }
+
+    #[test]
+    #[cfg(target_endian = "little")]
+    fn check_parse_socket_address() {
+        assert_eq!(
+            parse_socket_address("0100007F:0277"),
+            Some("127.0.0.1:631".parse().unwrap())
+        );
+        assert_eq!(
+            parse_socket_address("00000000000000000000000001000000:1F90"),
+            Some("[::1]:8080".parse().unwrap())
+        );
+        assert_eq!(parse_socket_address("0100007F"), None);
+        assert_eq!(parse_socket_address("01000:0277"), None);
+    }
+
+    #[test]
+    fn check_sock_diag_request() {
+        let request = build_sock_diag_request(libc::AF_INET6);
+        assert_eq!(request.len(), 72);
+        assert_eq!(request[0..4], 72u32.to_ne_bytes());
+        assert_eq!(request[16], libc::AF_INET6 as u8);
+        assert_eq!(request[17], libc::IPPROTO_TCP as u8);
+        assert_eq!(request[18], 2);
+    }
+
+    #[test]
+    fn check_parse_sock_diag_messages() {
+        let mut data = new_sock_diag_message(42, 1000, 2000);
+        // Without the `tcp_info` (like on old kernels), the socket is ignored.
+        data.extend(new_message(SOCK_DIAG_BY_FAMILY, &[0; INET_DIAG_MSG_LEN]));
+        data.extend(new_sock_diag_message(43, 10, 20));
+
+        let mut usage = HashMap::new();
+        assert_eq!(parse_sock_diag_messages(&data, &mut usage), Some(false));
+        assert_eq!(usage.len(), 2);
+        assert_eq!(
+            usage[&42],
+            NetworkUsage {
+                total_received_bytes: 1000,
+                total_transmitted_bytes: 2000,
+            }
+        );
+        assert_eq!(usage[&43].total_received_bytes, 10);
+
+        let done = new_message(NLMSG_DONE, &[0; 4]);
+        assert_eq!(parse_sock_diag_messages(&done, &mut usage), Some(true));
+        // Truncated message.
+        assert_eq!(parse_sock_diag_messages(&data[..20], &mut usage), None);
+    }
+
+    #[test]
+    fn check_socket_tables_are_shared() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+        let address = listener.local_addr().unwrap();
+        let pid = Pid(std::process::id() as _);
+        let tables = SocketTables::new(Path::new("/proc"));
+
+        let sockets = tables.get_process_sockets(pid);
+        assert!(sockets.iter().any(|socket| socket.local_address == address));
+        assert!(sockets.iter().all(|socket| socket.pid == Some(pid)));
+        // The table of the namespace is kept, so the new socket isn't listed.
+        let other = std::net::TcpListener::bind("127.0.0.1:0").expect("failed to bind");
+        let other_address = other.local_addr().unwrap();
+        let sockets = tables.get_process_sockets(pid);
+        assert!(!sockets
+            .iter()
+            .any(|socket| socket.local_address == other_address));
+        assert_eq!(tables.sockets.lock().unwrap().len(), 1);
+    }
+}
@@ -4,27 +4,59 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
+use crate::sys::battery;
+#[cfg(feature = "component")]
 use crate::sys::component::{self, Component};
+#[cfg(feature = "disk")]
 use crate::sys::disk;
+#[cfg(feature = "component")]
+use crate::sys::fan::{self, Fan};
+use crate::sys::gpu::{self, Gpu};
+#[cfg(feature = "process")]
 use crate::sys::process::*;
+#[cfg(feature = "cpu")]
 use crate::sys::processor::*;
-use crate::{Disk, LoadAvg, Networks, Pid, ProcessExt, RefreshKind, SystemExt, User};
-
-use libc::{self, c_char, gid_t, sysconf, uid_t, _SC_CLK_TCK, _SC_HOST_NAME_MAX, _SC_PAGESIZE};
+#[cfg(feature = "process")]
+use crate::sys::socket::SocketTables;
+#[cfg(feature = "cpu")]
+use crate::CacheInfo;
+#[cfg(feature = "network")]
+use crate::Networks;
+use crate::{Battery, LoadAvg, RefreshKind, SwapDevice, SwapType, SystemExt, User, Virtualization};
+#[cfg(feature = "disk")]
+use crate::{Disk, Mount};
+#[cfg(feature = "process")]
+use crate::{Pid, ProcessExt, ProcessRefreshKind, ProcessWatcher, SocketInfo};
+
+use libc::{self, c_char, sysconf, _SC_HOST_NAME_MAX};
+#[cfg(feature = "process")]
+use libc::{gid_t, uid_t, _SC_CLK_TCK, _SC_PAGESIZE};
+#[cfg(feature = "process")]
 use std::cell::UnsafeCell;
+#[cfg(any(feature = "cpu", feature = "process"))]
 use std::collections::HashMap;
 use std::fs::{self, File};
 use std::io::{self, BufRead, BufReader, Read};
 use std::path::{Path, PathBuf};
+#[cfg(feature = "process")]
+use std::process::ExitStatus;
 use std::str::FromStr;
+#[cfg(feature = "process")]
 use std::sync::{Arc, Mutex};
-use std::time::SystemTime;
+#[cfg(feature = "cpu")]
+use std::time::Duration;
 
-use crate::utils::{into_iter, realpath};
+#[cfg(feature = "cpu")]
+use crate::utils::RefreshInterval;
+#[cfg(feature = "disk")]
+use crate::utils::{filter_disks, DiskFilter};
+#[cfg(feature = "process")]
+use crate::utils::{get_secs_since_epoch, into_iter, realpath};
 
 // This whole thing is to prevent having too many files open at once. It could be problematic
 // for processes using a lot of files and using sysinfo at the same time.
 #[allow(clippy::mutex_atomic)]
+#[cfg(feature = "process")]
 pub(crate) static mut REMAINING_FILES: once_cell::sync::Lazy<Arc<Mutex<isize>>> =
     once_cell::sync::Lazy::new(|| {
         unsafe {
@@ -53,6 +85,7 @@ pub(crate) static mut REMAINING_FILES: once_cell::sync::Lazy<Arc<Mutex<isize>>>
         }
     });
 
+#[cfg(feature = "process")]
 pub(crate) fn get_max_nb_fds() -> isize {
     unsafe {
         let mut limits = libc::rlimit {
@@ -68,14 +101,15 @@ pub(crate) fn get_max_nb_fds() -> isize {
     }
 }
 
+#[cfg(feature = "cpu")]
 macro_rules! to_str {
     ($e:expr) => {
         unsafe { std::str::from_utf8_unchecked($e) }
     };
 }
 
-fn boot_time() -> u64 {
-    if let Ok(f) = File::open("/proc/stat") {
+fn boot_time(sysroot: &Path) -> u64 {
+    if let Ok(f) = File::open(sysroot.join("proc/stat")) {
         let buf = BufReader::new(f);
         let line = buf
             .split(b'\n')
@@ -106,6 +140,7 @@ fn boot_time() -> u64 {
 
 /// Structs containing system's information.
 pub struct System {
+    #[cfg(feature = "process")]
     process_list: Process,
     mem_total: u64,
     mem_free: u64,
@@ -115,29 +150,181 @@ pub struct System {
     mem_slab_reclaimable: u64,
     swap_total: u64,
     swap_free: u64,
+    swap_devices: Vec<SwapDevice>,
+    #[cfg(feature = "cpu")]
     global_processor: Processor,
+    #[cfg(feature = "cpu")]
     processors: Vec<Processor>,
+    #[cfg(feature = "cpu")]
+    cpu_refresh_interval: RefreshInterval,
+    #[cfg(feature = "process")]
+    include_threads: bool,
+    #[cfg(feature = "process")]
+    keep_exited_processes: bool,
+    #[cfg(feature = "process")]
     page_size_kb: u64,
+    #[cfg(feature = "component")]
     components: Vec<Component>,
+    #[cfg(feature = "component")]
+    fans: Vec<Fan>,
+    gpus: Vec<Gpu>,
+    #[cfg(feature = "disk")]
     disks: Vec<Disk>,
+    #[cfg(feature = "disk")]
+    disk_filter: Option<DiskFilter>,
+    #[cfg(feature = "network")]
     networks: Networks,
     uptime: u64,
     users: Vec<User>,
+    #[cfg(feature = "process")]
+    sockets: Vec<SocketInfo>,
+    #[cfg(feature = "cpu")]
+    context_switches: u64,
+    #[cfg(feature = "cpu")]
+    old_context_switches: u64,
+    #[cfg(feature = "cpu")]
+    interrupts: u64,
+    #[cfg(feature = "cpu")]
+    old_interrupts: u64,
     boot_time: u64,
+    /// Total processors time at the last processes refresh, kept apart from the processors so
+    /// that `refresh_cpu` and `refresh_processes` don't affect each other's usage computation.
+    #[cfg(feature = "process")]
+    processes_total_time: u64,
+    /// Directory containing the `proc` and `sys` directories to read, `/` unless the system was
+    /// created with [`System::with_sysroot`].
+    sysroot: PathBuf,
 }
 
 impl System {
+    /// Creates a new [`System`] reading the files of the `proc` and `sys` directories of
+    /// `sysroot` instead of the ones of `/`. Nothing is refreshed. It allows to test the parsing
+    /// of the processes, memory and network information on a fixture directory.
+    ///
+    /// This function is only available on Linux, with the `test-utils` feature.
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let mut s = System::with_sysroot(Path::new("tests/fixtures/sysroot"));
+    /// s.refresh_processes();
+    /// for (pid, process) in s.get_processes() {
+    ///     println!("{} {}", pid, process.name());
+    /// }
+    /// ```
+    #[cfg(feature = "test-utils")]
+    pub fn with_sysroot(sysroot: &Path) -> System {
+        System::new_with_sysroot(sysroot, RefreshKind::new())
+    }
+
+    fn new_with_sysroot(sysroot: &Path, refreshes: RefreshKind) -> System {
+        let mut s = System {
+            #[cfg(feature = "process")]
+            process_list: Process::new(Pid(0), None, 0),
+            mem_total: 0,
+            mem_free: 0,
+            mem_available: 0,
+            mem_buffers: 0,
+            mem_page_cache: 0,
+            mem_slab_reclaimable: 0,
+            swap_total: 0,
+            swap_free: 0,
+            swap_devices: Vec::new(),
+            #[cfg(feature = "cpu")]
+            global_processor: Processor::new_with_values(
+                "",
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                String::new(),
+                String::new(),
+            ),
+            #[cfg(feature = "cpu")]
+            processors: Vec::with_capacity(4),
+            #[cfg(feature = "cpu")]
+            cpu_refresh_interval: RefreshInterval::default(),
+            #[cfg(feature = "process")]
+            include_threads: false,
+            #[cfg(feature = "process")]
+            keep_exited_processes: false,
+            #[cfg(feature = "process")]
+            page_size_kb: unsafe { sysconf(_SC_PAGESIZE) as u64 / 1024 },
+            #[cfg(feature = "component")]
+            components: Vec::new(),
+            #[cfg(feature = "component")]
+            fans: Vec::new(),
+            gpus: Vec::new(),
+            #[cfg(feature = "disk")]
+            disks: Vec::with_capacity(2),
+            #[cfg(feature = "disk")]
+            disk_filter: None,
+            #[cfg(feature = "network")]
+            networks: Networks::new_with_sysroot(sysroot),
+            uptime: get_uptime(sysroot),
+            users: Vec::new(),
+            #[cfg(feature = "process")]
+            sockets: Vec::new(),
+            #[cfg(feature = "cpu")]
+            context_switches: 0,
+            #[cfg(feature = "cpu")]
+            old_context_switches: 0,
+            #[cfg(feature = "cpu")]
+            interrupts: 0,
+            #[cfg(feature = "cpu")]
+            old_interrupts: 0,
+            boot_time: boot_time(sysroot),
+            #[cfg(feature = "process")]
+            processes_total_time: 0,
+            sysroot: sysroot.to_owned(),
+        };
+        #[cfg(feature = "cpu")]
+        if !refreshes.cpu() {
+            s.refresh_processors(None); // We need the processors to be filled.
+        }
+        s.refresh_specifics(refreshes);
+        s
+    }
+
+    /// Returns the path of the `proc` directory to read.
+    #[cfg(any(feature = "disk", feature = "process"))]
+    fn proc_path(&self) -> PathBuf {
+        self.sysroot.join("proc")
+    }
+
+    /// Returns the processors time elapsed since the last processes refresh.
+    #[cfg(feature = "process")]
+    fn update_processes_total_time(&mut self) -> f32 {
+        let old = self.processes_total_time;
+        let new = get_total_cpu_time(&self.sysroot).unwrap_or(old);
+        self.processes_total_time = new;
+        (if old >= new { 1 } else { new - old }) as f32
+    }
+
+    #[cfg(feature = "process")]
     fn clear_procs(&mut self) {
         if !self.processors.is_empty() {
-            let (new, old) = get_raw_times(&self.global_processor);
-            let total_time = (if old > new { 1 } else { new - old }) as f32;
+            let total_time = self.update_processes_total_time();
             let mut to_delete = Vec::with_capacity(20);
 
             for (pid, proc_) in &mut self.process_list.tasks {
-                if !has_been_updated(proc_) {
-                    to_delete.push(*pid);
-                } else {
+                if has_been_updated(proc_) {
+                    proc_.exists = true;
                     compute_cpu_usage(proc_, self.processors.len() as u64, total_time);
+                    update_threads(proc_, self.processors.len() as u64, total_time);
+                } else if self.keep_exited_processes && proc_.exists {
+                    // It's removed at the next refresh.
+                    proc_.exists = false;
+                } else {
+                    to_delete.push(*pid);
                 }
             }
             for pid in to_delete {
@@ -146,18 +333,57 @@ impl System {
         }
     }
 
+    /// Replaces the threads entries of the processes list with the up-to-date tasks of the
+    /// processes (if `include_threads` is enabled).
+    #[cfg(feature = "process")]
+    fn update_thread_entries(&mut self) {
+        let processes = &mut self.process_list.tasks;
+        processes.retain(|_, p| !p.is_thread());
+        if !self.include_threads {
+            return;
+        }
+        let threads = processes
+            .values()
+            .flat_map(|p| p.tasks.values().map(new_thread_entry))
+            .collect::<Vec<_>>();
+        for thread in threads {
+            processes.insert(thread.pid, thread);
+        }
+    }
+
+    /// Updates the counters from the lines of `/proc/stat` following the processors ones.
+    #[cfg(feature = "cpu")]
+    fn update_stat_counter(&mut self, line: &[u8]) {
+        let mut parts = line.split(|x| *x == b' ').filter(|s| !s.is_empty());
+        let counter = match parts.next() {
+            Some(b"ctxt") => &mut self.context_switches,
+            // The first value is the total, the other ones are the per-interrupt counts.
+            Some(b"intr") => &mut self.interrupts,
+            _ => return,
+        };
+        if let Some(value) = parts.next() {
+            *counter = to_u64(value);
+        }
+    }
+
+    #[cfg(feature = "cpu")]
     fn refresh_processors(&mut self, limit: Option<u32>) {
-        if let Ok(f) = File::open("/proc/stat") {
+        if let Ok(f) = File::open(self.sysroot.join("proc/stat")) {
             let buf = BufReader::new(f);
             let mut i: usize = 0;
             let first = self.processors.is_empty();
             let mut it = buf.split(b'\n');
             let mut count = 0;
             let (vendor_id, brand) = if first {
-                get_vendor_id_and_brand()
+                get_vendor_id_and_brand(&self.sysroot)
             } else {
                 (String::new(), String::new())
             };
+            let temperature_files = if first {
+                get_core_temperature_files(&self.sysroot.join("sys/class/hwmon"))
+            } else {
+                HashMap::new()
+            };
 
             if let Some(Ok(line)) = it.next() {
                 if &line[..4] != b"cpu " {
@@ -190,6 +416,14 @@ impl System {
             }
             while let Some(Ok(line)) = it.next() {
                 if &line[..3] != b"cpu" {
+                    if limit.is_none() {
+                        self.old_context_switches = self.context_switches;
+                        self.old_interrupts = self.interrupts;
+                        for line in std::iter::once(line).chain(it.by_ref().filter_map(Result::ok))
+                        {
+                            self.update_stat_counter(&line);
+                        }
+                    }
                     break;
                 }
 
@@ -207,10 +441,13 @@ impl System {
                         parts.next().map(|v| to_u64(v)).unwrap_or(0),
                         parts.next().map(|v| to_u64(v)).unwrap_or(0),
                         parts.next().map(|v| to_u64(v)).unwrap_or(0),
-                        get_cpu_frequency(i),
+                        get_cpu_frequency(&self.sysroot, i),
                         vendor_id.clone(),
                         brand.clone(),
                     ));
+                    self.processors[i].temperature_file =
+                        get_cpu_topology(&self.sysroot.join("sys/devices/system/cpu"), i)
+                            .and_then(|topology| temperature_files.get(&topology).cloned());
                 } else {
                     parts.next(); // we don't want the name again
                     self.processors[i].set(
@@ -225,8 +462,9 @@ impl System {
                         parts.next().map(|v| to_u64(v)).unwrap_or(0),
                         parts.next().map(|v| to_u64(v)).unwrap_or(0),
                     );
-                    self.processors[i].frequency = get_cpu_frequency(i);
+                    self.processors[i].frequency = get_cpu_frequency(&self.sysroot, i);
                 }
+                self.processors[i].refresh_temperature();
                 i += 1;
                 count += 1;
                 if let Some(limit) = limit {
@@ -245,55 +483,22 @@ impl System {
 
 impl SystemExt for System {
     fn new_with_specifics(refreshes: RefreshKind) -> System {
-        let mut s = System {
-            process_list: Process::new(0, None, 0),
-            mem_total: 0,
-            mem_free: 0,
-            mem_available: 0,
-            mem_buffers: 0,
-            mem_page_cache: 0,
-            mem_slab_reclaimable: 0,
-            swap_total: 0,
-            swap_free: 0,
-            global_processor: Processor::new_with_values(
-                "",
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                0,
-                String::new(),
-                String::new(),
-            ),
-            processors: Vec::with_capacity(4),
-            page_size_kb: unsafe { sysconf(_SC_PAGESIZE) as u64 / 1024 },
-            components: Vec::new(),
-            disks: Vec::with_capacity(2),
-            networks: Networks::new(),
-            uptime: get_uptime(),
-            users: Vec::new(),
-            boot_time: boot_time(),
-        };
-        if !refreshes.cpu() {
-            s.refresh_processors(None); // We need the processors to be filled.
-        }
-        s.refresh_specifics(refreshes);
-        s
+        System::new_with_sysroot(Path::new("/"), refreshes)
     }
 
+    #[cfg(feature = "component")]
     fn refresh_components_list(&mut self) {
         self.components = component::get_components();
+        self.fans = fan::get_fans();
+    }
+
+    fn refresh_gpus_list(&mut self) {
+        self.gpus = gpu::get_gpus();
     }
 
     fn refresh_memory(&mut self) {
-        self.uptime = get_uptime();
-        if let Ok(data) = get_all_data("/proc/meminfo", 16_385) {
+        self.uptime = get_uptime(&self.sysroot);
+        if let Ok(data) = get_all_data(self.sysroot.join("proc/meminfo"), 16_385) {
             for line in data.split('\n') {
                 let field = match line.split(':').next() {
                     Some("MemTotal") => &mut self.mem_total,
@@ -314,96 +519,186 @@ impl SystemExt for System {
                 }
             }
         }
+        self.swap_devices = get_all_data(self.sysroot.join("proc/swaps"), 16_385)
+            .map(|data| parse_proc_swaps(&data))
+            .unwrap_or_default();
     }
 
+    #[cfg(feature = "cpu")]
     fn refresh_cpu(&mut self) {
-        self.uptime = get_uptime();
+        if !self.cpu_refresh_interval.should_refresh() {
+            return;
+        }
+        self.uptime = get_uptime(&self.sysroot);
         self.refresh_processors(None);
     }
 
-    fn refresh_processes(&mut self) {
-        self.uptime = get_uptime();
+    #[cfg(feature = "cpu")]
+    fn refresh_cpu_over(&mut self, window: Duration) {
+        self.cpu_refresh_interval.reset();
+        self.refresh_cpu();
+        std::thread::sleep(window);
+        self.cpu_refresh_interval.reset();
+        self.refresh_cpu();
+    }
+
+    #[cfg(feature = "cpu")]
+    fn set_min_refresh_interval(&mut self, interval: Duration) {
+        self.cpu_refresh_interval.set_min_interval(interval);
+    }
+
+    #[cfg(feature = "process")]
+    fn set_include_threads(&mut self, include: bool) {
+        self.include_threads = include;
+    }
+
+    #[cfg(feature = "process")]
+    fn set_keep_exited_processes(&mut self, keep: bool) {
+        self.keep_exited_processes = keep;
+    }
+
+    #[cfg(feature = "disk")]
+    fn set_disk_filter<F>(&mut self, filter: F)
+    where
+        F: Fn(&Path, &str) -> bool + Send + Sync + 'static,
+    {
+        self.disk_filter = Some(Box::new(filter));
+    }
+
+    #[cfg(feature = "process")]
+    fn refresh_processes_specifics(&mut self, refresh_kind: ProcessRefreshKind) {
+        self.uptime = get_uptime(&self.sysroot);
         if refresh_procs(
             &mut self.process_list,
-            Path::new("/proc"),
+            &self.sysroot.join("proc"),
             self.page_size_kb,
-            0,
+            Pid(0),
             self.uptime,
             get_secs_since_epoch(),
+            has_delay_accounting(&self.sysroot),
+            refresh_kind,
         ) {
+            let socket_tables = Arc::new(SocketTables::new(&self.proc_path()));
+            for process in self.process_list.tasks.values_mut() {
+                set_socket_tables(process, &socket_tables);
+            }
+            self.update_thread_entries();
             self.clear_procs();
         }
     }
 
+    #[cfg(feature = "process")]
     fn refresh_process(&mut self, pid: Pid) -> bool {
-        self.uptime = get_uptime();
+        self.uptime = get_uptime(&self.sysroot);
         let found = match _get_process_data(
-            &Path::new("/proc/").join(pid.to_string()),
+            &self.proc_path().join(pid.to_string()),
             &mut self.process_list,
             self.page_size_kb,
-            0,
+            Pid(0),
             self.uptime,
             get_secs_since_epoch(),
+            has_delay_accounting(&self.sysroot),
+            ProcessRefreshKind::everything(),
         ) {
             Ok((Some(p), pid)) => {
                 self.process_list.tasks.insert(pid, p);
                 true
             }
             Ok(_) => true,
-            Err(_) => false,
+            Err(_) => {
+                // The process doesn't exist anymore so we remove it from the list.
+                self.process_list.tasks.remove(&pid);
+                false
+            }
         };
+        let socket_tables = Arc::new(SocketTables::new(&self.proc_path()));
+        if let Some(p) = self.process_list.tasks.get_mut(&pid) {
+            set_socket_tables(p, &socket_tables);
+        }
         if found && !self.processors.is_empty() {
-            self.refresh_processors(Some(1));
-            let (new, old) = get_raw_times(&self.global_processor);
-            let total_time = (if old >= new { 1 } else { new - old }) as f32;
+            let total_time = self.update_processes_total_time();
 
             if let Some(p) = self.process_list.tasks.get_mut(&pid) {
                 compute_cpu_usage(p, self.processors.len() as u64, total_time);
+                update_threads(p, self.processors.len() as u64, total_time);
             }
         }
         found
     }
 
+    #[cfg(feature = "disk")]
     fn refresh_disks_list(&mut self) {
         self.disks = disk::get_all_disks();
+        filter_disks(&mut self.disks, &self.disk_filter);
     }
 
     fn refresh_users_list(&mut self) {
         self.users = crate::linux::users::get_users_list();
     }
 
+    #[cfg(feature = "process")]
+    fn refresh_sockets(&mut self) {
+        self.sockets = crate::linux::socket::get_all_sockets(&self.proc_path());
+    }
+
     // COMMON PART
     //
     // Need to be moved into a "common" file to avoid duplication.
 
+    #[cfg(feature = "process")]
     fn get_processes(&self) -> &HashMap<Pid, Process> {
         &self.process_list.tasks
     }
 
+    #[cfg(feature = "process")]
     fn get_process(&self, pid: Pid) -> Option<&Process> {
         self.process_list.tasks.get(&pid)
     }
 
+    #[cfg(feature = "process")]
+    fn watch_process_exit<F>(&self, pid: Pid, f: F) -> ProcessWatcher
+    where
+        F: FnOnce(Option<ExitStatus>) + Send + 'static,
+    {
+        ProcessWatcher::new(move |should_stop| wait_pid(pid, should_stop), f)
+    }
+
+    #[cfg(feature = "network")]
     fn get_networks(&self) -> &Networks {
         &self.networks
     }
 
+    #[cfg(feature = "network")]
     fn get_networks_mut(&mut self) -> &mut Networks {
         &mut self.networks
     }
 
+    #[cfg(feature = "cpu")]
     fn get_global_processor_info(&self) -> &Processor {
         &self.global_processor
     }
 
+    #[cfg(feature = "cpu")]
     fn get_processors(&self) -> &[Processor] {
         &self.processors
     }
 
+    #[cfg(feature = "cpu")]
     fn get_physical_core_count(&self) -> Option<usize> {
         get_physical_core_count()
     }
 
+    #[cfg(feature = "cpu")]
+    fn get_context_switches(&self) -> u64 {
+        self.context_switches
+            .saturating_sub(self.old_context_switches)
+    }
+
+    #[cfg(feature = "cpu")]
+    fn get_interrupts(&self) -> u64 {
+        self.interrupts.saturating_sub(self.old_interrupts)
+    }
+
     fn get_total_memory(&self) -> u64 {
         self.mem_total
     }
@@ -437,22 +732,53 @@ impl SystemExt for System {
         self.swap_total - self.swap_free
     }
 
+    fn get_swap_devices(&self) -> &[SwapDevice] {
+        &self.swap_devices
+    }
+
+    #[cfg(feature = "component")]
     fn get_components(&self) -> &[Component] {
         &self.components
     }
 
+    #[cfg(feature = "component")]
     fn get_components_mut(&mut self) -> &mut [Component] {
         &mut self.components
     }
 
+    #[cfg(feature = "component")]
+    fn get_fans(&self) -> &[Fan] {
+        &self.fans
+    }
+
+    #[cfg(feature = "component")]
+    fn get_fans_mut(&mut self) -> &mut [Fan] {
+        &mut self.fans
+    }
+
+    fn get_gpus(&self) -> &[Gpu] {
+        &self.gpus
+    }
+
+    fn get_gpus_mut(&mut self) -> &mut [Gpu] {
+        &mut self.gpus
+    }
+
+    #[cfg(feature = "disk")]
     fn get_disks(&self) -> &[Disk] {
         &self.disks
     }
 
+    #[cfg(feature = "disk")]
     fn get_disks_mut(&mut self) -> &mut [Disk] {
         &mut self.disks
     }
 
+    #[cfg(feature = "disk")]
+    fn get_all_mounts(&self) -> Vec<Mount> {
+        disk::get_all_mounts(&self.proc_path().join("mounts"))
+    }
+
     fn get_uptime(&self) -> u64 {
         self.uptime
     }
@@ -482,15 +808,24 @@ impl SystemExt for System {
         }
     }
 
+    fn get_battery(&self) -> Option<Battery> {
+        battery::get_battery()
+    }
+
     fn get_users(&self) -> &[User] {
         &self.users
     }
 
+    #[cfg(feature = "process")]
+    fn get_sockets(&self) -> &[SocketInfo] {
+        &self.sockets
+    }
+
     #[cfg(not(target_os = "android"))]
     fn get_name(&self) -> Option<String> {
         get_system_info_linux(
             InfoType::Name,
-            Path::new("/etc/os-release"),
+            get_os_release_path(),
             Path::new("/etc/lsb-release"),
         )
     }
@@ -553,7 +888,7 @@ impl SystemExt for System {
     fn get_os_version(&self) -> Option<String> {
         get_system_info_linux(
             InfoType::OsVersion,
-            Path::new("/etc/os-release"),
+            get_os_release_path(),
             Path::new("/etc/lsb-release"),
         )
     }
@@ -562,6 +897,40 @@ impl SystemExt for System {
     fn get_os_version(&self) -> Option<String> {
         get_system_info_android(InfoType::OsVersion)
     }
+
+    #[cfg(feature = "cpu")]
+    fn get_cpu_arch(&self) -> Option<String> {
+        let mut raw = std::mem::MaybeUninit::<libc::utsname>::zeroed();
+
+        if unsafe { libc::uname(raw.as_mut_ptr()) } == 0 {
+            let info = unsafe { raw.assume_init() };
+
+            let machine = info
+                .machine
+                .iter()
+                .filter(|c| **c != 0)
+                .map(|c| *c as u8 as char)
+                .collect::<String>();
+
+            Some(machine)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "cpu")]
+    fn get_cpu_features(&self) -> Vec<String> {
+        get_cpu_features()
+    }
+
+    #[cfg(feature = "cpu")]
+    fn get_cpu_caches(&self) -> Vec<CacheInfo> {
+        get_cpu_caches(Path::new("/sys/devices/system/cpu/cpu0/cache"))
+    }
+
+    fn get_virtualization(&self) -> Virtualization {
+        get_virtualization()
+    }
 }
 
 impl Default for System {
@@ -580,17 +949,23 @@ fn to_u64(v: &[u8]) -> u64 {
     x
 }
 
+#[cfg(feature = "process")]
 struct Wrap<'a, T>(UnsafeCell<&'a mut T>);
 
+#[cfg(feature = "process")]
 impl<'a, T> Wrap<'a, T> {
     fn get(&self) -> &'a mut T {
         unsafe { *(self.0.get()) }
     }
 }
 
+#[cfg(feature = "process")]
 unsafe impl<'a, T> Send for Wrap<'a, T> {}
+#[cfg(feature = "process")]
 unsafe impl<'a, T> Sync for Wrap<'a, T> {}
 
+#[allow(clippy::too_many_arguments)]
+#[cfg(feature = "process")]
 fn refresh_procs(
     proc_list: &mut Process,
     path: &Path,
@@ -598,6 +973,8 @@ fn refresh_procs(
     pid: Pid,
     uptime: u64,
     now: u64,
+    delay_accounting: bool,
+    refresh_kind: ProcessRefreshKind,
 ) -> bool {
     if let Ok(d) = fs::read_dir(path) {
         let folders = d
@@ -615,7 +992,7 @@ fn refresh_procs(
                 }
             })
             .collect::<Vec<_>>();
-        if pid == 0 {
+        if pid.0 == 0 {
             let proc_list = Wrap(UnsafeCell::new(proc_list));
 
             #[cfg(feature = "multithread")]
@@ -630,6 +1007,8 @@ fn refresh_procs(
                         pid,
                         uptime,
                         now,
+                        delay_accounting,
+                        refresh_kind,
                     ) {
                         p
                     } else {
@@ -642,9 +1021,16 @@ fn refresh_procs(
             let new_tasks = folders
                 .iter()
                 .filter_map(|e| {
-                    if let Ok((p, pid)) =
-                        _get_process_data(e.as_path(), proc_list, page_size_kb, pid, uptime, now)
-                    {
+                    if let Ok((p, pid)) = _get_process_data(
+                        e.as_path(),
+                        proc_list,
+                        page_size_kb,
+                        pid,
+                        uptime,
+                        now,
+                        delay_accounting,
+                        refresh_kind,
+                    ) {
                         updated_pids.push(pid);
                         p
                     } else {
@@ -668,7 +1054,63 @@ fn refresh_procs(
     }
 }
 
+/// Parses the `/proc/swaps` file. It looks like this:
+///
+/// ```text
+/// Filename                                Type            Size            Used            Priority
+/// /dev/sda2                               partition       8388604         0               -2
+/// ```
+///
+/// The sizes are in KiB.
+fn parse_proc_swaps(content: &str) -> Vec<SwapDevice> {
+    // The first line is the header.
+    content
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts
+                .next()?
+                .replace("\\134", "\\")
+                .replace("\\040", " ")
+                .replace("\\011", "\t")
+                .replace("\\012", "\n");
+            let type_ = match parts.next()? {
+                "partition" => SwapType::Partition,
+                _ => SwapType::File,
+            };
+            let size = u64::from_str(parts.next()?).ok()?;
+            let used = u64::from_str(parts.next()?).ok()?;
+            Some(SwapDevice {
+                name,
+                type_,
+                size: size * 1024,
+                used: used * 1024,
+            })
+        })
+        .collect()
+}
+
+/// Returns the number of resident shared pages, which is the third field of the `statm` file.
+#[cfg(feature = "process")]
+fn parse_statm_shared(statm: &str) -> Option<u64> {
+    statm.split_whitespace().nth(2)?.parse().ok()
+}
+
+/// Returns the value (in kB) of the `VmSwap` line of the `status` file.
+#[cfg(feature = "process")]
+fn parse_status_vm_swap(status: &str) -> Option<u64> {
+    status
+        .lines()
+        .find(|line| line.starts_with("VmSwap:"))?
+        .split_whitespace()
+        .nth(1)?
+        .parse()
+        .ok()
+}
+
 #[allow(clippy::too_many_arguments)]
+#[cfg(feature = "process")]
 fn update_time_and_memory(
     path: &Path,
     entry: &mut Process,
@@ -677,29 +1119,120 @@ fn update_time_and_memory(
     parent_memory: u64,
     parent_virtual_memory: u64,
     pid: Pid,
+    is_task: bool,
     uptime: u64,
     now: u64,
+    delay_accounting: bool,
+    refresh_kind: ProcessRefreshKind,
 ) {
     {
-        // rss
-        entry.memory = u64::from_str(parts[23]).unwrap_or(0) * page_size_kb;
-        if entry.memory >= parent_memory {
-            entry.memory -= parent_memory;
+        if refresh_kind.memory() {
+            // rss
+            entry.memory = u64::from_str(parts[23]).unwrap_or(0) * page_size_kb;
+            if entry.memory >= parent_memory {
+                entry.memory -= parent_memory;
+            }
+            // vsz
+            entry.virtual_memory = u64::from_str(parts[22]).unwrap_or(0);
+            if entry.virtual_memory >= parent_virtual_memory {
+                entry.virtual_memory -= parent_virtual_memory;
+            }
+            // Tasks share the address space of their process so we only read it once.
+            if !is_task {
+                entry.shared_memory = get_all_data(path.join("statm"), 1024)
+                    .ok()
+                    .and_then(|data| parse_statm_shared(&data))
+                    .unwrap_or(0)
+                    * page_size_kb;
+                entry.swap_memory = get_all_data(path.join("status"), 16_385)
+                    .ok()
+                    .and_then(|data| parse_status_vm_swap(&data))
+                    .unwrap_or(0);
+            }
+            entry.oom_score = read_value(&path.join("oom_score"));
+            entry.oom_score_adj = read_value(&path.join("oom_score_adj"));
+            // minflt and majflt
+            set_faults(
+                entry,
+                u64::from_str(parts[9]).unwrap_or(0),
+                u64::from_str(parts[11]).unwrap_or(0),
+            );
+        }
+        if refresh_kind.cpu() {
+            set_time(
+                entry,
+                u64::from_str(parts[13]).unwrap_or(0),
+                u64::from_str(parts[14]).unwrap_or(0),
+            );
+            // Without delay accounting, `delayacct_blkio_ticks` is always 0.
+            entry.io_wait_time = if delay_accounting {
+                parts
+                    .get(41)
+                    .and_then(|ticks| u64::from_str(ticks).ok())
+                    .and_then(ticks_to_ms)
+            } else {
+                None
+            };
+            entry.sched_delay = get_all_data(path.join("schedstat"), 1024)
+                .ok()
+                .and_then(|data| parse_schedstat(&data));
         }
-        // vsz
-        entry.virtual_memory = u64::from_str(parts[22]).unwrap_or(0);
-        if entry.virtual_memory >= parent_virtual_memory {
-            entry.virtual_memory -= parent_virtual_memory;
+        entry.run_time = now.saturating_sub(entry.start_time());
+        entry.updated = true;
+        entry.priority = i32::from_str(parts[18]).unwrap_or(0);
+        entry.thread_count = u32::from_str(parts[19]).unwrap_or(0);
+        entry.session_id = i32::from_str(parts[5]).ok();
+        let tty_nr = u32::from_str(parts[6]).unwrap_or(0);
+        if tty_nr != entry.tty_nr {
+            entry.tty_nr = tty_nr;
+            entry.tty = get_tty_path(tty_nr);
+        }
+        // Tasks share the file descriptors table of their process so there is no need to count
+        // them again. If we can't read this folder, it means we don't have enough rights.
+        if !is_task {
+            entry.open_files_count = fs::read_dir(path.join("fd")).ok().map(|d| d.count());
         }
-        set_time(
-            entry,
-            u64::from_str(parts[13]).unwrap_or(0),
-            u64::from_str(parts[14]).unwrap_or(0),
-        );
     }
-    refresh_procs(entry, &path.join("task"), page_size_kb, pid, uptime, now);
+    refresh_procs(
+        entry,
+        &path.join("task"),
+        page_size_kb,
+        pid,
+        uptime,
+        now,
+        delay_accounting,
+        refresh_kind,
+    );
+}
+
+/// Returns `false` if the delay accounting of the tasks has been disabled with the
+/// `kernel.task_delayacct` sysctl. Older kernels don't have it and always enable it.
+#[cfg(feature = "process")]
+fn has_delay_accounting(sysroot: &Path) -> bool {
+    get_all_data(sysroot.join("proc/sys/kernel/task_delayacct"), 50)
+        .map(|data| data.trim() != "0")
+        .unwrap_or(true)
+}
+
+/// Converts a duration in clock ticks into milliseconds.
+#[cfg(feature = "process")]
+fn ticks_to_ms(ticks: u64) -> Option<u64> {
+    let clock_ticks = unsafe { sysconf(_SC_CLK_TCK) };
+    if clock_ticks <= 0 {
+        return None;
+    }
+    Some(ticks * 1_000 / clock_ticks as u64)
+}
+
+/// Parses the content of a `/proc/[pid]` file containing a single value.
+#[cfg(feature = "process")]
+fn read_value<T: FromStr>(path: &Path) -> Option<T> {
+    get_all_data(path, 50)
+        .ok()
+        .and_then(|data| data.trim().parse().ok())
 }
 
+#[cfg(feature = "process")]
 macro_rules! unwrap_or_return {
     ($data:expr) => {{
         match $data {
@@ -709,14 +1242,21 @@ macro_rules! unwrap_or_return {
     }};
 }
 
-fn _get_uid_and_gid(status_data: String) -> Option<(uid_t, gid_t)> {
+/// Returns the real and effective user ids and the effective group id.
+#[cfg(feature = "process")]
+fn _get_uid_and_gid(status_data: String) -> Option<(uid_t, uid_t, gid_t)> {
     // We're only interested in the lines starting with Uid: and Gid:
-    // here. From these lines, we're looking at the second entry to get
-    // the effective u/gid.
+    // here. These lines contain the real, effective, saved set and
+    // filesystem ids, in this order.
 
-    let f = |h: &str, n: &str| -> Option<uid_t> {
+    let f = |h: &str, n: &str| -> Option<[uid_t; 4]> {
         if h.starts_with(n) {
-            h.split_whitespace().nth(2).unwrap_or("0").parse().ok()
+            let mut ids = [0; 4];
+            let mut parts = h.split_whitespace().skip(1);
+            for id in ids.iter_mut() {
+                *id = parts.next()?.parse().ok()?;
+            }
+            Some(ids)
         } else {
             None
         }
@@ -738,12 +1278,13 @@ fn _get_uid_and_gid(status_data: String) -> Option<(uid_t, gid_t)> {
         }
     }
     match (uid, gid) {
-        (Some(u), Some(g)) => Some((u, g)),
+        (Some(u), Some(g)) => Some((u[0], u[1], g[1])),
         _ => None,
     }
 }
 
-fn parse_stat_file(data: &str) -> Result<Vec<&str>, ()> {
+#[cfg(feature = "process")]
+pub(crate) fn parse_stat_file(data: &str) -> Result<Vec<&str>, ()> {
     // The stat file is "interesting" to parse, because spaces cannot
     // be used as delimiters. The second field stores the command name
     // surrounded by parentheses. Unfortunately, whitespace and
@@ -767,6 +1308,41 @@ fn parse_stat_file(data: &str) -> Result<Vec<&str>, ()> {
     Ok(parts)
 }
 
+/// Maps the `tty_nr` field of `/proc/[pid]/stat` to the path of the terminal device.
+#[cfg(feature = "process")]
+fn get_tty_path(tty_nr: u32) -> Option<String> {
+    if tty_nr == 0 {
+        return None;
+    }
+    let major = (tty_nr >> 8) & 0xfff;
+    let minor = (tty_nr & 0xff) | ((tty_nr >> 12) & 0xfff00);
+    match major {
+        // Unix98 pseudo-terminals aren't listed in sysfs.
+        136..=143 => Some(format!("/dev/pts/{}", (major - 136) * 256 + minor)),
+        _ => get_all_data(format!("/sys/dev/char/{}:{}/uevent", major, minor), 1024)
+            .ok()
+            .and_then(|data| {
+                data.lines()
+                    .find_map(|line| line.strip_prefix("DEVNAME="))
+                    .map(|name| format!("/dev/{}", name))
+            }),
+    }
+}
+
+/// Returns `true` if the `stat` file fields belong to a kernel thread.
+#[cfg(feature = "process")]
+fn is_kernel_thread(parts: &[&str]) -> bool {
+    // Flag set on kernel threads (from `include/linux/sched.h`).
+    const PF_KTHREAD: u32 = 0x0020_0000;
+
+    match parts.get(8).and_then(|flags| u32::from_str(flags).ok()) {
+        Some(flags) => flags & PF_KTHREAD != 0,
+        // `kthreadd` (pid 2) is the parent of all the other kernel threads.
+        None => parts.first() == Some(&"2") || parts.get(3) == Some(&"2"),
+    }
+}
+
+#[cfg(feature = "process")]
 fn check_nb_open_files(f: File) -> Option<File> {
     if let Ok(ref mut x) = unsafe { REMAINING_FILES.lock() } {
         if **x > 0 {
@@ -787,6 +1363,19 @@ enum InfoType {
     OsVersion,
 }
 
+/// `/etc/os-release` takes precedence over `/usr/lib/os-release` which is only used if the first
+/// one doesn't exist, as described in
+/// [os-release(5)](https://www.freedesktop.org/software/systemd/man/os-release.html).
+#[cfg(not(target_os = "android"))]
+fn get_os_release_path() -> &'static Path {
+    let path = Path::new("/etc/os-release");
+    if path.exists() {
+        path
+    } else {
+        Path::new("/usr/lib/os-release")
+    }
+}
+
 #[cfg(not(target_os = "android"))]
 fn get_system_info_linux(info: InfoType, path: &Path, fallback_path: &Path) -> Option<String> {
     if let Ok(f) = File::open(path) {
@@ -857,6 +1446,8 @@ fn get_system_info_android(info: InfoType) -> Option<String> {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+#[cfg(feature = "process")]
 fn _get_process_data(
     path: &Path,
     proc_list: &mut Process,
@@ -864,6 +1455,8 @@ fn _get_process_data(
     pid: Pid,
     uptime: u64,
     now: u64,
+    delay_accounting: bool,
+    refresh_kind: ProcessRefreshKind,
 ) -> Result<(Option<Process>, Pid), ()> {
     let nb = match path.file_name().and_then(|x| x.to_str()).map(Pid::from_str) {
         Some(Ok(nb)) if nb != pid => nb,
@@ -880,18 +1473,22 @@ fn _get_process_data(
     let parent_memory = proc_list.memory;
     let parent_virtual_memory = proc_list.virtual_memory;
     if let Some(ref mut entry) = proc_list.tasks.get_mut(&nb) {
-        let data = if let Some(ref mut f) = entry.stat_file {
+        let data = if let Some(ref mut f) = entry.stat_file.0 {
             get_all_data_from_file(f, 1024).map_err(|_| ())?
         } else {
             let mut tmp = PathBuf::from(path);
             tmp.push("stat");
             let mut file = File::open(tmp).map_err(|_| ())?;
             let data = get_all_data_from_file(&mut file, 1024).map_err(|_| ())?;
-            entry.stat_file = check_nb_open_files(file);
+            entry.stat_file.0 = check_nb_open_files(file);
             data
         };
         let parts = parse_stat_file(&data)?;
         get_status(entry, parts[2]);
+        if proc_list.pid.0 != 0 && entry.thread_name != parts[1] {
+            // Same as the content of the task's `comm` file, threads can rename themselves.
+            entry.thread_name = parts[1].to_owned();
+        }
         update_time_and_memory(
             path,
             entry,
@@ -900,10 +1497,15 @@ fn _get_process_data(
             parent_memory,
             parent_virtual_memory,
             nb,
+            proc_list.pid.0 != 0,
             uptime,
             now,
+            delay_accounting,
+            refresh_kind,
         );
-        update_process_disk_activity(entry, path);
+        if refresh_kind.disk_usage() {
+            update_process_disk_activity(entry, path);
+        }
         return Ok((None, nb));
     }
 
@@ -916,11 +1518,11 @@ fn _get_process_data(
     let parts = parse_stat_file(&data)?;
     let name = parts[1];
 
-    let parent_pid = if proc_list.pid != 0 {
+    let parent_pid = if proc_list.pid.0 != 0 {
         Some(proc_list.pid)
     } else {
         match Pid::from_str(parts[3]) {
-            Ok(p) if p != 0 => Some(p),
+            Ok(p) if p.0 != 0 => Some(p),
             _ => None,
         }
     };
@@ -930,51 +1532,81 @@ fn _get_process_data(
     let start_time = now.saturating_sub(uptime.saturating_sub(since_boot));
     let mut p = Process::new(nb, parent_pid, start_time);
 
-    p.stat_file = stat_file;
+    p.stat_file.0 = stat_file;
+    if proc_list.pid.0 != 0 {
+        p.tgid = proc_list.pid;
+    }
+    p.is_kernel_thread = is_kernel_thread(&parts);
     get_status(&mut p, parts[2]);
 
-    tmp.pop();
-    tmp.push("status");
-    if let Ok(data) = get_all_data(&tmp, 16_385) {
-        if let Some((uid, gid)) = _get_uid_and_gid(data) {
-            p.uid = uid;
-            p.gid = gid;
+    if refresh_kind.user() {
+        tmp.pop();
+        tmp.push("status");
+        if let Ok(data) = get_all_data(&tmp, 16_385) {
+            if let Some((uid, effective_uid, gid)) = _get_uid_and_gid(data) {
+                p.uid = uid;
+                p.effective_uid = effective_uid;
+                p.gid = gid;
+            }
         }
     }
 
-    if proc_list.pid != 0 {
+    if proc_list.pid.0 != 0 {
         // If we're getting information for a child, no need to get those info since we
         // already have them...
+        p.thread_name = name.to_owned();
         p.cmd = proc_list.cmd.clone();
         p.name = proc_list.name.clone();
+        p.short_name = proc_list.short_name.clone();
         p.environ = proc_list.environ.clone();
         p.exe = proc_list.exe.clone();
         p.cwd = proc_list.cwd.clone();
         p.root = proc_list.root.clone();
+        p.cgroup = proc_list.cgroup.clone();
+        p.last_error = proc_list.last_error;
     } else {
-        p.name = name.into();
-        tmp.pop();
-        tmp.push("cmdline");
-        p.cmd = copy_from_file(&tmp);
+        p.short_name = name.into();
         tmp.pop();
+        if refresh_kind.cmd() {
+            tmp.push("cmdline");
+            p.cmd = copy_from_file(&tmp);
+            tmp.pop();
+        }
         tmp.push("exe");
         match tmp.read_link() {
             Ok(exe_path) => {
                 p.exe = exe_path;
             }
-            Err(_) => {
+            Err(e) => {
+                // Kernel threads have no executable.
+                if e.kind() != io::ErrorKind::NotFound {
+                    p.last_error = Some(e.into());
+                }
                 p.exe = PathBuf::new();
             }
         }
         tmp.pop();
-        tmp.push("environ");
-        p.environ = copy_from_file(&tmp);
-        tmp.pop();
+        if refresh_kind.environ() {
+            tmp.push("environ");
+            match try_copy_from_file(&tmp) {
+                Ok(environ) => p.environ = environ,
+                Err(e) => {
+                    p.last_error.get_or_insert(e.into());
+                }
+            }
+            tmp.pop();
+        }
         tmp.push("cwd");
         p.cwd = realpath(&tmp);
         tmp.pop();
         tmp.push("root");
         p.root = realpath(&tmp);
+        tmp.pop();
+        tmp.push("cgroup");
+        p.cgroup = get_all_data(&tmp, 16_384)
+            .ok()
+            .and_then(|data| parse_cgroup_path(&data));
+        p.name = get_full_name(name, &p.exe, &p.cmd);
     }
 
     update_time_and_memory(
@@ -985,41 +1617,70 @@ fn _get_process_data(
         proc_list.memory,
         proc_list.virtual_memory,
         nb,
+        proc_list.pid.0 != 0,
         uptime,
         now,
+        delay_accounting,
+        refresh_kind,
     );
-    update_process_disk_activity(&mut p, path);
+    if refresh_kind.disk_usage() {
+        update_process_disk_activity(&mut p, path);
+    }
     Ok((Some(p), nb))
 }
 
+/// The name in the `stat` file is truncated to 15 characters (`TASK_COMM_LEN - 1`). If it's the
+/// case, we get the full name from the executable path or from the command line.
+#[cfg(feature = "process")]
+fn get_full_name(comm: &str, exe: &Path, cmd: &[String]) -> String {
+    const TASK_COMM_LEN: usize = 16;
+
+    if comm.len() >= TASK_COMM_LEN - 1 {
+        let exe_name = exe
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name.trim_end_matches(" (deleted)"));
+        let cmd_name = cmd
+            .first()
+            .and_then(|arg| Path::new(arg).file_name())
+            .and_then(|name| name.to_str());
+        if let Some(name) = exe_name
+            .into_iter()
+            .chain(cmd_name)
+            .find(|name| name.len() > comm.len() && name.starts_with(comm))
+        {
+            return name.to_owned();
+        }
+    }
+    comm.to_owned()
+}
+
+#[cfg(feature = "process")]
 fn copy_from_file(entry: &Path) -> Vec<String> {
-    match File::open(entry) {
-        Ok(mut f) => {
-            let mut data = vec![0; 16_384];
-
-            if let Ok(size) = f.read(&mut data) {
-                data.truncate(size);
-                let mut out = Vec::with_capacity(20);
-                let mut start = 0;
-                for (pos, x) in data.iter().enumerate() {
-                    if *x == 0 {
-                        if pos - start >= 1 {
-                            if let Ok(s) =
-                                std::str::from_utf8(&data[start..pos]).map(|x| x.trim().to_owned())
-                            {
-                                out.push(s);
-                            }
-                        }
-                        start = pos + 1; // to keeping prevent '\0'
-                    }
+    try_copy_from_file(entry).unwrap_or_default()
+}
+
+/// Same as [`copy_from_file`] but returns the error if the file cannot be read (if it belongs
+/// to another user for example).
+#[cfg(feature = "process")]
+fn try_copy_from_file(entry: &Path) -> io::Result<Vec<String>> {
+    let mut f = File::open(entry)?;
+    let mut data = vec![0; 16_384];
+    let size = f.read(&mut data)?;
+    data.truncate(size);
+    let mut out = Vec::with_capacity(20);
+    let mut start = 0;
+    for (pos, x) in data.iter().enumerate() {
+        if *x == 0 {
+            if pos - start >= 1 {
+                if let Ok(s) = std::str::from_utf8(&data[start..pos]).map(|x| x.trim().to_owned()) {
+                    out.push(s);
                 }
-                out
-            } else {
-                Vec::new()
             }
+            start = pos + 1; // to keeping prevent '\0'
         }
-        Err(_) => Vec::new(),
     }
+    Ok(out)
 }
 
 fn get_all_data_from_file(file: &mut File, size: usize) -> io::Result<String> {
@@ -1035,8 +1696,94 @@ pub fn get_all_data<P: AsRef<Path>>(file_path: P, size: usize) -> io::Result<Str
     get_all_data_from_file(&mut file, size)
 }
 
-fn get_uptime() -> u64 {
-    let content = get_all_data("/proc/uptime", 50).unwrap_or_default();
+fn get_virtualization() -> Virtualization {
+    get_container()
+        .or_else(|| {
+            let names = ["sys_vendor", "product_name", "bios_vendor"]
+                .iter()
+                .filter_map(|name| fs::read_to_string(format!("/sys/class/dmi/id/{}", name)).ok())
+                .collect::<Vec<_>>();
+            crate::utils::get_dmi_virtualization(&names)
+        })
+        .or_else(|| match fs::read_to_string("/sys/hypervisor/type") {
+            Ok(ref kind) if kind.trim() == "xen" => Some(Virtualization::Xen),
+            _ => None,
+        })
+        .or_else(get_cpuid_virtualization)
+        .unwrap_or(Virtualization::None)
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn get_cpuid_virtualization() -> Option<Virtualization> {
+    crate::utils::get_cpuid_virtualization()
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+fn get_cpuid_virtualization() -> Option<Virtualization> {
+    None
+}
+
+fn get_container() -> Option<Virtualization> {
+    if Path::new("/.dockerenv").exists() {
+        return Some(Virtualization::Docker);
+    }
+    if Path::new("/run/.containerenv").exists() {
+        return Some(Virtualization::Podman);
+    }
+    // Only readable by root.
+    if let Ok(environ) = fs::read("/proc/1/environ") {
+        let container = environ
+            .split(|c| *c == 0)
+            .find(|var| var.starts_with(b"container="))
+            .map(|var| &var[b"container=".len()..]);
+        if let Some(container) = container {
+            return Some(match container {
+                b"docker" => Virtualization::Docker,
+                b"podman" => Virtualization::Podman,
+                b"lxc" | b"lxc-libvirt" => Virtualization::Lxc,
+                _ => Virtualization::Other,
+            });
+        }
+    }
+    if let Some(container) = fs::read_to_string("/proc/1/cgroup")
+        .ok()
+        .and_then(|cgroup| parse_cgroup_container(&cgroup))
+    {
+        return Some(container);
+    }
+    match fs::read_to_string("/proc/sys/kernel/osrelease") {
+        Ok(ref release) if release.to_lowercase().contains("microsoft") => {
+            Some(Virtualization::Wsl)
+        }
+        _ => None,
+    }
+}
+
+/// Looks for the container runtime in the cgroups of a process. The lines look like this:
+///
+/// ```text
+/// 12:pids:/docker/0123456789abcdef
+/// 0::/lxc.payload.container/init.scope
+/// ```
+fn parse_cgroup_container(cgroup: &str) -> Option<Virtualization> {
+    cgroup
+        .lines()
+        .filter_map(|line| line.splitn(3, ':').nth(2))
+        .find_map(|path| {
+            if path.contains("docker") {
+                Some(Virtualization::Docker)
+            } else if path.contains("libpod") {
+                Some(Virtualization::Podman)
+            } else if path.contains("lxc") {
+                Some(Virtualization::Lxc)
+            } else {
+                None
+            }
+        })
+}
+
+fn get_uptime(sysroot: &Path) -> u64 {
+    let content = get_all_data(sysroot.join("proc/uptime"), 50).unwrap_or_default();
     content
         .split('.')
         .next()
@@ -1044,20 +1791,165 @@ fn get_uptime() -> u64 {
         .unwrap_or_default()
 }
 
-fn get_secs_since_epoch() -> u64 {
-    match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
-        Ok(n) => n.as_secs(),
-        _ => panic!("SystemTime before UNIX EPOCH!"),
-    }
-}
-
 #[cfg(test)]
 mod test {
     #[cfg(target_os = "android")]
     use super::get_system_info_android;
     #[cfg(not(target_os = "android"))]
     use super::get_system_info_linux;
-    use super::InfoType;
+    #[cfg(feature = "process")]
+    use super::{
+        _get_uid_and_gid, get_full_name, get_tty_path, is_kernel_thread, parse_stat_file,
+        parse_statm_shared, parse_status_vm_swap,
+    };
+    use super::{parse_cgroup_container, parse_proc_swaps, InfoType};
+    use crate::{SwapDevice, SwapType, Virtualization};
+    #[cfg(feature = "process")]
+    use std::path::Path;
+
+    #[test]
+    #[cfg(feature = "process")]
+    fn check_get_uid_and_gid() {
+        // Status of a setuid executable run by the user 1000.
+        let status = "Name:\tpasswd\nUmask:\t0022\nState:\tS (sleeping)\n\
+                      Uid:\t1000\t0\t0\t0\nGid:\t1000\t1000\t1000\t1000\n\
+                      Groups:\t4 27 1000\n";
+        assert_eq!(_get_uid_and_gid(status.to_owned()), Some((1000, 0, 1000)));
+        // Missing fields.
+        assert_eq!(
+            _get_uid_and_gid("Uid:\t1000\t0\nGid:\t1000\t1000\t1000\t1000\n".to_owned()),
+            None
+        );
+        assert_eq!(_get_uid_and_gid("Name:\tbash\n".to_owned()), None);
+    }
+
+    #[test]
+    #[cfg(feature = "process")]
+    fn check_is_kernel_thread() {
+        let kthreadd = "2 (kthreadd) S 0 0 0 0 -1 2129984 0 0 0 0 0 2 0 0 20 0 1 0 7 0 0 \
+                        18446744073709551615 0 0 0 0 0 0 0 2147483647 0 0 0 0 0 1 0 0 0 0 0";
+        let kworker = "31 (kworker/1:0H-events_highpri) I 2 0 0 0 -1 69238880 0 0 0 0 0 0 0 0 \
+                       0 -20 1 0 9 0 0 18446744073709551615 0 0 0 0 0 0 0 2147483647 0 0 0 0";
+        let bash = "1234 (bash) S 1 1234 1234 34816 5678 4194304 4321 5432 0 0 2 1 0 0 20 0 1 0 \
+                    456 10240000 1300 18446744073709551615 1 1 0 0 0 0 65536 3670020 1266777851";
+        assert!(is_kernel_thread(&parse_stat_file(kthreadd).unwrap()));
+        assert!(is_kernel_thread(&parse_stat_file(kworker).unwrap()));
+        assert!(!is_kernel_thread(&parse_stat_file(bash).unwrap()));
+
+        // Without flags, we rely on the parent pid.
+        assert!(is_kernel_thread(&["31", "kworker/0:1", "I", "2"]));
+        assert!(!is_kernel_thread(&["1234", "bash", "S", "1"]));
+    }
+
+    #[test]
+    #[cfg(feature = "process")]
+    fn check_get_tty_path() {
+        assert_eq!(get_tty_path(0), None);
+        // The `tty_nr` of bash in the test above.
+        assert_eq!(get_tty_path(34816).as_deref(), Some("/dev/pts/0"));
+        assert_eq!(get_tty_path((136 << 8) | 3).as_deref(), Some("/dev/pts/3"));
+        assert_eq!(
+            get_tty_path((137 << 8) | 44).as_deref(),
+            Some("/dev/pts/300")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "process")]
+    fn check_get_full_name() {
+        let exe = Path::new("/usr/lib/gnome-settings-daemon/gsd-screensaver-proxy");
+        assert_eq!(
+            get_full_name("gsd-screensaver", exe, &[]),
+            "gsd-screensaver-proxy"
+        );
+        let exe = Path::new("/tmp/gsd-screensaver-proxy (deleted)");
+        assert_eq!(
+            get_full_name("gsd-screensaver", exe, &[]),
+            "gsd-screensaver-proxy"
+        );
+        // A script run by an interpreter.
+        let exe = Path::new("/usr/bin/python3.9");
+        let cmd = vec!["/usr/bin/networkd-dispatcher".to_owned()];
+        assert_eq!(
+            get_full_name("networkd-dispat", exe, &cmd),
+            "networkd-dispatcher"
+        );
+        assert_eq!(
+            get_full_name("networkd-dispat", exe, &[]),
+            "networkd-dispat"
+        );
+        // The name isn't truncated, so it isn't replaced.
+        assert_eq!(
+            get_full_name("bash", Path::new("/usr/bin/bash5"), &[]),
+            "bash"
+        );
+    }
+
+    #[test]
+    fn check_parse_cgroup_container() {
+        assert_eq!(parse_cgroup_container("0::/\n"), None);
+        assert_eq!(
+            parse_cgroup_container("0::/user.slice/user-1000.slice/session-2.scope\n"),
+            None
+        );
+        assert_eq!(
+            parse_cgroup_container("12:pids:/docker/0123456789abcdef\n1:cpu:/docker/0123\n"),
+            Some(Virtualization::Docker)
+        );
+        assert_eq!(
+            parse_cgroup_container("0::/lxc.payload.container/init.scope\n"),
+            Some(Virtualization::Lxc)
+        );
+        assert_eq!(
+            parse_cgroup_container("0::/machine.slice/libpod-0123456789abcdef.scope\n"),
+            Some(Virtualization::Podman)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "process")]
+    fn check_process_memory_files_parsing() {
+        assert_eq!(parse_statm_shared("2893 560 448 183 0 307 0\n"), Some(448));
+        assert_eq!(parse_statm_shared("2893 560"), None);
+        assert_eq!(
+            parse_status_vm_swap("Name:\tcat\nVmRSS:\t    2240 kB\nVmSwap:\t      12 kB\n"),
+            Some(12)
+        );
+        // Kernel threads don't have memory information.
+        assert_eq!(
+            parse_status_vm_swap("Name:\tkthreadd\nState:\tS (sleeping)\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn check_parse_proc_swaps() {
+        let swaps = "Filename\t\t\t\tType\t\tSize\t\tUsed\t\tPriority\n\
+                     /dev/sda2                               partition\t8388604\t\t1024\t\t-2\n\
+                     /swap\\040file                          file\t\t2097148\t\t0\t\t-3\n";
+        assert_eq!(
+            parse_proc_swaps(swaps),
+            [
+                SwapDevice {
+                    name: "/dev/sda2".to_owned(),
+                    type_: SwapType::Partition,
+                    size: 8_589_930_496,
+                    used: 1_048_576,
+                },
+                SwapDevice {
+                    name: "/swap file".to_owned(),
+                    type_: SwapType::File,
+                    size: 2_147_479_552,
+                    used: 0,
+                },
+            ]
+        );
+        // No swap in use.
+        assert_eq!(
+            parse_proc_swaps("Filename\tType\tSize\tUsed\tPriority\n"),
+            []
+        );
+    }
 
     #[test]
     #[cfg(target_os = "android")]
@@ -1121,4 +2013,105 @@ DISTRIB_DESCRIPTION="Ubuntu 20.10"
             Some("Ubuntu".to_owned())
         );
     }
+
+    #[cfg(all(feature = "network", feature = "process", not(target_os = "android")))]
+    #[test]
+    fn check_sysroot_fixture() {
+        use super::{ticks_to_ms, System};
+        use crate::{NetworkExt, NetworksExt, Pid, ProcessExt, RefreshKind, SystemExt};
+        use std::fs;
+
+        let sysroot = tempfile::tempdir().expect("failed to create temporary directory");
+        let write = |path: &str, content: &str| {
+            let path = sysroot.path().join(path);
+            fs::create_dir_all(path.parent().unwrap()).expect("failed to create directory");
+            fs::write(path, content).expect("failed to write fixture file");
+        };
+        write(
+            "proc/stat",
+            "cpu  100 0 100 800 0 0 0 0 0 0\n\
+             cpu0 100 0 100 800 0 0 0 0 0 0\n\
+             ctxt 1000\nbtime 1600000000\n",
+        );
+        write("proc/uptime", "1000.50 3000.00\n");
+        write(
+            "proc/meminfo",
+            "MemTotal:       16000000 kB\nMemFree:         8000000 kB\n\
+             MemAvailable:   12000000 kB\nSwapTotal:       2000000 kB\n\
+             SwapFree:        1000000 kB\n",
+        );
+        write(
+            "proc/42/stat",
+            "42 (fixture) S 1 42 42 0 -1 4194304 100 0 0 0 150 50 0 0 20 0 1 0 5000 \
+             10485760 256 18446744073709551615 1 1 0 0 0 0 0 0 0 0 0 0 17 0 0 0 25 0 0",
+        );
+        write(
+            "proc/42/status",
+            "Name:\tfixture\nUid:\t1000\t1000\t1000\t1000\n\
+             Gid:\t1000\t1000\t1000\t1000\nVmSwap:\t      12 kB\n",
+        );
+        write("proc/42/cmdline", "fixture\0--flag\0");
+        write("proc/42/oom_score", "667\n");
+        write("proc/42/schedstat", "1254623 54470000 12\n");
+        write("proc/42/cgroup", "0::/system.slice/fixture.service\n");
+        write("proc/sys/kernel/task_delayacct", "1\n");
+        write("proc/42/oom_score_adj", "-500\n");
+        write(
+            "proc/42/environ",
+            "HOME=/home/fixture\0LANG=C\0LANG=en_US.UTF-8\0EMPTY=\0",
+        );
+        std::os::unix::fs::symlink("/usr/bin/fixture", sysroot.path().join("proc/42/exe"))
+            .expect("failed to create exe link");
+        write("sys/class/net/eth0/type", "1\n");
+        write("sys/class/net/eth0/statistics/rx_bytes", "1000\n");
+        write("sys/class/net/eth0/statistics/tx_bytes", "2000\n");
+        fs::create_dir(sysroot.path().join("sys/class/net/eth0/device"))
+            .expect("failed to create directory");
+
+        let mut s = System::new_with_sysroot(sysroot.path(), RefreshKind::new());
+        assert_eq!(s.get_uptime(), 1000);
+        assert_eq!(s.get_boot_time(), 1_600_000_000);
+        assert_eq!(s.get_processors().len(), 1);
+
+        s.refresh_memory();
+        assert_eq!(s.get_total_memory(), 16_384_000);
+        assert_eq!(s.get_available_memory(), 12_288_000);
+        assert_eq!(s.get_total_swap(), 2_048_000);
+        assert_eq!(s.get_free_swap(), 1_024_000);
+
+        s.refresh_processes();
+        assert_eq!(s.get_processes().len(), 1);
+        let p = s.get_process(Pid(42)).expect("fixture process not found");
+        assert_eq!(p.name(), "fixture");
+        assert_eq!(p.cmd(), ["fixture", "--flag"]);
+        assert_eq!(
+            p.environ(),
+            ["HOME=/home/fixture", "LANG=C", "LANG=en_US.UTF-8", "EMPTY="]
+        );
+        assert_eq!(p.environ_var("HOME"), Some("/home/fixture"));
+        assert_eq!(p.environ_var("LANG"), Some("en_US.UTF-8"));
+        assert_eq!(p.environ_var("EMPTY"), Some(""));
+        assert_eq!(p.environ_var("HOM"), None);
+        assert_eq!(p.exe(), Path::new("/usr/bin/fixture"));
+        assert_eq!(p.parent(), Some(Pid(1)));
+        assert_eq!(p.uid, 1000);
+        assert_eq!(p.memory(), 256 * s.page_size_kb);
+        assert_eq!(p.swap_memory(), 12);
+        assert_eq!(p.oom_score(), Some(667));
+        assert_eq!(p.oom_score_adj(), Some(-500));
+        assert_eq!(p.io_wait_time(), ticks_to_ms(25));
+        assert_eq!(p.sched_delay(), Some(54));
+        assert_eq!(p.cgroup().as_deref(), Some("/system.slice/fixture.service"));
+
+        s.get_networks_mut().refresh_networks_list();
+        let eth0 = s
+            .get_networks()
+            .iter()
+            .find(|(name, _)| name.as_str() == "eth0")
+            .map(|(_, data)| data)
+            .expect("fixture interface not found");
+        assert_eq!(eth0.get_total_received(), 1000);
+        assert_eq!(eth0.get_total_transmitted(), 2000);
+        assert!(eth0.is_physical());
+    }
 }
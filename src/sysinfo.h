@@ -8,10 +8,12 @@
 
 #include <sys/types.h>
 #include <stdbool.h>
+#include <stdint.h>
 
 typedef void* CSystem;
 typedef const void* CProcess;
 typedef const char* RString;
+typedef void* CNetworks;
 
 CSystem    *sysinfo_init();
 void        sysinfo_destroy(CSystem system);
@@ -47,3 +49,15 @@ RString     sysinfo_process_get_executable_path(CProcess process);
 RString     sysinfo_process_get_root_directory(CProcess process);
 RString     sysinfo_process_get_current_directory(CProcess process);
 void        sysinfo_rstring_free(RString str);
+
+CNetworks   sysinfo_networks_new();
+void        sysinfo_networks_free(CNetworks networks);
+void        sysinfo_networks_refresh(CNetworks networks);
+void        sysinfo_networks_refresh_list(CNetworks networks);
+size_t      sysinfo_networks_iter(CNetworks networks, bool (*fn_pointer)(const char*, void*),
+                                  void *data);
+uint64_t    sysinfo_networks_get_received(CNetworks networks, const char *interface_name);
+uint64_t    sysinfo_networks_get_total_received(CNetworks networks, const char *interface_name);
+uint64_t    sysinfo_networks_get_transmitted(CNetworks networks, const char *interface_name);
+uint64_t    sysinfo_networks_get_total_transmitted(CNetworks networks,
+                                                   const char *interface_name);
@@ -7,7 +7,37 @@
 // Once https://github.com/rust-lang/rfcs/blob/master/text/1422-pub-restricted.md
 // feature gets stabilized, we can move common parts in here.
 
-#[cfg(test)]
+#[cfg(feature = "tokio")]
+impl crate::System {
+    /// Same as [`SystemExt::refresh_all`][crate::SystemExt::refresh_all] but the refresh is run
+    /// on the tokio blocking threads pool so it doesn't block the async runtime.
+    ///
+    /// Since the blocking task needs to own the data it works on, `self` is moved into it and is
+    /// returned once the refresh is done.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// # async fn run() {
+    /// let s = System::new();
+    /// let s = s.refresh_all_async().await;
+    /// println!("{} processes", s.get_processes().len());
+    /// # }
+    /// ```
+    pub async fn refresh_all_async(self) -> crate::System {
+        use crate::SystemExt;
+
+        let mut s = self;
+        tokio::task::spawn_blocking(move || {
+            s.refresh_all();
+            s
+        })
+        .await
+        .expect("the refresh task panicked")
+    }
+}
+
+#[cfg(all(test, feature = "process"))]
 mod tests {
     use crate::{utils, ProcessExt, System, SystemExt};
 
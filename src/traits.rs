@@ -4,18 +4,51 @@
 // Copyright (c) 2017 Guillaume Gomez
 //
 
+#[cfg(all(feature = "network", feature = "system"))]
+use crate::sys::Networks;
+#[cfg(feature = "component")]
+use crate::sys::{Component, Fan};
+#[cfg(feature = "system")]
 use crate::{
     common::{Gid, Uid},
-    sys::{Component, Disk, Networks, Process, Processor},
+    sys::Gpu,
+    Battery, LoadAvg, RefreshKind, SwapDevice, User, Virtualization,
 };
+#[cfg(feature = "disk")]
+use crate::{sys::Disk, DiskType, Mount};
+#[cfg(feature = "network")]
 use crate::{
-    DiskType, DiskUsage, LoadAvg, NetworksIter, Pid, ProcessStatus, RefreshKind, Signal, User,
+    sys::NetworkData, CountingBasis, InterfaceStatus, InterfaceType, NetworkDelta, NetworkStats,
+    NetworksIter, RefreshError,
 };
+#[cfg(feature = "process")]
+use crate::{
+    sys::Process, AccessError, DiskUsage, NetworkUsage, Pid, PriorityError, ProcessRefreshKind,
+    ProcessStatus, ProcessWatcher, Signal, SocketInfo, SocketProtocol, SocketState, ThreadInfo,
+};
+#[cfg(feature = "cpu")]
+use crate::{sys::Processor, CacheInfo, CpuTimes};
 
+#[cfg(any(feature = "network", feature = "process"))]
 use std::collections::HashMap;
+#[cfg(feature = "disk")]
+use std::collections::HashSet;
+#[cfg(feature = "network")]
+use std::collections::VecDeque;
+#[cfg(feature = "disk")]
 use std::ffi::OsStr;
+#[cfg(any(feature = "network", feature = "system"))]
 use std::fmt::Debug;
+#[cfg(feature = "network")]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+#[cfg(any(feature = "component", feature = "disk", feature = "process"))]
 use std::path::Path;
+#[cfg(feature = "process")]
+use std::process::ExitStatus;
+#[cfg(any(feature = "cpu", feature = "network", feature = "process"))]
+use std::time::Duration;
+#[cfg(feature = "network")]
+use std::time::Instant;
 
 /// Contains all the methods of the [`Disk`][crate::Disk] struct.
 ///
@@ -27,6 +60,7 @@ use std::path::Path;
 ///     println!("{:?}: {:?}", disk.get_name(), disk.get_type());
 /// }
 /// ```
+#[cfg(feature = "disk")]
 pub trait DiskExt: Debug {
     /// Returns the disk type.
     ///
@@ -52,6 +86,19 @@ pub trait DiskExt: Debug {
     /// ```
     fn get_name(&self) -> &OsStr;
 
+    /// Returns the device backing this disk (`/dev/sda1` on Linux, `/dev/disk1s1` on macOS or the
+    /// volume GUID path on Windows).
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for disk in s.get_disks() {
+    ///     println!("{:?}", disk.get_device_name());
+    /// }
+    /// ```
+    fn get_device_name(&self) -> &OsStr;
+
     /// Returns the file system used on this disk (so for example: `EXT4`, `NTFS`, etc...).
     ///
     /// ```no_run
@@ -100,6 +147,125 @@ pub trait DiskExt: Debug {
     /// ```
     fn get_available_space(&self) -> u64;
 
+    /// Returns `true` if the disk is mounted read-only.
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for disk in s.get_disks() {
+    ///     println!("{}", disk.is_read_only());
+    /// }
+    /// ```
+    fn is_read_only(&self) -> bool;
+
+    /// Returns the options the disk was mounted with (`rw`, `noexec`, `nosuid`, etc...).
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for disk in s.get_disks() {
+    ///     println!("{:?}", disk.get_mount_options());
+    /// }
+    /// ```
+    fn get_mount_options(&self) -> &[String];
+
+    /// Returns `true` if the disk is removable (like USB drives).
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for disk in s.get_disks() {
+    ///     println!("{}", disk.is_removable());
+    /// }
+    /// ```
+    fn is_removable(&self) -> bool;
+
+    /// Returns the number of read operations completed on this disk since the last refresh.
+    ///
+    /// The operations are the ones of the device backing the disk: the partition on Linux, the
+    /// volume on Windows and the whole drive on macOS.
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for disk in s.get_disks() {
+    ///     println!("{} reads", disk.get_read_count());
+    /// }
+    /// ```
+    fn get_read_count(&self) -> u64;
+
+    /// Returns the total number of read operations completed on this disk.
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for disk in s.get_disks() {
+    ///     println!("{} reads", disk.get_total_read_count());
+    /// }
+    /// ```
+    fn get_total_read_count(&self) -> u64;
+
+    /// Returns the number of write operations completed on this disk since the last refresh.
+    ///
+    /// The operations are the ones of the device backing the disk: the partition on Linux, the
+    /// volume on Windows and the whole drive on macOS.
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for disk in s.get_disks() {
+    ///     println!("{} writes", disk.get_write_count());
+    /// }
+    /// ```
+    fn get_write_count(&self) -> u64;
+
+    /// Returns the total number of write operations completed on this disk.
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for disk in s.get_disks() {
+    ///     println!("{} writes", disk.get_total_write_count());
+    /// }
+    /// ```
+    fn get_total_write_count(&self) -> u64;
+
+    /// Returns the percentage of time this disk was busy doing I/O between the last two
+    /// refreshes, like the `%util` column of `iostat`.
+    ///
+    /// It's always 0 on macOS, iOS and on unsupported platforms.
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for disk in s.get_disks() {
+    ///     println!("{}%", disk.get_io_utilization());
+    /// }
+    /// ```
+    fn get_io_utilization(&self) -> f32;
+
+    /// Returns the number of I/O requests in progress on this disk when it was last refreshed.
+    ///
+    /// It's always 0 on macOS, iOS and on unsupported platforms.
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for disk in s.get_disks() {
+    ///     println!("{} requests in progress", disk.get_queue_depth());
+    /// }
+    /// ```
+    fn get_queue_depth(&self) -> u64;
+
     /// Updates the disk' information.
     ///
     /// ```no_run
@@ -114,6 +280,7 @@ pub trait DiskExt: Debug {
 }
 
 /// Contains all the methods of the [`Process`][crate::Process] struct.
+#[cfg(feature = "process")]
 pub trait ProcessExt: Debug {
     /// Creates a new process only containing the given information.
     ///
@@ -124,34 +291,87 @@ pub trait ProcessExt: Debug {
     /// Sends the given `signal` to the process.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, Signal, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, Signal, System, SystemExt};
     ///
     /// let s = System::new();
-    /// if let Some(process) = s.get_process(1337) {
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
     ///     process.kill(Signal::Kill);
     /// }
     /// ```
     fn kill(&self, signal: Signal) -> bool;
 
+    /// Blocks until the process ends and returns its exit status.
+    ///
+    /// The exit status is only available on Windows and, on other platforms, if the process is
+    /// a child of the current one. In this case, the process isn't reaped so its owner can still
+    /// wait for it. Otherwise, `None` is returned once the process is gone. It always returns
+    /// `None` right away on iOS and on unsupported platforms.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{:?}", process.wait());
+    /// }
+    /// ```
+    fn wait(&self) -> Option<ExitStatus>;
+
+    /// Same as [`ProcessExt::wait`] but returns `None` if the process is still running once
+    /// `timeout` is elapsed.
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{:?}", process.wait_timeout(Duration::from_secs(1)));
+    /// }
+    /// ```
+    fn wait_timeout(&self, timeout: Duration) -> Option<ExitStatus>;
+
     /// Returns the name of the process.
     ///
+    /// On Linux, the kernel truncates the name to 15 characters. When it's the case, the full
+    /// name is taken from the executable path (or from the first argument of the command line if
+    /// they don't match). The name as given by the kernel is available with
+    /// [`short_name`][ProcessExt::short_name].
+    ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// if let Some(process) = s.get_process(1337) {
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
     ///     println!("{}", process.name());
     /// }
     /// ```
     fn name(&self) -> &str;
 
+    /// Returns the name of the process as given by the kernel, without trying to complete it.
+    ///
+    /// On Linux, it's truncated to 15 characters. It's the same as [`name`][ProcessExt::name]
+    /// on other platforms.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{}", process.short_name());
+    /// }
+    /// ```
+    fn short_name(&self) -> &str {
+        self.name()
+    }
+
     /// Returns the command line.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// if let Some(process) = s.get_process(1337) {
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
     ///     println!("{:?}", process.cmd());
     /// }
     /// ```
@@ -160,10 +380,10 @@ pub trait ProcessExt: Debug {
     /// Returns the path to the process.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// if let Some(process) = s.get_process(1337) {
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
     ///     println!("{}", process.exe().display());
     /// }
     /// ```
@@ -172,10 +392,10 @@ pub trait ProcessExt: Debug {
     /// Returns the pid of the process.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// if let Some(process) = s.get_process(1337) {
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
     ///     println!("{}", process.pid());
     /// }
     /// ```
@@ -186,24 +406,53 @@ pub trait ProcessExt: Debug {
     /// Always empty on Windows, except for current process.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// if let Some(process) = s.get_process(1337) {
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
     ///     println!("{:?}", process.environ());
     /// }
     /// ```
     fn environ(&self) -> &[String];
 
+    /// Returns the value of the `key` environment variable of the process, from the `KEY=VALUE`
+    /// entries of [`ProcessExt::environ`]. If the variable is set more than once, the last value
+    /// is returned. The names are compared case-insensitively on Windows.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{:?}", process.environ_var("PATH"));
+    /// }
+    /// ```
+    fn environ_var(&self, key: &str) -> Option<&str> {
+        self.environ().iter().rev().find_map(|var| {
+            let mut parts = var.splitn(2, '=');
+            let name = parts.next()?;
+            let found = if cfg!(windows) {
+                name.eq_ignore_ascii_case(key)
+            } else {
+                name == key
+            };
+            if found {
+                parts.next()
+            } else {
+                None
+            }
+        })
+    }
+
     /// Returns the current working directory.
     ///
     /// Always empty on Windows.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// if let Some(process) = s.get_process(1337) {
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
     ///     println!("{}", process.cwd().display());
     /// }
     /// ```
@@ -214,10 +463,10 @@ pub trait ProcessExt: Debug {
     /// Always empty on Windows.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// if let Some(process) = s.get_process(1337) {
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
     ///     println!("{}", process.root().display());
     /// }
     /// ```
@@ -226,10 +475,10 @@ pub trait ProcessExt: Debug {
     /// Returns the memory usage (in kB).
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// if let Some(process) = s.get_process(1337) {
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
     ///     println!("{} kB", process.memory());
     /// }
     /// ```
@@ -238,1043 +487,3236 @@ pub trait ProcessExt: Debug {
     /// Returns the virtual memory usage (in kB).
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// if let Some(process) = s.get_process(1337) {
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
     ///     println!("{} kB", process.virtual_memory());
     /// }
     /// ```
     fn virtual_memory(&self) -> u64;
 
+    /// Returns the amount of resident memory shared with other processes (in kB).
+    ///
+    /// It's only computed on Linux (and not for tasks), it's always 0 on other platforms.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{} kB", process.shared_memory());
+    /// }
+    /// ```
+    fn shared_memory(&self) -> u64;
+
+    /// Returns the amount of memory swapped out to disk (in kB).
+    ///
+    /// It's only computed on Linux (and not for tasks), it's always 0 on other platforms.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{} kB", process.swap_memory());
+    /// }
+    /// ```
+    fn swap_memory(&self) -> u64;
+
     /// Returns the parent pid.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// if let Some(process) = s.get_process(1337) {
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
     ///     println!("{:?}", process.parent());
     /// }
     /// ```
     fn parent(&self) -> Option<Pid>;
 
-    /// Returns the status of the processus.
+    /// Returns `true` if this process is a kernel thread.
+    ///
+    /// On Linux, kernel threads (like `kthreadd` or `kworker/*`) are detected with the `PF_KTHREAD`
+    /// flag of the process, they have no executable nor command line. On Windows, the "System" and
+    /// "Idle" processes are considered as kernel threads. It always returns `false` on other
+    /// platforms.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// if let Some(process) = s.get_process(1337) {
-    ///     println!("{:?}", process.status());
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{}", process.is_kernel_thread());
     /// }
     /// ```
-    fn status(&self) -> ProcessStatus;
+    fn is_kernel_thread(&self) -> bool;
 
-    /// Returns the time of process launch (in seconds).
+    /// Returns `true` if this entry is a thread of another process.
+    ///
+    /// Threads are only listed on Linux, either in the `tasks` field of their process or as
+    /// processes if [`SystemExt::set_include_threads`] was enabled.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// if let Some(process) = s.get_process(1337) {
-    ///     println!("Running since {} seconds", process.start_time());
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{}", process.is_thread());
     /// }
     /// ```
-    fn start_time(&self) -> u64;
+    fn is_thread(&self) -> bool;
 
-    /// Returns the total CPU usage (in %). Notice that it might be bigger than 100 if run on a
-    /// multicore machine.
-    ///
-    /// If you want a value between 0% and 100%, divide the returned value by the number of CPU
-    /// processors.
+    /// Returns the thread group id, which is the pid of the process this thread belongs to. For
+    /// processes, it's the same as [`pid`].
     ///
-    /// **Warning**: If you want accurate CPU usage number, better leave a bit of time
-    /// between two calls of this method (200 ms for example).
+    /// [`pid`]: ProcessExt::pid
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// if let Some(process) = s.get_process(1337) {
-    ///     println!("{}%", process.cpu_usage());
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{}", process.tgid());
     /// }
     /// ```
-    fn cpu_usage(&self) -> f32;
+    fn tgid(&self) -> Pid;
 
-    /// Returns number of bytes read and written to disk.
+    /// Returns the ID of the session this process belongs to.
     ///
-    /// /!\\ On Windows, this method actually returns **ALL** I/O read and written bytes.
+    /// It returns `None` on Windows.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// if let Some(process) = s.get_process(1337) {
-    ///     let disk_usage = process.disk_usage();
-    ///     println!("read bytes   : new/total => {}/{}",
-    ///         disk_usage.read_bytes,
-    ///         disk_usage.total_read_bytes,
-    ///     );
-    ///     println!("written bytes: new/total => {}/{}",
-    ///         disk_usage.written_bytes,
-    ///         disk_usage.total_written_bytes,
-    ///     );
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("Session id: {:?}", process.session_id());
     /// }
     /// ```
-    fn disk_usage(&self) -> DiskUsage;
-}
+    fn session_id(&self) -> Option<i32>;
 
-/// Contains all the methods of the [`Processor`][crate::Processor] struct.
-pub trait ProcessorExt: Debug {
-    /// Returns this processor's usage.
+    /// Returns the path of the controlling terminal of this process (e.g. "/dev/pts/0").
     ///
-    /// Note: You'll need to refresh it at least twice (diff between the first and the second is
-    /// how CPU usage is computed) at first if you want to have a non-zero value.
+    /// It returns `None` if the process has no controlling terminal, as well as on Windows.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// for processor in s.get_processors() {
-    ///     println!("{}%", processor.get_cpu_usage());
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     if let Some(tty) = process.tty() {
+    ///         println!("Controlling terminal: {}", tty);
+    ///     }
     /// }
     /// ```
-    fn get_cpu_usage(&self) -> f32;
+    fn tty(&self) -> Option<&str>;
 
-    /// Returns this processor's name.
+    /// Returns the status of the processus.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// for processor in s.get_processors() {
-    ///     println!("{}", processor.get_name());
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{:?}", process.status());
     /// }
     /// ```
-    fn get_name(&self) -> &str;
+    fn status(&self) -> ProcessStatus;
 
-    /// Returns the processor's vendor id.
+    /// Returns the time of process launch (in seconds since UNIX epoch).
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// for processor in s.get_processors() {
-    ///     println!("{}", processor.get_vendor_id());
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("Started at {} seconds", process.start_time());
     /// }
     /// ```
-    fn get_vendor_id(&self) -> &str;
+    fn start_time(&self) -> u64;
 
-    /// Returns the processor's brand.
+    /// Returns for how much time the process has been running (in seconds), computed during the
+    /// last refresh.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// for processor in s.get_processors() {
-    ///     println!("{}", processor.get_brand());
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("Running since {} seconds", process.run_time());
     /// }
     /// ```
-    fn get_brand(&self) -> &str;
+    fn run_time(&self) -> u64;
 
-    /// Returns the processor's frequency.
+    /// Returns the number of threads of this process.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
-    /// for processor in s.get_processors() {
-    ///     println!("{}", processor.get_frequency());
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{} threads", process.thread_count());
     /// }
     /// ```
-    fn get_frequency(&self) -> u64;
-}
+    fn thread_count(&self) -> u32;
 
-/// Contains all the methods of the [`System`][crate::System] type.
-pub trait SystemExt: Sized + Debug + Default {
-    /// Creates a new [`System`] instance with nothing loaded except the processors list. If you
-    /// want to load components, network interfaces or the disks, you'll have to use the
-    /// `refresh_*_list` methods. [`SystemExt::refresh_networks_list`] for example.
-    ///
-    /// Use the [`refresh_all`] method to update its internal information (or any of the `refresh_`
-    /// method).
+    /// Returns the threads of this process. Their CPU usage is computed like the process' one so
+    /// the processes need to be refreshed at least twice to get it.
     ///
-    /// [`System`]: crate::System
-    /// [`refresh_all`]: #method.refresh_all
+    /// It's only available on Linux, it's always empty on the other platforms.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
     /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     for thread in process.threads() {
+    ///         println!("{} {}: {}%", thread.tid, thread.name, thread.cpu_usage);
+    ///     }
+    /// }
     /// ```
-    fn new() -> Self {
-        Self::new_with_specifics(RefreshKind::new())
-    }
+    fn threads(&self) -> &[ThreadInfo];
 
-    /// Creates a new [`System`] instance with everything loaded.
-    ///
-    /// It is an equivalent of [`SystemExt::new_with_specifics`]`(`[`RefreshKind::everything`]`())`.
+    /// Returns the number of files (or handles on Windows) opened by this process.
     ///
-    /// [`System`]: crate::System
+    /// Returns `None` if we don't have enough rights to get this information. On Linux, it's
+    /// also always `None` for tasks since they share the file descriptors of their process.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
     ///
-    /// let s = System::new_all();
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     if let Some(count) = process.open_files_count() {
+    ///         println!("{} opened files", count);
+    ///     }
+    /// }
     /// ```
-    fn new_all() -> Self {
-        Self::new_with_specifics(RefreshKind::everything())
-    }
+    fn open_files_count(&self) -> Option<usize>;
 
-    /// Creates a new [`System`] instance and refresh the data corresponding to the
-    /// given [`RefreshKind`].
+    /// Returns the TCP and UDP sockets opened by this process.
     ///
-    /// [`System`]: crate::System
+    /// It returns an empty list if we don't have enough rights to get this information.
     ///
-    /// ```
-    /// use sysinfo::{RefreshKind, System, SystemExt};
+    /// **Important**: the sockets of the process are listed every time this function is called,
+    /// but the socket tables of the system are only read once per refresh of the process.
     ///
-    /// // We want everything except disks.
-    /// let mut system = System::new_with_specifics(RefreshKind::everything().without_disks_list());
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, SocketState, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     for socket in process.open_sockets() {
+    ///         if socket.state == Some(SocketState::Listen) {
+    ///             println!("listening on {}", socket.local_address);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    fn open_sockets(&self) -> Vec<SocketInfo>;
+
+    /// Returns the number of bytes transferred over the TCP sockets currently opened by this
+    /// process. The bytes of the sockets which were closed aren't counted anymore.
+    ///
+    /// It's only supported on Linux (with a kernel 4.2 or newer), it returns `None` on other
+    /// platforms or if we don't have enough rights to get this information. The sockets of a
+    /// process in another network namespace aren't found.
+    ///
+    /// **Important**: the sockets of the process are listed every time this function is called,
+    /// but the transferred bytes of the system sockets are only read once per refresh of the
+    /// process.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     if let Some(usage) = process.network_usage() {
+    ///         println!(
+    ///             "received: {} B, transmitted: {} B",
+    ///             usage.total_received_bytes, usage.total_transmitted_bytes,
+    ///         );
+    ///     }
+    /// }
+    /// ```
+    fn network_usage(&self) -> Option<NetworkUsage>;
+
+    /// Returns the real ID of the owner user of this process.
+    ///
+    /// On Windows, it is the relative identifier (RID) of the user SID.
+    ///
+    /// **Breaking change**: it used to return the effective user ID on Linux and macOS, use
+    /// [`effective_user_id`] to get it.
+    ///
+    /// [`effective_user_id`]: ProcessExt::effective_user_id
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("User id for process 1337: {:?}", process.user_id());
+    /// }
+    /// ```
+    fn user_id(&self) -> Option<Uid>;
+
+    /// Returns the effective ID of the owner user of this process. It only differs from
+    /// [`user_id`] for processes running a setuid executable.
+    ///
+    /// On Windows, it always returns the same value as [`user_id`].
+    ///
+    /// [`user_id`]: ProcessExt::user_id
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("Effective user id for process 1337: {:?}", process.effective_user_id());
+    /// }
+    /// ```
+    fn effective_user_id(&self) -> Option<Uid>;
+
+    /// Returns the ID of the owner group of this process.
+    ///
+    /// On Windows, it is the relative identifier (RID) of the primary group SID.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("Group id for process 1337: {:?}", process.group_id());
+    /// }
+    /// ```
+    fn group_id(&self) -> Option<Gid>;
+
+    /// Returns the priority of the process, computed during the last refresh.
+    ///
+    /// On Unix systems, it is the nice value (between -20 and 19, lower values meaning higher
+    /// priority). On Windows, the priority class is mapped to a comparable value: `19` for idle,
+    /// `10` for below normal, `0` for normal, `-10` for above normal, `-15` for high and `-20`
+    /// for realtime.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("priority: {}", process.priority());
+    /// }
+    /// ```
+    fn priority(&self) -> i32;
+
+    /// Sets the priority of the process. The value uses the same scale as
+    /// [`ProcessExt::priority`]. On Windows, it is rounded to the closest priority class.
+    ///
+    /// Returns `false` if it failed, for example if you don't have enough rights. Use
+    /// [`ProcessExt::set_nice`] to know why it failed.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     process.set_priority(10);
+    /// }
+    /// ```
+    fn set_priority(&self, priority: i32) -> bool {
+        self.set_nice(priority).is_ok()
+    }
+
+    /// Sets the nice value of the process (between -20 and 19, lower values meaning higher
+    /// priority), like [`ProcessExt::set_priority`] but returning why it failed.
+    ///
+    /// * On Unix systems, it calls `setpriority`. Without privileges, you can only change the
+    ///   value of your own processes, and only to lower their priority (in other words, to
+    ///   increase their nice value): otherwise, [`PriorityError::PermissionDenied`] is returned.
+    /// * On Windows, it calls `SetPriorityClass` with the priority class closest to `nice` (see
+    ///   [`ProcessExt::priority`] for the mapping).
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, PriorityError, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     match process.set_nice(-5) {
+    ///         Ok(()) => println!("priority raised"),
+    ///         Err(PriorityError::PermissionDenied) => eprintln!("missing privileges"),
+    ///         Err(e) => eprintln!("failed to set priority: {}", e),
+    ///     }
+    /// }
+    /// ```
+    fn set_nice(&self, nice: i32) -> Result<(), PriorityError>;
+
+    /// Returns the badness score of the process used by the Linux OOM killer, between 0 and
+    /// 1000 (or more when [`oom_score_adj`] is positive). The process with the highest score is
+    /// killed first when the system runs out of memory. It is updated when the memory of the
+    /// process is refreshed.
+    ///
+    /// It's always `None` on other platforms.
+    ///
+    /// [`oom_score_adj`]: ProcessExt::oom_score_adj
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{:?}", process.oom_score());
+    /// }
+    /// ```
+    fn oom_score(&self) -> Option<u32>;
+
+    /// Returns the adjustment (between -1000 and 1000) added to the [`oom_score`] of the process
+    /// on Linux. -1000 means that the OOM killer never kills it. It is updated when the memory of
+    /// the process is refreshed.
+    ///
+    /// It's always `None` on other platforms.
+    ///
+    /// [`oom_score`]: ProcessExt::oom_score
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{:?}", process.oom_score_adj());
+    /// }
+    /// ```
+    fn oom_score_adj(&self) -> Option<i32>;
+
+    /// Returns the path of the control group of the process, relative to the root of the
+    /// cgroup hierarchy (like `/system.slice/docker-[id].scope`). Processes of the same container
+    /// share the same control group. It's only read when the process is seen for the first time.
+    ///
+    /// The cgroup v2 path is used, unless it's the root group while cgroup v1 hierarchies are
+    /// mounted (like on hybrid systems). In this case, the path of the first v1 hierarchy in which
+    /// the process isn't in the root group is returned.
+    ///
+    /// It's always `None` on other platforms than Linux.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{:?}", process.cgroup());
+    /// }
+    /// ```
+    fn cgroup(&self) -> Option<String>;
+
+    /// Returns `false` if the process exited before the last processes refresh.
+    ///
+    /// It can only happen if [`SystemExt::set_keep_exited_processes`] was enabled: the exited
+    /// processes are then kept (with their last values) for one refresh cycle before being
+    /// removed. Otherwise, it always returns `true`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.set_keep_exited_processes(true);
+    /// s.refresh_processes();
+    /// for process in s.get_processes().values().filter(|p| !p.exists()) {
+    ///     println!("{} exited ({}% CPU)", process.name(), process.cpu_usage());
+    /// }
+    /// ```
+    fn exists(&self) -> bool;
+
+    /// Returns the error which prevented some information of this process from being
+    /// retrieved, or `None` if everything could be read.
+    ///
+    /// It allows to tell apart a process which has genuinely no environment variables or no
+    /// executable path (like kernel threads) from a process owned by another user, for which
+    /// these values are left empty because the current user isn't allowed to read them.
+    ///
+    /// ```no_run
+    /// use sysinfo::{AccessError, Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(Pid::from(1)) {
+    ///     if process.last_error() == Some(AccessError::PermissionDenied) {
+    ///         println!("not allowed to read the environment of {}", process.name());
+    ///     } else {
+    ///         println!("{:?}", process.environ());
+    ///     }
+    /// }
+    /// ```
+    fn last_error(&self) -> Option<AccessError>;
+
+    /// Returns the total time (in milliseconds) the process spent waiting for block I/O to
+    /// complete. It is updated when the CPU usage of the process is refreshed.
+    ///
+    /// It's only available on Linux when delay accounting is enabled (with the
+    /// `kernel.task_delayacct` sysctl or the `delayacct` boot parameter on recent kernels),
+    /// otherwise it returns `None`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{:?} ms", process.io_wait_time());
+    /// }
+    /// ```
+    fn io_wait_time(&self) -> Option<u64>;
+
+    /// Returns the total time (in milliseconds) the process spent runnable but waiting for a
+    /// processor, in the scheduler run queue. It is updated when the CPU usage of the process is
+    /// refreshed.
+    ///
+    /// It's only available on Linux (from `/proc/[pid]/schedstat`), it returns `None` on other
+    /// platforms or if the kernel doesn't provide scheduler statistics.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{:?} ms", process.sched_delay());
+    /// }
+    /// ```
+    fn sched_delay(&self) -> Option<u64>;
+
+    /// Returns the total CPU usage (in %). Notice that it might be bigger than 100 if run on a
+    /// multicore machine.
+    ///
+    /// If you want a value between 0% and 100%, divide the returned value by the number of CPU
+    /// processors.
+    ///
+    /// **Warning**: If you want accurate CPU usage number, better leave a bit of time
+    /// between two calls of this method (200 ms for example).
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{}%", process.cpu_usage());
+    /// }
+    /// ```
+    fn cpu_usage(&self) -> f32;
+
+    /// Returns the CPU usage (in %) divided by the number of logical cores, so it stays between
+    /// 0% and 100% even for multithreaded processes. 100% means that the process used all the
+    /// cores.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{}%", process.cpu_usage_normalized());
+    /// }
+    /// ```
+    fn cpu_usage_normalized(&self) -> f32 {
+        let nb_cores = crate::utils::get_logical_core_count() as f32;
+        (self.cpu_usage() / nb_cores).min(100.)
+    }
+
+    /// Returns the total CPU time (user and system) consumed by the process since it started
+    /// (in milliseconds). This is the raw counter from which [`cpu_usage`] is computed.
+    ///
+    /// It's always 0 on iOS and on unsupported platforms.
+    ///
+    /// [`cpu_usage`]: ProcessExt::cpu_usage
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{} ms", process.accumulated_cpu_time());
+    /// }
+    /// ```
+    fn accumulated_cpu_time(&self) -> u64;
+
+    /// Returns number of bytes read and written to disk.
+    ///
+    /// /!\\ On Windows, this method actually returns **ALL** I/O read and written bytes.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     let disk_usage = process.disk_usage();
+    ///     println!("read bytes   : new/total => {}/{}",
+    ///         disk_usage.read_bytes,
+    ///         disk_usage.total_read_bytes,
+    ///     );
+    ///     println!("written bytes: new/total => {}/{}",
+    ///         disk_usage.written_bytes,
+    ///         disk_usage.total_written_bytes,
+    ///     );
+    /// }
+    /// ```
+    fn disk_usage(&self) -> DiskUsage;
+
+    /// Returns the number of minor page faults (resolved without any disk access) of the
+    /// process since the last refresh.
+    ///
+    /// On Windows, the system doesn't tell soft and hard page faults apart so all of them are
+    /// counted here. It's always 0 on iOS and on unsupported platforms.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{}", process.minor_faults());
+    /// }
+    /// ```
+    fn minor_faults(&self) -> u64;
+
+    /// Returns the number of major page faults (which required loading a page from the disk) of
+    /// the process since the last refresh.
+    ///
+    /// It's always 0 on Windows, iOS and on unsupported platforms.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{}", process.major_faults());
+    /// }
+    /// ```
+    fn major_faults(&self) -> u64;
+
+    /// Returns the total number of minor page faults of the process since it started.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{}", process.total_minor_faults());
+    /// }
+    /// ```
+    fn total_minor_faults(&self) -> u64;
+
+    /// Returns the total number of major page faults of the process since it started.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{}", process.total_major_faults());
+    /// }
+    /// ```
+    fn total_major_faults(&self) -> u64;
+}
+
+/// Contains all the methods of the [`Processor`][crate::Processor] struct.
+#[cfg(feature = "cpu")]
+pub trait ProcessorExt: Debug {
+    /// Returns this processor's usage.
+    ///
+    /// Note: You'll need to refresh it at least twice (diff between the first and the second is
+    /// how CPU usage is computed) at first if you want to have a non-zero value.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for processor in s.get_processors() {
+    ///     println!("{}%", processor.get_cpu_usage());
+    /// }
+    /// ```
+    fn get_cpu_usage(&self) -> f32;
+
+    /// Returns this processor's name.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for processor in s.get_processors() {
+    ///     println!("{}", processor.get_name());
+    /// }
+    /// ```
+    fn get_name(&self) -> &str;
+
+    /// Returns the processor's vendor id.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for processor in s.get_processors() {
+    ///     println!("{}", processor.get_vendor_id());
+    /// }
+    /// ```
+    fn get_vendor_id(&self) -> &str;
+
+    /// Returns the processor's brand.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for processor in s.get_processors() {
+    ///     println!("{}", processor.get_brand());
+    /// }
+    /// ```
+    fn get_brand(&self) -> &str;
+
+    /// Returns the processor's frequency.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for processor in s.get_processors() {
+    ///     println!("{}", processor.get_frequency());
+    /// }
+    /// ```
+    fn get_frequency(&self) -> u64;
+
+    /// Returns the temperature (in celsius degree) of the core this processor runs on, if
+    /// available. It is updated when the processors are refreshed.
+    ///
+    /// On Linux, it comes from the `coretemp` sensors. On macOS, from the `TC{N}C` SMC keys.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for processor in s.get_processors() {
+    ///     println!("{:?}", processor.get_temperature());
+    /// }
+    /// ```
+    fn get_temperature(&self) -> Option<f32>;
+
+    /// Returns the raw tick counters of the processor, as of the last CPU refresh. They can be
+    /// used to compute the CPU usage over any window of time. For the global processor, they
+    /// are the sum of all the processors' ones.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for processor in s.get_processors() {
+    ///     let times = processor.get_times();
+    ///     println!("{}: {} idle ticks", processor.get_name(), times.idle);
+    /// }
+    /// ```
+    fn get_times(&self) -> CpuTimes;
+}
+
+/// Contains all the methods of the [`System`][crate::System] type.
+#[cfg(feature = "system")]
+pub trait SystemExt: Sized + Debug + Default {
+    /// Creates a new [`System`] instance with nothing loaded except the processors list. If you
+    /// want to load components, network interfaces or the disks, you'll have to use the
+    /// `refresh_*_list` methods. [`SystemExt::refresh_networks_list`] for example.
+    ///
+    /// Use the [`refresh_all`] method to update its internal information (or any of the `refresh_`
+    /// method).
+    ///
+    /// [`System`]: crate::System
+    /// [`refresh_all`]: #method.refresh_all
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// ```
+    fn new() -> Self {
+        Self::new_with_specifics(RefreshKind::new())
+    }
+
+    /// Creates a new [`System`] instance with everything loaded.
+    ///
+    /// It is an equivalent of [`SystemExt::new_with_specifics`]`(`[`RefreshKind::everything`]`())`.
+    ///
+    /// [`System`]: crate::System
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// ```
+    fn new_all() -> Self {
+        Self::new_with_specifics(RefreshKind::everything())
+    }
+
+    /// Creates a new [`System`] instance and refresh the data corresponding to the
+    /// given [`RefreshKind`].
+    ///
+    /// [`System`]: crate::System
+    ///
+    /// ```
+    /// # #[cfg(all(feature = "process", feature = "disk"))] {
+    /// use sysinfo::{RefreshKind, System, SystemExt};
+    ///
+    /// // We want everything except disks.
+    /// let mut system = System::new_with_specifics(RefreshKind::everything().without_disks_list());
     ///
     /// assert_eq!(system.get_disks().len(), 0);
     /// assert!(system.get_processes().len() > 0);
     ///
-    /// // If you want the disks list afterwards, just call the corresponding
-    /// // "refresh_disks_list":
-    /// system.refresh_disks_list();
-    /// let disks = system.get_disks();
+    /// // If you want the disks list afterwards, just call the corresponding
+    /// // "refresh_disks_list":
+    /// system.refresh_disks_list();
+    /// let disks = system.get_disks();
+    /// # }
+    /// ```
+    fn new_with_specifics(refreshes: RefreshKind) -> Self;
+
+    /// Refreshes according to the given [`RefreshKind`]. It calls the corresponding
+    /// "refresh_" methods. The refreshes of the disabled cargo features are ignored.
+    ///
+    /// ```
+    /// use sysinfo::{RefreshKind, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    ///
+    /// // Let's just update networks and processes:
+    /// s.refresh_specifics(RefreshKind::new().with_networks().with_processes());
+    /// ```
+    fn refresh_specifics(&mut self, refreshes: RefreshKind) {
+        if refreshes.memory() {
+            self.refresh_memory();
+        }
+        #[cfg(feature = "cpu")]
+        if refreshes.cpu() {
+            self.refresh_cpu();
+        }
+        #[cfg(feature = "component")]
+        if refreshes.components_list() {
+            self.refresh_components_list();
+        } else if refreshes.components() {
+            self.refresh_components();
+        }
+        #[cfg(feature = "network")]
+        if refreshes.networks_list() {
+            self.refresh_networks_list();
+        } else if refreshes.networks() {
+            self.refresh_networks();
+        }
+        #[cfg(feature = "process")]
+        if refreshes.processes() {
+            self.refresh_processes();
+        }
+        #[cfg(feature = "disk")]
+        if refreshes.disks_list() {
+            self.refresh_disks_list();
+        } else if refreshes.disks() {
+            self.refresh_disks();
+        }
+        if refreshes.users_list() {
+            self.refresh_users_list();
+        }
+        if refreshes.gpus_list() {
+            self.refresh_gpus_list();
+        } else if refreshes.gpus() {
+            self.refresh_gpus();
+        }
+    }
+
+    /// Refreshes system information (RAM, swap, CPU usage and components' temperature).
+    ///
+    /// If you want some more specific refreshes, you might be interested into looking at
+    /// [`refresh_memory`], [`refresh_cpu`] and [`refresh_components`].
+    ///
+    /// [`refresh_memory`]: SystemExt::refresh_memory
+    /// [`refresh_cpu`]: SystemExt::refresh_memory
+    /// [`refresh_components`]: SystemExt::refresh_components
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_system();
+    /// ```
+    fn refresh_system(&mut self) {
+        self.refresh_memory();
+        #[cfg(feature = "cpu")]
+        self.refresh_cpu();
+        #[cfg(feature = "component")]
+        self.refresh_components();
+    }
+
+    /// Refreshes RAM and SWAP usage, as well as the [swap devices][SystemExt::get_swap_devices].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_memory();
+    /// ```
+    fn refresh_memory(&mut self);
+
+    /// Refreshes CPU usage.
+    ///
+    /// Only the global and per-processor usages are updated: the processes list isn't touched, so
+    /// this is cheap enough to be called often. Processes CPU usage is computed independently
+    /// when calling [`SystemExt::refresh_processes`].
+    ///
+    /// If it was refreshed less than the minimum refresh interval ago, nothing is done. Take a
+    /// look at [`SystemExt::set_min_refresh_interval`] for more information.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_cpu();
+    /// ```
+    #[cfg(feature = "cpu")]
+    fn refresh_cpu(&mut self);
+
+    /// Refreshes CPU usage, computed over exactly the given `window`.
+    ///
+    /// This method **blocks** the current thread: it takes a first sample of the processors
+    /// times, sleeps for `window` and then takes a second one. The global and per-processor usages
+    /// then reflect what happened between the two samples. The minimum refresh interval (see
+    /// [`SystemExt::set_min_refresh_interval`]) is ignored.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    /// use std::time::Duration;
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_cpu_over(Duration::from_millis(200));
+    /// println!("{}%", s.get_global_processor_info().get_cpu_usage());
+    /// ```
+    #[cfg(feature = "cpu")]
+    fn refresh_cpu_over(&mut self, window: Duration);
+
+    /// Sets the minimum interval between two CPU usage refreshes. If [`SystemExt::refresh_cpu`]
+    /// is called again before this interval elapsed, the previous values are kept.
+    ///
+    /// The CPU usage is computed from the time spent by the processors between two refreshes, so
+    /// refreshing too often gives noisy values (or even zero). On the other hand, a big interval
+    /// means that the values are updated less often. By default, there is no minimum interval.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    /// use std::time::Duration;
+    ///
+    /// let mut s = System::new();
+    /// s.set_min_refresh_interval(Duration::from_millis(200));
+    /// s.refresh_cpu();
+    /// // Too soon, the previous values are kept.
+    /// s.refresh_cpu();
+    /// ```
+    #[cfg(feature = "cpu")]
+    fn set_min_refresh_interval(&mut self, interval: Duration);
+
+    /// Sets whether the threads of the processes should be listed as processes by
+    /// [`refresh_processes`] (like `top -H`). Use [`ProcessExt::is_thread`] and
+    /// [`ProcessExt::tgid`] to tell them apart from the processes. By default, they aren't.
+    ///
+    /// It's only supported on Linux, it does nothing on other platforms.
+    ///
+    /// [`refresh_processes`]: SystemExt::refresh_processes
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.set_include_threads(true);
+    /// s.refresh_processes();
+    /// for thread in s.get_processes().values().filter(|p| p.is_thread()) {
+    ///     println!("{} (thread of {})", thread.pid(), thread.tgid());
+    /// }
+    /// ```
+    #[cfg(feature = "process")]
+    fn set_include_threads(&mut self, include: bool);
+
+    /// Sets a predicate used by [`refresh_disks_list`] to decide which disks are kept. It's
+    /// called with the mount point and the file system of each disk, which is only kept if it
+    /// returns `true`. By default, all disks are kept.
+    ///
+    /// The disks already listed aren't affected until the next [`refresh_disks_list`] call.
+    ///
+    /// [`refresh_disks_list`]: SystemExt::refresh_disks_list
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.set_disk_filter(|mount_point, file_system| {
+    ///     !mount_point.starts_with("/snap") && file_system != "overlay"
+    /// });
+    /// s.refresh_disks_list();
+    /// ```
+    #[cfg(feature = "disk")]
+    fn set_disk_filter<F>(&mut self, filter: F)
+    where
+        F: Fn(&Path, &str) -> bool + Send + Sync + 'static;
+
+    /// Sets whether the processes which exited should be kept for one more refresh by
+    /// [`refresh_processes`], with [`ProcessExt::exists`] returning `false`, instead of being
+    /// removed right away. It allows to still see what a short-lived process did during its last
+    /// refresh interval. By default, they're removed immediately.
+    ///
+    /// It does nothing on iOS and on unsupported platforms.
+    ///
+    /// [`refresh_processes`]: SystemExt::refresh_processes
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.set_keep_exited_processes(true);
+    /// s.refresh_processes();
+    /// for process in s.get_processes().values().filter(|p| !p.exists()) {
+    ///     println!("{} exited", process.name());
+    /// }
+    /// ```
+    #[cfg(feature = "process")]
+    fn set_keep_exited_processes(&mut self, keep: bool);
+
+    /// Refreshes components' temperature and fans' speed.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_components();
+    /// ```
+    #[cfg(feature = "component")]
+    fn refresh_components(&mut self) {
+        for component in self.get_components_mut() {
+            component.refresh();
+        }
+        for fan in self.get_fans_mut() {
+            fan.refresh();
+        }
+    }
+
+    /// Refreshes components and fans list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_components_list();
+    /// ```
+    #[cfg(feature = "component")]
+    fn refresh_components_list(&mut self);
+
+    /// Refreshes GPUs' memory and usage.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_gpus();
+    /// ```
+    fn refresh_gpus(&mut self) {
+        for gpu in self.get_gpus_mut() {
+            gpu.refresh();
+        }
+    }
+
+    /// Refreshes GPUs list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_gpus_list();
+    /// ```
+    fn refresh_gpus_list(&mut self);
+
+    /// Gets all processes and updates their information.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_processes();
+    /// ```
+    #[cfg(feature = "process")]
+    fn refresh_processes(&mut self) {
+        self.refresh_processes_specifics(ProcessRefreshKind::everything());
+    }
+
+    /// Gets all processes and updates the specified information.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessRefreshKind, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_processes_specifics(ProcessRefreshKind::new().with_cpu().with_memory());
+    /// ```
+    #[cfg(feature = "process")]
+    fn refresh_processes_specifics(&mut self, refresh_kind: ProcessRefreshKind);
+
+    /// Refreshes *only* the process corresponding to `pid`. Returns `false` if the process doesn't
+    /// exist (in which case it is removed from the processes list). If it isn't listed yet, it'll
+    /// be added.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_process(Pid::from(1337));
+    /// ```
+    #[cfg(feature = "process")]
+    fn refresh_process(&mut self, pid: Pid) -> bool;
+
+    /// Refreshes the listed disks' information.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_disks();
+    /// ```
+    #[cfg(feature = "disk")]
+    fn refresh_disks(&mut self) {
+        for disk in self.get_disks_mut() {
+            disk.refresh();
+        }
+    }
+
+    /// Refreshes *only* the disk mounted on `mount_point`. Returns `false` if there is no listed
+    /// disk mounted there or if its information couldn't be updated.
+    ///
+    /// ```no_run
+    /// use std::path::Path;
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_disk(Path::new("/"));
+    /// ```
+    #[cfg(feature = "disk")]
+    fn refresh_disk(&mut self, mount_point: &Path) -> bool {
+        match self
+            .get_disks_mut()
+            .iter_mut()
+            .find(|disk| disk.get_mount_point() == mount_point)
+        {
+            Some(disk) => disk.refresh(),
+            None => false,
+        }
+    }
+
+    /// The disk list will be emptied then completely recomputed.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_disks_list();
+    /// ```
+    #[cfg(feature = "disk")]
+    fn refresh_disks_list(&mut self);
+
+    /// Refreshes users list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_users_list();
+    /// ```
+    fn refresh_users_list(&mut self);
+
+    /// Refreshes the list of the TCP and UDP sockets of the system. They aren't refreshed by
+    /// [`SystemExt::refresh_all`] since it can be costly.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_sockets();
+    /// ```
+    #[cfg(feature = "process")]
+    fn refresh_sockets(&mut self);
+
+    /// Refreshes networks data.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_networks();
+    /// ```
+    ///
+    /// It is a shortcut for:
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworksExt, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// let networks = s.get_networks_mut();
+    /// networks.refresh();
+    /// ```
+    #[cfg(feature = "network")]
+    fn refresh_networks(&mut self) {
+        self.get_networks_mut().refresh();
+    }
+
+    /// The network list will be updated: removing not existing anymore interfaces and adding new
+    /// ones.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_networks_list();
+    /// ```
+    ///
+    /// This is a shortcut for:
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworksExt, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// let networks = s.get_networks_mut();
+    /// networks.refresh_networks_list();
+    /// ```
+    #[cfg(feature = "network")]
+    fn refresh_networks_list(&mut self) {
+        self.get_networks_mut().refresh_networks_list();
+    }
+
+    /// Refreshes all system, processes, disks and network interfaces information.
+    ///
+    /// Please note that it doesn't recompute disks list, components list, network interfaces
+    /// list nor users list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// s.refresh_all();
+    /// ```
+    fn refresh_all(&mut self) {
+        self.refresh_system();
+        #[cfg(feature = "process")]
+        self.refresh_processes();
+        #[cfg(feature = "disk")]
+        self.refresh_disks();
+        #[cfg(feature = "network")]
+        self.refresh_networks();
+    }
+
+    /// Returns the process list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for (pid, process) in s.get_processes() {
+    ///     println!("{} {}", pid, process.name());
+    /// }
+    /// ```
+    #[cfg(feature = "process")]
+    fn get_processes(&self) -> &HashMap<Pid, Process>;
+
+    /// Returns the process corresponding to the given pid or `None` if no such process exists.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     println!("{}", process.name());
+    /// }
+    /// ```
+    #[cfg(feature = "process")]
+    fn get_process(&self, pid: Pid) -> Option<&Process>;
+
+    /// Spawns a thread waiting for the process `pid` to end, which then calls `f` with the
+    /// result of [`ProcessExt::wait`]. Like it, it doesn't reap the process.
+    ///
+    /// The watch can be stopped with [`ProcessWatcher::cancel`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{Pid, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// s.watch_process_exit(Pid::from(1337), |status| {
+    ///     println!("process ended: {:?}", status);
+    /// });
+    /// ```
+    #[cfg(feature = "process")]
+    fn watch_process_exit<F>(&self, pid: Pid, f: F) -> ProcessWatcher
+    where
+        F: FnOnce(Option<ExitStatus>) + Send + 'static;
+
+    /// Returns a list of process containing the given `name`.
+    ///
+    /// ```no_run
+    /// # #![allow(deprecated)]
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for process in s.get_process_by_name("htop") {
+    ///     println!("{} {}", process.pid(), process.name());
+    /// }
+    /// ```
+    #[cfg(feature = "process")]
+    #[deprecated(note = "use `get_processes_by_name` instead")]
+    fn get_process_by_name(&self, name: &str) -> Vec<&Process> {
+        let mut ret = vec![];
+        for val in self.get_processes().values() {
+            if val.name().contains(name) {
+                ret.push(val);
+            }
+        }
+        ret
+    }
+
+    /// Returns an iterator over the processes whose name contains the given `name`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for process in s.get_processes_by_name("htop") {
+    ///     println!("{} {}", process.pid(), process.name());
+    /// }
+    /// ```
+    #[cfg(feature = "process")]
+    fn get_processes_by_name<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> Box<dyn Iterator<Item = &'a Process> + 'a> {
+        Box::new(
+            self.get_processes()
+                .values()
+                .filter(move |val| val.name().contains(name)),
+        )
+    }
+
+    /// Returns an iterator over the processes whose name is exactly `name`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for process in s.get_processes_by_exact_name("htop") {
+    ///     println!("{} {}", process.pid(), process.name());
+    /// }
+    /// ```
+    #[cfg(feature = "process")]
+    fn get_processes_by_exact_name<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> Box<dyn Iterator<Item = &'a Process> + 'a> {
+        Box::new(
+            self.get_processes()
+                .values()
+                .filter(move |val| val.name() == name),
+        )
+    }
+
+    /// Returns an iterator over the processes run by the user `uid`.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt, UserExt};
+    ///
+    /// let s = System::new_all();
+    /// if let Some(user) = s.get_users().first() {
+    ///     for process in s.get_processes_by_user(user.get_uid()) {
+    ///         println!("{} {}", process.pid(), process.name());
+    ///     }
+    /// }
+    /// ```
+    #[cfg(feature = "process")]
+    fn get_processes_by_user<'a>(&'a self, uid: Uid) -> Box<dyn Iterator<Item = &'a Process> + 'a> {
+        Box::new(
+            self.get_processes()
+                .values()
+                .filter(move |val| val.user_id() == Some(uid)),
+        )
+    }
+
+    /// Returns an iterator over the processes which aren't kernel threads.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for process in s.get_user_processes() {
+    ///     println!("{} {}", process.pid(), process.name());
+    /// }
+    /// ```
+    #[cfg(feature = "process")]
+    fn get_user_processes<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Process> + 'a> {
+        Box::new(
+            self.get_processes()
+                .values()
+                .filter(|val| !val.is_kernel_thread()),
+        )
+    }
+
+    /// Returns the number of processes.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} processes", s.get_process_count());
+    /// ```
+    #[cfg(feature = "process")]
+    fn get_process_count(&self) -> usize {
+        self.get_processes().len()
+    }
+
+    /// Returns the number of processes which are currently runnable.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} running processes", s.get_running_process_count());
+    /// ```
+    #[cfg(feature = "process")]
+    fn get_running_process_count(&self) -> usize {
+        self.get_processes()
+            .values()
+            .filter(|p| p.status().is_running())
+            .count()
+    }
+
+    /// Returns the sum of the threads of all processes.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} threads", s.get_total_thread_count());
+    /// ```
+    #[cfg(feature = "process")]
+    fn get_total_thread_count(&self) -> usize {
+        self.get_processes()
+            .values()
+            .map(|p| p.thread_count() as usize)
+            .sum()
+    }
+
+    /// Returns "global" processors information (aka the addition of all the processors).
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// println!("{}%", s.get_global_processor_info().get_cpu_usage());
+    /// ```
+    #[cfg(feature = "cpu")]
+    fn get_global_processor_info(&self) -> &Processor;
+
+    /// Returns the list of the processors.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for processor in s.get_processors() {
+    ///     println!("{}%", processor.get_cpu_usage());
+    /// }
+    /// ```
+    #[cfg(feature = "cpu")]
+    fn get_processors(&self) -> &[Processor];
+
+    /// Returns the number of physical cores on the processor or `None` if it couldn't get it.
+    ///
+    /// In case there are multiple CPUs, it will combine the physical core count of all the CPUs.
+    ///
+    /// **Important**: this information is computed every time this function is called.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// println!("{:?}", s.get_physical_core_count());
+    /// ```
+    #[cfg(feature = "cpu")]
+    fn get_physical_core_count(&self) -> Option<usize>;
+
+    /// Returns the number of context switches done by the system between the last two
+    /// [`refresh_cpu`] calls.
+    ///
+    /// It's always 0 on macOS, iOS and on unsupported platforms.
+    ///
+    /// [`refresh_cpu`]: SystemExt::refresh_cpu
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} context switches", s.get_context_switches());
+    /// ```
+    #[cfg(feature = "cpu")]
+    fn get_context_switches(&self) -> u64;
+
+    /// Returns the number of interrupts serviced by the system between the last two
+    /// [`refresh_cpu`] calls.
+    ///
+    /// It's always 0 on macOS, iOS and on unsupported platforms.
+    ///
+    /// [`refresh_cpu`]: SystemExt::refresh_cpu
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} interrupts", s.get_interrupts());
+    /// ```
+    #[cfg(feature = "cpu")]
+    fn get_interrupts(&self) -> u64;
+
+    /// Returns the RAM size in kB (1 kB = 1000 bytes).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} kB", s.get_total_memory());
+    /// ```
+    fn get_total_memory(&self) -> u64;
+
+    /// Returns the amount of free RAM in kB (1 kB = 1000 bytes).
+    ///
+    /// Generally, "free" memory refers to unallocated memory whereas "available" memory refers to
+    /// memory that is available for (re)use.
+    ///
+    /// Side note: Windows doesn't report "free" memory so this method returns the same value
+    /// as [`get_available_memory`](#tymethod.get_available_memory).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} kB", s.get_free_memory());
+    /// ```
+    fn get_free_memory(&self) -> u64;
+
+    /// Returns the amount of available RAM in kB (1 kB = 1000 bytes).
+    ///
+    /// Generally, "free" memory refers to unallocated memory whereas "available" memory refers to
+    /// memory that is available for (re)use.
+    ///
+    /// Side note: Windows doesn't report "free" memory so
+    /// [`get_free_memory`](#tymethod.get_free_memory) returns the same value as this method.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} kB", s.get_available_memory());
+    /// ```
+    fn get_available_memory(&self) -> u64;
+
+    /// Returns the amound of used RAM in kB (1 kB = 1000 bytes).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} kB", s.get_used_memory());
+    /// ```
+    fn get_used_memory(&self) -> u64;
+
+    /// Returns the SWAP size in kB (1 kB = 1000 bytes).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} kB", s.get_total_swap());
+    /// ```
+    fn get_total_swap(&self) -> u64;
+
+    /// Returns the amount of free SWAP in kB (1 kB = 1000 bytes).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} kB", s.get_free_swap());
+    /// ```
+    fn get_free_swap(&self) -> u64;
+
+    /// Returns the amount of used SWAP in kB (1 kB = 1000 bytes).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} kB", s.get_used_swap());
+    /// ```
+    fn get_used_swap(&self) -> u64;
+
+    /// Returns the swap partitions and files in use, as of the last
+    /// [`refresh_memory`][SystemExt::refresh_memory] call.
+    ///
+    /// On macOS, the swap files are created and removed by the system depending on the memory
+    /// pressure. Since their individual usage isn't available, they're considered to be filled
+    /// one after the other.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for device in s.get_swap_devices() {
+    ///     println!("{}: {} bytes used", device.name, device.used);
+    /// }
+    /// ```
+    fn get_swap_devices(&self) -> &[SwapDevice];
+
+    /// Returns the RAM size in bytes. It's the value returned by [`get_total_memory`] multiplied by
+    /// 1000.
+    ///
+    /// [`get_total_memory`]: SystemExt::get_total_memory
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} bytes", s.get_total_memory_bytes());
+    /// ```
+    fn get_total_memory_bytes(&self) -> u64 {
+        self.get_total_memory() * 1_000
+    }
+
+    /// Returns the amount of free RAM in bytes. It's the value returned by [`get_free_memory`]
+    /// multiplied by 1000.
+    ///
+    /// [`get_free_memory`]: SystemExt::get_free_memory
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} bytes", s.get_free_memory_bytes());
+    /// ```
+    fn get_free_memory_bytes(&self) -> u64 {
+        self.get_free_memory() * 1_000
+    }
+
+    /// Returns the amount of available RAM in bytes. It's the value returned by
+    /// [`get_available_memory`] multiplied by 1000.
+    ///
+    /// [`get_available_memory`]: SystemExt::get_available_memory
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} bytes", s.get_available_memory_bytes());
+    /// ```
+    fn get_available_memory_bytes(&self) -> u64 {
+        self.get_available_memory() * 1_000
+    }
+
+    /// Returns the amount of used RAM in bytes. It's the value returned by [`get_used_memory`]
+    /// multiplied by 1000.
+    ///
+    /// [`get_used_memory`]: SystemExt::get_used_memory
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} bytes", s.get_used_memory_bytes());
+    /// ```
+    fn get_used_memory_bytes(&self) -> u64 {
+        self.get_used_memory() * 1_000
+    }
+
+    /// Returns the SWAP size in bytes. It's the value returned by [`get_total_swap`] multiplied by
+    /// 1000.
+    ///
+    /// [`get_total_swap`]: SystemExt::get_total_swap
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} bytes", s.get_total_swap_bytes());
+    /// ```
+    fn get_total_swap_bytes(&self) -> u64 {
+        self.get_total_swap() * 1_000
+    }
+
+    /// Returns the amount of free SWAP in bytes. It's the value returned by [`get_free_swap`]
+    /// multiplied by 1000.
+    ///
+    /// [`get_free_swap`]: SystemExt::get_free_swap
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} bytes", s.get_free_swap_bytes());
+    /// ```
+    fn get_free_swap_bytes(&self) -> u64 {
+        self.get_free_swap() * 1_000
+    }
+
+    /// Returns the amount of used SWAP in bytes. It's the value returned by [`get_used_swap`]
+    /// multiplied by 1000.
+    ///
+    /// [`get_used_swap`]: SystemExt::get_used_swap
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} bytes", s.get_used_swap_bytes());
+    /// ```
+    fn get_used_swap_bytes(&self) -> u64 {
+        self.get_used_swap() * 1_000
+    }
+
+    /// Returns the components list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ComponentExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for component in s.get_components() {
+    ///     println!("{}: {}°C", component.get_label(), component.get_temperature());
+    /// }
+    /// ```
+    #[cfg(feature = "component")]
+    fn get_components(&self) -> &[Component];
+
+    /// Returns a mutable components list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{ComponentExt, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// for component in s.get_components_mut() {
+    ///     component.refresh();
+    /// }
+    /// ```
+    #[cfg(feature = "component")]
+    fn get_components_mut(&mut self) -> &mut [Component];
+
+    /// Returns the fans list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{FanExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for fan in s.get_fans() {
+    ///     println!("{}: {} RPM", fan.get_label(), fan.get_rpm());
+    /// }
+    /// ```
+    #[cfg(feature = "component")]
+    fn get_fans(&self) -> &[Fan];
+
+    /// Returns a mutable fans list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{FanExt, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// for fan in s.get_fans_mut() {
+    ///     fan.refresh();
+    /// }
+    /// ```
+    #[cfg(feature = "component")]
+    fn get_fans_mut(&mut self) -> &mut [Fan];
+
+    /// Returns the GPUs list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{GpuExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for gpu in s.get_gpus() {
+    ///     println!("{}: {}%", gpu.get_name(), gpu.get_usage());
+    /// }
+    /// ```
+    fn get_gpus(&self) -> &[Gpu];
+
+    /// Returns a mutable GPUs list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{GpuExt, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// for gpu in s.get_gpus_mut() {
+    ///     gpu.refresh();
+    /// }
+    /// ```
+    fn get_gpus_mut(&mut self) -> &mut [Gpu];
+
+    /// Returns the disks list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// for disk in s.get_disks() {
+    ///     println!("{:?}", disk.get_name());
+    /// }
+    /// ```
+    #[cfg(feature = "disk")]
+    fn get_disks(&self) -> &[Disk];
+
+    /// Returns the total space of all the disks (in bytes).
+    ///
+    /// Disks sharing the same device (like bind mounts or a device mounted more than once) are
+    /// only counted once.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} bytes", s.get_total_disk_space());
+    /// ```
+    #[cfg(feature = "disk")]
+    fn get_total_disk_space(&self) -> u64 {
+        sum_disks(self.get_disks(), DiskExt::get_total_space)
+    }
+
+    /// Returns the available space of all the disks (in bytes).
+    ///
+    /// Disks sharing the same device (like bind mounts or a device mounted more than once) are
+    /// only counted once.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("{} bytes", s.get_available_disk_space());
+    /// ```
+    #[cfg(feature = "disk")]
+    fn get_available_disk_space(&self) -> u64 {
+        sum_disks(self.get_disks(), DiskExt::get_available_space)
+    }
+
+    /// Returns the users list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt, UserExt};
+    ///
+    /// let mut s = System::new_all();
+    /// for user in s.get_users() {
+    ///     println!("{} is in {} groups", user.get_name(), user.get_groups().len());
+    /// }
+    /// ```
+    fn get_users(&self) -> &[User];
+
+    /// Returns the user matching the given `user_id`, if any.
+    ///
+    /// **Important**: The user list must be filled before using this method, otherwise it will
+    /// always return `None` (through the `refresh_*` methods).
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "process")] {
+    /// use sysinfo::{Pid, ProcessExt, System, SystemExt, UserExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_processes();
+    /// s.refresh_users_list();
+    ///
+    /// if let Some(process) = s.get_process(Pid::from(1337)) {
+    ///     if let Some(user) = process.user_id().and_then(|id| s.get_user_by_id(id)) {
+    ///         println!("User for process 1337: {}", user.get_name());
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    fn get_user_by_id(&self, user_id: Uid) -> Option<&User> {
+        self.get_users()
+            .iter()
+            .find(|user| user.get_uid() == user_id)
+    }
+
+    /// Returns the TCP and UDP sockets of the system, as of the last call to
+    /// [`SystemExt::refresh_sockets`].
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_sockets();
+    /// for socket in s.get_sockets() {
+    ///     println!("{:?}: {} ({:?})", socket.pid, socket.local_address, socket.state);
+    /// }
+    /// ```
+    #[cfg(feature = "process")]
+    fn get_sockets(&self) -> &[SocketInfo];
+
+    /// Returns the PID of the process listening on the given local `port`, as of the last call
+    /// to [`SystemExt::refresh_sockets`]. For UDP, any socket bound to this port is considered to
+    /// be listening.
+    ///
+    /// `None` is returned if no socket is listening on this port or if its owner couldn't be
+    /// found (like when it belongs to another user).
+    ///
+    /// ```no_run
+    /// use sysinfo::{SocketProtocol, System, SystemExt};
+    ///
+    /// let mut s = System::new();
+    /// s.refresh_sockets();
+    /// if let Some(pid) = s.get_process_by_port(8080, SocketProtocol::Tcp) {
+    ///     println!("{} is listening on port 8080", pid);
+    /// }
+    /// ```
+    #[cfg(feature = "process")]
+    fn get_process_by_port(&self, port: u16, protocol: SocketProtocol) -> Option<Pid> {
+        self.get_sockets()
+            .iter()
+            .filter(|socket| {
+                socket.protocol == protocol
+                    && socket.local_address.port() == port
+                    && (protocol == SocketProtocol::Udp
+                        || socket.state == Some(SocketState::Listen))
+            })
+            .find_map(|socket| socket.pid)
+    }
+
+    /// Returns the disks list.
+    ///
+    /// ```no_run
+    /// use sysinfo::{DiskExt, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// for disk in s.get_disks_mut() {
+    ///     disk.refresh();
+    /// }
+    /// ```
+    #[cfg(feature = "disk")]
+    fn get_disks_mut(&mut self) -> &mut [Disk];
+
+    /// Returns every mounted file system.
+    ///
+    /// Unlike [`SystemExt::get_disks`], which only lists the file systems backed by a storage
+    /// device, pseudo file systems (like `proc`, `sysfs` or `tmpfs`) are included and the filter
+    /// set with [`SystemExt::set_disk_filter`] isn't applied. The list is retrieved on each call:
+    ///
+    /// * On Linux, it's read from `/proc/mounts`.
+    /// * On macOS, it's retrieved with `getfsstat`.
+    /// * On Windows, it contains the volumes mounted on a drive letter.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// for mount in s.get_all_mounts() {
+    ///     println!("{:?}: {}", mount.mount_point, mount.file_system);
+    /// }
+    /// ```
+    #[cfg(feature = "disk")]
+    fn get_all_mounts(&self) -> Vec<Mount>;
+
+    /// Returns the network interfaces object.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// let networks = s.get_networks();
+    /// for (interface_name, data) in networks {
+    ///     println!(
+    ///         "[{}] in: {}, out: {}",
+    ///         interface_name,
+    ///         data.get_received(),
+    ///         data.get_transmitted(),
+    ///     );
+    /// }
+    /// ```
+    #[cfg(feature = "network")]
+    fn get_networks(&self) -> &Networks;
+
+    /// Returns a mutable access to network interfaces.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    ///
+    /// let mut s = System::new_all();
+    /// let networks = s.get_networks_mut();
+    /// networks.refresh_networks_list();
+    /// ```
+    #[cfg(feature = "network")]
+    fn get_networks_mut(&mut self) -> &mut Networks;
+
+    /// Returns system uptime (in seconds).
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// println!("System running since {} seconds", s.get_uptime());
+    /// ```
+    fn get_uptime(&self) -> u64;
+
+    /// Returns the time (in seconds) when the system booted since UNIX epoch.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new();
+    /// println!("System booted at {} seconds", s.get_boot_time());
+    /// ```
+    fn get_boot_time(&self) -> u64;
+
+    /// Returns the system load average value.
+    ///
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
+    ///
+    /// let s = System::new_all();
+    /// let load_avg = s.get_load_average();
+    /// println!(
+    ///     "one minute: {}%, five minutes: {}%, fifteen minutes: {}%",
+    ///     load_avg.one,
+    ///     load_avg.five,
+    ///     load_avg.fifteen,
+    /// );
     /// ```
-    fn new_with_specifics(refreshes: RefreshKind) -> Self;
+    fn get_load_average(&self) -> LoadAvg;
 
-    /// Refreshes according to the given [`RefreshKind`]. It calls the corresponding
-    /// "refresh_" methods.
+    /// Returns the battery information or `None` if the system doesn't have a battery.
     ///
-    /// ```
-    /// use sysinfo::{RefreshKind, System, SystemExt};
+    /// **Important**: this information is computed every time this function is called.
     ///
-    /// let mut s = System::new_all();
+    /// ```no_run
+    /// use sysinfo::{System, SystemExt};
     ///
-    /// // Let's just update networks and processes:
-    /// s.refresh_specifics(RefreshKind::new().with_networks().with_processes());
+    /// let s = System::new();
+    /// match s.get_battery() {
+    ///     Some(battery) => println!("battery: {}%", battery.percentage),
+    ///     None => println!("no battery"),
+    /// }
     /// ```
-    fn refresh_specifics(&mut self, refreshes: RefreshKind) {
-        if refreshes.memory() {
-            self.refresh_memory();
-        }
-        if refreshes.cpu() {
-            self.refresh_cpu();
-        }
-        if refreshes.components_list() {
-            self.refresh_components_list();
-        } else if refreshes.components() {
-            self.refresh_components();
-        }
-        if refreshes.networks_list() {
-            self.refresh_networks_list();
-        } else if refreshes.networks() {
-            self.refresh_networks();
-        }
-        if refreshes.processes() {
-            self.refresh_processes();
-        }
-        if refreshes.disks_list() {
-            self.refresh_disks_list();
-        } else if refreshes.disks() {
-            self.refresh_disks();
-        }
-        if refreshes.users_list() {
-            self.refresh_users_list();
-        }
-    }
+    fn get_battery(&self) -> Option<Battery>;
 
-    /// Refreshes system information (RAM, swap, CPU usage and components' temperature).
-    ///
-    /// If you want some more specific refreshes, you might be interested into looking at
-    /// [`refresh_memory`], [`refresh_cpu`] and [`refresh_components`].
+    /// Returns the system name.
     ///
-    /// [`refresh_memory`]: SystemExt::refresh_memory
-    /// [`refresh_cpu`]: SystemExt::refresh_memory
-    /// [`refresh_components`]: SystemExt::refresh_components
+    /// **Important**: this information is computed every time this function is called.
     ///
     /// ```no_run
     /// use sysinfo::{System, SystemExt};
     ///
-    /// let mut s = System::new_all();
-    /// s.refresh_system();
+    /// let s = System::new();
+    /// println!("OS: {:?}", s.get_name());
     /// ```
-    fn refresh_system(&mut self) {
-        self.refresh_memory();
-        self.refresh_cpu();
-        self.refresh_components();
-    }
+    fn get_name(&self) -> Option<String>;
 
-    /// Refreshes RAM and SWAP usage.
+    /// Returns the system's kernel version.
+    ///
+    /// **Important**: this information is computed every time this function is called.
     ///
     /// ```no_run
     /// use sysinfo::{System, SystemExt};
     ///
-    /// let mut s = System::new_all();
-    /// s.refresh_memory();
+    /// let s = System::new();
+    /// println!("kernel version: {:?}", s.get_kernel_version());
     /// ```
-    fn refresh_memory(&mut self);
+    fn get_kernel_version(&self) -> Option<String>;
 
-    /// Refreshes CPU usage.
+    /// Returns the system version (e.g. for MacOS this will return 11.1 rather than the kernel version).
+    ///
+    /// **Important**: this information is computed every time this function is called.
     ///
     /// ```no_run
     /// use sysinfo::{System, SystemExt};
     ///
-    /// let mut s = System::new_all();
-    /// s.refresh_cpu();
+    /// let s = System::new();
+    /// println!("OS version: {:?}", s.get_os_version());
     /// ```
-    fn refresh_cpu(&mut self);
+    fn get_os_version(&self) -> Option<String>;
 
-    /// Refreshes components' temperature.
+    /// Returns the system long os version (e.g "MacOS 11.2 BigSur").
+    ///
+    /// **Important**: this information is computed every time this function is called.
     ///
     /// ```no_run
     /// use sysinfo::{System, SystemExt};
     ///
-    /// let mut s = System::new_all();
-    /// s.refresh_components();
+    /// let s = System::new();
+    /// println!("Long OS Version: {:?}", s.get_long_os_version());
     /// ```
-    fn refresh_components(&mut self) {
-        for component in self.get_components_mut() {
-            component.refresh();
-        }
-    }
+    fn get_long_os_version(&self) -> Option<String>;
 
-    /// Refreshes components list.
+    /// Returns the system hostname based off DNS
+    ///
+    /// **Important**: this information is computed every time this function is called.
     ///
     /// ```no_run
     /// use sysinfo::{System, SystemExt};
     ///
-    /// let mut s = System::new();
-    /// s.refresh_components_list();
+    /// let s = System::new();
+    /// println!("Hostname: {:?}", s.get_host_name());
     /// ```
-    fn refresh_components_list(&mut self);
+    fn get_host_name(&self) -> Option<String>;
 
-    /// Gets all processes and updates their information.
+    /// Returns the architecture of the host CPU (e.g. "x86_64" or "aarch64").
+    ///
+    /// Unlike the architecture the program was compiled for, this is the one of the machine it
+    /// runs on, so it can differ when running under emulation.
+    ///
+    /// **Important**: this information is computed every time this function is called.
     ///
     /// ```no_run
     /// use sysinfo::{System, SystemExt};
     ///
-    /// let mut s = System::new_all();
-    /// s.refresh_processes();
+    /// let s = System::new();
+    /// println!("CPU architecture: {:?}", s.get_cpu_arch());
     /// ```
-    fn refresh_processes(&mut self);
+    #[cfg(feature = "cpu")]
+    fn get_cpu_arch(&self) -> Option<String>;
 
-    /// Refreshes *only* the process corresponding to `pid`. Returns `false` if the process doesn't
-    /// exist. If it isn't listed yet, it'll be added.
+    /// Returns the instruction set extensions supported by the host CPU (e.g. "sse2" or
+    /// "avx2" on x86, "neon" on ARM).
+    ///
+    /// The names follow the ones used by the platform, so they are not the same on every
+    /// architecture. An empty list is returned if they cannot be retrieved.
+    ///
+    /// **Important**: this information is computed every time this function is called.
     ///
     /// ```no_run
     /// use sysinfo::{System, SystemExt};
     ///
-    /// let mut s = System::new_all();
-    /// s.refresh_process(1337);
+    /// let s = System::new();
+    /// if s.get_cpu_features().iter().any(|f| f == "avx2") {
+    ///     println!("AVX2 is supported");
+    /// }
     /// ```
-    fn refresh_process(&mut self, pid: Pid) -> bool;
+    #[cfg(feature = "cpu")]
+    fn get_cpu_features(&self) -> Vec<String>;
 
-    /// Refreshes the listed disks' information.
+    /// Returns the caches of the first CPU core, from the smallest level to the biggest one. The
+    /// caches shared between cores are listed as well.
+    ///
+    /// An empty list is returned if they cannot be retrieved.
+    ///
+    /// **Important**: this information is computed every time this function is called.
     ///
     /// ```no_run
     /// use sysinfo::{System, SystemExt};
     ///
-    /// let mut s = System::new_all();
-    /// s.refresh_disks();
+    /// let s = System::new();
+    /// for cache in s.get_cpu_caches() {
+    ///     println!("L{} {:?}: {} KiB", cache.level, cache.type_, cache.size / 1024);
+    /// }
     /// ```
-    fn refresh_disks(&mut self) {
-        for disk in self.get_disks_mut() {
-            disk.refresh();
-        }
-    }
+    #[cfg(feature = "cpu")]
+    fn get_cpu_caches(&self) -> Vec<CacheInfo>;
 
-    /// The disk list will be emptied then completely recomputed.
+    /// Returns the virtualization technology or the container runtime the system runs in, or
+    /// [`Virtualization::None`][crate::Virtualization::None] if none was detected. Containers
+    /// are checked first, so a Docker container running in a KVM guest is reported as
+    /// [`Virtualization::Docker`][crate::Virtualization::Docker].
+    ///
+    /// On Linux, it looks at the container marker files, `/proc/1/cgroup`, the DMI information
+    /// and `cpuid`. On Windows, at the BIOS information stored in the registry and `cpuid`. On
+    /// macOS, at the `kern.hv_vmm_present` sysctl.
+    ///
+    /// **Important**: this information is computed every time this function is called.
     ///
     /// ```no_run
     /// use sysinfo::{System, SystemExt};
     ///
-    /// let mut s = System::new_all();
-    /// s.refresh_disks_list();
+    /// let s = System::new();
+    /// println!("virtualization: {:?}", s.get_virtualization());
+    /// ```
+    fn get_virtualization(&self) -> Virtualization;
+}
+
+/// Getting volume of received and transmitted data.
+#[cfg(feature = "network")]
+pub trait NetworkExt: Debug {
+    /// Returns the number of received bytes since the last refresh.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("in: {} B", network.get_received());
+    /// }
+    /// ```
+    fn get_received(&self) -> u64;
+
+    /// Returns the total number of received bytes.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("in: {} B", network.get_total_received());
+    /// }
+    /// ```
+    fn get_total_received(&self) -> u64;
+
+    /// Returns the number of transmitted bytes since the last refresh.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("out: {} B", network.get_transmitted());
+    /// }
+    /// ```
+    fn get_transmitted(&self) -> u64;
+
+    /// Returns the total number of transmitted bytes.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("out: {} B", network.get_total_transmitted());
+    /// }
+    /// ```
+    fn get_total_transmitted(&self) -> u64;
+
+    /// Returns the number of incoming packets since the last refresh.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("in: {}", network.get_packets_received());
+    /// }
+    /// ```
+    fn get_packets_received(&self) -> u64;
+
+    /// Returns the total number of incoming packets.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("in: {}", network.get_total_packets_received());
+    /// }
+    /// ```
+    fn get_total_packets_received(&self) -> u64;
+
+    /// Returns the number of outcoming packets since the last refresh.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("out: {}", network.get_packets_transmitted());
+    /// }
+    /// ```
+    fn get_packets_transmitted(&self) -> u64;
+
+    /// Returns the total number of outcoming packets.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("out: {}", network.get_total_packets_transmitted());
+    /// }
+    /// ```
+    fn get_total_packets_transmitted(&self) -> u64;
+
+    /// Returns the number of incoming errors since the last refresh.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("in: {}", network.get_errors_on_received());
+    /// }
+    /// ```
+    fn get_errors_on_received(&self) -> u64;
+
+    /// Returns the total number of incoming errors.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("in: {}", network.get_total_errors_on_received());
+    /// }
+    /// ```
+    fn get_total_errors_on_received(&self) -> u64;
+
+    /// Returns the number of outcoming errors since the last refresh.
+    ///
+    /// ```no_run
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("out: {}", network.get_errors_on_transmitted());
+    /// }
     /// ```
-    fn refresh_disks_list(&mut self);
+    fn get_errors_on_transmitted(&self) -> u64;
 
-    /// Refreshes users list.
+    /// Returns the total number of outcoming errors.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let mut s = System::new_all();
-    /// s.refresh_users_list();
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("out: {}", network.get_total_errors_on_transmitted());
+    /// }
     /// ```
-    fn refresh_users_list(&mut self);
+    fn get_total_errors_on_transmitted(&self) -> u64;
 
-    /// Refreshes networks data.
+    /// Returns the number of incoming packets dropped since the last refresh.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let mut s = System::new_all();
-    /// s.refresh_networks();
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("in: {}", network.get_drops_on_received());
+    /// }
     /// ```
-    ///
-    /// It is a shortcut for:
+    fn get_drops_on_received(&self) -> u64;
+
+    /// Returns the total number of incoming packets dropped.
     ///
     /// ```no_run
-    /// use sysinfo::{NetworksExt, System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let mut s = System::new_all();
-    /// let networks = s.get_networks_mut();
-    /// networks.refresh();
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("in: {}", network.get_total_drops_on_received());
+    /// }
     /// ```
-    fn refresh_networks(&mut self) {
-        self.get_networks_mut().refresh();
-    }
+    fn get_total_drops_on_received(&self) -> u64;
 
-    /// The network list will be updated: removing not existing anymore interfaces and adding new
-    /// ones.
+    /// Returns the number of outgoing packets dropped since the last refresh.
+    ///
+    /// It's always 0 on macOS since this counter isn't available.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let mut s = System::new_all();
-    /// s.refresh_networks_list();
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("out: {}", network.get_drops_on_transmitted());
+    /// }
     /// ```
+    fn get_drops_on_transmitted(&self) -> u64;
+
+    /// Returns the total number of outgoing packets dropped.
     ///
-    /// This is a shortcut for:
+    /// It's always 0 on macOS since this counter isn't available.
     ///
     /// ```no_run
-    /// use sysinfo::{NetworksExt, System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let mut s = System::new_all();
-    /// let networks = s.get_networks_mut();
+    /// let mut networks = Networks::new();
     /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("out: {}", network.get_total_drops_on_transmitted());
+    /// }
     /// ```
-    fn refresh_networks_list(&mut self) {
-        self.get_networks_mut().refresh_networks_list();
-    }
+    fn get_total_drops_on_transmitted(&self) -> u64;
 
-    /// Refreshes all system, processes, disks and network interfaces information.
+    /// Returns the number of incoming multicast packets since last refresh.
     ///
-    /// Please note that it doesn't recompute disks list, components list, network interfaces
-    /// list nor users list.
+    /// On Windows, there is no dedicated multicast counter so the non-unicast one is used: it
+    /// also counts the broadcast packets.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let mut s = System::new_all();
-    /// s.refresh_all();
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("in: {}", network.get_packets_received_multicast());
+    /// }
     /// ```
-    fn refresh_all(&mut self) {
-        self.refresh_system();
-        self.refresh_processes();
-        self.refresh_disks();
-        self.refresh_networks();
-    }
+    fn get_packets_received_multicast(&self) -> u64;
 
-    /// Returns the process list.
+    /// Returns the total number of incoming multicast packets.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// for (pid, process) in s.get_processes() {
-    ///     println!("{} {}", pid, process.name());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("in: {}", network.get_total_packets_received_multicast());
     /// }
     /// ```
-    fn get_processes(&self) -> &HashMap<Pid, Process>;
+    fn get_total_packets_received_multicast(&self) -> u64;
 
-    /// Returns the process corresponding to the given pid or `None` if no such process exists.
+    /// Returns the number of incoming bytes since the last refresh which weren't part of IPv6
+    /// packets, meaning [`get_received`][NetworkExt::get_received] minus
+    /// [`get_received_ipv6`][NetworkExt::get_received_ipv6].
+    ///
+    /// Only Linux counts IPv6 traffic separately. On other platforms, this is the same value as
+    /// [`get_received`][NetworkExt::get_received].
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// if let Some(process) = s.get_process(1337) {
-    ///     println!("{}", process.name());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("in (IPv4): {} B", network.get_received_ipv4());
     /// }
     /// ```
-    fn get_process(&self, pid: Pid) -> Option<&Process>;
+    fn get_received_ipv4(&self) -> u64;
 
-    /// Returns a list of process containing the given `name`.
+    /// Returns the number of incoming bytes in IPv6 packets since the last refresh.
+    ///
+    /// Only Linux counts IPv6 traffic separately (in `/proc/net/dev_snmp6`). On other platforms,
+    /// it always returns 0.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessExt, System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// for process in s.get_process_by_name("htop") {
-    ///     println!("{} {}", process.pid(), process.name());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("in (IPv6): {} B", network.get_received_ipv6());
     /// }
     /// ```
-    fn get_process_by_name(&self, name: &str) -> Vec<&Process> {
-        let mut ret = vec![];
-        for val in self.get_processes().values() {
-            if val.name().contains(name) {
-                ret.push(val);
-            }
-        }
-        ret
-    }
+    fn get_received_ipv6(&self) -> u64;
 
-    /// Returns "global" processors information (aka the addition of all the processors).
+    /// Returns the signal strength (in dBm) of the wireless interface. Returns `None` if this
+    /// isn't a wireless interface or if it isn't connected.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let s = System::new();
-    /// println!("{}%", s.get_global_processor_info().get_cpu_usage());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     if let Some(signal) = network.get_wifi_signal() {
+    ///         println!("{}: {} dBm", interface_name, signal);
+    ///     }
+    /// }
     /// ```
-    fn get_global_processor_info(&self) -> &Processor;
+    fn get_wifi_signal(&self) -> Option<i32>;
 
-    /// Returns the list of the processors.
+    /// Returns the type of the network interface.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let s = System::new();
-    /// for processor in s.get_processors() {
-    ///     println!("{}%", processor.get_cpu_usage());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("{}: {:?}", interface_name, network.get_interface_type());
     /// }
     /// ```
-    fn get_processors(&self) -> &[Processor];
+    fn get_interface_type(&self) -> InterfaceType;
 
-    /// Returns the number of physical cores on the processor or `None` if it couldn't get it.
+    /// Returns `true` if this interface is backed by a hardware adapter, `false` for the
+    /// software ones (loopback, tunnels, bridges, virtual machines adapters...).
     ///
-    /// In case there are multiple CPUs, it will combine the physical core count of all the CPUs.
+    /// This doesn't depend on whether the interface is listed or not: on Windows for example,
+    /// the interfaces listed are the ones which look interesting, which isn't the same thing.
     ///
-    /// **Important**: this information is computed every time this function is called.
+    /// * On Linux, an interface is physical if it has a `device` entry in sysfs. When the
+    ///   interfaces are read from `/proc/net/dev` (network namespaces), this is always `false`.
+    /// * On macOS, it's computed from the interface type and name since there is no dedicated
+    ///   flag.
+    /// * On Windows, it's the `HardwareInterface` flag of the interface.
     ///
     /// ```no_run
-    /// use sysinfo::{ProcessorExt, System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let s = System::new();
-    /// println!("{:?}", s.get_physical_core_count());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("{}: physical: {}", interface_name, network.is_physical());
+    /// }
     /// ```
-    fn get_physical_core_count(&self) -> Option<usize>;
+    fn is_physical(&self) -> bool;
 
-    /// Returns the RAM size in kB.
+    /// Returns which bytes are counted by the received and transmitted counters of this
+    /// interface, to be able to compare them between interfaces and platforms.
+    ///
+    /// On Linux, macOS and Windows, the counters include the link-layer header of the frames,
+    /// except for tunnel interfaces which don't have any.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// println!("{} kB", s.get_total_memory());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("{}: {:?}", interface_name, network.get_counting_basis());
+    /// }
     /// ```
-    fn get_total_memory(&self) -> u64;
+    fn get_counting_basis(&self) -> CountingBasis;
 
-    /// Returns the amount of free RAM in kB.
+    /// Returns the administrative status of the interface, in other words whether it was enabled
+    /// or disabled (by the user or the system).
     ///
-    /// Generally, "free" memory refers to unallocated memory whereas "available" memory refers to
-    /// memory that is available for (re)use.
+    /// * On Linux, it's the `IFF_UP` flag of `/sys/class/net/[interface]/flags`.
+    /// * On macOS, it's the `IFF_UP` flag of the interface.
+    /// * On Windows, it's the `AdminStatus` of the interface.
     ///
-    /// Side note: Windows doesn't report "free" memory so this method returns the same value
-    /// as [`get_available_memory`](#tymethod.get_available_memory).
+    /// For network namespaces on Linux, it's always [`InterfaceStatus::Unknown`].
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// println!("{} kB", s.get_free_memory());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("{}: {:?}", interface_name, network.get_admin_status());
+    /// }
     /// ```
-    fn get_free_memory(&self) -> u64;
+    fn get_admin_status(&self) -> InterfaceStatus;
 
-    /// Returns the amount of available RAM in kB.
+    /// Returns the operational status of the interface, in other words whether it can actually
+    /// send and receive packets. An interface can be administratively up but operationally down,
+    /// if its cable is unplugged for example.
     ///
-    /// Generally, "free" memory refers to unallocated memory whereas "available" memory refers to
-    /// memory that is available for (re)use.
+    /// * On Linux, it's read from `/sys/class/net/[interface]/operstate`. Some drivers (like the
+    ///   loopback one) don't report it, in which case the interface is considered up if it's
+    ///   administratively up and has a carrier.
+    /// * On macOS, the interface is up if it has both the `IFF_UP` and `IFF_RUNNING` flags.
+    /// * On Windows, it's the `OperStatus` of the interface.
     ///
-    /// Side note: Windows doesn't report "free" memory so
-    /// [`get_free_memory`](#tymethod.get_free_memory) returns the same value as this method.
+    /// For network namespaces on Linux, it's always [`InterfaceStatus::Unknown`].
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// println!("{} kB", s.get_available_memory());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("{}: {:?}", interface_name, network.get_oper_status());
+    /// }
     /// ```
-    fn get_available_memory(&self) -> u64;
+    fn get_oper_status(&self) -> InterfaceStatus;
 
-    /// Returns the amound of used RAM in kB.
+    /// Returns the number of received bytes between each of the last refreshes, from the oldest
+    /// to the most recent one. It is empty unless [`NetworksExt::set_history_len`] was called.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// println!("{} kB", s.get_used_memory());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// networks.set_history_len(10);
+    /// networks.refresh();
+    /// for (interface_name, network) in &networks {
+    ///     println!("{}: {:?}", interface_name, network.get_received_history());
+    /// }
     /// ```
-    fn get_used_memory(&self) -> u64;
+    fn get_received_history(&self) -> &VecDeque<u64>;
 
-    /// Returns the SWAP size in kB.
+    /// Returns the number of transmitted bytes between each of the last refreshes, from the
+    /// oldest to the most recent one. It is empty unless [`NetworksExt::set_history_len`] was
+    /// called.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// println!("{} kB", s.get_total_swap());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// networks.set_history_len(10);
+    /// networks.refresh();
+    /// for (interface_name, network) in &networks {
+    ///     println!("{}: {:?}", interface_name, network.get_transmitted_history());
+    /// }
     /// ```
-    fn get_total_swap(&self) -> u64;
+    fn get_transmitted_history(&self) -> &VecDeque<u64>;
 
-    /// Returns the amount of free SWAP in kB.
+    /// Returns when the counters of this interface were last updated.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// println!("{} kB", s.get_free_swap());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("{}: {:?}", interface_name, network.get_last_refreshed().elapsed());
+    /// }
     /// ```
-    fn get_free_swap(&self) -> u64;
+    fn get_last_refreshed(&self) -> Instant;
 
-    /// Returns the amount of used SWAP in kB.
+    /// Returns the number of received bytes per second, computed with the time elapsed between
+    /// the two last updates of the counters. It returns 0 until the counters were updated twice.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// println!("{} kB", s.get_used_swap());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// std::thread::sleep(std::time::Duration::from_secs(1));
+    /// networks.refresh();
+    /// for (interface_name, network) in &networks {
+    ///     println!("{}: {:.0} B/s", interface_name, network.get_received_per_second());
+    /// }
     /// ```
-    fn get_used_swap(&self) -> u64;
+    fn get_received_per_second(&self) -> f64;
 
-    /// Returns the components list.
+    /// Returns the number of transmitted bytes per second, computed with the time elapsed
+    /// between the two last updates of the counters. It returns 0 until the counters were
+    /// updated twice.
     ///
     /// ```no_run
-    /// use sysinfo::{ComponentExt, System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// for component in s.get_components() {
-    ///     println!("{}: {}°C", component.get_label(), component.get_temperature());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// std::thread::sleep(std::time::Duration::from_secs(1));
+    /// networks.refresh();
+    /// for (interface_name, network) in &networks {
+    ///     println!("{}: {:.0} B/s", interface_name, network.get_transmitted_per_second());
     /// }
     /// ```
-    fn get_components(&self) -> &[Component];
+    fn get_transmitted_per_second(&self) -> f64;
 
-    /// Returns a mutable components list.
+    /// Returns a snapshot of the counters of the network interface.
     ///
     /// ```no_run
-    /// use sysinfo::{ComponentExt, System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let mut s = System::new_all();
-    /// for component in s.get_components_mut() {
-    ///     component.refresh();
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("{}: {:?}", interface_name, network.get_stats());
     /// }
     /// ```
-    fn get_components_mut(&mut self) -> &mut [Component];
+    fn get_stats(&self) -> NetworkStats {
+        NetworkStats {
+            received: self.get_received(),
+            total_received: self.get_total_received(),
+            transmitted: self.get_transmitted(),
+            total_transmitted: self.get_total_transmitted(),
+            packets_received: self.get_packets_received(),
+            total_packets_received: self.get_total_packets_received(),
+            packets_transmitted: self.get_packets_transmitted(),
+            total_packets_transmitted: self.get_total_packets_transmitted(),
+            errors_on_received: self.get_errors_on_received(),
+            total_errors_on_received: self.get_total_errors_on_received(),
+            errors_on_transmitted: self.get_errors_on_transmitted(),
+            total_errors_on_transmitted: self.get_total_errors_on_transmitted(),
+            drops_on_received: self.get_drops_on_received(),
+            total_drops_on_received: self.get_total_drops_on_received(),
+            drops_on_transmitted: self.get_drops_on_transmitted(),
+            total_drops_on_transmitted: self.get_total_drops_on_transmitted(),
+            packets_received_multicast: self.get_packets_received_multicast(),
+            total_packets_received_multicast: self.get_total_packets_received_multicast(),
+        }
+    }
+}
 
-    /// Returns the disks list.
+/// Interacting with network interfaces.
+#[cfg(feature = "network")]
+pub trait NetworksExt: Debug {
+    /// Returns an iterator over the network interfaces.
     ///
     /// ```no_run
-    /// use sysinfo::{DiskExt, System, SystemExt};
+    /// use sysinfo::{NetworkExt, Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// for disk in s.get_disks() {
-    ///     println!("{:?}", disk.get_name());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("in: {} B", network.get_received());
     /// }
     /// ```
-    fn get_disks(&self) -> &[Disk];
+    fn iter(&self) -> NetworksIter;
 
-    /// Returns the users list.
+    /// Returns an iterator over the names of the network interfaces.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt, UserExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let mut s = System::new_all();
-    /// for user in s.get_users() {
-    ///     println!("{} is in {} groups", user.get_name(), user.get_groups().len());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for interface_name in networks.get_interface_names() {
+    ///     println!("{}", interface_name);
     /// }
     /// ```
-    fn get_users(&self) -> &[User];
+    fn get_interface_names<'a>(&'a self) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+        Box::new(self.iter().map(|(name, _)| name.as_str()))
+    }
 
-    /// Returns the disks list.
+    /// Refreshes the network interfaces list.
     ///
     /// ```no_run
-    /// use sysinfo::{DiskExt, System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let mut s = System::new_all();
-    /// for disk in s.get_disks_mut() {
-    ///     disk.refresh();
-    /// }
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// networks.refresh_networks_list();
     /// ```
-    fn get_disks_mut(&mut self) -> &mut [Disk];
+    fn refresh_networks_list(&mut self) {
+        let _ = self.try_refresh_networks_list();
+    }
 
-    /// Returns the network interfaces object.
+    /// Refreshes the network interfaces list. If the list cannot be retrieved, an error is
+    /// returned and the previously listed interfaces are kept.
     ///
     /// ```no_run
-    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// let networks = s.get_networks();
-    /// for (interface_name, data) in networks {
-    ///     println!(
-    ///         "[{}] in: {}, out: {}",
-    ///         interface_name,
-    ///         data.get_received(),
-    ///         data.get_transmitted(),
-    ///     );
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// if let Err(e) = networks.try_refresh_networks_list() {
+    ///     eprintln!("{}", e);
     /// }
     /// ```
-    fn get_networks(&self) -> &Networks;
+    fn try_refresh_networks_list(&mut self) -> Result<(), RefreshError>;
 
-    /// Returns a mutable access to network interfaces.
+    /// Refreshes the network interfaces list like [`NetworksExt::refresh_networks_list`] but
+    /// without removing the interfaces which aren't listed anymore. New interfaces are added and
+    /// the counters of the existing ones are updated.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// networks.refresh_networks_list_no_prune();
+    /// ```
+    fn refresh_networks_list_no_prune(&mut self);
+
+    /// Refreshes the network interfaces' content.
+    ///
+    /// Only the interfaces which are already listed are updated: new interfaces aren't added
+    /// and the ones which are gone aren't removed. Use
+    /// [`NetworksExt::refresh_networks_list`] to update the list itself.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// networks.refresh();
+    /// ```
+    fn refresh(&mut self);
+
+    /// Sets how many received/transmitted samples are kept for each network interface. A new
+    /// sample is recorded on each [`NetworksExt::refresh`] call. It is 0 (disabled) by default.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// networks.set_history_len(60);
+    /// ```
+    fn set_history_len(&mut self, len: usize);
+
+    /// Sets whether loopback interfaces are included in the sums returned by
+    /// [`NetworksExt::get_received`] and the other aggregate getters. They are skipped by default.
     ///
     /// ```no_run
-    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let mut s = System::new_all();
-    /// let networks = s.get_networks_mut();
+    /// let mut networks = Networks::new();
     /// networks.refresh_networks_list();
+    /// networks.set_include_loopback(true);
     /// ```
-    fn get_networks_mut(&mut self) -> &mut Networks;
+    fn set_include_loopback(&mut self, include_loopback: bool);
 
-    /// Returns system uptime (in seconds).
+    /// Returns `true` if loopback interfaces are included in the aggregate getters.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// println!("System running since {} seconds", s.get_uptime());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// println!("{}", networks.is_loopback_included());
     /// ```
-    fn get_uptime(&self) -> u64;
+    fn is_loopback_included(&self) -> bool;
 
-    /// Returns the time (in seconds) when the system booted since UNIX epoch.
+    /// Sets the minimum interval between two [`NetworksExt::refresh`] calls. If `refresh` is
+    /// called again before this interval elapsed, nothing is done and the values computed by the
+    /// previous refresh are kept. By default, there is no minimum interval.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
+    /// use std::time::Duration;
     ///
-    /// let s = System::new();
-    /// println!("System booted at {} seconds", s.get_boot_time());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// networks.set_min_refresh_interval(Duration::from_millis(500));
+    /// networks.refresh();
+    /// // Too soon, the previous values are kept.
+    /// networks.refresh();
     /// ```
-    fn get_boot_time(&self) -> u64;
+    fn set_min_refresh_interval(&mut self, interval: Duration);
 
-    /// Returns the system load average value.
+    /// Returns the IPv4 address of the default gateway, or `None` if there is no default route.
+    ///
+    /// The routing table is read at each call. On Linux, if there are several default routes,
+    /// the gateway of the one with the lowest metric is returned.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// let load_avg = s.get_load_average();
-    /// println!(
-    ///     "one minute: {}%, five minutes: {}%, fifteen minutes: {}%",
-    ///     load_avg.one,
-    ///     load_avg.five,
-    ///     load_avg.fifteen,
-    /// );
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// if let Some(gateway) = networks.get_default_gateway_v4() {
+    ///     println!("default gateway: {}", gateway);
+    /// }
     /// ```
-    fn get_load_average(&self) -> LoadAvg;
+    fn get_default_gateway_v4(&self) -> Option<Ipv4Addr>;
 
-    /// Returns the system name.
+    /// Returns the IPv6 address of the default gateway, or `None` if there is no default route.
     ///
-    /// **Important**: this information is computed every time this function is called.
+    /// It works the same as [`NetworksExt::get_default_gateway_v4`]. Note that it's often a
+    /// link-local address.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let s = System::new();
-    /// println!("OS: {:?}", s.get_name());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// if let Some(gateway) = networks.get_default_gateway_v6() {
+    ///     println!("default gateway: {}", gateway);
+    /// }
     /// ```
-    fn get_name(&self) -> Option<String>;
+    fn get_default_gateway_v6(&self) -> Option<Ipv6Addr>;
 
-    /// Returns the system's kernel version.
+    /// Returns the addresses of the DNS servers, in the order they're used.
     ///
-    /// **Important**: this information is computed every time this function is called.
+    /// * On Linux, they're read from `/etc/resolv.conf`, so if a local resolver (like
+    ///   `systemd-resolved`) is used, its address is returned.
+    /// * On macOS, they're read from the `SystemConfiguration` framework.
+    /// * On Windows, they're the ones of the network adapters which are up.
+    ///
+    /// It's always empty on iOS.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let s = System::new();
-    /// println!("kernel version: {:?}", s.get_kernel_version());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for server in networks.get_dns_servers() {
+    ///     println!("DNS server: {}", server);
+    /// }
     /// ```
-    fn get_kernel_version(&self) -> Option<String>;
+    fn get_dns_servers(&self) -> Vec<IpAddr>;
 
-    /// Returns the system version (e.g. for MacOS this will return 11.1 rather than the kernel version).
-    ///
-    /// **Important**: this information is computed every time this function is called.
+    /// Returns the number of received bytes since the last refresh, summed over all the network
+    /// interfaces. Loopback interfaces are skipped unless [`NetworksExt::set_include_loopback`] was
+    /// called.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let s = System::new();
-    /// println!("OS version: {:?}", s.get_os_version());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// println!("{} B", networks.get_received());
     /// ```
-    fn get_os_version(&self) -> Option<String>;
+    fn get_received(&self) -> u64 {
+        sum_networks(self, NetworkExt::get_received)
+    }
 
-    /// Returns the system long os version (e.g "MacOS 11.2 BigSur").
-    ///
-    /// **Important**: this information is computed every time this function is called.
+    /// Returns the total number of received bytes, summed over all the network interfaces. Loopback
+    /// interfaces are skipped unless [`NetworksExt::set_include_loopback`] was called.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let s = System::new();
-    /// println!("Long OS Version: {:?}", s.get_long_os_version());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// println!("{} B", networks.get_total_received());
     /// ```
-    fn get_long_os_version(&self) -> Option<String>;
+    fn get_total_received(&self) -> u64 {
+        sum_networks(self, NetworkExt::get_total_received)
+    }
 
-    /// Returns the system hostname based off DNS
-    ///
-    /// **Important**: this information is computed every time this function is called.
+    /// Returns the number of transmitted bytes since the last refresh, summed over all the network
+    /// interfaces. Loopback interfaces are skipped unless [`NetworksExt::set_include_loopback`] was
+    /// called.
     ///
     /// ```no_run
-    /// use sysinfo::{System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let s = System::new();
-    /// println!("Hostname: {:?}", s.get_host_name());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// println!("{} B", networks.get_transmitted());
     /// ```
-    fn get_host_name(&self) -> Option<String>;
-}
+    fn get_transmitted(&self) -> u64 {
+        sum_networks(self, NetworkExt::get_transmitted)
+    }
 
-/// Getting volume of received and transmitted data.
-pub trait NetworkExt: Debug {
-    /// Returns the number of received bytes since the last refresh.
+    /// Returns the total number of transmitted bytes, summed over all the network interfaces.
+    /// Loopback interfaces are skipped unless [`NetworksExt::set_include_loopback`] was called.
     ///
     /// ```no_run
-    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// let networks = s.get_networks();
-    /// for (interface_name, network) in networks {
-    ///     println!("in: {} B", network.get_received());
-    /// }
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// println!("{} B", networks.get_total_transmitted());
     /// ```
-    fn get_received(&self) -> u64;
+    fn get_total_transmitted(&self) -> u64 {
+        sum_networks(self, NetworkExt::get_total_transmitted)
+    }
 
-    /// Returns the total number of received bytes.
+    /// Returns the number of errors on incoming and outgoing packets since the last refresh, summed
+    /// over all the network interfaces. Loopback interfaces are skipped unless
+    /// [`NetworksExt::set_include_loopback`] was called.
     ///
     /// ```no_run
-    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// let networks = s.get_networks();
-    /// for (interface_name, network) in networks {
-    ///     println!("in: {} B", network.get_total_received());
-    /// }
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// println!("{} errors", networks.get_errors());
     /// ```
-    fn get_total_received(&self) -> u64;
+    fn get_errors(&self) -> u64 {
+        sum_networks(self, |data| {
+            data.get_errors_on_received() + data.get_errors_on_transmitted()
+        })
+    }
 
-    /// Returns the number of transmitted bytes since the last refresh.
+    /// Returns the total number of errors on incoming and outgoing packets, summed over all the
+    /// network interfaces. Loopback interfaces are skipped unless
+    /// [`NetworksExt::set_include_loopback`] was called.
     ///
     /// ```no_run
-    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// let networks = s.get_networks();
-    /// for (interface_name, network) in networks {
-    ///     println!("out: {} B", network.get_transmitted());
-    /// }
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// println!("{} errors", networks.get_total_errors());
     /// ```
-    fn get_transmitted(&self) -> u64;
+    fn get_total_errors(&self) -> u64 {
+        sum_networks(self, |data| {
+            data.get_total_errors_on_received() + data.get_total_errors_on_transmitted()
+        })
+    }
 
-    /// Returns the total number of transmitted bytes.
+    /// Returns the number of incoming and outgoing packets dropped since the last refresh, summed
+    /// over all the network interfaces. Loopback interfaces are skipped unless
+    /// [`NetworksExt::set_include_loopback`] was called.
     ///
     /// ```no_run
-    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// let networks = s.get_networks();
-    /// for (interface_name, network) in networks {
-    ///     println!("out: {} B", network.get_total_transmitted());
-    /// }
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// println!("{} dropped packets", networks.get_drops());
     /// ```
-    fn get_total_transmitted(&self) -> u64;
+    fn get_drops(&self) -> u64 {
+        sum_networks(self, |data| {
+            data.get_drops_on_received() + data.get_drops_on_transmitted()
+        })
+    }
 
-    /// Returns the number of incoming packets since the last refresh.
+    /// Returns the total number of incoming and outgoing packets dropped, summed over all the
+    /// network interfaces. Loopback interfaces are skipped unless
+    /// [`NetworksExt::set_include_loopback`] was called.
     ///
     /// ```no_run
-    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// let networks = s.get_networks();
-    /// for (interface_name, network) in networks {
-    ///     println!("in: {}", network.get_packets_received());
-    /// }
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// println!("{} dropped packets", networks.get_total_drops());
     /// ```
-    fn get_packets_received(&self) -> u64;
+    fn get_total_drops(&self) -> u64 {
+        sum_networks(self, |data| {
+            data.get_total_drops_on_received() + data.get_total_drops_on_transmitted()
+        })
+    }
 
-    /// Returns the total number of incoming packets.
+    /// Returns the changes between `previous` (an older clone of these network interfaces) and
+    /// `self`: the interfaces which appeared or disappeared and the counters which changed.
     ///
     /// ```no_run
-    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    /// use sysinfo::{Networks, NetworksExt};
     ///
-    /// let s = System::new_all();
-    /// let networks = s.get_networks();
-    /// for (interface_name, network) in networks {
-    ///     println!("in: {}", network.get_total_packets_received());
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// let previous = networks.clone();
+    /// networks.refresh();
+    /// for delta in networks.diff(&previous) {
+    ///     println!("{:?}", delta);
     /// }
     /// ```
-    fn get_total_packets_received(&self) -> u64;
+    fn diff(&self, previous: &Self) -> Vec<NetworkDelta>
+    where
+        Self: Sized,
+    {
+        let previous = previous.iter().collect::<HashMap<_, _>>();
+        let mut deltas = Vec::new();
+        for (name, data) in self.iter() {
+            let old = match previous.get(name) {
+                Some(old) => old,
+                None => {
+                    deltas.push(NetworkDelta::Added(name.clone()));
+                    continue;
+                }
+            };
+            let received = data
+                .get_total_received()
+                .saturating_sub(old.get_total_received());
+            let transmitted = data
+                .get_total_transmitted()
+                .saturating_sub(old.get_total_transmitted());
+            let packets_received = data
+                .get_total_packets_received()
+                .saturating_sub(old.get_total_packets_received());
+            let packets_transmitted = data
+                .get_total_packets_transmitted()
+                .saturating_sub(old.get_total_packets_transmitted());
+            if received != 0
+                || transmitted != 0
+                || packets_received != 0
+                || packets_transmitted != 0
+            {
+                deltas.push(NetworkDelta::Changed {
+                    name: name.clone(),
+                    received,
+                    transmitted,
+                    packets_received,
+                    packets_transmitted,
+                });
+            }
+        }
+        for name in previous.keys() {
+            if !self.iter().any(|(n, _)| n == *name) {
+                deltas.push(NetworkDelta::Removed((*name).clone()));
+            }
+        }
+        deltas
+    }
+}
 
-    /// Returns the number of outcoming packets since the last refresh.
+#[cfg(feature = "disk")]
+pub(crate) fn sum_disks(disks: &[Disk], getter: fn(&Disk) -> u64) -> u64 {
+    let mut devices = HashSet::new();
+    disks
+        .iter()
+        .filter(|disk| devices.insert(disk.get_device_name()))
+        .map(getter)
+        .sum()
+}
+
+#[cfg(feature = "network")]
+fn sum_networks<N: NetworksExt + ?Sized>(networks: &N, getter: fn(&NetworkData) -> u64) -> u64 {
+    let include_loopback = networks.is_loopback_included();
+    networks
+        .iter()
+        .filter(|(_, data)| {
+            include_loopback || data.get_interface_type() != InterfaceType::Loopback
+        })
+        .map(|(_, data)| getter(data))
+        .sum()
+}
+
+/// Getting a component temperature information.
+#[cfg(feature = "component")]
+pub trait ComponentExt: Debug {
+    /// Returns the temperature of the component (in celsius degree).
     ///
     /// ```no_run
-    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    /// use sysinfo::{ComponentExt, System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// let networks = s.get_networks();
-    /// for (interface_name, network) in networks {
-    ///     println!("out: {}", network.get_packets_transmitted());
+    /// for component in s.get_components() {
+    ///     println!("{}°C", component.get_temperature());
     /// }
     /// ```
-    fn get_packets_transmitted(&self) -> u64;
+    fn get_temperature(&self) -> f32;
 
-    /// Returns the total number of outcoming packets.
+    /// Returns the maximum temperature of the component (in celsius degree).
     ///
     /// ```no_run
-    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    /// use sysinfo::{ComponentExt, System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// let networks = s.get_networks();
-    /// for (interface_name, network) in networks {
-    ///     println!("out: {}", network.get_total_packets_transmitted());
+    /// for component in s.get_components() {
+    ///     println!("{}°C", component.get_max());
     /// }
     /// ```
-    fn get_total_packets_transmitted(&self) -> u64;
+    fn get_max(&self) -> f32;
 
-    /// Returns the number of incoming errors since the last refresh.
+    /// Returns the highest temperature before the component halts (in celsius degree).
     ///
     /// ```no_run
-    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    /// use sysinfo::{ComponentExt, System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// let networks = s.get_networks();
-    /// for (interface_name, network) in networks {
-    ///     println!("in: {}", network.get_errors_on_received());
+    /// for component in s.get_components() {
+    ///     println!("{:?}°C", component.get_critical());
     /// }
     /// ```
-    fn get_errors_on_received(&self) -> u64;
+    fn get_critical(&self) -> Option<f32>;
 
-    /// Returns the total number of incoming errors.
+    /// Returns the label of the component.
     ///
     /// ```no_run
-    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    /// use sysinfo::{ComponentExt, System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// let networks = s.get_networks();
-    /// for (interface_name, network) in networks {
-    ///     println!("in: {}", network.get_total_errors_on_received());
+    /// for component in s.get_components() {
+    ///     println!("{}", component.get_label());
     /// }
     /// ```
-    fn get_total_errors_on_received(&self) -> u64;
+    fn get_label(&self) -> &str;
 
-    /// Returns the number of outcoming errors since the last refresh.
+    /// Returns the identifier of the sensor, which is unique even when several components have
+    /// the same label: the temperature input file on Linux (like
+    /// `/sys/class/hwmon/hwmon1/temp2_input`) and the SMC key on macOS (like `TC0P`).
+    ///
+    /// It returns `None` on other platforms.
     ///
     /// ```no_run
-    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    /// use sysinfo::{ComponentExt, System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// let networks = s.get_networks();
-    /// for (interface_name, network) in networks {
-    ///     println!("out: {}", network.get_errors_on_transmitted());
+    /// for component in s.get_components() {
+    ///     println!("{}: {:?}", component.get_label(), component.get_device_path());
     /// }
     /// ```
-    fn get_errors_on_transmitted(&self) -> u64;
+    fn get_device_path(&self) -> Option<&Path>;
 
-    /// Returns the total number of outcoming errors.
+    /// Refreshes component.
     ///
     /// ```no_run
-    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    /// use sysinfo::{ComponentExt, System, SystemExt};
     ///
-    /// let s = System::new_all();
-    /// let networks = s.get_networks();
-    /// for (interface_name, network) in networks {
-    ///     println!("out: {}", network.get_total_errors_on_transmitted());
+    /// let mut s = System::new_all();
+    /// for component in s.get_components_mut() {
+    ///     component.refresh();
     /// }
     /// ```
-    fn get_total_errors_on_transmitted(&self) -> u64;
+    fn refresh(&mut self);
 }
 
-/// Interacting with network interfaces.
-pub trait NetworksExt: Debug {
-    /// Returns an iterator over the network interfaces.
+/// Getting a fan speed information.
+#[cfg(feature = "component")]
+pub trait FanExt: Debug {
+    /// Returns the speed of the fan (in revolutions per minute).
+    ///
+    /// It isn't available on Windows (WMI doesn't provide the measured speed) and always
+    /// returns `0`.
     ///
     /// ```no_run
-    /// use sysinfo::{NetworkExt, NetworksExt, System, SystemExt};
+    /// use sysinfo::{FanExt, System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// let networks = s.get_networks();
-    /// for (interface_name, network) in networks {
-    ///     println!("in: {} B", network.get_received());
+    /// for fan in s.get_fans() {
+    ///     println!("{} RPM", fan.get_rpm());
     /// }
     /// ```
-    fn iter(&self) -> NetworksIter;
+    fn get_rpm(&self) -> u32;
 
-    /// Refreshes the network interfaces list.
+    /// Returns the label of the fan.
     ///
     /// ```no_run
-    /// use sysinfo::{NetworksExt, System, SystemExt};
+    /// use sysinfo::{FanExt, System, SystemExt};
     ///
-    /// let mut s = System::new_all();
-    /// let networks = s.get_networks_mut();
-    /// networks.refresh_networks_list();
+    /// let s = System::new_all();
+    /// for fan in s.get_fans() {
+    ///     println!("{}", fan.get_label());
+    /// }
     /// ```
-    fn refresh_networks_list(&mut self);
+    fn get_label(&self) -> &str;
 
-    /// Refreshes the network interfaces' content.
+    /// Refreshes fan.
     ///
     /// ```no_run
-    /// use sysinfo::{NetworksExt, System, SystemExt};
+    /// use sysinfo::{FanExt, System, SystemExt};
     ///
     /// let mut s = System::new_all();
-    /// let networks = s.get_networks_mut();
-    /// networks.refresh();
+    /// for fan in s.get_fans_mut() {
+    ///     fan.refresh();
+    /// }
     /// ```
     fn refresh(&mut self);
 }
 
-/// Getting a component temperature information.
-pub trait ComponentExt: Debug {
-    /// Returns the temperature of the component (in celsius degree).
+/// Getting GPU information.
+#[cfg(feature = "system")]
+pub trait GpuExt: Debug {
+    /// Returns the name of the GPU.
     ///
     /// ```no_run
-    /// use sysinfo::{ComponentExt, System, SystemExt};
+    /// use sysinfo::{GpuExt, System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// for component in s.get_components() {
-    ///     println!("{}°C", component.get_temperature());
+    /// for gpu in s.get_gpus() {
+    ///     println!("{}", gpu.get_name());
     /// }
     /// ```
-    fn get_temperature(&self) -> f32;
+    fn get_name(&self) -> &str;
 
-    /// Returns the maximum temperature of the component (in celsius degree).
+    /// Returns the total amount of video memory (in kB).
+    ///
+    /// It returns `0` if the GPU doesn't have dedicated memory or if it isn't available.
     ///
     /// ```no_run
-    /// use sysinfo::{ComponentExt, System, SystemExt};
+    /// use sysinfo::{GpuExt, System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// for component in s.get_components() {
-    ///     println!("{}°C", component.get_max());
+    /// for gpu in s.get_gpus() {
+    ///     println!("{} kB", gpu.get_total_memory());
     /// }
     /// ```
-    fn get_max(&self) -> f32;
+    fn get_total_memory(&self) -> u64;
 
-    /// Returns the highest temperature before the component halts (in celsius degree).
+    /// Returns the amount of used video memory (in kB).
     ///
     /// ```no_run
-    /// use sysinfo::{ComponentExt, System, SystemExt};
+    /// use sysinfo::{GpuExt, System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// for component in s.get_components() {
-    ///     println!("{:?}°C", component.get_critical());
+    /// for gpu in s.get_gpus() {
+    ///     println!("{} kB", gpu.get_used_memory());
     /// }
     /// ```
-    fn get_critical(&self) -> Option<f32>;
+    fn get_used_memory(&self) -> u64;
 
-    /// Returns the label of the component.
+    /// Returns the GPU usage (in %).
+    ///
+    /// On Windows, it is computed between two refreshes so it returns `0.` until
+    /// [`GpuExt::refresh`] is called.
     ///
     /// ```no_run
-    /// use sysinfo::{ComponentExt, System, SystemExt};
+    /// use sysinfo::{GpuExt, System, SystemExt};
     ///
     /// let s = System::new_all();
-    /// for component in s.get_components() {
-    ///     println!("{}", component.get_label());
+    /// for gpu in s.get_gpus() {
+    ///     println!("{}%", gpu.get_usage());
     /// }
     /// ```
-    fn get_label(&self) -> &str;
+    fn get_usage(&self) -> f32;
 
-    /// Refreshes component.
+    /// Refreshes GPU memory and usage.
     ///
     /// ```no_run
-    /// use sysinfo::{ComponentExt, System, SystemExt};
+    /// use sysinfo::{GpuExt, System, SystemExt};
     ///
     /// let mut s = System::new_all();
-    /// for component in s.get_components_mut() {
-    ///     component.refresh();
+    /// for gpu in s.get_gpus_mut() {
+    ///     gpu.refresh();
     /// }
     /// ```
     fn refresh(&mut self);
@@ -1292,6 +3734,7 @@ pub trait ComponentExt: Debug {
 ///     println!("{} is in {} groups", user.get_name(), user.get_groups().len());
 /// }
 /// ```
+#[cfg(feature = "system")]
 pub trait UserExt: Debug {
     /// Return the user id of the user.
     ///
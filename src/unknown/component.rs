@@ -6,6 +6,8 @@
 
 use crate::ComponentExt;
 
+use std::path::Path;
+
 /// Dummy struct representing a component.
 pub struct Component {}
 
@@ -26,5 +28,9 @@ impl ComponentExt for Component {
         ""
     }
 
+    fn get_device_path(&self) -> Option<&Path> {
+        None
+    }
+
     fn refresh(&mut self) {}
 }
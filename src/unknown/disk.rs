@@ -20,6 +20,10 @@ impl DiskExt for Disk {
         unreachable!()
     }
 
+    fn get_device_name(&self) -> &OsStr {
+        OsStr::new("")
+    }
+
     fn get_file_system(&self) -> &[u8] {
         &[]
     }
@@ -36,6 +40,42 @@ impl DiskExt for Disk {
         0
     }
 
+    fn is_read_only(&self) -> bool {
+        false
+    }
+
+    fn is_removable(&self) -> bool {
+        false
+    }
+
+    fn get_mount_options(&self) -> &[String] {
+        &[]
+    }
+
+    fn get_read_count(&self) -> u64 {
+        0
+    }
+
+    fn get_total_read_count(&self) -> u64 {
+        0
+    }
+
+    fn get_write_count(&self) -> u64 {
+        0
+    }
+
+    fn get_total_write_count(&self) -> u64 {
+        0
+    }
+
+    fn get_io_utilization(&self) -> f32 {
+        0.
+    }
+
+    fn get_queue_depth(&self) -> u64 {
+        0
+    }
+
     fn refresh(&mut self) -> bool {
         true
     }
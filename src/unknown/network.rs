@@ -4,26 +4,53 @@
 // Copyright (c) 2017 Guillaume Gomez
 //
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::{Duration, Instant};
 
-use crate::{NetworkExt, NetworksExt, NetworksIter};
+use crate::common::NetworkHistory;
+use crate::{
+    CountingBasis, InterfaceStatus, InterfaceType, NetworkExt, NetworksExt, NetworksIter,
+    RefreshError,
+};
 
 /// Network interfaces.
 ///
 /// ```no_run
-/// use sysinfo::{NetworksExt, System, SystemExt};
+/// use sysinfo::{Networks, NetworksExt};
 ///
-/// let s = System::new_all();
-/// let networks = s.get_networks();
+/// let mut networks = Networks::new();
+/// networks.refresh_networks_list();
 /// ```
+#[derive(Clone)]
 pub struct Networks {
     interfaces: HashMap<String, NetworkData>,
+    history_len: usize,
+    include_loopback: bool,
+}
+
+impl Default for Networks {
+    fn default() -> Self {
+        Networks::new()
+    }
 }
 
 impl Networks {
-    pub(crate) fn new() -> Networks {
+    /// Creates an empty `Networks`, which can be used on its own. Call
+    /// [`NetworksExt::refresh_networks_list`][crate::NetworksExt::refresh_networks_list] to fill
+    /// it.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// ```
+    pub fn new() -> Networks {
         Networks {
             interfaces: HashMap::new(),
+            history_len: 0,
+            include_loopback: false,
         }
     }
 }
@@ -33,13 +60,50 @@ impl NetworksExt for Networks {
         NetworksIter::new(self.interfaces.iter())
     }
 
-    fn refresh_networks_list(&mut self) {}
+    fn try_refresh_networks_list(&mut self) -> Result<(), RefreshError> {
+        Ok(())
+    }
+
+    fn refresh_networks_list_no_prune(&mut self) {}
 
     fn refresh(&mut self) {}
+
+    fn set_history_len(&mut self, len: usize) {
+        self.history_len = len;
+        for data in self.interfaces.values_mut() {
+            data.history.set_len(len);
+        }
+    }
+
+    fn set_include_loopback(&mut self, include_loopback: bool) {
+        self.include_loopback = include_loopback;
+    }
+
+    fn is_loopback_included(&self) -> bool {
+        self.include_loopback
+    }
+
+    fn set_min_refresh_interval(&mut self, _interval: Duration) {}
+
+    fn get_default_gateway_v4(&self) -> Option<Ipv4Addr> {
+        None
+    }
+
+    fn get_default_gateway_v6(&self) -> Option<Ipv6Addr> {
+        None
+    }
+
+    fn get_dns_servers(&self) -> Vec<IpAddr> {
+        Vec::new()
+    }
 }
 
 /// Contains network information.
-pub struct NetworkData;
+#[derive(Clone)]
+pub struct NetworkData {
+    history: NetworkHistory,
+    last_refreshed: Instant,
+}
 
 impl NetworkExt for NetworkData {
     fn get_received(&self) -> u64 {
@@ -89,4 +153,80 @@ impl NetworkExt for NetworkData {
     fn get_total_errors_on_transmitted(&self) -> u64 {
         0
     }
+
+    fn get_drops_on_received(&self) -> u64 {
+        0
+    }
+
+    fn get_total_drops_on_received(&self) -> u64 {
+        0
+    }
+
+    fn get_drops_on_transmitted(&self) -> u64 {
+        0
+    }
+
+    fn get_total_drops_on_transmitted(&self) -> u64 {
+        0
+    }
+
+    fn get_packets_received_multicast(&self) -> u64 {
+        0
+    }
+
+    fn get_total_packets_received_multicast(&self) -> u64 {
+        0
+    }
+
+    fn get_received_ipv4(&self) -> u64 {
+        self.get_received()
+    }
+
+    fn get_received_ipv6(&self) -> u64 {
+        0
+    }
+
+    fn get_wifi_signal(&self) -> Option<i32> {
+        None
+    }
+
+    fn get_interface_type(&self) -> InterfaceType {
+        InterfaceType::Other
+    }
+
+    fn is_physical(&self) -> bool {
+        false
+    }
+
+    fn get_counting_basis(&self) -> CountingBasis {
+        CountingBasis::LinkLayer
+    }
+
+    fn get_admin_status(&self) -> InterfaceStatus {
+        InterfaceStatus::Unknown
+    }
+
+    fn get_oper_status(&self) -> InterfaceStatus {
+        InterfaceStatus::Unknown
+    }
+
+    fn get_received_history(&self) -> &VecDeque<u64> {
+        &self.history.received
+    }
+
+    fn get_transmitted_history(&self) -> &VecDeque<u64> {
+        &self.history.transmitted
+    }
+
+    fn get_last_refreshed(&self) -> Instant {
+        self.last_refreshed
+    }
+
+    fn get_received_per_second(&self) -> f64 {
+        0.
+    }
+
+    fn get_transmitted_per_second(&self) -> f64 {
+        0.
+    }
 }
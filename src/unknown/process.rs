@@ -4,14 +4,25 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
-use crate::{DiskUsage, Pid, ProcessExt, Signal};
+use crate::{
+    AccessError, DiskUsage, Gid, NetworkUsage, Pid, PriorityError, ProcessExt, Signal, SocketInfo,
+    ThreadInfo, Uid,
+};
 
 use std::path::Path;
+use std::process::ExitStatus;
+use std::time::Duration;
 
 /// Enum describing the different status of a process.
 #[derive(Clone, Copy, Debug)]
 pub struct ProcessStatus;
 
+impl ProcessStatus {
+    pub(crate) fn is_running(&self) -> bool {
+        false
+    }
+}
+
 /// Struct containing a process' information.
 #[derive(Clone)]
 pub struct Process {
@@ -28,6 +39,14 @@ impl ProcessExt for Process {
         false
     }
 
+    fn wait(&self) -> Option<ExitStatus> {
+        None
+    }
+
+    fn wait_timeout(&self, _timeout: Duration) -> Option<ExitStatus> {
+        None
+    }
+
     fn name(&self) -> &str {
         ""
     }
@@ -64,10 +83,38 @@ impl ProcessExt for Process {
         0
     }
 
+    fn shared_memory(&self) -> u64 {
+        0
+    }
+
+    fn swap_memory(&self) -> u64 {
+        0
+    }
+
     fn parent(&self) -> Option<Pid> {
         self.parent
     }
 
+    fn is_kernel_thread(&self) -> bool {
+        false
+    }
+
+    fn is_thread(&self) -> bool {
+        false
+    }
+
+    fn tgid(&self) -> Pid {
+        self.pid()
+    }
+
+    fn session_id(&self) -> Option<i32> {
+        None
+    }
+
+    fn tty(&self) -> Option<&str> {
+        None
+    }
+
     fn status(&self) -> ProcessStatus {
         ProcessStatus
     }
@@ -76,11 +123,107 @@ impl ProcessExt for Process {
         0
     }
 
+    fn run_time(&self) -> u64 {
+        0
+    }
+
+    fn thread_count(&self) -> u32 {
+        0
+    }
+
+    fn threads(&self) -> &[ThreadInfo] {
+        &[]
+    }
+
+    fn open_files_count(&self) -> Option<usize> {
+        None
+    }
+
+    fn open_sockets(&self) -> Vec<SocketInfo> {
+        Vec::new()
+    }
+
+    fn network_usage(&self) -> Option<NetworkUsage> {
+        None
+    }
+
+    fn user_id(&self) -> Option<Uid> {
+        None
+    }
+
+    fn effective_user_id(&self) -> Option<Uid> {
+        None
+    }
+
+    fn group_id(&self) -> Option<Gid> {
+        None
+    }
+
+    fn priority(&self) -> i32 {
+        0
+    }
+
+    fn set_nice(&self, _nice: i32) -> Result<(), PriorityError> {
+        Err(PriorityError::Unsupported)
+    }
+
+    fn oom_score(&self) -> Option<u32> {
+        None
+    }
+
+    fn oom_score_adj(&self) -> Option<i32> {
+        None
+    }
+
+    fn cgroup(&self) -> Option<String> {
+        None
+    }
+
+    fn exists(&self) -> bool {
+        true
+    }
+
+    fn last_error(&self) -> Option<AccessError> {
+        None
+    }
+
+    fn io_wait_time(&self) -> Option<u64> {
+        None
+    }
+
+    fn sched_delay(&self) -> Option<u64> {
+        None
+    }
+
     fn cpu_usage(&self) -> f32 {
         0.0
     }
 
+    fn accumulated_cpu_time(&self) -> u64 {
+        0
+    }
+
     fn disk_usage(&self) -> DiskUsage {
         DiskUsage::default()
     }
+
+    fn minor_faults(&self) -> u64 {
+        0
+    }
+
+    fn major_faults(&self) -> u64 {
+        0
+    }
+
+    fn total_minor_faults(&self) -> u64 {
+        0
+    }
+
+    fn total_major_faults(&self) -> u64 {
+        0
+    }
+}
+
+pub(crate) fn wait_pid<S: Fn() -> bool>(_pid: Pid, _should_stop: S) -> Option<ExitStatus> {
+    None
 }
@@ -4,7 +4,7 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
-use crate::ProcessorExt;
+use crate::{CpuTimes, ProcessorExt};
 
 /// Dummy struct that represents a processor.
 pub struct Processor {}
@@ -35,4 +35,12 @@ impl ProcessorExt for Processor {
     fn get_brand(&self) -> &str {
         ""
     }
+
+    fn get_temperature(&self) -> Option<f32> {
+        None
+    }
+
+    fn get_times(&self) -> CpuTimes {
+        CpuTimes::default()
+    }
 }
@@ -4,77 +4,159 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
-use crate::{
-    sys::{component::Component, Disk, Networks, Process, Processor},
-    LoadAvg, Pid, RefreshKind, SystemExt, User,
-};
-
+#[cfg(feature = "disk")]
+use crate::sys::Disk;
+#[cfg(feature = "network")]
+use crate::sys::Networks;
+#[cfg(feature = "cpu")]
+use crate::sys::Processor;
+#[cfg(feature = "process")]
+use crate::sys::{process::wait_pid, Process};
+#[cfg(feature = "component")]
+use crate::sys::{Component, Fan};
+#[cfg(feature = "cpu")]
+use crate::CacheInfo;
+#[cfg(feature = "disk")]
+use crate::Mount;
+use crate::{sys::Gpu, Battery, LoadAvg, RefreshKind, SwapDevice, SystemExt, User, Virtualization};
+#[cfg(feature = "process")]
+use crate::{Pid, ProcessRefreshKind, ProcessWatcher, SocketInfo};
+
+#[cfg(feature = "process")]
 use std::collections::HashMap;
+#[cfg(feature = "disk")]
+use std::path::Path;
+#[cfg(feature = "process")]
+use std::process::ExitStatus;
+#[cfg(feature = "cpu")]
+use std::time::Duration;
 
 /// Structs containing system's information.
 pub struct System {
+    #[cfg(feature = "process")]
     processes_list: HashMap<Pid, Process>,
+    #[cfg(feature = "network")]
     networks: Networks,
+    #[cfg(feature = "cpu")]
     global_processor: Processor,
 }
 
 impl SystemExt for System {
     fn new_with_specifics(_: RefreshKind) -> System {
         System {
+            #[cfg(feature = "process")]
             processes_list: Default::default(),
+            #[cfg(feature = "network")]
             networks: Networks::new(),
+            #[cfg(feature = "cpu")]
             global_processor: Processor::new(),
         }
     }
 
     fn refresh_memory(&mut self) {}
 
+    #[cfg(feature = "cpu")]
     fn refresh_cpu(&mut self) {}
 
+    #[cfg(feature = "cpu")]
+    fn refresh_cpu_over(&mut self, window: Duration) {
+        std::thread::sleep(window);
+    }
+
+    #[cfg(feature = "cpu")]
+    fn set_min_refresh_interval(&mut self, _interval: Duration) {}
+
+    #[cfg(feature = "process")]
+    fn set_include_threads(&mut self, _include: bool) {}
+
+    #[cfg(feature = "process")]
+    fn set_keep_exited_processes(&mut self, _keep: bool) {}
+
+    #[cfg(feature = "disk")]
+    fn set_disk_filter<F>(&mut self, _filter: F)
+    where
+        F: Fn(&Path, &str) -> bool + Send + Sync + 'static,
+    {
+    }
+
+    #[cfg(feature = "component")]
     fn refresh_components_list(&mut self) {}
 
-    fn refresh_processes(&mut self) {}
+    fn refresh_gpus_list(&mut self) {}
+
+    #[cfg(feature = "process")]
+    fn refresh_processes_specifics(&mut self, _refresh_kind: ProcessRefreshKind) {}
 
+    #[cfg(feature = "process")]
     fn refresh_process(&mut self, _pid: Pid) -> bool {
         false
     }
 
+    #[cfg(feature = "disk")]
     fn refresh_disks_list(&mut self) {}
 
     fn refresh_users_list(&mut self) {}
 
+    #[cfg(feature = "process")]
+    fn refresh_sockets(&mut self) {}
+
     // COMMON PART
     //
     // Need to be moved into a "common" file to avoid duplication.
 
+    #[cfg(feature = "process")]
     fn get_processes(&self) -> &HashMap<Pid, Process> {
         &self.processes_list
     }
 
+    #[cfg(feature = "process")]
     fn get_process(&self, _pid: Pid) -> Option<&Process> {
         None
     }
 
+    #[cfg(feature = "process")]
+    fn watch_process_exit<F>(&self, pid: Pid, f: F) -> ProcessWatcher
+    where
+        F: FnOnce(Option<ExitStatus>) + Send + 'static,
+    {
+        ProcessWatcher::new(move |should_stop| wait_pid(pid, should_stop), f)
+    }
+
+    #[cfg(feature = "network")]
     fn get_networks(&self) -> &Networks {
         &self.networks
     }
 
+    #[cfg(feature = "network")]
     fn get_networks_mut(&mut self) -> &mut Networks {
         &mut self.networks
     }
 
+    #[cfg(feature = "cpu")]
     fn get_global_processor_info(&self) -> &Processor {
         &self.global_processor
     }
 
+    #[cfg(feature = "cpu")]
     fn get_processors(&self) -> &[Processor] {
         &[]
     }
 
+    #[cfg(feature = "cpu")]
     fn get_physical_core_count(&self) -> Option<usize> {
         None
     }
 
+    #[cfg(feature = "cpu")]
+    fn get_context_switches(&self) -> u64 {
+        0
+    }
+
+    #[cfg(feature = "cpu")]
+    fn get_interrupts(&self) -> u64 {
+        0
+    }
+
     fn get_total_memory(&self) -> u64 {
         0
     }
@@ -103,22 +185,53 @@ impl SystemExt for System {
         0
     }
 
+    fn get_swap_devices(&self) -> &[SwapDevice] {
+        &[]
+    }
+
+    #[cfg(feature = "component")]
     fn get_components(&self) -> &[Component] {
         &[]
     }
 
+    #[cfg(feature = "component")]
     fn get_components_mut(&mut self) -> &mut [Component] {
         &mut []
     }
 
+    #[cfg(feature = "component")]
+    fn get_fans(&self) -> &[Fan] {
+        &[]
+    }
+
+    #[cfg(feature = "component")]
+    fn get_fans_mut(&mut self) -> &mut [Fan] {
+        &mut []
+    }
+
+    fn get_gpus(&self) -> &[Gpu] {
+        &[]
+    }
+
+    fn get_gpus_mut(&mut self) -> &mut [Gpu] {
+        &mut []
+    }
+
+    #[cfg(feature = "disk")]
     fn get_disks(&self) -> &[Disk] {
         &[]
     }
 
+    #[cfg(feature = "disk")]
     fn get_disks_mut(&mut self) -> &mut [Disk] {
         &mut []
     }
 
+    #[cfg(feature = "disk")]
+    fn get_all_mounts(&self) -> Vec<Mount> {
+        Vec::new()
+    }
+
     fn get_uptime(&self) -> u64 {
         0
     }
@@ -135,10 +248,19 @@ impl SystemExt for System {
         }
     }
 
+    fn get_battery(&self) -> Option<Battery> {
+        None
+    }
+
     fn get_users(&self) -> &[User] {
         &[]
     }
 
+    #[cfg(feature = "process")]
+    fn get_sockets(&self) -> &[SocketInfo] {
+        &[]
+    }
+
     fn get_name(&self) -> Option<String> {
         None
     }
@@ -158,6 +280,25 @@ impl SystemExt for System {
     fn get_host_name(&self) -> Option<String> {
         None
     }
+
+    #[cfg(feature = "cpu")]
+    fn get_cpu_arch(&self) -> Option<String> {
+        None
+    }
+
+    #[cfg(feature = "cpu")]
+    fn get_cpu_features(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    #[cfg(feature = "cpu")]
+    fn get_cpu_caches(&self) -> Vec<CacheInfo> {
+        Vec::new()
+    }
+
+    fn get_virtualization(&self) -> Virtualization {
+        Virtualization::None
+    }
 }
 
 impl Default for System {
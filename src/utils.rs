@@ -4,12 +4,20 @@
 // Copyright (c) 2017 Guillaume Gomez
 //
 
+#[cfg(any(
+    feature = "disk",
+    all(feature = "system", any(target_os = "linux", target_os = "android"))
+))]
+#[cfg(any(target_os = "linux", target_os = "android", target_vendor = "apple"))]
+use std::path::Path;
+#[cfg(feature = "disk")]
 #[cfg(any(target_os = "linux", target_os = "android", target_vendor = "apple"))]
-use std::{ffi::OsStr, os::unix::ffi::OsStrExt, path::Path};
+use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
 
 use crate::Pid;
 
 #[allow(clippy::useless_conversion)]
+#[cfg(feature = "process")]
 #[cfg(any(target_os = "linux", target_os = "android"))]
 pub fn realpath(original: &Path) -> std::path::PathBuf {
     use libc::{c_char, lstat, stat, S_IFLNK, S_IFMT};
@@ -42,7 +50,357 @@ pub fn realpath(original: &Path) -> std::path::PathBuf {
     }
 }
 
+/// Reads the content of `file`, preallocating `capacity` bytes.
+#[cfg(feature = "system")]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn get_file_line(file: &Path, capacity: usize) -> Option<String> {
+    use std::io::Read;
+
+    let mut reader = String::with_capacity(capacity);
+    if let Ok(mut f) = std::fs::File::open(file) {
+        if f.read_to_string(&mut reader).is_ok() {
+            Some(reader)
+        } else {
+            None
+        }
+    } else {
+        None
+    }
+}
+
+#[cfg(feature = "component")]
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn is_file<T: AsRef<Path>>(path: T) -> bool {
+    std::fs::metadata(path)
+        .ok()
+        .map(|m| m.is_file())
+        .unwrap_or(false)
+}
+
+/// Returns the number of seconds elapsed since UNIX epoch.
+#[cfg(feature = "process")]
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_vendor = "apple",
+    target_os = "windows"
+))]
+pub(crate) fn get_secs_since_epoch() -> u64 {
+    match std::time::SystemTime::now().duration_since(std::time::SystemTime::UNIX_EPOCH) {
+        Ok(n) => n.as_secs(),
+        _ => panic!("SystemTime before UNIX EPOCH!"),
+    }
+}
+
+/// Returns `value` divided by the number of seconds elapsed between `previous` and `last`, or 0
+/// if no time elapsed.
+#[cfg(feature = "network")]
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_vendor = "apple",
+    target_os = "windows"
+))]
+pub(crate) fn get_rate(value: u64, previous: std::time::Instant, last: std::time::Instant) -> f64 {
+    let elapsed = last.saturating_duration_since(previous).as_secs_f64();
+    if elapsed > 0. {
+        value as f64 / elapsed
+    } else {
+        0.
+    }
+}
+
+/// Returns the instruction set extensions supported by the CPU, as detected through `cpuid`.
+#[cfg(feature = "cpu")]
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    any(
+        target_os = "linux",
+        target_os = "android",
+        target_vendor = "apple",
+        target_os = "windows"
+    )
+))]
+pub(crate) fn get_x86_cpu_features() -> Vec<String> {
+    macro_rules! detect {
+        ($($feature:tt),*) => {{
+            let mut features = Vec::new();
+            $(
+                if is_x86_feature_detected!($feature) {
+                    features.push($feature.to_owned());
+                }
+            )*
+            features
+        }};
+    }
+
+    detect!(
+        "sse",
+        "sse2",
+        "sse3",
+        "ssse3",
+        "sse4.1",
+        "sse4.2",
+        "popcnt",
+        "avx",
+        "avx2",
+        "fma",
+        "f16c",
+        "aes",
+        "pclmulqdq",
+        "rdrand",
+        "rdseed",
+        "bmi1",
+        "bmi2",
+        "lzcnt",
+        "sha",
+        "avx512f"
+    )
+}
+
+/// Used to prevent refreshing information more often than a given interval.
+#[cfg(any(feature = "cpu", feature = "network"))]
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_vendor = "apple",
+    target_os = "windows"
+))]
+#[derive(Default, Clone)]
+pub(crate) struct RefreshInterval {
+    min_interval: std::time::Duration,
+    last_refresh: Option<std::time::Instant>,
+}
+
+#[cfg(any(feature = "cpu", feature = "network"))]
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_vendor = "apple",
+    target_os = "windows"
+))]
+impl RefreshInterval {
+    pub(crate) fn set_min_interval(&mut self, min_interval: std::time::Duration) {
+        self.min_interval = min_interval;
+    }
+
+    /// Forgets the last refresh time, so the next [`RefreshInterval::should_refresh`] call returns
+    /// `true`.
+    #[cfg(feature = "cpu")]
+    pub(crate) fn reset(&mut self) {
+        self.last_refresh = None;
+    }
+
+    /// Returns `true` if the minimum interval elapsed since the last refresh, in which case the
+    /// last refresh time is updated.
+    pub(crate) fn should_refresh(&mut self) -> bool {
+        let now = std::time::Instant::now();
+        match self.last_refresh {
+            Some(last_refresh) if now.duration_since(last_refresh) < self.min_interval => false,
+            _ => {
+                self.last_refresh = Some(now);
+                true
+            }
+        }
+    }
+}
+
+/// Returns the hypervisor the system runs on, as reported by `cpuid`.
+#[cfg(feature = "system")]
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    any(
+        target_os = "linux",
+        target_os = "android",
+        target_vendor = "apple",
+        target_os = "windows"
+    )
+))]
+// `__cpuid` is only safe in recent Rust versions.
+#[allow(unused_unsafe)]
+pub(crate) fn get_cpuid_virtualization() -> Option<crate::Virtualization> {
+    use crate::Virtualization;
+    #[cfg(target_arch = "x86")]
+    use std::arch::x86::__cpuid;
+    #[cfg(target_arch = "x86_64")]
+    use std::arch::x86_64::__cpuid;
+
+    // The "hypervisor present" bit.
+    if unsafe { __cpuid(1) }.ecx & (1 << 31) == 0 {
+        return None;
+    }
+    let leaf = unsafe { __cpuid(0x4000_0000) };
+    let mut vendor = Vec::with_capacity(12);
+    for register in &[leaf.ebx, leaf.ecx, leaf.edx] {
+        vendor.extend_from_slice(&register.to_le_bytes());
+    }
+    Some(match &vendor[..] {
+        b"KVMKVMKVM\0\0\0" => Virtualization::Kvm,
+        b"TCGTCGTCGTCG" => Virtualization::Qemu,
+        b"VMwareVMware" => Virtualization::VMware,
+        b"VBoxVBoxVBox" => Virtualization::VirtualBox,
+        b"XenVMMXenVMM" => Virtualization::Xen,
+        b"Microsoft Hv" => Virtualization::HyperV,
+        b"prl hyperv  " | b" lrpepyh  vr" => Virtualization::Parallels,
+        _ => Virtualization::Other,
+    })
+}
+
+/// Returns the hypervisor matching the vendor or product names found in the DMI (or SMBIOS)
+/// information, if any.
+#[cfg(feature = "system")]
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "windows"))]
+pub(crate) fn get_dmi_virtualization(names: &[String]) -> Option<crate::Virtualization> {
+    use crate::Virtualization;
+
+    const HYPERVISORS: &[(&str, Virtualization)] = &[
+        ("KVM", Virtualization::Kvm),
+        ("QEMU", Virtualization::Qemu),
+        ("VMware", Virtualization::VMware),
+        ("VirtualBox", Virtualization::VirtualBox),
+        ("innotek", Virtualization::VirtualBox),
+        ("Xen", Virtualization::Xen),
+        // The product name used by Hyper-V.
+        ("Virtual Machine", Virtualization::HyperV),
+        ("Parallels", Virtualization::Parallels),
+    ];
+
+    names.iter().find_map(|name| {
+        HYPERVISORS
+            .iter()
+            .find(|(pattern, _)| name.contains(pattern))
+            .map(|(_, virtualization)| *virtualization)
+    })
+}
+
+/// Returns the exit status of the child process `pid` if it ended, without reaping it so its
+/// owner can still wait for it. Returns `Err(())` if `pid` isn't a child of the current process.
+#[cfg(feature = "process")]
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "macos"))]
+fn peek_child_exit_status(pid: Pid) -> Result<Option<std::process::ExitStatus>, ()> {
+    use std::os::unix::process::ExitStatusExt;
+
+    // These values are the same on Linux and macOS.
+    const CLD_EXITED: libc::c_int = 1;
+    const CLD_DUMPED: libc::c_int = 3;
+
+    loop {
+        let mut info: libc::siginfo_t = unsafe { std::mem::zeroed() };
+        let ret = unsafe {
+            libc::waitid(
+                libc::P_PID,
+                pid.0 as _,
+                &mut info,
+                libc::WEXITED | libc::WNOHANG | libc::WNOWAIT,
+            )
+        };
+        if ret != 0 {
+            if std::io::Error::last_os_error().raw_os_error() == Some(libc::EINTR) {
+                continue;
+            }
+            return Err(());
+        }
+        // With `WNOHANG`, `si_pid` is left to 0 if the child is still running.
+        if unsafe { info.si_pid() } == 0 {
+            return Ok(None);
+        }
+        // Converts it back to the format returned by `waitpid`.
+        let status = unsafe { info.si_status() };
+        let raw = match info.si_code {
+            CLD_EXITED => (status & 0xff) << 8,
+            CLD_DUMPED => (status & 0x7f) | 0x80,
+            _ => status & 0x7f,
+        };
+        return Ok(Some(std::process::ExitStatus::from_raw(raw)));
+    }
+}
+
+/// Waits for the process `pid` to end. The exit status can only be retrieved for children of
+/// the current process, which aren't reaped. For the other ones, `get_start_time` is polled until
+/// it returns `None` or another value, meaning that the process is gone and that its PID might
+/// have been reused. Returns `None` early once `should_stop` returns `true`.
+#[cfg(feature = "process")]
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "macos"))]
+pub(crate) fn wait_process<F: Fn() -> Option<u64>, S: Fn() -> bool>(
+    pid: Pid,
+    get_start_time: F,
+    should_stop: S,
+) -> Option<std::process::ExitStatus> {
+    let start_time = get_start_time();
+    loop {
+        match peek_child_exit_status(pid) {
+            Ok(Some(status)) => return Some(status),
+            Ok(None) => {}
+            Err(()) => {
+                if start_time.is_none() || get_start_time() != start_time {
+                    return None;
+                }
+            }
+        }
+        if should_stop() {
+            return None;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+}
+
+/// Predicate set with [`SystemExt::set_disk_filter`][crate::SystemExt::set_disk_filter].
+#[cfg(feature = "disk")]
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "windows"
+))]
+pub(crate) type DiskFilter = Box<dyn Fn(&std::path::Path, &str) -> bool + Send + Sync>;
+
+/// Removes the disks rejected by `filter` from `disks`.
+#[cfg(feature = "disk")]
+#[cfg(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    target_os = "windows"
+))]
+pub(crate) fn filter_disks<D: crate::DiskExt>(disks: &mut Vec<D>, filter: &Option<DiskFilter>) {
+    if let Some(filter) = filter {
+        disks.retain(|disk| {
+            filter(
+                disk.get_mount_point(),
+                &String::from_utf8_lossy(disk.get_file_system()),
+            )
+        });
+    }
+}
+
+/// Returns the number of logical cores which are online, or 1 if it cannot be retrieved.
+#[cfg(feature = "process")]
+pub(crate) fn get_logical_core_count() -> usize {
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "ios"
+    ))]
+    {
+        let count = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) };
+        if count > 0 {
+            return count as usize;
+        }
+    }
+    #[cfg(target_os = "windows")]
+    {
+        let mut info = unsafe { std::mem::zeroed::<winapi::um::sysinfoapi::SYSTEM_INFO>() };
+        unsafe { winapi::um::sysinfoapi::GetSystemInfo(&mut info) };
+        if info.dwNumberOfProcessors > 0 {
+            return info.dwNumberOfProcessors as usize;
+        }
+    }
+    1
+}
+
 /* convert a path to a NUL-terminated Vec<u8> suitable for use with C functions */
+#[cfg(feature = "disk")]
 #[cfg(any(target_os = "linux", target_os = "android", target_vendor = "apple"))]
 pub fn to_cpath(path: &Path) -> Vec<u8> {
     let path_os: &OsStr = path.as_ref();
@@ -72,13 +430,13 @@ pub fn get_current_pid() -> Result<Pid, &'static str> {
     cfg_if::cfg_if! {
         if #[cfg(not(any(target_os = "windows", target_os = "unknown", target_arch = "wasm32")))] {
             fn inner() -> Result<Pid, &'static str> {
-                unsafe { Ok(::libc::getpid()) }
+                unsafe { Ok(Pid(::libc::getpid())) }
             }
         } else if #[cfg(target_os = "windows")] {
             fn inner() -> Result<Pid, &'static str> {
                 use winapi::um::processthreadsapi::GetCurrentProcessId;
 
-                unsafe { Ok(GetCurrentProcessId() as Pid) }
+                unsafe { Ok(Pid(GetCurrentProcessId() as _)) }
             }
         } else if #[cfg(target_os = "unknown")] {
             fn inner() -> Result<Pid, &'static str> {
@@ -95,6 +453,7 @@ pub fn get_current_pid() -> Result<Pid, &'static str> {
 
 /// Converts the value into a parallel iterator (if the multithread feature is enabled)
 /// Uses the rayon::iter::IntoParallelIterator trait
+#[cfg(any(feature = "process", all(target_os = "windows", feature = "disk")))]
 #[cfg(all(
     all(
         any(
@@ -116,6 +475,7 @@ where
 
 /// Converts the value into a sequential iterator (if the multithread feature is disabled)
 /// Uses the std::iter::IntoIterator trait
+#[cfg(any(feature = "process", all(target_os = "windows", feature = "disk")))]
 #[cfg(all(
     all(
         any(
@@ -134,3 +494,97 @@ where
 {
     val.into_iter()
 }
+
+/// The unit system used by [`format_bytes_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnitSystem {
+    /// Powers of 1000: `KB`, `MB`, `GB`...
+    Si,
+    /// Powers of 1024: `KiB`, `MiB`, `GiB`...
+    Iec,
+}
+
+const SI_UNITS: &[&str] = &["B", "KB", "MB", "GB", "TB", "PB", "EB"];
+const IEC_UNITS: &[&str] = &["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+
+/// Returns a human-readable representation of `bytes`, using [`UnitSystem::Si`] units.
+///
+/// ```
+/// use sysinfo::format_bytes;
+///
+/// assert_eq!(format_bytes(345_000), "345 KB");
+/// assert_eq!(format_bytes(1_234_000_000), "1.2 GB");
+/// ```
+pub fn format_bytes(bytes: u64) -> String {
+    format_bytes_with(bytes, UnitSystem::Si)
+}
+
+/// Returns a human-readable representation of `bytes` in the given unit system.
+///
+/// Values lower than 10 in the chosen unit keep one decimal, bigger ones are rounded.
+///
+/// ```
+/// use sysinfo::{format_bytes_with, UnitSystem};
+///
+/// assert_eq!(format_bytes_with(1000, UnitSystem::Si), "1.0 KB");
+/// assert_eq!(format_bytes_with(1000, UnitSystem::Iec), "1000 B");
+/// assert_eq!(format_bytes_with(1536, UnitSystem::Iec), "1.5 KiB");
+/// ```
+pub fn format_bytes_with(bytes: u64, units: UnitSystem) -> String {
+    let (base, names) = match units {
+        UnitSystem::Si => (1000., SI_UNITS),
+        UnitSystem::Iec => (1024., IEC_UNITS),
+    };
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= base && unit + 1 < names.len() {
+        value /= base;
+        unit += 1;
+    }
+    if unit == 0 {
+        return format!("{} {}", bytes, names[0]);
+    }
+    if (value * 10.).round() < 100. {
+        return format!("{:.1} {}", value, names[unit]);
+    }
+    let value = value.round();
+    // Rounding can reach the next unit (999.7 KB for example).
+    if value >= base && unit + 1 < names.len() {
+        format!("{:.1} {}", value / base, names[unit + 1])
+    } else {
+        format!("{:.0} {}", value, names[unit])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{format_bytes, format_bytes_with, UnitSystem};
+
+    #[test]
+    fn check_format_bytes_si() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(999), "999 B");
+        assert_eq!(format_bytes(1000), "1.0 KB");
+        assert_eq!(format_bytes(1023), "1.0 KB");
+        assert_eq!(format_bytes(1024), "1.0 KB");
+        assert_eq!(format_bytes(9_960), "10 KB");
+        assert_eq!(format_bytes(345_000), "345 KB");
+        assert_eq!(format_bytes(999_700), "1.0 MB");
+        assert_eq!(format_bytes(1_234_000_000), "1.2 GB");
+        assert_eq!(format_bytes(u64::MAX), "18 EB");
+    }
+
+    #[test]
+    fn check_format_bytes_iec() {
+        let format = |bytes| format_bytes_with(bytes, UnitSystem::Iec);
+        assert_eq!(format(0), "0 B");
+        assert_eq!(format(999), "999 B");
+        assert_eq!(format(1000), "1000 B");
+        assert_eq!(format(1023), "1023 B");
+        assert_eq!(format(1024), "1.0 KiB");
+        assert_eq!(format(1536), "1.5 KiB");
+        assert_eq!(format(1024 * 1024 - 1), "1.0 MiB");
+        assert_eq!(format(5 * 1024 * 1024 * 1024), "5.0 GiB");
+        assert_eq!(format(u64::MAX), "16 EiB");
+    }
+}
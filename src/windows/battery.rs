@@ -0,0 +1,53 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::{Battery, BatteryState};
+
+use winapi::um::winbase::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+
+const BATTERY_FLAG_CHARGING: u8 = 8;
+const BATTERY_FLAG_NO_BATTERY: u8 = 128;
+const BATTERY_FLAG_UNKNOWN: u8 = 255;
+const BATTERY_PERCENTAGE_UNKNOWN: u8 = 255;
+const BATTERY_TIME_UNKNOWN: u32 = u32::MAX;
+const AC_LINE_ONLINE: u8 = 1;
+
+pub(crate) fn get_battery() -> Option<Battery> {
+    let mut status: SYSTEM_POWER_STATUS = unsafe { std::mem::zeroed() };
+    if unsafe { GetSystemPowerStatus(&mut status) } == 0 {
+        sysinfo_debug!("GetSystemPowerStatus failed");
+        return None;
+    }
+    // Desktops don't have a battery.
+    if status.BatteryFlag == BATTERY_FLAG_UNKNOWN
+        || status.BatteryFlag & BATTERY_FLAG_NO_BATTERY != 0
+        || status.BatteryLifePercent == BATTERY_PERCENTAGE_UNKNOWN
+    {
+        return None;
+    }
+    let percentage = status.BatteryLifePercent.min(100);
+    let state = if status.BatteryFlag & BATTERY_FLAG_CHARGING != 0 {
+        BatteryState::Charging
+    } else if status.ACLineStatus != AC_LINE_ONLINE {
+        BatteryState::Discharging
+    } else if percentage == 100 {
+        BatteryState::Full
+    } else {
+        BatteryState::Unknown
+    };
+    Some(Battery {
+        percentage: percentage as f32,
+        state,
+        // Windows doesn't provide this information.
+        time_to_full: None,
+        time_to_empty: match state {
+            BatteryState::Discharging if status.BatteryLifeTime != BATTERY_TIME_UNKNOWN => {
+                Some(status.BatteryLifeTime as u64)
+            }
+            _ => None,
+        },
+    })
+}
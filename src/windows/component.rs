@@ -6,6 +6,7 @@
 
 use crate::ComponentExt;
 
+use std::path::Path;
 use std::ptr::null_mut;
 
 use winapi::shared::rpcdce::{
@@ -13,6 +14,7 @@ use winapi::shared::rpcdce::{
     RPC_C_IMP_LEVEL_IMPERSONATE,
 };
 use winapi::shared::winerror::{FAILED, SUCCEEDED};
+use winapi::shared::wtypes::{BSTR, VT_BSTR};
 use winapi::shared::wtypesbase::CLSCTX_INPROC_SERVER;
 use winapi::um::combaseapi::{
     CoCreateInstance, CoInitializeEx, CoInitializeSecurity, CoSetProxyBlanket, CoUninitialize,
@@ -25,6 +27,9 @@ use winapi::um::wbemcli::{
     IWbemServices, WBEM_FLAG_FORWARD_ONLY, WBEM_FLAG_NONSYSTEM_ONLY, WBEM_FLAG_RETURN_IMMEDIATELY,
 };
 
+const WMI_NAMESPACE: &str = "root\\WMI";
+const THERMAL_QUERY: &str = "SELECT * FROM MSAcpi_ThermalZoneTemperature";
+
 /// Struct containing a component information (temperature and name for the moment).
 ///
 /// Please note that on Windows, you need to have Administrator priviledges to get this
@@ -43,9 +48,9 @@ impl Component {
         let mut c = Connection::new()
             .and_then(|x| x.initialize_security())
             .and_then(|x| x.create_instance())
-            .and_then(|x| x.connect_server())
+            .and_then(|x| x.connect_server(WMI_NAMESPACE))
             .and_then(|x| x.set_proxy_blanket())
-            .and_then(|x| x.exec_query())?;
+            .and_then(|x| x.exec_query(THERMAL_QUERY))?;
 
         c.get_temperature(true)
             .map(|(temperature, critical)| Component {
@@ -75,16 +80,20 @@ impl ComponentExt for Component {
         &self.label
     }
 
+    fn get_device_path(&self) -> Option<&Path> {
+        None
+    }
+
     fn refresh(&mut self) {
         if self.connection.is_none() {
             self.connection = Connection::new()
                 .and_then(|x| x.initialize_security())
                 .and_then(|x| x.create_instance())
-                .and_then(|x| x.connect_server())
+                .and_then(|x| x.connect_server(WMI_NAMESPACE))
                 .and_then(|x| x.set_proxy_blanket());
         }
         self.connection = if let Some(x) = self.connection.take() {
-            x.exec_query()
+            x.exec_query(THERMAL_QUERY)
         } else {
             None
         };
@@ -149,18 +158,55 @@ macro_rules! bstr {
     }}
 }
 
-struct Connection {
+unsafe fn str_to_bstr(s: &str) -> BSTR {
+    let x = s.encode_utf16().chain(Some(0)).collect::<Vec<_>>();
+    SysAllocString(x.as_ptr())
+}
+
+/// Returns the value of the given property of a WMI object if it is a string.
+///
+/// Please note that WMI returns `uint64` properties as strings too.
+pub(crate) unsafe fn get_string_property(
+    p_obj: *mut IWbemClassObject,
+    name: &str,
+) -> Option<String> {
+    let name = str_to_bstr(name);
+    let mut p_val = std::mem::MaybeUninit::<VARIANT>::uninit();
+    let res = (*p_obj).Get(name, 0, p_val.as_mut_ptr(), null_mut(), null_mut());
+    SysFreeString(name);
+    if FAILED(res) {
+        return None;
+    }
+    let mut p_val = p_val.assume_init();
+    let ret = if p_val.n1.n2().vt == VT_BSTR as u16 {
+        let s = *p_val.n1.n2().n3.bstrVal();
+        if s.is_null() {
+            None
+        } else {
+            let len = libc::wcslen(s);
+            Some(String::from_utf16_lossy(std::slice::from_raw_parts(s, len)))
+        }
+    } else {
+        None
+    };
+    VariantClear(&mut p_val as *mut _ as *mut _);
+    ret
+}
+
+pub(crate) struct Connection {
     instance: Option<Instance>,
     server_connection: Option<ServerConnection>,
     enumerator: Option<Enumerator>,
 }
 
+// The WMI interfaces are only released in `Drop` and COM is initialized with
+// `COINIT_MULTITHREADED`, so they can be called from any thread.
 unsafe impl Send for Connection {}
 unsafe impl Sync for Connection {}
 
 impl Connection {
     #[allow(clippy::unnecessary_wraps)]
-    fn new() -> Option<Connection> {
+    pub(crate) fn new() -> Option<Connection> {
         // "Funnily", this function returns ok, false or "this function has already been called".
         // So whatever, let's just ignore whatever it might return then!
         unsafe { CoInitializeEx(null_mut(), 0) };
@@ -171,7 +217,7 @@ impl Connection {
         })
     }
 
-    fn initialize_security(self) -> Option<Connection> {
+    pub(crate) fn initialize_security(self) -> Option<Connection> {
         if FAILED(unsafe {
             CoInitializeSecurity(
                 null_mut(),
@@ -191,7 +237,7 @@ impl Connection {
         }
     }
 
-    fn create_instance(mut self) -> Option<Connection> {
+    pub(crate) fn create_instance(mut self) -> Option<Connection> {
         let mut p_loc = null_mut();
 
         if FAILED(unsafe {
@@ -210,13 +256,12 @@ impl Connection {
         }
     }
 
-    fn connect_server(mut self) -> Option<Connection> {
+    pub(crate) fn connect_server(mut self, namespace: &str) -> Option<Connection> {
         let mut p_svc = null_mut();
 
         if let Some(ref instance) = self.instance {
             unsafe {
-                // "root\WMI"
-                let s = bstr!('r', 'o', 'o', 't', '\\', 'W', 'M', 'I');
+                let s = str_to_bstr(namespace);
                 let res = (*instance.0).ConnectServer(
                     s,
                     null_mut(),
@@ -239,7 +284,7 @@ impl Connection {
         Some(self)
     }
 
-    fn set_proxy_blanket(self) -> Option<Connection> {
+    pub(crate) fn set_proxy_blanket(self) -> Option<Connection> {
         if let Some(ref server_connection) = self.server_connection {
             unsafe {
                 if FAILED(CoSetProxyBlanket(
@@ -261,19 +306,14 @@ impl Connection {
         Some(self)
     }
 
-    fn exec_query(mut self) -> Option<Connection> {
+    pub(crate) fn exec_query(mut self, query: &str) -> Option<Connection> {
         let mut p_enumerator = null_mut();
 
         if let Some(ref server_connection) = self.server_connection {
             unsafe {
                 // "WQL"
                 let s = bstr!('W', 'Q', 'L'); // query kind
-                                              // "SELECT * FROM MSAcpi_ThermalZoneTemperature"
-                let query = bstr!(
-                    'S', 'E', 'L', 'E', 'C', 'T', ' ', '*', ' ', 'F', 'R', 'O', 'M', ' ', 'M', 'S',
-                    'A', 'c', 'p', 'i', '_', 'T', 'h', 'e', 'r', 'm', 'a', 'l', 'Z', 'o', 'n', 'e',
-                    'T', 'e', 'm', 'p', 'e', 'r', 'a', 't', 'u', 'r', 'e'
-                );
+                let query = str_to_bstr(query);
                 let hres = (*server_connection.0).ExecQuery(
                     s,
                     query,
@@ -294,6 +334,35 @@ impl Connection {
         Some(self)
     }
 
+    /// Calls `f` on every object returned by the last executed query.
+    pub(crate) fn for_each_object<F: FnMut(*mut IWbemClassObject)>(&mut self, mut f: F) {
+        let p_enum = match self.enumerator.take() {
+            Some(x) => x,
+            None => return,
+        };
+        loop {
+            let mut p_obj: *mut IWbemClassObject = null_mut();
+            let mut nb_returned = 0;
+
+            unsafe {
+                use winapi::um::wbemcli::WBEM_INFINITE;
+                (*p_enum.0).Next(
+                    WBEM_INFINITE as _, // Time out
+                    1,                  // One object
+                    &mut p_obj as *mut _,
+                    &mut nb_returned,
+                );
+            }
+            if nb_returned == 0 {
+                break;
+            }
+            f(p_obj);
+            unsafe {
+                (*p_obj).Release();
+            }
+        }
+    }
+
     fn get_temperature(&mut self, get_critical: bool) -> Option<(f32, Option<f32>)> {
         let p_enum = match self.enumerator.take() {
             Some(x) => x,
@@ -4,46 +4,78 @@
 // Copyright (c) 2018 Guillaume Gomez
 //
 
+use crate::sys::tools::{get_disk_performance, open_drive};
 use crate::{DiskExt, DiskType};
 
 use std::ffi::{OsStr, OsString};
 use std::path::Path;
 
 use winapi::um::fileapi::GetDiskFreeSpaceExW;
+use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
 use winapi::um::winnt::ULARGE_INTEGER;
 
+#[allow(clippy::too_many_arguments)]
 pub fn new_disk(
     name: &OsStr,
+    device_name: &OsStr,
     mount_point: &[u16],
     file_system: &[u8],
     type_: DiskType,
+    is_removable: bool,
     total_space: u64,
+    is_read_only: bool,
 ) -> Option<Disk> {
     if total_space == 0 {
         return None;
     }
     let mut d = Disk {
         type_,
+        is_removable,
         name: name.to_owned(),
+        device_name: device_name.to_owned(),
         file_system: file_system.to_vec(),
         mount_point: mount_point.to_vec(),
         s_mount_point: String::from_utf16_lossy(&mount_point[..mount_point.len() - 1]),
         total_space,
         available_space: 0,
+        mount_options: vec![if is_read_only { "ro" } else { "rw" }.to_owned()],
+        read_count: 0,
+        old_read_count: 0,
+        write_count: 0,
+        old_write_count: 0,
+        queue_depth: 0,
+        idle_time: 0,
+        query_time: 0,
+        io_utilization: 0.,
     };
     d.refresh();
+    // There is no previous value to compare with yet.
+    d.old_read_count = d.read_count;
+    d.old_write_count = d.write_count;
+    d.io_utilization = 0.;
     Some(d)
 }
 
 /// Struct containing a disk information.
 pub struct Disk {
     type_: DiskType,
+    is_removable: bool,
     name: OsString,
+    device_name: OsString,
     file_system: Vec<u8>,
     mount_point: Vec<u16>,
     s_mount_point: String,
     total_space: u64,
     available_space: u64,
+    mount_options: Vec<String>,
+    read_count: u64,
+    old_read_count: u64,
+    write_count: u64,
+    old_write_count: u64,
+    queue_depth: u64,
+    idle_time: i64,
+    query_time: i64,
+    io_utilization: f32,
 }
 
 impl DiskExt for Disk {
@@ -55,6 +87,10 @@ impl DiskExt for Disk {
         &self.name
     }
 
+    fn get_device_name(&self) -> &OsStr {
+        &self.device_name
+    }
+
     fn get_file_system(&self) -> &[u8] {
         &self.file_system
     }
@@ -71,6 +107,42 @@ impl DiskExt for Disk {
         self.available_space
     }
 
+    fn is_read_only(&self) -> bool {
+        self.mount_options.iter().any(|o| o == "ro")
+    }
+
+    fn is_removable(&self) -> bool {
+        self.is_removable
+    }
+
+    fn get_mount_options(&self) -> &[String] {
+        &self.mount_options
+    }
+
+    fn get_read_count(&self) -> u64 {
+        self.read_count.saturating_sub(self.old_read_count)
+    }
+
+    fn get_total_read_count(&self) -> u64 {
+        self.read_count
+    }
+
+    fn get_write_count(&self) -> u64 {
+        self.write_count.saturating_sub(self.old_write_count)
+    }
+
+    fn get_total_write_count(&self) -> u64 {
+        self.write_count
+    }
+
+    fn get_io_utilization(&self) -> f32 {
+        self.io_utilization
+    }
+
+    fn get_queue_depth(&self) -> u64 {
+        self.queue_depth
+    }
+
     fn refresh(&mut self) -> bool {
         if self.total_space != 0 {
             unsafe {
@@ -83,6 +155,7 @@ impl DiskExt for Disk {
                 ) != 0
                 {
                     self.available_space = *tmp.QuadPart();
+                    self.update_performance();
                     return true;
                 }
             }
@@ -90,3 +163,42 @@ impl DiskExt for Disk {
         false
     }
 }
+
+impl Disk {
+    fn update_performance(&mut self) {
+        // The volume is opened as `\\.\C:`, without any access right as we only query it.
+        let drive_name = [
+            b'\\' as u16,
+            b'\\' as u16,
+            b'.' as u16,
+            b'\\' as u16,
+            self.mount_point[0],
+            b':' as u16,
+            0,
+        ];
+        unsafe {
+            let handle = open_drive(&drive_name, 0);
+            if handle == INVALID_HANDLE_VALUE {
+                return;
+            }
+            if let Some(perf) = get_disk_performance(handle) {
+                self.old_read_count = self.read_count;
+                self.read_count = perf.ReadCount as u64;
+                self.old_write_count = self.write_count;
+                self.write_count = perf.WriteCount as u64;
+                self.queue_depth = perf.QueueDepth as u64;
+                // Both times are expressed in 100-nanosecond intervals.
+                let idle_time = *perf.IdleTime.QuadPart();
+                let query_time = *perf.QueryTime.QuadPart();
+                let elapsed = query_time - self.query_time;
+                if elapsed > 0 {
+                    let idle = (idle_time - self.idle_time).max(0) as f32;
+                    self.io_utilization = (100. - idle * 100. / elapsed as f32).max(0.);
+                }
+                self.idle_time = idle_time;
+                self.query_time = query_time;
+            }
+            CloseHandle(handle);
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::sys::component::{get_string_property, Connection};
+use crate::FanExt;
+
+const CIMV2_NAMESPACE: &str = "root\\CIMV2";
+const FAN_QUERY: &str = "SELECT * FROM Win32_Fan";
+
+/// Struct containing a fan information (speed and name for the moment).
+///
+/// Please note that on Windows, the fans are listed through WMI but that `Win32_Fan` doesn't
+/// provide their measured speed (`DesiredSpeed` is only the requested one, when the vendor
+/// fills it), so [`FanExt::get_rpm`] always returns `0`.
+pub struct Fan {
+    label: String,
+}
+
+impl FanExt for Fan {
+    fn get_rpm(&self) -> u32 {
+        0
+    }
+
+    fn get_label(&self) -> &str {
+        &self.label
+    }
+
+    fn refresh(&mut self) {}
+}
+
+pub fn get_fans() -> Vec<Fan> {
+    let mut fans = Vec::new();
+    let mut connection = match Connection::new()
+        .and_then(|x| x.initialize_security())
+        .and_then(|x| x.create_instance())
+        .and_then(|x| x.connect_server(CIMV2_NAMESPACE))
+        .and_then(|x| x.set_proxy_blanket())
+        .and_then(|x| x.exec_query(FAN_QUERY))
+    {
+        Some(c) => c,
+        None => return fans,
+    };
+    connection.for_each_object(|p_obj| unsafe {
+        let label = get_string_property(p_obj, "Name")
+            .or_else(|| get_string_property(p_obj, "DeviceID"))
+            .unwrap_or_else(|| format!("Fan {}", fans.len() + 1));
+        fans.push(Fan { label });
+    });
+    fans
+}
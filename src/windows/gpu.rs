@@ -0,0 +1,254 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::GpuExt;
+
+use std::ffi::OsString;
+use std::os::windows::ffi::OsStringExt;
+use std::ptr::null_mut;
+use std::time::Instant;
+
+use winapi::km::d3dkmthk::{D3DKMT_CLOSEADAPTER, D3DKMT_OPENADAPTERFROMLUID};
+use winapi::shared::dxgi::{
+    CreateDXGIFactory1, IDXGIAdapter1, IDXGIFactory1, DXGI_ADAPTER_DESC1,
+    DXGI_ADAPTER_FLAG_SOFTWARE,
+};
+use winapi::shared::dxgi1_4::{
+    IDXGIAdapter3, DXGI_MEMORY_SEGMENT_GROUP_LOCAL, DXGI_QUERY_VIDEO_MEMORY_INFO,
+};
+use winapi::shared::minwindef::ULONG;
+use winapi::shared::ntdef::{HANDLE, NTSTATUS, NT_SUCCESS};
+use winapi::shared::winerror::SUCCEEDED;
+use winapi::um::unknwnbase::IUnknown;
+use winapi::um::winnt::LUID;
+use winapi::Interface;
+
+// Not provided by `winapi`.
+const D3DKMT_QUERYSTATISTICS_ADAPTER: u32 = 0;
+const D3DKMT_QUERYSTATISTICS_NODE: u32 = 5;
+
+// Not provided by `winapi`. `QueryResult` is a union of which we only read the beginning: the
+// `NodeCount` field of `D3DKMT_QUERYSTATISTICS_ADAPTER_INFORMATION` and the `RunningTime` field
+// of `D3DKMT_QUERYSTATISTICS_NODE_INFORMATION`.
+#[allow(non_snake_case)]
+#[repr(C)]
+struct D3DKMT_QUERYSTATISTICS {
+    Type: u32,
+    AdapterLuid: LUID,
+    hProcess: HANDLE,
+    QueryResult: [u64; 97],
+    NodeId: ULONG,
+}
+
+#[link(name = "gdi32")]
+extern "system" {
+    fn D3DKMTOpenAdapterFromLuid(open: *mut D3DKMT_OPENADAPTERFROMLUID) -> NTSTATUS;
+    fn D3DKMTCloseAdapter(close: *const D3DKMT_CLOSEADAPTER) -> NTSTATUS;
+    fn D3DKMTQueryStatistics(stats: *const D3DKMT_QUERYSTATISTICS) -> NTSTATUS;
+}
+
+/// Struct containing a GPU information.
+///
+/// GPUs are read through DXGI and their usage is computed from the running time of their
+/// engines, returned by `D3DKMTQueryStatistics`.
+pub struct Gpu {
+    name: String,
+    total_memory: u64,
+    used_memory: u64,
+    usage: f32,
+    luid: LUID,
+    // Running time of each engine (in 100 nanoseconds unit) at the last refresh.
+    running_times: Vec<i64>,
+    last_refresh: Instant,
+}
+
+impl GpuExt for Gpu {
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn get_total_memory(&self) -> u64 {
+        self.total_memory
+    }
+
+    fn get_used_memory(&self) -> u64 {
+        self.used_memory
+    }
+
+    fn get_usage(&self) -> f32 {
+        self.usage
+    }
+
+    fn refresh(&mut self) {
+        let luid = self.luid;
+        for_each_adapter(|adapter, desc| {
+            if desc.AdapterLuid.LowPart == luid.LowPart
+                && desc.AdapterLuid.HighPart == luid.HighPart
+            {
+                if let Some(used) = get_used_memory(adapter) {
+                    self.used_memory = used;
+                }
+                false
+            } else {
+                true
+            }
+        });
+        let running_times = get_running_times(luid);
+        let elapsed = self.last_refresh.elapsed().as_nanos() as f32 / 100.;
+        self.last_refresh = Instant::now();
+        // The usage of the GPU is the one of its busiest engine, like in the task manager.
+        self.usage = if elapsed > 0. && running_times.len() == self.running_times.len() {
+            running_times
+                .iter()
+                .zip(self.running_times.iter())
+                .map(|(new, old)| (new - old) as f32 / elapsed * 100.)
+                .fold(0., f32::max)
+                .min(100.)
+        } else {
+            0.
+        };
+        self.running_times = running_times;
+    }
+}
+
+/// Releases the COM object when dropped.
+struct ComPtr<T: Interface>(*mut T);
+
+impl<T: Interface> ComPtr<T> {
+    fn as_unknown(&self) -> &IUnknown {
+        unsafe { &*(self.0 as *mut IUnknown) }
+    }
+
+    fn cast<U: Interface>(&self) -> Option<ComPtr<U>> {
+        let mut obj = null_mut();
+        unsafe {
+            if SUCCEEDED(self.as_unknown().QueryInterface(&U::uuidof(), &mut obj)) {
+                Some(ComPtr(obj as *mut U))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+impl<T: Interface> Drop for ComPtr<T> {
+    fn drop(&mut self) {
+        unsafe {
+            self.as_unknown().Release();
+        }
+    }
+}
+
+/// Calls `f` on each hardware adapter until it returns `false`.
+fn for_each_adapter<F: FnMut(&ComPtr<IDXGIAdapter1>, &DXGI_ADAPTER_DESC1) -> bool>(mut f: F) {
+    unsafe {
+        let mut factory = null_mut();
+        if !SUCCEEDED(CreateDXGIFactory1(&IDXGIFactory1::uuidof(), &mut factory)) {
+            sysinfo_debug!("CreateDXGIFactory1 failed");
+            return;
+        }
+        let factory = ComPtr(factory as *mut IDXGIFactory1);
+        let mut index = 0;
+        loop {
+            let mut adapter = null_mut();
+            if !SUCCEEDED((*factory.0).EnumAdapters1(index, &mut adapter)) {
+                break;
+            }
+            index += 1;
+            let adapter = ComPtr(adapter);
+            let mut desc: DXGI_ADAPTER_DESC1 = std::mem::zeroed();
+            if !SUCCEEDED((*adapter.0).GetDesc1(&mut desc)) {
+                continue;
+            }
+            // We skip the "Microsoft Basic Render Driver".
+            if desc.Flags & DXGI_ADAPTER_FLAG_SOFTWARE != 0 {
+                continue;
+            }
+            if !f(&adapter, &desc) {
+                break;
+            }
+        }
+    }
+}
+
+fn get_used_memory(adapter: &ComPtr<IDXGIAdapter1>) -> Option<u64> {
+    // `IDXGIAdapter3` is only available starting Windows 10.
+    let adapter = adapter.cast::<IDXGIAdapter3>()?;
+    unsafe {
+        let mut info: DXGI_QUERY_VIDEO_MEMORY_INFO = std::mem::zeroed();
+        if SUCCEEDED((*adapter.0).QueryVideoMemoryInfo(
+            0,
+            DXGI_MEMORY_SEGMENT_GROUP_LOCAL,
+            &mut info,
+        )) {
+            Some(info.CurrentUsage / 1_000)
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns the running time of each engine of the adapter.
+fn get_running_times(luid: LUID) -> Vec<i64> {
+    unsafe {
+        let mut open = D3DKMT_OPENADAPTERFROMLUID {
+            AdapterLuid: luid,
+            hAdapter: 0,
+        };
+        if !NT_SUCCESS(D3DKMTOpenAdapterFromLuid(&mut open)) {
+            sysinfo_debug!("D3DKMTOpenAdapterFromLuid failed");
+            return Vec::new();
+        }
+        let mut stats: D3DKMT_QUERYSTATISTICS = std::mem::zeroed();
+        stats.Type = D3DKMT_QUERYSTATISTICS_ADAPTER;
+        stats.AdapterLuid = luid;
+        let mut running_times = Vec::new();
+        if NT_SUCCESS(D3DKMTQueryStatistics(&stats)) {
+            let node_count = (stats.QueryResult[0] >> 32) as ULONG;
+            for node in 0..node_count {
+                let mut stats: D3DKMT_QUERYSTATISTICS = std::mem::zeroed();
+                stats.Type = D3DKMT_QUERYSTATISTICS_NODE;
+                stats.AdapterLuid = luid;
+                stats.NodeId = node;
+                if !NT_SUCCESS(D3DKMTQueryStatistics(&stats)) {
+                    running_times.clear();
+                    break;
+                }
+                running_times.push(stats.QueryResult[0] as i64);
+            }
+        } else {
+            sysinfo_debug!("D3DKMTQueryStatistics failed");
+        }
+        D3DKMTCloseAdapter(&D3DKMT_CLOSEADAPTER {
+            hAdapter: open.hAdapter,
+        });
+        running_times
+    }
+}
+
+pub(crate) fn get_gpus() -> Vec<Gpu> {
+    let mut gpus = Vec::new();
+    for_each_adapter(|adapter, desc| {
+        let len = desc
+            .Description
+            .iter()
+            .position(|c| *c == 0)
+            .unwrap_or(desc.Description.len());
+        gpus.push(Gpu {
+            name: OsString::from_wide(&desc.Description[..len])
+                .to_string_lossy()
+                .into_owned(),
+            total_memory: desc.DedicatedVideoMemory as u64 / 1_000,
+            used_memory: get_used_memory(adapter).unwrap_or(0),
+            usage: 0.,
+            luid: desc.AdapterLuid,
+            running_times: get_running_times(desc.AdapterLuid),
+            last_refresh: Instant::now(),
+        });
+        true
+    });
+    gpus
+}
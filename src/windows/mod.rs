@@ -4,20 +4,46 @@
 // Copyright (c) 2015 Guillaume Gomez
 //
 
+#[cfg(feature = "system")]
+mod battery;
+#[cfg(feature = "component")]
 mod component;
+#[cfg(feature = "disk")]
 mod disk;
+#[cfg(feature = "component")]
+mod fan;
+#[cfg(feature = "system")]
+mod gpu;
 #[macro_use]
 mod macros;
+#[cfg(feature = "network")]
 mod network;
+#[cfg(feature = "process")]
 mod process;
+#[cfg(feature = "cpu")]
 mod processor;
+#[cfg(feature = "process")]
+mod socket;
+#[cfg(feature = "system")]
 mod system;
+#[cfg(any(feature = "cpu", feature = "disk"))]
 mod tools;
+#[cfg(feature = "system")]
 mod users;
 
+#[cfg(feature = "component")]
 pub use self::component::Component;
+#[cfg(feature = "disk")]
 pub use self::disk::Disk;
+#[cfg(feature = "component")]
+pub use self::fan::Fan;
+#[cfg(feature = "system")]
+pub use self::gpu::Gpu;
+#[cfg(feature = "network")]
 pub use self::network::{NetworkData, Networks};
+#[cfg(feature = "process")]
 pub use self::process::{Process, ProcessStatus};
+#[cfg(feature = "cpu")]
 pub use self::processor::Processor;
+#[cfg(feature = "system")]
 pub use self::system::System;
@@ -4,15 +4,41 @@
 // Copyright (c) 2017 Guillaume Gomez
 //
 
-use crate::{NetworkExt, NetworksExt, NetworksIter};
+use crate::common::NetworkHistory;
+use crate::utils::{get_rate, RefreshInterval};
+use crate::{
+    CountingBasis, InterfaceStatus, InterfaceType, NetworkExt, NetworksExt, NetworksIter,
+    RefreshError,
+};
+
+use std::collections::{hash_map, HashMap, VecDeque};
 
-use std::collections::{hash_map, HashMap};
+use std::mem::size_of;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::ptr::null_mut;
+use std::time::{Duration, Instant};
 
-use winapi::shared::ifdef::{MediaConnectStateDisconnected, NET_LUID};
+use winapi::shared::guiddef::GUID;
+use winapi::shared::ifdef::{
+    IfOperStatusDormant, IfOperStatusDown, IfOperStatusLowerLayerDown, IfOperStatusNotPresent,
+    IfOperStatusTesting, IfOperStatusUp, MediaConnectStateDisconnected, IF_OPER_STATUS,
+    NET_IF_ADMIN_STATUS, NET_IF_ADMIN_STATUS_DOWN, NET_IF_ADMIN_STATUS_TESTING,
+    NET_IF_ADMIN_STATUS_UP, NET_LUID,
+};
 use winapi::shared::netioapi::{
     FreeMibTable, GetIfEntry2, GetIfTable2, MIB_IF_ROW2, PMIB_IF_TABLE2,
 };
-use winapi::shared::winerror::NO_ERROR;
+use winapi::shared::ntdef::{HANDLE, LONG};
+use winapi::shared::winerror::{ERROR_BUFFER_OVERFLOW, ERROR_SUCCESS, NO_ERROR};
+use winapi::shared::ws2def::{AF_INET, AF_INET6, AF_UNSPEC, SOCKET_ADDRESS};
+use winapi::um::iphlpapi::GetAdaptersAddresses;
+use winapi::um::iptypes::{
+    GAA_FLAG_INCLUDE_GATEWAYS, GAA_FLAG_SKIP_ANYCAST, GAA_FLAG_SKIP_MULTICAST,
+    GAA_FLAG_SKIP_UNICAST, IP_ADAPTER_ADDRESSES,
+};
+use winapi::um::wlanapi::{
+    wlan_intf_opcode_rssi, WlanCloseHandle, WlanFreeMemory, WlanOpenHandle, WlanQueryInterface,
+};
 
 macro_rules! old_and_new {
     ($ty_:expr, $name:ident, $old:ident, $new_val:expr) => {{
@@ -24,78 +50,77 @@ macro_rules! old_and_new {
 /// Network interfaces.
 ///
 /// ```no_run
-/// use sysinfo::{NetworksExt, System, SystemExt};
+/// use sysinfo::{Networks, NetworksExt};
 ///
-/// let s = System::new_all();
-/// let networks = s.get_networks();
+/// let mut networks = Networks::new();
+/// networks.refresh_networks_list();
 /// ```
+#[derive(Clone)]
 pub struct Networks {
     interfaces: HashMap<String, NetworkData>,
+    history_len: usize,
+    include_loopback: bool,
+    refresh_interval: RefreshInterval,
+}
+
+fn get_interface_type(if_type: u32) -> InterfaceType {
+    match if_type {
+        // IF_TYPE_ETHERNET_CSMACD
+        6 => InterfaceType::Ethernet,
+        // IF_TYPE_IEEE80211
+        71 => InterfaceType::WiFi,
+        // IF_TYPE_SOFTWARE_LOOPBACK
+        24 => InterfaceType::Loopback,
+        // IF_TYPE_TUNNEL
+        131 => InterfaceType::Tunnel,
+        _ => InterfaceType::Other,
+    }
+}
+
+impl Default for Networks {
+    fn default() -> Self {
+        Networks::new()
+    }
 }
 
 impl Networks {
-    pub(crate) fn new() -> Networks {
+    /// Creates an empty `Networks`, which can be used on its own. Call
+    /// [`NetworksExt::refresh_networks_list`][crate::NetworksExt::refresh_networks_list] to fill
+    /// it.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// ```
+    pub fn new() -> Networks {
         Networks {
             interfaces: HashMap::new(),
+            history_len: 0,
+            include_loopback: false,
+            refresh_interval: RefreshInterval::default(),
         }
     }
-}
-
-impl NetworksExt for Networks {
-    #[allow(clippy::needless_lifetimes)]
-    fn iter<'a>(&'a self) -> NetworksIter<'a> {
-        NetworksIter::new(self.interfaces.iter())
-    }
 
-    fn refresh_networks_list(&mut self) {
+    /// If `prune` is `true`, the interfaces which aren't listed anymore are removed.
+    fn update_networks_list(&mut self, prune: bool) -> Result<(), RefreshError> {
         let mut table: PMIB_IF_TABLE2 = std::ptr::null_mut();
-        if unsafe { GetIfTable2(&mut table) } != NO_ERROR {
-            return;
+        let ret = unsafe { GetIfTable2(&mut table) };
+        if ret != NO_ERROR {
+            // The existing interfaces are kept as is.
+            return Err(RefreshError::Os(ret as i32));
         }
 
         for (_, data) in self.interfaces.iter_mut() {
             data.updated = false;
         }
 
-        // In here, this is tricky: we have to filter out the software interfaces to only keep
-        // the hardware ones. To do so, we first check the connection potential speed (if 0, not
-        // interesting), then we check its state: if not open, not interesting either. And finally,
-        // we count the members of a same group: if there is more than 1, then it's software level.
-        let mut groups = HashMap::new();
-        let mut indexes = Vec::new();
-        let ptr = unsafe { (*table).Table.as_ptr() };
-        for i in 0..unsafe { *table }.NumEntries {
-            let ptr = unsafe { &*ptr.offset(i as _) };
-            if (ptr.TransmitLinkSpeed == 0 && ptr.ReceiveLinkSpeed == 0)
-                || ptr.MediaConnectState == MediaConnectStateDisconnected
-                || ptr.PhysicalAddressLength == 0
-            {
-                continue;
-            }
-            let id = vec![
-                ptr.InterfaceGuid.Data2,
-                ptr.InterfaceGuid.Data3,
-                ptr.InterfaceGuid.Data4[0] as _,
-                ptr.InterfaceGuid.Data4[1] as _,
-                ptr.InterfaceGuid.Data4[2] as _,
-                ptr.InterfaceGuid.Data4[3] as _,
-                ptr.InterfaceGuid.Data4[4] as _,
-                ptr.InterfaceGuid.Data4[5] as _,
-                ptr.InterfaceGuid.Data4[6] as _,
-                ptr.InterfaceGuid.Data4[7] as _,
-            ];
-            let entry = groups.entry(id.clone()).or_insert(0);
-            *entry += 1;
-            if *entry > 1 {
-                continue;
-            }
-            indexes.push((i, id));
-        }
-        for (i, id) in indexes {
-            let ptr = unsafe { &*ptr.offset(i as _) };
-            if *groups.get(&id).unwrap_or(&0) > 1 {
-                continue;
-            }
+        let wlan = WlanHandle::new();
+        let rows = unsafe {
+            std::slice::from_raw_parts((*table).Table.as_ptr(), (*table).NumEntries as usize)
+        };
+        for ptr in get_hardware_interfaces(rows) {
             let mut pos = 0;
             for x in ptr.Alias.iter() {
                 if *x == 0 {
@@ -107,6 +132,9 @@ impl NetworksExt for Networks {
                 Ok(s) => s,
                 _ => continue,
             };
+            let wifi_signal = wlan
+                .as_ref()
+                .and_then(|wlan| wlan.get_signal(&ptr.InterfaceGuid));
             match self.interfaces.entry(interface_name) {
                 hash_map::Entry::Occupied(mut e) => {
                     let mut interface = e.get_mut();
@@ -126,6 +154,18 @@ impl NetworksExt for Networks {
                     );
                     old_and_new!(interface, errors_in, old_errors_in, ptr.InErrors);
                     old_and_new!(interface, errors_out, old_errors_out, ptr.OutErrors);
+                    old_and_new!(interface, drops_in, old_drops_in, ptr.InDiscards);
+                    old_and_new!(interface, drops_out, old_drops_out, ptr.OutDiscards);
+                    old_and_new!(interface, multicast_in, old_multicast_in, ptr.InNUcastPkts);
+                    old_and_new!(
+                        interface,
+                        last_refreshed,
+                        old_last_refreshed,
+                        Instant::now()
+                    );
+                    interface.wifi_signal = wifi_signal;
+                    interface.admin_status = get_admin_status(ptr.AdminStatus);
+                    interface.oper_status = get_oper_status(ptr.OperStatus);
                     interface.updated = true;
                 }
                 hash_map::Entry::Vacant(e) => {
@@ -134,6 +174,7 @@ impl NetworksExt for Networks {
 
                     e.insert(NetworkData {
                         id: ptr.InterfaceLuid,
+                        guid: ptr.InterfaceGuid,
                         current_out: ptr.OutOctets,
                         old_out: ptr.OutOctets,
                         current_in: ptr.InOctets,
@@ -146,6 +187,20 @@ impl NetworksExt for Networks {
                         old_errors_in: ptr.InErrors,
                         errors_out: ptr.OutErrors,
                         old_errors_out: ptr.OutErrors,
+                        drops_in: ptr.InDiscards,
+                        old_drops_in: ptr.InDiscards,
+                        drops_out: ptr.OutDiscards,
+                        old_drops_out: ptr.OutDiscards,
+                        multicast_in: ptr.InNUcastPkts,
+                        old_multicast_in: ptr.InNUcastPkts,
+                        wifi_signal,
+                        interface_type: get_interface_type(ptr.Type),
+                        is_physical: is_hardware_interface(ptr),
+                        admin_status: get_admin_status(ptr.AdminStatus),
+                        oper_status: get_oper_status(ptr.OperStatus),
+                        history: NetworkHistory::new(self.history_len),
+                        last_refreshed: Instant::now(),
+                        old_last_refreshed: Instant::now(),
                         updated: true,
                     });
                 }
@@ -154,14 +209,225 @@ impl NetworksExt for Networks {
         unsafe {
             FreeMibTable(table as _);
         }
-        // Remove interfaces which are gone.
-        self.interfaces.retain(|_, d| d.updated);
+        if prune {
+            // Remove interfaces which are gone.
+            self.interfaces.retain(|_, d| d.updated);
+        }
+        Ok(())
+    }
+}
+
+fn get_admin_status(status: NET_IF_ADMIN_STATUS) -> InterfaceStatus {
+    match status {
+        NET_IF_ADMIN_STATUS_UP => InterfaceStatus::Up,
+        NET_IF_ADMIN_STATUS_DOWN => InterfaceStatus::Down,
+        NET_IF_ADMIN_STATUS_TESTING => InterfaceStatus::Testing,
+        _ => InterfaceStatus::Unknown,
+    }
+}
+
+fn get_oper_status(status: IF_OPER_STATUS) -> InterfaceStatus {
+    match status {
+        IfOperStatusUp => InterfaceStatus::Up,
+        IfOperStatusDown => InterfaceStatus::Down,
+        IfOperStatusTesting => InterfaceStatus::Testing,
+        IfOperStatusDormant => InterfaceStatus::Dormant,
+        IfOperStatusNotPresent => InterfaceStatus::NotPresent,
+        IfOperStatusLowerLayerDown => InterfaceStatus::LowerLayerDown,
+        _ => InterfaceStatus::Unknown,
+    }
+}
+
+fn is_hardware_interface(row: &MIB_IF_ROW2) -> bool {
+    row.InterfaceAndOperStatusFlags.HardwareInterface() != 0
+}
+
+/// In here, this is tricky: we have to filter out the software interfaces to only keep the
+/// hardware ones. To do so, we first check the connection potential speed (if 0, not
+/// interesting), then we check its state: if not open, not interesting either. And finally, we
+/// count the members of a same group (interfaces sharing the same `InterfaceGuid`): if there is
+/// more than 1, then it's software level.
+fn get_hardware_interfaces(rows: &[MIB_IF_ROW2]) -> Vec<&MIB_IF_ROW2> {
+    let mut groups = HashMap::new();
+    let mut interfaces = Vec::new();
+    for row in rows {
+        if (row.TransmitLinkSpeed == 0 && row.ReceiveLinkSpeed == 0)
+            || row.MediaConnectState == MediaConnectStateDisconnected
+            || row.PhysicalAddressLength == 0
+        {
+            continue;
+        }
+        let guid = &row.InterfaceGuid;
+        let id = (guid.Data1, guid.Data2, guid.Data3, guid.Data4);
+        *groups.entry(id).or_insert(0) += 1;
+        interfaces.push((row, id));
+    }
+    interfaces
+        .into_iter()
+        .filter(|(_, id)| groups.get(id).copied().unwrap_or(0) < 2)
+        .map(|(row, _)| row)
+        .collect()
+}
+
+/// Returns the list of the network adapters, as filled by `GetAdaptersAddresses`. The buffer is
+/// made of `u64` so the structures are correctly aligned.
+fn get_adapters_addresses() -> Option<Vec<u64>> {
+    let flags = GAA_FLAG_INCLUDE_GATEWAYS
+        | GAA_FLAG_SKIP_UNICAST
+        | GAA_FLAG_SKIP_ANYCAST
+        | GAA_FLAG_SKIP_MULTICAST;
+    // The recommended initial size, to avoid calling it twice most of the time.
+    let mut size = 15_000;
+    loop {
+        let mut buffer: Vec<u64> = vec![0; (size as usize + 7) / 8];
+        match unsafe {
+            GetAdaptersAddresses(
+                AF_UNSPEC as _,
+                flags,
+                null_mut(),
+                buffer.as_mut_ptr() as *mut IP_ADAPTER_ADDRESSES,
+                &mut size,
+            )
+        } {
+            ERROR_SUCCESS => return Some(buffer),
+            ERROR_BUFFER_OVERFLOW => {}
+            _ => return None,
+        }
+    }
+}
+
+/// Reads the address of a `SOCKADDR_IN` or `SOCKADDR_IN6`.
+unsafe fn get_ip_address(address: &SOCKET_ADDRESS) -> Option<IpAddr> {
+    if address.lpSockaddr.is_null() {
+        return None;
+    }
+    let bytes = std::slice::from_raw_parts(
+        address.lpSockaddr as *const u8,
+        address.iSockaddrLength as usize,
+    );
+    match (*address.lpSockaddr).sa_family as i32 {
+        AF_INET if bytes.len() >= 8 => {
+            let mut ip = [0; 4];
+            ip.copy_from_slice(&bytes[4..8]);
+            Some(IpAddr::V4(Ipv4Addr::from(ip)))
+        }
+        AF_INET6 if bytes.len() >= 24 => {
+            let mut ip = [0; 16];
+            ip.copy_from_slice(&bytes[8..24]);
+            Some(IpAddr::V6(Ipv6Addr::from(ip)))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the gateways and the DNS servers of the network adapters which are up.
+fn get_gateways_and_dns_servers() -> (Vec<IpAddr>, Vec<IpAddr>) {
+    let mut gateways = Vec::new();
+    let mut dns_servers = Vec::new();
+    let buffer = match get_adapters_addresses() {
+        Some(buffer) => buffer,
+        None => return (gateways, dns_servers),
+    };
+    unsafe {
+        let mut adapter = buffer.as_ptr() as *const IP_ADAPTER_ADDRESSES;
+        while !adapter.is_null() {
+            if (*adapter).OperStatus == IfOperStatusUp {
+                let mut gateway = (*adapter).FirstGatewayAddress;
+                while !gateway.is_null() {
+                    gateways.extend(get_ip_address(&(*gateway).Address));
+                    gateway = (*gateway).Next;
+                }
+                let mut server = (*adapter).FirstDnsServerAddress;
+                while !server.is_null() {
+                    if let Some(ip) = get_ip_address(&(*server).Address) {
+                        if !dns_servers.contains(&ip) {
+                            dns_servers.push(ip);
+                        }
+                    }
+                    server = (*server).Next;
+                }
+            }
+            adapter = (*adapter).Next;
+        }
+    }
+    (gateways, dns_servers)
+}
+
+/// Handle to the WLAN service, used to retrieve the signal strength of the wireless interfaces.
+struct WlanHandle(HANDLE);
+
+impl WlanHandle {
+    fn new() -> Option<WlanHandle> {
+        let mut version = 0;
+        let mut handle = null_mut();
+        // 2 is the client version for Windows Vista and later.
+        if unsafe { WlanOpenHandle(2, null_mut(), &mut version, &mut handle) } != ERROR_SUCCESS {
+            return None;
+        }
+        Some(WlanHandle(handle))
+    }
+
+    /// Returns `None` if the interface isn't a (connected) wireless one.
+    fn get_signal(&self, guid: &GUID) -> Option<i32> {
+        let mut size = 0;
+        let mut data = null_mut();
+        unsafe {
+            if WlanQueryInterface(
+                self.0,
+                guid,
+                wlan_intf_opcode_rssi,
+                null_mut(),
+                &mut size,
+                &mut data,
+                null_mut(),
+            ) != ERROR_SUCCESS
+            {
+                return None;
+            }
+            let signal = if size as usize >= size_of::<LONG>() {
+                Some(*(data as *const LONG))
+            } else {
+                None
+            };
+            WlanFreeMemory(data);
+            signal
+        }
+    }
+}
+
+impl Drop for WlanHandle {
+    fn drop(&mut self) {
+        unsafe {
+            WlanCloseHandle(self.0, null_mut());
+        }
+    }
+}
+
+impl NetworksExt for Networks {
+    #[allow(clippy::needless_lifetimes)]
+    fn iter<'a>(&'a self) -> NetworksIter<'a> {
+        NetworksIter::new(self.interfaces.iter())
+    }
+
+    fn try_refresh_networks_list(&mut self) -> Result<(), RefreshError> {
+        self.update_networks_list(true)
+    }
+
+    fn refresh_networks_list_no_prune(&mut self) {
+        let _ = self.update_networks_list(false);
     }
 
     #[allow(clippy::uninit_assumed_init)]
     fn refresh(&mut self) {
+        if !self.refresh_interval.should_refresh() {
+            return;
+        }
         let mut entry = unsafe { std::mem::MaybeUninit::<MIB_IF_ROW2>::uninit().assume_init() };
+        let wlan = WlanHandle::new();
         for (_, interface) in self.interfaces.iter_mut() {
+            interface.wifi_signal = wlan
+                .as_ref()
+                .and_then(|wlan| wlan.get_signal(&interface.guid));
             entry.InterfaceLuid = interface.id;
             entry.InterfaceIndex = 0; // to prevent the function to pick this one as index
             if unsafe { GetIfEntry2(&mut entry) } != NO_ERROR {
@@ -183,13 +449,77 @@ impl NetworksExt for Networks {
             );
             old_and_new!(interface, errors_in, old_errors_in, entry.InErrors);
             old_and_new!(interface, errors_out, old_errors_out, entry.OutErrors);
+            old_and_new!(interface, drops_in, old_drops_in, entry.InDiscards);
+            old_and_new!(interface, drops_out, old_drops_out, entry.OutDiscards);
+            interface.admin_status = get_admin_status(entry.AdminStatus);
+            interface.oper_status = get_oper_status(entry.OperStatus);
+            old_and_new!(
+                interface,
+                multicast_in,
+                old_multicast_in,
+                entry.InNUcastPkts
+            );
+            old_and_new!(
+                interface,
+                last_refreshed,
+                old_last_refreshed,
+                Instant::now()
+            );
+            interface
+                .history
+                .update(interface.get_received(), interface.get_transmitted());
         }
     }
+
+    fn set_history_len(&mut self, len: usize) {
+        self.history_len = len;
+        for data in self.interfaces.values_mut() {
+            data.history.set_len(len);
+        }
+    }
+
+    fn set_include_loopback(&mut self, include_loopback: bool) {
+        self.include_loopback = include_loopback;
+    }
+
+    fn is_loopback_included(&self) -> bool {
+        self.include_loopback
+    }
+
+    fn set_min_refresh_interval(&mut self, interval: Duration) {
+        self.refresh_interval.set_min_interval(interval);
+    }
+
+    fn get_default_gateway_v4(&self) -> Option<Ipv4Addr> {
+        get_gateways_and_dns_servers()
+            .0
+            .into_iter()
+            .find_map(|gateway| match gateway {
+                IpAddr::V4(gateway) => Some(gateway),
+                IpAddr::V6(_) => None,
+            })
+    }
+
+    fn get_default_gateway_v6(&self) -> Option<Ipv6Addr> {
+        get_gateways_and_dns_servers()
+            .0
+            .into_iter()
+            .find_map(|gateway| match gateway {
+                IpAddr::V6(gateway) => Some(gateway),
+                IpAddr::V4(_) => None,
+            })
+    }
+
+    fn get_dns_servers(&self) -> Vec<IpAddr> {
+        get_gateways_and_dns_servers().1
+    }
 }
 
 /// Contains network information.
+#[derive(Clone)]
 pub struct NetworkData {
     id: NET_LUID,
+    guid: GUID,
     current_out: u64,
     old_out: u64,
     current_in: u64,
@@ -202,9 +532,43 @@ pub struct NetworkData {
     old_errors_in: u64,
     errors_out: u64,
     old_errors_out: u64,
+    drops_in: u64,
+    old_drops_in: u64,
+    drops_out: u64,
+    old_drops_out: u64,
+    multicast_in: u64,
+    old_multicast_in: u64,
+    wifi_signal: Option<i32>,
+    interface_type: InterfaceType,
+    is_physical: bool,
+    admin_status: InterfaceStatus,
+    oper_status: InterfaceStatus,
+    history: NetworkHistory,
+    last_refreshed: Instant,
+    old_last_refreshed: Instant,
     updated: bool,
 }
 
+impl NetworkData {
+    /// Returns the locally unique identifier (`NET_LUID`) of this interface, which can be used
+    /// to call `netioapi` functions directly.
+    ///
+    /// This method is Windows-only and therefore not portable.
+    ///
+    /// ```no_run
+    /// use sysinfo::{Networks, NetworksExt};
+    ///
+    /// let mut networks = Networks::new();
+    /// networks.refresh_networks_list();
+    /// for (interface_name, network) in &networks {
+    ///     println!("{}: {}", interface_name, network.get_luid());
+    /// }
+    /// ```
+    pub fn get_luid(&self) -> u64 {
+        self.id.Value
+    }
+}
+
 impl NetworkExt for NetworkData {
     fn get_received(&self) -> u64 {
         self.current_in.saturating_sub(self.old_in)
@@ -253,4 +617,140 @@ impl NetworkExt for NetworkData {
     fn get_total_errors_on_transmitted(&self) -> u64 {
         self.errors_out
     }
+
+    fn get_drops_on_received(&self) -> u64 {
+        self.drops_in.saturating_sub(self.old_drops_in)
+    }
+
+    fn get_total_drops_on_received(&self) -> u64 {
+        self.drops_in
+    }
+
+    fn get_drops_on_transmitted(&self) -> u64 {
+        self.drops_out.saturating_sub(self.old_drops_out)
+    }
+
+    fn get_total_drops_on_transmitted(&self) -> u64 {
+        self.drops_out
+    }
+
+    fn get_packets_received_multicast(&self) -> u64 {
+        self.multicast_in.saturating_sub(self.old_multicast_in)
+    }
+
+    fn get_total_packets_received_multicast(&self) -> u64 {
+        self.multicast_in
+    }
+
+    fn get_received_ipv4(&self) -> u64 {
+        self.get_received()
+    }
+
+    fn get_received_ipv6(&self) -> u64 {
+        0
+    }
+
+    fn get_wifi_signal(&self) -> Option<i32> {
+        self.wifi_signal
+    }
+
+    fn get_interface_type(&self) -> InterfaceType {
+        self.interface_type
+    }
+
+    fn is_physical(&self) -> bool {
+        self.is_physical
+    }
+
+    fn get_counting_basis(&self) -> CountingBasis {
+        // `InOctets` and `OutOctets` include the framing characters, tunnels carry IP packets.
+        match self.interface_type {
+            InterfaceType::Tunnel => CountingBasis::Network,
+            _ => CountingBasis::LinkLayer,
+        }
+    }
+
+    fn get_admin_status(&self) -> InterfaceStatus {
+        self.admin_status
+    }
+
+    fn get_oper_status(&self) -> InterfaceStatus {
+        self.oper_status
+    }
+
+    fn get_received_history(&self) -> &VecDeque<u64> {
+        &self.history.received
+    }
+
+    fn get_transmitted_history(&self) -> &VecDeque<u64> {
+        &self.history.transmitted
+    }
+
+    fn get_last_refreshed(&self) -> Instant {
+        self.last_refreshed
+    }
+
+    fn get_received_per_second(&self) -> f64 {
+        get_rate(
+            self.get_received(),
+            self.old_last_refreshed,
+            self.last_refreshed,
+        )
+    }
+
+    fn get_transmitted_per_second(&self) -> f64 {
+        get_rate(
+            self.get_transmitted(),
+            self.old_last_refreshed,
+            self.last_refreshed,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{get_hardware_interfaces, is_hardware_interface};
+    use winapi::shared::netioapi::MIB_IF_ROW2;
+
+    fn new_row(data1: u32, data2: u16) -> MIB_IF_ROW2 {
+        let mut row: MIB_IF_ROW2 = unsafe { std::mem::zeroed() };
+        row.TransmitLinkSpeed = 1_000_000_000;
+        row.ReceiveLinkSpeed = 1_000_000_000;
+        row.PhysicalAddressLength = 6;
+        row.InterfaceGuid.Data1 = data1;
+        row.InterfaceGuid.Data2 = data2;
+        row.InterfaceGuid.Data3 = 0x4242;
+        row.InterfaceGuid.Data4 = [1, 2, 3, 4, 5, 6, 7, 8];
+        row.InterfaceAndOperStatusFlags.set_HardwareInterface(1);
+        row
+    }
+
+    #[test]
+    fn check_interfaces_grouping() {
+        // The two first adapters only differ by `Data1` so they're distinct.
+        let rows = [
+            new_row(1, 0x1234),
+            new_row(2, 0x1234),
+            new_row(3, 0x5678),
+            new_row(3, 0x5678),
+        ];
+        let interfaces = get_hardware_interfaces(&rows);
+        assert_eq!(interfaces.len(), 2);
+        assert_eq!(interfaces[0].InterfaceGuid.Data1, 1);
+        assert_eq!(interfaces[1].InterfaceGuid.Data1, 2);
+    }
+
+    #[test]
+    fn check_hardware_interface() {
+        let mut software = new_row(4, 0x9abc);
+        software
+            .InterfaceAndOperStatusFlags
+            .set_HardwareInterface(0);
+        let rows = [new_row(1, 0x1234), software];
+        // The software interface isn't filtered out but it isn't reported as physical either.
+        let interfaces = get_hardware_interfaces(&rows);
+        assert_eq!(interfaces.len(), 2);
+        assert!(is_hardware_interface(interfaces[0]));
+        assert!(!is_hardware_interface(interfaces[1]));
+    }
 }
@@ -4,48 +4,86 @@
 // Copyright (c) 2018 Guillaume Gomez
 //
 
-use crate::{DiskUsage, Pid, ProcessExt, Signal};
+use crate::sys::socket::SocketTables;
+use crate::{
+    AccessError, DiskUsage, Gid, NetworkUsage, Pid, PriorityError, ProcessExt, ProcessRefreshKind,
+    Signal, SocketInfo, ThreadInfo, Uid,
+};
 
+use std::ffi::OsString;
 use std::fmt::{self, Debug};
 use std::mem::{size_of, zeroed, MaybeUninit};
 use std::ops::Deref;
+use std::os::windows::ffi::OsStringExt;
+use std::os::windows::process::ExitStatusExt;
 use std::path::{Path, PathBuf};
-use std::process;
+use std::process::{self, ExitStatus};
 use std::ptr::null_mut;
 use std::str;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use libc::{c_void, memcpy};
 
 use once_cell::sync::Lazy;
 
+use ntapi::ntexapi::SYSTEM_THREAD_INFORMATION;
+use ntapi::ntkeapi::{DeferredReady, Ready, Running, Standby, Suspended, Waiting, WrSuspended};
+use ntapi::ntpebteb::{PEB, PPEB};
 use ntapi::ntpsapi::{
     NtQueryInformationProcess, ProcessBasicInformation, ProcessCommandLineInformation,
     PROCESSINFOCLASS, PROCESS_BASIC_INFORMATION,
 };
-use ntapi::ntrtl::RtlGetVersion;
+use ntapi::ntrtl::{RtlGetVersion, PRTL_USER_PROCESS_PARAMETERS, RTL_USER_PROCESS_PARAMETERS};
+use winapi::shared::basetsd::SIZE_T;
 use winapi::shared::minwindef::{DWORD, FALSE, FILETIME, MAX_PATH, TRUE, ULONG};
 use winapi::shared::ntdef::{NT_SUCCESS, UNICODE_STRING};
 use winapi::shared::ntstatus::{
     STATUS_BUFFER_OVERFLOW, STATUS_BUFFER_TOO_SMALL, STATUS_INFO_LENGTH_MISMATCH,
 };
-use winapi::um::handleapi::CloseHandle;
-use winapi::um::processthreadsapi::{GetProcessTimes, GetSystemTimes, OpenProcess};
+use winapi::shared::winerror::WAIT_TIMEOUT;
+use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+use winapi::um::memoryapi::ReadProcessMemory;
+use winapi::um::processthreadsapi::{
+    GetExitCodeProcess, GetPriorityClass, GetProcessHandleCount, GetProcessTimes, GetSystemTimes,
+    OpenProcess, OpenProcessToken, SetPriorityClass,
+};
 use winapi::um::psapi::{
     EnumProcessModulesEx, GetModuleBaseNameW, GetModuleFileNameExW, GetProcessMemoryInfo,
     LIST_MODULES_ALL, PROCESS_MEMORY_COUNTERS, PROCESS_MEMORY_COUNTERS_EX,
 };
+use winapi::um::securitybaseapi::{
+    GetSidSubAuthority, GetSidSubAuthorityCount, GetTokenInformation, IsValidSid,
+};
+use winapi::um::synchapi::WaitForSingleObject;
 use winapi::um::sysinfoapi::GetSystemTimeAsFileTime;
-use winapi::um::winbase::GetProcessIoCounters;
+use winapi::um::tlhelp32::{
+    CreateToolhelp32Snapshot, Thread32First, Thread32Next, TH32CS_SNAPTHREAD, THREADENTRY32,
+};
+use winapi::um::winbase::{
+    GetProcessIoCounters, QueryFullProcessImageNameW, ABOVE_NORMAL_PRIORITY_CLASS,
+    BELOW_NORMAL_PRIORITY_CLASS, HIGH_PRIORITY_CLASS, IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS,
+    REALTIME_PRIORITY_CLASS, WAIT_OBJECT_0,
+};
 use winapi::um::winnt::{
-    HANDLE, IO_COUNTERS, PROCESS_QUERY_INFORMATION, PROCESS_VM_READ, RTL_OSVERSIONINFOEXW,
-    ULARGE_INTEGER,
+    TokenPrimaryGroup, TokenUser, HANDLE, IO_COUNTERS, PROCESS_QUERY_INFORMATION,
+    PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SET_INFORMATION, PROCESS_VM_READ, PSID,
+    RTL_OSVERSIONINFOEXW, SYNCHRONIZE, TOKEN_INFORMATION_CLASS, TOKEN_QUERY, ULARGE_INTEGER,
 };
 
 /// Enum describing the different status of a process.
+///
+/// Windows doesn't have a process state, so it is computed from the state of its threads.
 #[derive(Clone, Copy, Debug)]
 pub enum ProcessStatus {
-    /// Currently runnable.
+    /// At least one of its threads is running or ready to run.
     Run,
+    /// All its threads are waiting.
+    Sleep,
+    /// All its threads are suspended.
+    Stop,
+    /// Unknown.
+    Unknown(u32),
 }
 
 impl ProcessStatus {
@@ -53,6 +91,35 @@ impl ProcessStatus {
     pub fn as_str(&self) -> &str {
         match *self {
             ProcessStatus::Run => "Runnable",
+            ProcessStatus::Sleep => "Sleeping",
+            ProcessStatus::Stop => "Stopped",
+            ProcessStatus::Unknown(_) => "Unknown",
+        }
+    }
+
+    /// Returns `true` if the process is currently runnable.
+    pub(crate) fn is_running(&self) -> bool {
+        matches!(*self, ProcessStatus::Run)
+    }
+}
+
+impl From<&[SYSTEM_THREAD_INFORMATION]> for ProcessStatus {
+    fn from(threads: &[SYSTEM_THREAD_INFORMATION]) -> ProcessStatus {
+        if threads.is_empty() {
+            return ProcessStatus::Unknown(0);
+        }
+        let mut all_suspended = true;
+        for thread in threads {
+            match thread.ThreadState {
+                Ready | Running | Standby | DeferredReady => return ProcessStatus::Run,
+                Waiting if thread.WaitReason == Suspended || thread.WaitReason == WrSuspended => {}
+                _ => all_suspended = false,
+            }
+        }
+        if all_suspended {
+            ProcessStatus::Stop
+        } else {
+            ProcessStatus::Sleep
         }
     }
 }
@@ -64,11 +131,11 @@ impl fmt::Display for ProcessStatus {
 }
 
 fn get_process_handler(pid: Pid) -> Option<HANDLE> {
-    if pid == 0 {
+    if pid.0 == 0 {
         return None;
     }
     let options = PROCESS_QUERY_INFORMATION | PROCESS_VM_READ;
-    let process_handler = unsafe { OpenProcess(options, FALSE, pid as DWORD) };
+    let process_handler = unsafe { OpenProcess(options, FALSE, pid.0 as DWORD) };
     if process_handler.is_null() {
         None
     } else {
@@ -76,6 +143,15 @@ fn get_process_handler(pid: Pid) -> Option<HANDLE> {
     }
 }
 
+/// Returns why [`get_process_handler`] failed. It must be called right after it.
+fn get_process_handler_error(pid: Pid) -> Option<AccessError> {
+    if pid.0 == 0 {
+        None
+    } else {
+        Some(AccessError::from(std::io::Error::last_os_error()))
+    }
+}
+
 #[derive(Clone)]
 struct PtrWrapper<T: Clone>(T);
 
@@ -87,8 +163,10 @@ impl<T: Clone> Deref for PtrWrapper<T> {
     }
 }
 
-unsafe impl<T: Clone> Send for PtrWrapper<T> {}
-unsafe impl<T: Clone> Sync for PtrWrapper<T> {}
+// The process handle is owned by the `Process` (it's closed when it's dropped) and a kernel
+// object handle can be used from any thread.
+unsafe impl Send for PtrWrapper<HANDLE> {}
+unsafe impl Sync for PtrWrapper<HANDLE> {}
 
 /// Struct containing a process' information.
 pub struct Process {
@@ -102,16 +180,32 @@ pub struct Process {
     pub(crate) memory: u64,
     pub(crate) virtual_memory: u64,
     parent: Option<Pid>,
-    status: ProcessStatus,
+    pub(crate) status: ProcessStatus,
     handle: PtrWrapper<HANDLE>,
     cpu_calc_values: CPUsageCalculationValues,
+    accumulated_cpu_time: u64,
     start_time: u64,
+    pub(crate) run_time: u64,
+    pub(crate) thread_count: u32,
+    pub(crate) open_files_count: Option<usize>,
+    priority: i32,
+    user_id: Option<Uid>,
+    group_id: Option<Gid>,
     cpu_usage: f32,
     pub(crate) updated: bool,
+    /// `false` if the process exited but is kept for one more refresh.
+    pub(crate) exists: bool,
+    last_error: Option<AccessError>,
     old_read_bytes: u64,
     old_written_bytes: u64,
     read_bytes: u64,
     written_bytes: u64,
+    old_minor_faults: u64,
+    old_major_faults: u64,
+    minor_faults: u64,
+    major_faults: u64,
+    /// Socket tables of the last refresh of this process.
+    pub(crate) socket_tables: Option<Arc<SocketTables>>,
 }
 
 struct CPUsageCalculationValues {
@@ -178,12 +272,15 @@ unsafe fn get_h_mod(process_handler: HANDLE, h_mod: &mut *mut c_void) -> bool {
 
 unsafe fn get_exe(process_handler: HANDLE, h_mod: *mut c_void) -> PathBuf {
     let mut exe_buf = [0u16; MAX_PATH + 1];
-    GetModuleFileNameExW(
+    if GetModuleFileNameExW(
         process_handler,
         h_mod as _,
         exe_buf.as_mut_ptr(),
         MAX_PATH as DWORD + 1,
-    );
+    ) == 0
+    {
+        return query_full_process_image_name(process_handler).unwrap_or_default();
+    }
 
     let mut pos = 0;
     for x in exe_buf.iter() {
@@ -199,7 +296,7 @@ unsafe fn get_exe(process_handler: HANDLE, h_mod: *mut c_void) -> PathBuf {
 impl Process {
     #[allow(clippy::uninit_assumed_init)]
     pub(crate) fn new_from_pid(pid: Pid) -> Option<Process> {
-        let process_handler = unsafe { OpenProcess(PROCESS_QUERY_INFORMATION, FALSE, pid as _) };
+        let process_handler = unsafe { OpenProcess(PROCESS_QUERY_INFORMATION, FALSE, pid.0 as _) };
         if process_handler.is_null() {
             return None;
         }
@@ -220,7 +317,7 @@ impl Process {
         Some(Process::new_with_handle(
             pid,
             if info.InheritedFromUniqueProcessId as usize != 0 {
-                Some(info.InheritedFromUniqueProcessId as usize)
+                Some(Pid(info.InheritedFromUniqueProcessId as usize))
             } else {
                 None
             },
@@ -234,13 +331,23 @@ impl Process {
         memory: u64,
         virtual_memory: u64,
         name: String,
+        refresh_kind: ProcessRefreshKind,
     ) -> Process {
         if let Some(handle) = get_process_handler(pid) {
             let mut h_mod = null_mut();
             unsafe { get_h_mod(handle, &mut h_mod) };
-            let environ = unsafe { get_proc_env(handle, pid as u32, &name) };
+            let environ = if refresh_kind.environ() {
+                unsafe { get_proc_env(handle) }
+            } else {
+                Vec::new()
+            };
 
             let exe = unsafe { get_exe(handle, h_mod) };
+            let (user_id, group_id) = if refresh_kind.user() {
+                unsafe { get_user_and_group_ids(handle) }
+            } else {
+                (None, None)
+            };
             let mut root = exe.clone();
             root.pop();
             Process {
@@ -248,24 +355,43 @@ impl Process {
                 name,
                 pid,
                 parent,
-                cmd: get_cmd_line(handle),
+                cmd: if refresh_kind.cmd() {
+                    get_cmd_line(handle)
+                } else {
+                    Vec::new()
+                },
                 environ,
                 exe,
-                cwd: PathBuf::new(),
+                cwd: get_cwd(handle),
                 root,
                 status: ProcessStatus::Run,
                 memory,
                 virtual_memory,
                 cpu_usage: 0.,
                 cpu_calc_values: CPUsageCalculationValues::new(),
+                accumulated_cpu_time: 0,
                 start_time: unsafe { get_start_time(handle) },
+                run_time: 0,
+                thread_count: 0,
+                open_files_count: None,
+                priority: 0,
+                user_id,
+                group_id,
                 updated: true,
+                exists: true,
+                last_error: None,
                 old_read_bytes: 0,
                 old_written_bytes: 0,
                 read_bytes: 0,
                 written_bytes: 0,
+                old_minor_faults: 0,
+                old_major_faults: 0,
+                minor_faults: 0,
+                major_faults: 0,
+                socket_tables: None,
             }
         } else {
+            let last_error = get_process_handler_error(pid);
             Process {
                 handle: PtrWrapper(null_mut()),
                 name,
@@ -281,12 +407,26 @@ impl Process {
                 virtual_memory,
                 cpu_usage: 0.,
                 cpu_calc_values: CPUsageCalculationValues::new(),
+                accumulated_cpu_time: 0,
                 start_time: 0,
+                run_time: 0,
+                thread_count: 0,
+                open_files_count: None,
+                priority: 0,
+                user_id: None,
+                group_id: None,
                 updated: true,
+                exists: true,
+                last_error,
                 old_read_bytes: 0,
                 old_written_bytes: 0,
                 read_bytes: 0,
                 written_bytes: 0,
+                old_minor_faults: 0,
+                old_major_faults: 0,
+                minor_faults: 0,
+                major_faults: 0,
+                socket_tables: None,
             }
         }
     }
@@ -300,9 +440,10 @@ impl Process {
             } else {
                 String::new()
             };
-            let environ = get_proc_env(process_handler, pid as u32, &name);
+            let environ = get_proc_env(process_handler);
 
             let exe = get_exe(process_handler, h_mod);
+            let (user_id, group_id) = get_user_and_group_ids(process_handler);
             let mut root = exe.clone();
             root.pop();
             Process {
@@ -313,34 +454,44 @@ impl Process {
                 cmd: get_cmd_line(process_handler),
                 environ,
                 exe,
-                cwd: PathBuf::new(),
+                cwd: get_cwd(process_handler),
                 root,
                 status: ProcessStatus::Run,
                 memory: 0,
                 virtual_memory: 0,
                 cpu_usage: 0.,
                 cpu_calc_values: CPUsageCalculationValues::new(),
+                accumulated_cpu_time: 0,
                 start_time: get_start_time(process_handler),
+                run_time: 0,
+                thread_count: 0,
+                open_files_count: None,
+                priority: 0,
+                user_id,
+                group_id,
                 updated: true,
+                exists: true,
+                last_error: None,
                 old_read_bytes: 0,
                 old_written_bytes: 0,
                 read_bytes: 0,
                 written_bytes: 0,
+                old_minor_faults: 0,
+                old_major_faults: 0,
+                minor_faults: 0,
+                major_faults: 0,
+                socket_tables: None,
             }
         }
     }
 }
 
-// TODO: it's possible to get environment variables like it's done in
-// https://github.com/processhacker/processhacker
-//
-// They have a very nice function called PhGetProcessEnvironment. Just very complicated as it
-// seems...
 impl ProcessExt for Process {
     fn new(pid: Pid, parent: Option<Pid>, _: u64) -> Process {
         if let Some(process_handler) = get_process_handler(pid) {
             Process::new_with_handle(pid, parent, process_handler)
         } else {
+            let last_error = get_process_handler_error(pid);
             Process {
                 handle: PtrWrapper(null_mut()),
                 name: String::new(),
@@ -356,12 +507,26 @@ impl ProcessExt for Process {
                 virtual_memory: 0,
                 cpu_usage: 0.,
                 cpu_calc_values: CPUsageCalculationValues::new(),
+                accumulated_cpu_time: 0,
                 start_time: 0,
+                run_time: 0,
+                thread_count: 0,
+                open_files_count: None,
+                priority: 0,
+                user_id: None,
+                group_id: None,
                 updated: true,
+                exists: true,
+                last_error,
                 old_read_bytes: 0,
                 old_written_bytes: 0,
                 read_bytes: 0,
                 written_bytes: 0,
+                old_minor_faults: 0,
+                old_major_faults: 0,
+                minor_faults: 0,
+                major_faults: 0,
+                socket_tables: None,
             }
         }
     }
@@ -375,6 +540,15 @@ impl ProcessExt for Process {
         }
     }
 
+    fn wait(&self) -> Option<ExitStatus> {
+        wait_pid(self.pid, || false)
+    }
+
+    fn wait_timeout(&self, timeout: Duration) -> Option<ExitStatus> {
+        let start = Instant::now();
+        wait_pid(self.pid, || start.elapsed() >= timeout)
+    }
+
     fn name(&self) -> &str {
         &self.name
     }
@@ -411,10 +585,39 @@ impl ProcessExt for Process {
         self.virtual_memory
     }
 
+    fn shared_memory(&self) -> u64 {
+        0
+    }
+
+    fn swap_memory(&self) -> u64 {
+        0
+    }
+
     fn parent(&self) -> Option<Pid> {
         self.parent
     }
 
+    fn is_kernel_thread(&self) -> bool {
+        // "Idle" and "System" processes.
+        self.pid.0 == 0 || self.pid.0 == 4
+    }
+
+    fn is_thread(&self) -> bool {
+        false
+    }
+
+    fn tgid(&self) -> Pid {
+        self.pid()
+    }
+
+    fn session_id(&self) -> Option<i32> {
+        None
+    }
+
+    fn tty(&self) -> Option<&str> {
+        None
+    }
+
     fn status(&self) -> ProcessStatus {
         self.status
     }
@@ -423,10 +626,114 @@ impl ProcessExt for Process {
         self.start_time
     }
 
+    fn run_time(&self) -> u64 {
+        self.run_time
+    }
+
+    fn thread_count(&self) -> u32 {
+        self.thread_count
+    }
+
+    fn threads(&self) -> &[ThreadInfo] {
+        &[]
+    }
+
+    fn open_files_count(&self) -> Option<usize> {
+        self.open_files_count
+    }
+
+    fn open_sockets(&self) -> Vec<SocketInfo> {
+        match self.socket_tables {
+            Some(ref tables) => tables.get_process_sockets(self.pid),
+            None => SocketTables::default().get_process_sockets(self.pid),
+        }
+    }
+
+    fn network_usage(&self) -> Option<NetworkUsage> {
+        None
+    }
+
+    fn user_id(&self) -> Option<Uid> {
+        self.user_id
+    }
+
+    fn effective_user_id(&self) -> Option<Uid> {
+        self.user_id
+    }
+
+    fn group_id(&self) -> Option<Gid> {
+        self.group_id
+    }
+
+    fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    fn set_nice(&self, nice: i32) -> Result<(), PriorityError> {
+        let priority_class = if nice >= 15 {
+            IDLE_PRIORITY_CLASS
+        } else if nice >= 5 {
+            BELOW_NORMAL_PRIORITY_CLASS
+        } else if nice > -5 {
+            NORMAL_PRIORITY_CLASS
+        } else if nice > -13 {
+            ABOVE_NORMAL_PRIORITY_CLASS
+        } else if nice > -18 {
+            HIGH_PRIORITY_CLASS
+        } else {
+            REALTIME_PRIORITY_CLASS
+        };
+        unsafe {
+            let handle = OpenProcess(PROCESS_SET_INFORMATION, FALSE, self.pid.0 as DWORD);
+            if handle.is_null() {
+                return Err(std::io::Error::last_os_error().into());
+            }
+            let ret = if SetPriorityClass(handle, priority_class) != FALSE {
+                Ok(())
+            } else {
+                Err(std::io::Error::last_os_error().into())
+            };
+            CloseHandle(handle);
+            ret
+        }
+    }
+
+    fn oom_score(&self) -> Option<u32> {
+        None
+    }
+
+    fn oom_score_adj(&self) -> Option<i32> {
+        None
+    }
+
+    fn cgroup(&self) -> Option<String> {
+        None
+    }
+
+    fn exists(&self) -> bool {
+        self.exists
+    }
+
+    fn last_error(&self) -> Option<AccessError> {
+        self.last_error
+    }
+
+    fn io_wait_time(&self) -> Option<u64> {
+        None
+    }
+
+    fn sched_delay(&self) -> Option<u64> {
+        None
+    }
+
     fn cpu_usage(&self) -> f32 {
         self.cpu_usage
     }
 
+    fn accumulated_cpu_time(&self) -> u64 {
+        self.accumulated_cpu_time
+    }
+
     fn disk_usage(&self) -> DiskUsage {
         DiskUsage {
             written_bytes: self.written_bytes - self.old_written_bytes,
@@ -435,6 +742,22 @@ impl ProcessExt for Process {
             total_read_bytes: self.read_bytes,
         }
     }
+
+    fn minor_faults(&self) -> u64 {
+        self.minor_faults.saturating_sub(self.old_minor_faults)
+    }
+
+    fn major_faults(&self) -> u64 {
+        self.major_faults.saturating_sub(self.old_major_faults)
+    }
+
+    fn total_minor_faults(&self) -> u64 {
+        self.minor_faults
+    }
+
+    fn total_major_faults(&self) -> u64 {
+        self.major_faults
+    }
 }
 
 impl Drop for Process {
@@ -448,6 +771,42 @@ impl Drop for Process {
     }
 }
 
+/// Returns the relative identifier (the last sub-authority) of the SID of the given class (either
+/// `TokenUser` or `TokenPrimaryGroup`) from the process token.
+unsafe fn get_token_rid(token: HANDLE, class: TOKEN_INFORMATION_CLASS) -> Option<u32> {
+    let mut size = 0;
+    GetTokenInformation(token, class, null_mut(), 0, &mut size);
+    if size == 0 {
+        return None;
+    }
+    // We use `u64` to ensure that the buffer is correctly aligned.
+    let mut buffer: Vec<u64> = vec![0; (size as usize + 7) / 8];
+    if GetTokenInformation(token, class, buffer.as_mut_ptr() as *mut _, size, &mut size) == FALSE {
+        return None;
+    }
+    // Both `TOKEN_USER` and `TOKEN_PRIMARY_GROUP` start with the SID pointer.
+    let sid = *(buffer.as_ptr() as *const PSID);
+    if sid.is_null() || IsValidSid(sid) == FALSE {
+        return None;
+    }
+    let count = *GetSidSubAuthorityCount(sid);
+    if count == 0 {
+        return None;
+    }
+    Some(*GetSidSubAuthority(sid, count as DWORD - 1))
+}
+
+unsafe fn get_user_and_group_ids(handle: HANDLE) -> (Option<Uid>, Option<Gid>) {
+    let mut token = null_mut();
+    if OpenProcessToken(handle, TOKEN_QUERY, &mut token) == FALSE {
+        return (None, None);
+    }
+    let user_id = get_token_rid(token, TokenUser).map(Uid);
+    let group_id = get_token_rid(token, TokenPrimaryGroup).map(Gid);
+    CloseHandle(token);
+    (user_id, group_id)
+}
+
 unsafe fn get_start_time(handle: HANDLE) -> u64 {
     let mut fstart: FILETIME = zeroed();
     let mut x = zeroed();
@@ -525,73 +884,89 @@ unsafe fn get_cmdline_from_buffer(buffer: *const u16) -> Vec<String> {
     res
 }
 
-fn get_cmd_line_old(handle: HANDLE) -> Vec<String> {
-    use ntapi::ntpebteb::{PEB, PPEB};
-    use ntapi::ntrtl::{PRTL_USER_PROCESS_PARAMETERS, RTL_USER_PROCESS_PARAMETERS};
-    use winapi::shared::basetsd::SIZE_T;
-    use winapi::um::memoryapi::ReadProcessMemory;
+unsafe fn get_process_params(handle: HANDLE) -> Option<RTL_USER_PROCESS_PARAMETERS> {
+    let mut pinfo = MaybeUninit::<PROCESS_BASIC_INFORMATION>::uninit();
+    if NtQueryInformationProcess(
+        handle,
+        0, // ProcessBasicInformation
+        pinfo.as_mut_ptr() as *mut _,
+        size_of::<PROCESS_BASIC_INFORMATION>() as u32,
+        null_mut(),
+    ) != 0
+    {
+        return None;
+    }
+    let pinfo = pinfo.assume_init();
+
+    let ppeb: PPEB = pinfo.PebBaseAddress;
+    let mut peb_copy = MaybeUninit::<PEB>::uninit();
+    if ReadProcessMemory(
+        handle,
+        ppeb as *mut _,
+        peb_copy.as_mut_ptr() as *mut _,
+        size_of::<PEB>() as SIZE_T,
+        std::ptr::null_mut(),
+    ) != TRUE
+    {
+        return None;
+    }
+    let peb_copy = peb_copy.assume_init();
 
+    let proc_param = peb_copy.ProcessParameters;
+    let mut rtl_proc_param_copy = MaybeUninit::<RTL_USER_PROCESS_PARAMETERS>::uninit();
+    if ReadProcessMemory(
+        handle,
+        proc_param as *mut PRTL_USER_PROCESS_PARAMETERS as *mut _,
+        rtl_proc_param_copy.as_mut_ptr() as *mut _,
+        size_of::<RTL_USER_PROCESS_PARAMETERS>() as SIZE_T,
+        std::ptr::null_mut(),
+    ) != TRUE
+    {
+        return None;
+    }
+    Some(rtl_proc_param_copy.assume_init())
+}
+
+/// Reads the content of a `UNICODE_STRING` living in the memory of another process. The returned
+/// buffer is NUL-terminated.
+unsafe fn read_remote_unicode_string(handle: HANDLE, s: &UNICODE_STRING) -> Option<Vec<u16>> {
+    let len = s.Length as usize / 2;
+
+    // For len symbols + '\0'
+    let mut buffer_copy: Vec<u16> = Vec::with_capacity(len + 1);
+    buffer_copy.set_len(len);
+    if ReadProcessMemory(
+        handle,
+        s.Buffer as *mut _,
+        buffer_copy.as_mut_ptr() as *mut _,
+        len * 2,
+        std::ptr::null_mut(),
+    ) != TRUE
+    {
+        return None;
+    }
+    buffer_copy.push(0);
+    Some(buffer_copy)
+}
+
+fn get_cmd_line_old(handle: HANDLE) -> Vec<String> {
     unsafe {
-        let mut pinfo = MaybeUninit::<PROCESS_BASIC_INFORMATION>::uninit();
-        if NtQueryInformationProcess(
-            handle,
-            0, // ProcessBasicInformation
-            pinfo.as_mut_ptr() as *mut _,
-            size_of::<PROCESS_BASIC_INFORMATION>() as u32,
-            null_mut(),
-        ) != 0
-        {
-            return Vec::new();
-        }
-        let pinfo = pinfo.assume_init();
-
-        let ppeb: PPEB = pinfo.PebBaseAddress;
-        let mut peb_copy = MaybeUninit::<PEB>::uninit();
-        if ReadProcessMemory(
-            handle,
-            ppeb as *mut _,
-            peb_copy.as_mut_ptr() as *mut _,
-            size_of::<PEB>() as SIZE_T,
-            std::ptr::null_mut(),
-        ) != TRUE
-        {
-            return Vec::new();
-        }
-        let peb_copy = peb_copy.assume_init();
-
-        let proc_param = peb_copy.ProcessParameters;
-        let mut rtl_proc_param_copy = MaybeUninit::<RTL_USER_PROCESS_PARAMETERS>::uninit();
-        if ReadProcessMemory(
-            handle,
-            proc_param as *mut PRTL_USER_PROCESS_PARAMETERS as *mut _,
-            rtl_proc_param_copy.as_mut_ptr() as *mut _,
-            size_of::<RTL_USER_PROCESS_PARAMETERS>() as SIZE_T,
-            std::ptr::null_mut(),
-        ) != TRUE
-        {
-            return Vec::new();
-        }
-        let rtl_proc_param_copy = rtl_proc_param_copy.assume_init();
-
-        let len = rtl_proc_param_copy.CommandLine.Length as usize;
-        let len = len / 2;
-
-        // For len symbols + '\0'
-        let mut buffer_copy: Vec<u16> = Vec::with_capacity(len + 1);
-        buffer_copy.set_len(len);
-        if ReadProcessMemory(
-            handle,
-            rtl_proc_param_copy.CommandLine.Buffer as *mut _,
-            buffer_copy.as_mut_ptr() as *mut _,
-            len * 2,
-            std::ptr::null_mut(),
-        ) != TRUE
-        {
-            return Vec::new();
-        }
-        buffer_copy.push(0);
+        get_process_params(handle)
+            .and_then(|params| read_remote_unicode_string(handle, &params.CommandLine))
+            .map(|buffer| get_cmdline_from_buffer(buffer.as_ptr()))
+            .unwrap_or_default()
+    }
+}
 
-        get_cmdline_from_buffer(buffer_copy.as_ptr())
+fn get_cwd(handle: HANDLE) -> PathBuf {
+    unsafe {
+        get_process_params(handle)
+            .and_then(|params| read_remote_unicode_string(handle, &params.CurrentDirectory.DosPath))
+            .map(|buffer| {
+                // We remove the ending NUL character.
+                PathBuf::from(OsString::from_wide(&buffer[..buffer.len() - 1]))
+            })
+            .unwrap_or_default()
     }
 }
 
@@ -617,83 +992,114 @@ fn get_cmd_line(handle: HANDLE) -> Vec<String> {
     }
 }
 
-#[allow(clippy::let_and_return)]
-unsafe fn get_proc_env(_handle: HANDLE, _pid: u32, _name: &str) -> Vec<String> {
-    let ret = Vec::new();
-    /*
-    println!("current pid: {}", kernel32::GetCurrentProcessId());
-    if kernel32::GetCurrentProcessId() == pid {
-        println!("current proc!");
-        for (key, value) in env::vars() {
-            ret.push(format!("{}={}", key, value));
+unsafe fn get_proc_env(handle: HANDLE) -> Vec<String> {
+    let params = match get_process_params(handle) {
+        Some(params) => params,
+        None => return Vec::new(),
+    };
+    let len = params.EnvironmentSize as usize / 2;
+    if params.Environment.is_null() || len == 0 {
+        return Vec::new();
+    }
+    let mut buffer: Vec<u16> = Vec::with_capacity(len);
+    if ReadProcessMemory(
+        handle,
+        params.Environment as *mut _,
+        buffer.as_mut_ptr() as *mut _,
+        len * 2,
+        null_mut(),
+    ) != TRUE
+    {
+        return Vec::new();
+    }
+    buffer.set_len(len);
+    // The environment block is a list of NUL-terminated "KEY=VALUE" strings, ending with an empty
+    // string.
+    buffer
+        .split(|c| *c == 0)
+        .take_while(|var| !var.is_empty())
+        .map(String::from_utf16_lossy)
+        .collect()
+}
+
+unsafe fn query_full_process_image_name(handle: HANDLE) -> Option<PathBuf> {
+    let mut exe_buf = [0u16; MAX_PATH + 1];
+    let mut size = exe_buf.len() as DWORD;
+    if QueryFullProcessImageNameW(handle, 0, exe_buf.as_mut_ptr(), &mut size) == 0 {
+        return None;
+    }
+    Some(PathBuf::from(OsString::from_wide(
+        &exe_buf[..size as usize],
+    )))
+}
+
+/// Used when we don't have enough rights to open the process with `PROCESS_VM_READ`: in this
+/// case, we can still get the executable path with a "limited" handle.
+pub(crate) fn get_executable_path(pid: Pid) -> PathBuf {
+    if pid.0 == 0 {
+        return PathBuf::new();
+    }
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, FALSE, pid.0 as DWORD);
+        if handle.is_null() {
+            return PathBuf::new();
+        }
+        let exe = query_full_process_image_name(handle);
+        CloseHandle(handle);
+        exe.unwrap_or_default()
+    }
+}
+
+/// Counts the threads owned by the given process by walking through a snapshot of all the
+/// threads of the system.
+pub(crate) fn get_thread_count(pid: Pid) -> u32 {
+    unsafe {
+        let snapshot = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0);
+        if snapshot == INVALID_HANDLE_VALUE {
+            return 0;
         }
-        return ret;
-    }
-    println!("1");
-    let snapshot_handle = kernel32::CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0);
-    if !snapshot_handle.is_null() {
-        println!("2");
-        let mut target_thread: THREADENTRY32 = zeroed();
-        target_thread.dwSize = size_of::<THREADENTRY32>() as DWORD;
-        if kernel32::Thread32First(snapshot_handle, &mut target_thread) == TRUE {
-            println!("3");
+        let mut entry: THREADENTRY32 = zeroed();
+        entry.dwSize = size_of::<THREADENTRY32>() as DWORD;
+        let mut count = 0;
+        if Thread32First(snapshot, &mut entry) != FALSE {
             loop {
-                if target_thread.th32OwnerProcessID == pid {
-                    println!("4");
-                    let thread_handle = kernel32::OpenThread(THREAD_SUSPEND_RESUME | THREAD_QUERY_INFORMATION | THREAD_GET_CONTEXT,
-                                                             FALSE,
-                                                             target_thread.th32ThreadID);
-                    if !thread_handle.is_null() {
-                        println!("5 -> {}", pid);
-                        if kernel32::SuspendThread(thread_handle) != DWORD::max_value() {
-                            println!("6");
-                            let mut context = zeroed();
-                            if kernel32::GetThreadContext(thread_handle, &mut context) != 0 {
-                                println!("7 --> {:?}", context);
-                                let mut x = vec![0u8; 10];
-                                if kernel32::ReadProcessMemory(handle,
-                                                               context.MxCsr as usize as *mut winapi::c_void,
-                                                               x.as_mut_ptr() as *mut winapi::c_void,
-                                                               x.len() as u64,
-                                                               null_mut()) != 0 {
-                                    for y in x {
-                                        print!("{}", y as char);
-                                    }
-                                    println!("");
-                                } else {
-                                    println!("failure... {:?}", kernel32::GetLastError());
-                                }
-                            } else {
-                                println!("-> {:?}", kernel32::GetLastError());
-                            }
-                            kernel32::ResumeThread(thread_handle);
-                        }
-                        kernel32::CloseHandle(thread_handle);
-                    }
-                    break;
+                if entry.th32OwnerProcessID as usize == pid.0 {
+                    count += 1;
                 }
-                if kernel32::Thread32Next(snapshot_handle, &mut target_thread) != TRUE {
+                if Thread32Next(snapshot, &mut entry) == FALSE {
                     break;
                 }
             }
         }
-        kernel32::CloseHandle(snapshot_handle);
-    }*/
-    ret
+        CloseHandle(snapshot);
+        count
+    }
 }
 
-pub(crate) fn get_executable_path(_pid: Pid) -> PathBuf {
-    /*let where_req = format!("ProcessId={}", pid);
+pub(crate) fn update_open_files_count(p: &mut Process) {
+    if p.handle.is_null() {
+        return;
+    }
+    let mut count: DWORD = 0;
+    p.open_files_count = if unsafe { GetProcessHandleCount(*p.handle, &mut count) } != FALSE {
+        Some(count as usize)
+    } else {
+        None
+    };
+}
 
-    if let Some(ret) = run_wmi(&["process", "where", &where_req, "get", "ExecutablePath"]) {
-        for line in ret.lines() {
-            if line.is_empty() || line == "ExecutablePath" {
-                continue
-            }
-            return line.to_owned();
-        }
-    }*/
-    PathBuf::new()
+pub(crate) fn update_priority(p: &mut Process) {
+    if p.handle.is_null() {
+        return;
+    }
+    p.priority = match unsafe { GetPriorityClass(*p.handle) } {
+        IDLE_PRIORITY_CLASS => 19,
+        BELOW_NORMAL_PRIORITY_CLASS => 10,
+        ABOVE_NORMAL_PRIORITY_CLASS => -10,
+        HIGH_PRIORITY_CLASS => -15,
+        REALTIME_PRIORITY_CLASS => -20,
+        _ => 0,
+    };
 }
 
 pub(crate) fn get_system_computation_time() -> ULARGE_INTEGER {
@@ -773,6 +1179,9 @@ pub(crate) fn compute_cpu_usage(p: &mut Process, nb_processors: u64, _now: ULARG
         let global_kernel_time = *global_kernel_time.QuadPart();
         let global_user_time = *global_user_time.QuadPart();
 
+        // `sys` and `user` are expressed in 100-nanosecond intervals.
+        p.accumulated_cpu_time = (sys + user) / 10_000;
+
         let delta_global_kernel_time =
             check_sub(global_kernel_time, p.cpu_calc_values.old_system_sys_cpu);
         let delta_global_user_time =
@@ -798,6 +1207,37 @@ pub(crate) fn compute_cpu_usage(p: &mut Process, nb_processors: u64, _now: ULARG
     }
 }
 
+/// Blocks until the process `pid` ends or until `should_stop` returns `true`.
+pub(crate) fn wait_pid<S: Fn() -> bool>(pid: Pid, should_stop: S) -> Option<ExitStatus> {
+    unsafe {
+        let handle = OpenProcess(
+            SYNCHRONIZE | PROCESS_QUERY_LIMITED_INFORMATION,
+            FALSE,
+            pid.0 as DWORD,
+        );
+        if handle.is_null() {
+            return None;
+        }
+        let mut exit_code = 0;
+        let ret = loop {
+            // The wait is done by slices so `should_stop` can be checked regularly.
+            match WaitForSingleObject(handle, 10) {
+                WAIT_OBJECT_0 => {
+                    break if GetExitCodeProcess(handle, &mut exit_code) != FALSE {
+                        Some(ExitStatus::from_raw(exit_code))
+                    } else {
+                        None
+                    };
+                }
+                WAIT_TIMEOUT if !should_stop() => {}
+                _ => break None,
+            }
+        };
+        CloseHandle(handle);
+        ret
+    }
+}
+
 pub fn get_handle(p: &Process) -> HANDLE {
     *p.handle
 }
@@ -828,6 +1268,9 @@ pub fn update_memory(p: &mut Process) {
         {
             p.memory = (pmc.WorkingSetSize as u64) / 1_000;
             p.virtual_memory = (pmc.PrivateUsage as u64) / 1_000;
+            // Soft and hard page faults are counted together.
+            p.old_minor_faults = p.minor_faults;
+            p.minor_faults = pmc.PageFaultCount as u64;
         }
     }
 }
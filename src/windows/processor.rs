@@ -5,138 +5,40 @@
 //
 
 use crate::sys::tools::KeyHandler;
-use crate::{LoadAvg, ProcessorExt};
+use crate::{CacheInfo, CacheType, CpuTimes, ProcessorExt};
 
 use std::collections::HashMap;
 use std::mem;
-use std::ops::DerefMut;
 use std::ptr::null_mut;
-use std::sync::Mutex;
 
+use ntapi::ntexapi::{
+    NtQuerySystemInformation, SystemProcessorPerformanceInformation,
+    SYSTEM_PROCESSOR_PERFORMANCE_INFORMATION,
+};
 use ntapi::ntpoapi::PROCESSOR_POWER_INFORMATION;
 
-use winapi::shared::minwindef::FALSE;
 use winapi::shared::winerror::ERROR_SUCCESS;
 use winapi::um::handleapi::CloseHandle;
 use winapi::um::pdh::{
-    PdhAddCounterW, PdhAddEnglishCounterA, PdhCloseQuery, PdhCollectQueryData,
-    PdhCollectQueryDataEx, PdhGetFormattedCounterValue, PdhOpenQueryA, PdhRemoveCounter,
-    PDH_FMT_COUNTERVALUE, PDH_FMT_DOUBLE, PDH_HCOUNTER, PDH_HQUERY,
+    PdhAddCounterW, PdhCloseQuery, PdhCollectQueryData, PdhGetFormattedCounterValue, PdhOpenQueryA,
+    PdhRemoveCounter, PDH_FMT_COUNTERVALUE, PDH_FMT_DOUBLE, PDH_HCOUNTER, PDH_HQUERY,
 };
 use winapi::um::powerbase::CallNtPowerInformation;
-use winapi::um::synchapi::CreateEventA;
 use winapi::um::sysinfoapi::GetLogicalProcessorInformationEx;
 use winapi::um::sysinfoapi::SYSTEM_INFO;
-use winapi::um::winbase::{RegisterWaitForSingleObject, INFINITE};
 use winapi::um::winnt::{
-    ProcessorInformation, BOOLEAN, HANDLE, PVOID, SYSTEM_LOGICAL_PROCESSOR_INFORMATION,
-    WT_EXECUTEDEFAULT,
+    CacheData, CacheInstruction, CacheUnified, ProcessorInformation, RelationCache,
+    CACHE_RELATIONSHIP, HANDLE, SYSTEM_LOGICAL_PROCESSOR_INFORMATION,
 };
 
-// This formula comes from linux's include/linux/sched/loadavg.h
-// https://github.com/torvalds/linux/blob/345671ea0f9258f410eb057b9ced9cefbbe5dc78/include/linux/sched/loadavg.h#L20-L23
-#[allow(clippy::excessive_precision)]
-const LOADAVG_FACTOR_1F: f64 = 0.9200444146293232478931553241;
-#[allow(clippy::excessive_precision)]
-const LOADAVG_FACTOR_5F: f64 = 0.9834714538216174894737477501;
-#[allow(clippy::excessive_precision)]
-const LOADAVG_FACTOR_15F: f64 = 0.9944598480048967508795473394;
-// The time interval in seconds between taking load counts, same as Linux
-const SAMPLING_INTERVAL: usize = 5;
-
-// maybe use a read/write lock instead?
-static LOAD_AVG: once_cell::sync::Lazy<Mutex<Option<LoadAvg>>> =
-    once_cell::sync::Lazy::new(|| unsafe { init_load_avg() });
-
-pub(crate) fn get_load_average() -> LoadAvg {
-    if let Ok(avg) = LOAD_AVG.lock() {
-        if let Some(avg) = &*avg {
-            return avg.clone();
-        }
-    }
-    LoadAvg::default()
-}
-
-unsafe extern "system" fn load_avg_callback(counter: PVOID, _: BOOLEAN) {
-    let mut display_value = mem::MaybeUninit::<PDH_FMT_COUNTERVALUE>::uninit();
-
-    if PdhGetFormattedCounterValue(
-        counter as _,
-        PDH_FMT_DOUBLE,
-        null_mut(),
-        display_value.as_mut_ptr(),
-    ) != ERROR_SUCCESS as _
-    {
-        return;
-    }
-    let display_value = display_value.assume_init();
-    if let Ok(mut avg) = LOAD_AVG.lock() {
-        if let Some(avg) = avg.deref_mut() {
-            let current_load = display_value.u.doubleValue();
-
-            avg.one = avg.one * LOADAVG_FACTOR_1F + current_load * (1.0 - LOADAVG_FACTOR_1F);
-            avg.five = avg.five * LOADAVG_FACTOR_5F + current_load * (1.0 - LOADAVG_FACTOR_5F);
-            avg.fifteen =
-                avg.fifteen * LOADAVG_FACTOR_15F + current_load * (1.0 - LOADAVG_FACTOR_15F);
-        }
-    }
-}
-
-unsafe fn init_load_avg() -> Mutex<Option<LoadAvg>> {
-    // You can see the original implementation here: https://github.com/giampaolo/psutil
-    let mut query = null_mut();
-
-    if PdhOpenQueryA(null_mut(), 0, &mut query) != ERROR_SUCCESS as _ {
-        return Mutex::new(None);
-    }
-
-    let mut counter: PDH_HCOUNTER = mem::zeroed();
-    if PdhAddEnglishCounterA(
-        query,
-        b"\\System\\Processor Queue Length\0".as_ptr() as _,
-        0,
-        &mut counter,
-    ) != ERROR_SUCCESS as _
-    {
-        PdhCloseQuery(query);
-        return Mutex::new(None);
-    }
-
-    let event = CreateEventA(null_mut(), FALSE, FALSE, b"LoadUpdateEvent\0".as_ptr() as _);
-    if event.is_null() {
-        PdhCloseQuery(query);
-        return Mutex::new(None);
-    }
-
-    if PdhCollectQueryDataEx(query, SAMPLING_INTERVAL as _, event) != ERROR_SUCCESS as _ {
-        PdhCloseQuery(query);
-        return Mutex::new(None);
-    }
-
-    let mut wait_handle = null_mut();
-    if RegisterWaitForSingleObject(
-        &mut wait_handle,
-        event,
-        Some(load_avg_callback),
-        counter as _,
-        INFINITE,
-        WT_EXECUTEDEFAULT,
-    ) == 0
-    {
-        PdhRemoveCounter(counter);
-        PdhCloseQuery(query);
-        Mutex::new(None)
-    } else {
-        Mutex::new(Some(LoadAvg::default()))
-    }
-}
-
 struct InternalQuery {
     query: PDH_HQUERY,
     event: HANDLE,
     data: HashMap<String, PDH_HCOUNTER>,
 }
 
+// The query, counter and event handles are owned by `InternalQuery` (they're closed in `Drop`) and
+// PDH doesn't tie them to the thread which created them.
 unsafe impl Send for InternalQuery {}
 unsafe impl Sync for InternalQuery {}
 
@@ -239,6 +141,9 @@ pub struct Processor {
     vendor_id: String,
     brand: String,
     frequency: u64,
+    old_idle_time: u64,
+    old_total_time: u64,
+    times: CpuTimes,
 }
 
 impl ProcessorExt for Processor {
@@ -261,6 +166,14 @@ impl ProcessorExt for Processor {
     fn get_brand(&self) -> &str {
         &self.brand
     }
+
+    fn get_temperature(&self) -> Option<f32> {
+        None
+    }
+
+    fn get_times(&self) -> CpuTimes {
+        self.times
+    }
 }
 
 impl Processor {
@@ -277,12 +190,107 @@ impl Processor {
             vendor_id,
             brand,
             frequency,
+            old_idle_time: 0,
+            old_total_time: 0,
+            times: CpuTimes::default(),
         }
     }
 
     pub(crate) fn set_cpu_usage(&mut self, value: f32) {
         self.cpu_usage = value;
     }
+
+    pub(crate) fn has_key_used(&self) -> bool {
+        self.key_used.is_some()
+    }
+
+    fn set_times(&mut self, times: CpuTimes, update_usage: bool) {
+        self.times = times;
+        if update_usage {
+            self.update_times(
+                times.idle,
+                times.user + times.system + times.idle + times.irq + times.softirq,
+            );
+        }
+    }
+
+    fn update_times(&mut self, idle_time: u64, total_time: u64) {
+        let idle_diff = idle_time.saturating_sub(self.old_idle_time);
+        let total_diff = total_time.saturating_sub(self.old_total_time);
+        // The first refresh only sets the reference values.
+        if self.old_total_time != 0 && total_diff > 0 {
+            self.cpu_usage = total_diff.saturating_sub(idle_diff) as f32 * 100. / total_diff as f32;
+        }
+        self.old_idle_time = idle_time;
+        self.old_total_time = total_time;
+    }
+}
+
+/// Converts the times (in 100 nanoseconds) returned for a processor.
+fn get_times(info: &SYSTEM_PROCESSOR_PERFORMANCE_INFORMATION) -> CpuTimes {
+    let (idle, kernel, user, dpc, interrupt) = unsafe {
+        (
+            *info.IdleTime.QuadPart() as u64,
+            *info.KernelTime.QuadPart() as u64,
+            *info.UserTime.QuadPart() as u64,
+            *info.DpcTime.QuadPart() as u64,
+            *info.InterruptTime.QuadPart() as u64,
+        )
+    };
+    CpuTimes {
+        user,
+        // The kernel time includes the idle, DPC and interrupt times.
+        system: kernel.saturating_sub(idle + dpc + interrupt),
+        idle,
+        irq: interrupt,
+        softirq: dpc,
+        ..CpuTimes::default()
+    }
+}
+
+/// Refreshes the processors' times. If `update_usage` is `true`, their usage is computed from
+/// their idle and total times as well. It is used when the PDH counters couldn't be added (if
+/// the counters' names translation failed for example).
+///
+/// Returns the total number of interrupts serviced by the processors.
+pub(crate) fn refresh_processors_times(
+    global_processor: &mut Processor,
+    processors: &mut [Processor],
+    update_usage: bool,
+) -> Option<u64> {
+    let mut infos: Vec<SYSTEM_PROCESSOR_PERFORMANCE_INFORMATION> =
+        Vec::with_capacity(processors.len());
+    let mut returned_len = 0;
+    let status = unsafe {
+        NtQuerySystemInformation(
+            SystemProcessorPerformanceInformation,
+            infos.as_mut_ptr() as _,
+            (processors.len() * mem::size_of::<SYSTEM_PROCESSOR_PERFORMANCE_INFORMATION>()) as _,
+            &mut returned_len,
+        )
+    };
+    if status < 0 {
+        sysinfo_debug!(
+            "NtQuerySystemInformation(SystemProcessorPerformanceInformation) failed: {}",
+            status
+        );
+        return None;
+    }
+    unsafe {
+        infos.set_len(
+            returned_len as usize / mem::size_of::<SYSTEM_PROCESSOR_PERFORMANCE_INFORMATION>(),
+        );
+    }
+    let mut global_times = CpuTimes::default();
+    let mut interrupts = 0;
+    for (processor, info) in processors.iter_mut().zip(infos.iter()) {
+        let times = get_times(info);
+        global_times += times;
+        interrupts += info.InterruptCount as u64;
+        processor.set_times(times, update_usage);
+    }
+    global_processor.set_times(global_times, update_usage);
+    Some(interrupts)
 }
 
 fn get_vendor_id_not_great(info: &SYSTEM_INFO) -> String {
@@ -448,3 +456,61 @@ pub fn get_physical_core_count() -> Option<usize> {
             .count(),
     )
 }
+
+/// Returns the caches used by the first logical processor.
+pub(crate) fn get_cpu_caches() -> Vec<CacheInfo> {
+    let mut needed_size = 0;
+    unsafe { GetLogicalProcessorInformationEx(RelationCache, null_mut(), &mut needed_size) };
+    if needed_size == 0 {
+        return Vec::new();
+    }
+    // The entries don't all have the same size, so we walk the buffer with the `Size` field of
+    // each of them.
+    let mut buf: Vec<u8> = Vec::with_capacity(needed_size as _);
+    if unsafe {
+        GetLogicalProcessorInformationEx(RelationCache, buf.as_mut_ptr() as _, &mut needed_size)
+    } == 0
+    {
+        return Vec::new();
+    }
+    unsafe {
+        buf.set_len(needed_size as _);
+    }
+
+    let mut caches = Vec::new();
+    let mut offset = 0;
+    // Each entry starts with the `Relationship` and `Size` fields, followed by the
+    // `CACHE_RELATIONSHIP`.
+    while offset + 8 + mem::size_of::<CACHE_RELATIONSHIP>() <= buf.len() {
+        let (size, cache) = unsafe {
+            let entry = buf.as_ptr().add(offset);
+            (
+                std::ptr::read_unaligned(entry.add(4) as *const u32),
+                std::ptr::read_unaligned(entry.add(8) as *const CACHE_RELATIONSHIP),
+            )
+        };
+        if size == 0 {
+            break;
+        }
+        offset += size as usize;
+        // Only keep the caches of the first processor of the first group.
+        if cache.GroupMask.Group != 0 || cache.GroupMask.Mask & 1 == 0 {
+            continue;
+        }
+        let type_ = match cache.Type {
+            CacheData => CacheType::Data,
+            CacheInstruction => CacheType::Instruction,
+            CacheUnified => CacheType::Unified,
+            // Trace caches are ignored.
+            _ => continue,
+        };
+        caches.push(CacheInfo {
+            level: cache.Level,
+            type_,
+            size: cache.CacheSize as u64,
+            line_size: cache.LineSize as u64,
+        });
+    }
+    caches.sort_by_key(|cache| (cache.level, cache.type_ as u8));
+    caches
+}
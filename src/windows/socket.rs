@@ -0,0 +1,166 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+use crate::{Pid, SocketInfo, SocketProtocol, SocketState};
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::slice;
+use std::sync::Mutex;
+
+use winapi::ctypes::c_void;
+use winapi::shared::iprtrmib::{TCP_TABLE_OWNER_PID_ALL, UDP_TABLE_OWNER_PID};
+use winapi::shared::minwindef::{DWORD, FALSE};
+use winapi::shared::tcpmib::{MIB_TCP6TABLE_OWNER_PID, MIB_TCPTABLE_OWNER_PID};
+use winapi::shared::udpmib::{MIB_UDP6TABLE_OWNER_PID, MIB_UDPTABLE_OWNER_PID};
+use winapi::shared::winerror::{ERROR_INSUFFICIENT_BUFFER, NO_ERROR};
+use winapi::shared::ws2def::{AF_INET, AF_INET6};
+use winapi::um::iphlpapi::{GetExtendedTcpTable, GetExtendedUdpTable};
+
+/// Calls `get_table` with a growing buffer until the whole table fits in it. The buffer is made
+/// of `u64` so the table is correctly aligned.
+fn get_table<F: Fn(*mut c_void, &mut DWORD) -> DWORD>(get_table: F) -> Option<Vec<u64>> {
+    let mut size = 0;
+    let mut buffer: Vec<u64> = Vec::new();
+    loop {
+        match get_table(buffer.as_mut_ptr() as *mut c_void, &mut size) {
+            NO_ERROR => return Some(buffer),
+            ERROR_INSUFFICIENT_BUFFER => {
+                buffer = vec![0; (size as usize + 7) / 8];
+            }
+            _ => return None,
+        }
+    }
+}
+
+fn get_tcp_table(family: i32) -> Option<Vec<u64>> {
+    get_table(|ptr, size| unsafe {
+        GetExtendedTcpTable(ptr, size, FALSE, family as _, TCP_TABLE_OWNER_PID_ALL, 0)
+    })
+}
+
+fn get_udp_table(family: i32) -> Option<Vec<u64>> {
+    get_table(|ptr, size| unsafe {
+        GetExtendedUdpTable(ptr, size, FALSE, family as _, UDP_TABLE_OWNER_PID, 0)
+    })
+}
+
+/// Ports are stored in network byte order in the lower 16 bits.
+fn get_port(port: DWORD) -> u16 {
+    u16::from_be(port as u16)
+}
+
+fn get_v4_address(addr: DWORD, port: DWORD) -> SocketAddr {
+    SocketAddr::new(
+        IpAddr::V4(Ipv4Addr::from(addr.to_ne_bytes())),
+        get_port(port),
+    )
+}
+
+fn get_v6_address(addr: [u8; 16], port: DWORD) -> SocketAddr {
+    SocketAddr::new(IpAddr::V6(Ipv6Addr::from(addr)), get_port(port))
+}
+
+/// Converts a `MIB_TCP_STATE` value.
+fn get_tcp_state(state: DWORD) -> SocketState {
+    match state {
+        1 => SocketState::Closed,
+        2 => SocketState::Listen,
+        3 => SocketState::SynSent,
+        4 => SocketState::SynReceived,
+        5 => SocketState::Established,
+        6 => SocketState::FinWait1,
+        7 => SocketState::FinWait2,
+        8 => SocketState::CloseWait,
+        9 => SocketState::Closing,
+        10 => SocketState::LastAck,
+        11 => SocketState::TimeWait,
+        x => SocketState::Unknown(x),
+    }
+}
+
+macro_rules! table_rows {
+    ($buffer:expr, $table:ty) => {{
+        let table = $buffer.as_ptr() as *const $table;
+        slice::from_raw_parts((*table).table.as_ptr(), (*table).dwNumEntries as usize)
+    }};
+}
+
+/// Socket tables shared by the processes refreshed at the same time. They're only read the first
+/// time they're needed, so they're read at most once per refresh.
+#[derive(Default)]
+pub(crate) struct SocketTables {
+    sockets: Mutex<Option<Vec<SocketInfo>>>,
+}
+
+impl SocketTables {
+    /// Returns the sockets opened by the process `pid`.
+    pub(crate) fn get_process_sockets(&self, pid: Pid) -> Vec<SocketInfo> {
+        let filter = |sockets: &[SocketInfo]| {
+            sockets
+                .iter()
+                .filter(|socket| socket.pid == Some(pid))
+                .copied()
+                .collect()
+        };
+        match self.sockets.lock() {
+            Ok(mut sockets) => filter(sockets.get_or_insert_with(get_sockets)),
+            Err(_) => filter(&get_sockets()),
+        }
+    }
+}
+
+/// Returns all the TCP and UDP sockets of the system.
+pub(crate) fn get_sockets() -> Vec<SocketInfo> {
+    let mut sockets = Vec::new();
+
+    unsafe {
+        if let Some(buffer) = get_tcp_table(AF_INET) {
+            for row in table_rows!(buffer, MIB_TCPTABLE_OWNER_PID) {
+                sockets.push(SocketInfo {
+                    protocol: SocketProtocol::Tcp,
+                    local_address: get_v4_address(row.dwLocalAddr, row.dwLocalPort),
+                    remote_address: get_v4_address(row.dwRemoteAddr, row.dwRemotePort),
+                    state: Some(get_tcp_state(row.dwState)),
+                    pid: Some(Pid(row.dwOwningPid as usize)),
+                });
+            }
+        }
+        if let Some(buffer) = get_tcp_table(AF_INET6) {
+            for row in table_rows!(buffer, MIB_TCP6TABLE_OWNER_PID) {
+                sockets.push(SocketInfo {
+                    protocol: SocketProtocol::Tcp,
+                    local_address: get_v6_address(row.ucLocalAddr, row.dwLocalPort),
+                    remote_address: get_v6_address(row.ucRemoteAddr, row.dwRemotePort),
+                    state: Some(get_tcp_state(row.dwState)),
+                    pid: Some(Pid(row.dwOwningPid as usize)),
+                });
+            }
+        }
+        if let Some(buffer) = get_udp_table(AF_INET) {
+            for row in table_rows!(buffer, MIB_UDPTABLE_OWNER_PID) {
+                sockets.push(SocketInfo {
+                    protocol: SocketProtocol::Udp,
+                    local_address: get_v4_address(row.dwLocalAddr, row.dwLocalPort),
+                    remote_address: get_v4_address(0, 0),
+                    state: None,
+                    pid: Some(Pid(row.dwOwningPid as usize)),
+                });
+            }
+        }
+        if let Some(buffer) = get_udp_table(AF_INET6) {
+            for row in table_rows!(buffer, MIB_UDP6TABLE_OWNER_PID) {
+                sockets.push(SocketInfo {
+                    protocol: SocketProtocol::Udp,
+                    local_address: get_v6_address(row.ucLocalAddr, row.dwLocalPort),
+                    remote_address: get_v6_address([0; 16], 0),
+                    state: None,
+                    pid: Some(Pid(row.dwOwningPid as usize)),
+                });
+            }
+        }
+    }
+    sockets
+}
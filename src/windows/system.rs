@@ -4,67 +4,155 @@
 // Copyright (c) 2018 Guillaume Gomez
 //
 
-use crate::{LoadAvg, Networks, Pid, ProcessExt, RefreshKind, SystemExt, User};
+#[cfg(feature = "cpu")]
+use crate::CacheInfo;
+#[cfg(feature = "disk")]
+use crate::Mount;
+#[cfg(feature = "network")]
+use crate::Networks;
+use crate::{Battery, LoadAvg, RefreshKind, SwapDevice, SwapType, SystemExt, User, Virtualization};
+#[cfg(feature = "process")]
+use crate::{Pid, ProcessExt, ProcessRefreshKind, ProcessWatcher, SocketInfo};
 use winapi::um::winreg::HKEY_LOCAL_MACHINE;
 
+use crate::sys::battery;
+#[cfg(feature = "component")]
 use crate::sys::component::{self, Component};
+#[cfg(feature = "disk")]
 use crate::sys::disk::Disk;
+#[cfg(feature = "component")]
+use crate::sys::fan::{self, Fan};
+use crate::sys::gpu::{self, Gpu};
+#[cfg(feature = "process")]
 use crate::sys::process::{
-    compute_cpu_usage, get_handle, get_system_computation_time, update_disk_usage, update_memory,
-    Process,
+    compute_cpu_usage, get_handle, get_system_computation_time, get_thread_count,
+    update_disk_usage, update_memory, update_open_files_count, update_priority, wait_pid, Process,
+    ProcessStatus,
 };
+#[cfg(feature = "cpu")]
 use crate::sys::processor::*;
+#[cfg(feature = "process")]
+use crate::sys::socket::SocketTables;
+#[cfg(any(feature = "cpu", feature = "disk"))]
 use crate::sys::tools::*;
 use crate::sys::users::get_users;
 
-use crate::utils::into_iter;
+#[cfg(feature = "cpu")]
+use crate::utils::RefreshInterval;
+#[cfg(feature = "disk")]
+use crate::utils::{filter_disks, DiskFilter};
+#[cfg(feature = "process")]
+use crate::utils::{get_secs_since_epoch, into_iter};
 
+#[cfg(feature = "process")]
 use std::cell::UnsafeCell;
+#[cfg(feature = "process")]
 use std::collections::HashMap;
 use std::ffi::OsStr;
-use std::mem::{size_of, zeroed};
+use std::mem::{self, size_of, zeroed};
+use std::ops::DerefMut;
 use std::os::windows::ffi::OsStrExt;
+#[cfg(feature = "disk")]
+use std::path::Path;
+#[cfg(feature = "process")]
+use std::process::ExitStatus;
+use std::ptr::null_mut;
 use std::slice::from_raw_parts;
+#[cfg(feature = "process")]
+use std::sync::Arc;
+use std::sync::Mutex;
+#[cfg(feature = "cpu")]
+use std::time::Duration;
 use std::time::SystemTime;
 
 use ntapi::ntexapi::{
-    NtQuerySystemInformation, SystemProcessInformation, SYSTEM_PROCESS_INFORMATION,
+    NtQuerySystemInformation, SystemPageFileInformation, SYSTEM_PAGEFILE_INFORMATION,
 };
+#[cfg(feature = "cpu")]
+use ntapi::ntexapi::{SystemPerformanceInformation, SYSTEM_PERFORMANCE_INFORMATION};
+#[cfg(feature = "process")]
+use ntapi::ntexapi::{SystemProcessInformation, SYSTEM_PROCESS_INFORMATION};
+use ntapi::ntrtl::RtlGetVersion;
 use winapi::ctypes::wchar_t;
 use winapi::shared::minwindef::{DWORD, FALSE, HKEY, LPBYTE, TRUE};
-use winapi::shared::ntdef::{PVOID, ULONG};
+use winapi::shared::ntdef::{NT_SUCCESS, ULONG};
 use winapi::shared::ntstatus::STATUS_INFO_LENGTH_MISMATCH;
-use winapi::shared::winerror;
+use winapi::shared::winerror::{self, ERROR_SUCCESS};
+#[cfg(feature = "process")]
 use winapi::um::minwinbase::STILL_ACTIVE;
+use winapi::um::pdh::{
+    PdhAddEnglishCounterA, PdhCloseQuery, PdhCollectQueryDataEx, PdhGetFormattedCounterValue,
+    PdhOpenQueryA, PdhRemoveCounter, PDH_FMT_COUNTERVALUE, PDH_FMT_DOUBLE, PDH_HCOUNTER,
+};
+#[cfg(feature = "process")]
 use winapi::um::processthreadsapi::GetExitCodeProcess;
+use winapi::um::synchapi::CreateEventA;
 use winapi::um::sysinfoapi::{
-    ComputerNamePhysicalDnsHostname, GetComputerNameExW, GetTickCount64, GlobalMemoryStatusEx,
-    MEMORYSTATUSEX,
+    ComputerNamePhysicalDnsHostname, GetComputerNameExW, GetNativeSystemInfo, GetTickCount64,
+    GlobalMemoryStatusEx, MEMORYSTATUSEX, SYSTEM_INFO,
+};
+use winapi::um::winbase::{RegisterWaitForSingleObject, INFINITE};
+#[cfg(feature = "process")]
+use winapi::um::winnt::HANDLE;
+use winapi::um::winnt::{BOOLEAN, KEY_READ, PVOID, RTL_OSVERSIONINFOW, WT_EXECUTEDEFAULT};
+#[cfg(feature = "cpu")]
+use winapi::um::winnt::{
+    PROCESSOR_ARCHITECTURE_AMD64, PROCESSOR_ARCHITECTURE_ARM, PROCESSOR_ARCHITECTURE_ARM64,
+    PROCESSOR_ARCHITECTURE_IA64, PROCESSOR_ARCHITECTURE_INTEL,
 };
-use winapi::um::winnt::{HANDLE, KEY_READ};
 use winapi::um::winreg::{RegOpenKeyExW, RegQueryValueExW};
 
 /// Struct containing the system's information.
 pub struct System {
-    process_list: HashMap<usize, Process>,
+    #[cfg(feature = "process")]
+    process_list: HashMap<Pid, Process>,
     mem_total: u64,
     mem_available: u64,
     swap_total: u64,
     swap_free: u64,
+    swap_devices: Vec<SwapDevice>,
+    #[cfg(feature = "cpu")]
     global_processor: Processor,
+    #[cfg(feature = "cpu")]
     processors: Vec<Processor>,
+    #[cfg(feature = "cpu")]
+    cpu_refresh_interval: RefreshInterval,
+    #[cfg(feature = "component")]
     components: Vec<Component>,
+    #[cfg(feature = "component")]
+    fans: Vec<Fan>,
+    gpus: Vec<Gpu>,
+    #[cfg(feature = "disk")]
     disks: Vec<Disk>,
+    #[cfg(feature = "disk")]
+    disk_filter: Option<DiskFilter>,
+    #[cfg(feature = "process")]
+    keep_exited_processes: bool,
+    #[cfg(feature = "cpu")]
     query: Option<Query>,
+    #[cfg(feature = "network")]
     networks: Networks,
     boot_time: u64,
     users: Vec<User>,
+    #[cfg(feature = "process")]
+    sockets: Vec<SocketInfo>,
+    #[cfg(feature = "cpu")]
+    context_switches: u64,
+    #[cfg(feature = "cpu")]
+    old_context_switches: u64,
+    #[cfg(feature = "cpu")]
+    interrupts: u64,
+    #[cfg(feature = "cpu")]
+    old_interrupts: u64,
 }
 
 // Useful for parallel iterations.
+#[cfg(feature = "process")]
 struct Wrap<T>(T);
 
+#[cfg(feature = "process")]
 unsafe impl<T> Send for Wrap<T> {}
+#[cfg(feature = "process")]
 unsafe impl<T> Sync for Wrap<T> {}
 
 unsafe fn boot_time() -> u64 {
@@ -77,26 +165,153 @@ unsafe fn boot_time() -> u64 {
     }
 }
 
+// This formula comes from linux's include/linux/sched/loadavg.h
+// https://github.com/torvalds/linux/blob/345671ea0f9258f410eb057b9ced9cefbbe5dc78/include/linux/sched/loadavg.h#L20-L23
+#[allow(clippy::excessive_precision)]
+const LOADAVG_FACTOR_1F: f64 = 0.9200444146293232478931553241;
+#[allow(clippy::excessive_precision)]
+const LOADAVG_FACTOR_5F: f64 = 0.9834714538216174894737477501;
+#[allow(clippy::excessive_precision)]
+const LOADAVG_FACTOR_15F: f64 = 0.9944598480048967508795473394;
+// The time interval in seconds between taking load counts, same as Linux
+const SAMPLING_INTERVAL: usize = 5;
+
+// maybe use a read/write lock instead?
+static LOAD_AVG: once_cell::sync::Lazy<Mutex<Option<LoadAvg>>> =
+    once_cell::sync::Lazy::new(|| unsafe { init_load_avg() });
+
+pub(crate) fn get_load_average() -> LoadAvg {
+    if let Ok(avg) = LOAD_AVG.lock() {
+        if let Some(avg) = &*avg {
+            return avg.clone();
+        }
+    }
+    LoadAvg::default()
+}
+
+unsafe extern "system" fn load_avg_callback(counter: PVOID, _: BOOLEAN) {
+    let mut display_value = mem::MaybeUninit::<PDH_FMT_COUNTERVALUE>::uninit();
+
+    if PdhGetFormattedCounterValue(
+        counter as _,
+        PDH_FMT_DOUBLE,
+        null_mut(),
+        display_value.as_mut_ptr(),
+    ) != ERROR_SUCCESS as _
+    {
+        return;
+    }
+    let display_value = display_value.assume_init();
+    if let Ok(mut avg) = LOAD_AVG.lock() {
+        if let Some(avg) = avg.deref_mut() {
+            let current_load = display_value.u.doubleValue();
+
+            avg.one = avg.one * LOADAVG_FACTOR_1F + current_load * (1.0 - LOADAVG_FACTOR_1F);
+            avg.five = avg.five * LOADAVG_FACTOR_5F + current_load * (1.0 - LOADAVG_FACTOR_5F);
+            avg.fifteen =
+                avg.fifteen * LOADAVG_FACTOR_15F + current_load * (1.0 - LOADAVG_FACTOR_15F);
+        }
+    }
+}
+
+unsafe fn init_load_avg() -> Mutex<Option<LoadAvg>> {
+    // You can see the original implementation here: https://github.com/giampaolo/psutil
+    let mut query = null_mut();
+
+    if PdhOpenQueryA(null_mut(), 0, &mut query) != ERROR_SUCCESS as _ {
+        return Mutex::new(None);
+    }
+
+    let mut counter: PDH_HCOUNTER = mem::zeroed();
+    if PdhAddEnglishCounterA(
+        query,
+        b"\\System\\Processor Queue Length\0".as_ptr() as _,
+        0,
+        &mut counter,
+    ) != ERROR_SUCCESS as _
+    {
+        PdhCloseQuery(query);
+        return Mutex::new(None);
+    }
+
+    let event = CreateEventA(null_mut(), FALSE, FALSE, b"LoadUpdateEvent\0".as_ptr() as _);
+    if event.is_null() {
+        PdhCloseQuery(query);
+        return Mutex::new(None);
+    }
+
+    if PdhCollectQueryDataEx(query, SAMPLING_INTERVAL as _, event) != ERROR_SUCCESS as _ {
+        PdhCloseQuery(query);
+        return Mutex::new(None);
+    }
+
+    let mut wait_handle = null_mut();
+    if RegisterWaitForSingleObject(
+        &mut wait_handle,
+        event,
+        Some(load_avg_callback),
+        counter as _,
+        INFINITE,
+        WT_EXECUTEDEFAULT,
+    ) == 0
+    {
+        PdhRemoveCounter(counter);
+        PdhCloseQuery(query);
+        Mutex::new(None)
+    } else {
+        Mutex::new(Some(LoadAvg::default()))
+    }
+}
+
 impl SystemExt for System {
     #[allow(non_snake_case)]
     fn new_with_specifics(refreshes: RefreshKind) -> System {
+        #[cfg(feature = "cpu")]
         let (processors, vendor_id, brand) = init_processors();
         let mut s = System {
+            #[cfg(feature = "process")]
             process_list: HashMap::with_capacity(500),
             mem_total: 0,
             mem_available: 0,
             swap_total: 0,
             swap_free: 0,
+            swap_devices: Vec::new(),
+            #[cfg(feature = "cpu")]
             global_processor: Processor::new_with_values("Total CPU", vendor_id, brand, 0),
+            #[cfg(feature = "cpu")]
             processors,
+            #[cfg(feature = "cpu")]
+            cpu_refresh_interval: RefreshInterval::default(),
+            #[cfg(feature = "component")]
             components: Vec::new(),
+            #[cfg(feature = "component")]
+            fans: Vec::new(),
+            gpus: Vec::new(),
+            #[cfg(feature = "disk")]
             disks: Vec::with_capacity(2),
+            #[cfg(feature = "disk")]
+            disk_filter: None,
+            #[cfg(feature = "process")]
+            keep_exited_processes: false,
+            #[cfg(feature = "cpu")]
             query: Query::new(),
+            #[cfg(feature = "network")]
             networks: Networks::new(),
             boot_time: unsafe { boot_time() },
             users: Vec::new(),
+            #[cfg(feature = "process")]
+            sockets: Vec::new(),
+            #[cfg(feature = "cpu")]
+            context_switches: 0,
+            #[cfg(feature = "cpu")]
+            old_context_switches: 0,
+            #[cfg(feature = "cpu")]
+            interrupts: 0,
+            #[cfg(feature = "cpu")]
+            old_interrupts: 0,
         };
         // TODO: in case a translation fails, it might be nice to log it somewhere...
+        #[cfg(feature = "cpu")]
         if let Some(ref mut query) = s.query {
             let x = unsafe { load_symbols() };
             if let Some(processor_trans) = get_translation(&"Processor".to_owned(), &x) {
@@ -128,7 +343,29 @@ impl SystemExt for System {
         s
     }
 
+    #[cfg(feature = "cpu")]
     fn refresh_cpu(&mut self) {
+        if !self.cpu_refresh_interval.should_refresh() {
+            return;
+        }
+        let update_usage = self.query.is_none()
+            || !self.global_processor.has_key_used()
+            || self.processors.iter().any(|p| !p.has_key_used());
+        self.old_context_switches = self.context_switches;
+        self.old_interrupts = self.interrupts;
+        if let Some(context_switches) = get_context_switches() {
+            self.context_switches = context_switches;
+        }
+        if let Some(interrupts) = refresh_processors_times(
+            &mut self.global_processor,
+            &mut self.processors,
+            update_usage,
+        ) {
+            self.interrupts = interrupts;
+        }
+        if update_usage {
+            return;
+        }
         if let Some(ref mut query) = self.query {
             query.refresh();
             let mut used_time = None;
@@ -158,6 +395,36 @@ impl SystemExt for System {
         }
     }
 
+    #[cfg(feature = "cpu")]
+    fn refresh_cpu_over(&mut self, window: Duration) {
+        self.cpu_refresh_interval.reset();
+        self.refresh_cpu();
+        std::thread::sleep(window);
+        self.cpu_refresh_interval.reset();
+        self.refresh_cpu();
+    }
+
+    #[cfg(feature = "cpu")]
+    fn set_min_refresh_interval(&mut self, interval: Duration) {
+        self.cpu_refresh_interval.set_min_interval(interval);
+    }
+
+    #[cfg(feature = "process")]
+    fn set_include_threads(&mut self, _include: bool) {}
+
+    #[cfg(feature = "process")]
+    fn set_keep_exited_processes(&mut self, keep: bool) {
+        self.keep_exited_processes = keep;
+    }
+
+    #[cfg(feature = "disk")]
+    fn set_disk_filter<F>(&mut self, filter: F)
+    where
+        F: Fn(&Path, &str) -> bool + Send + Sync + 'static,
+    {
+        self.disk_filter = Some(Box::new(filter));
+    }
+
     fn refresh_memory(&mut self) {
         unsafe {
             let mut mem_info: MEMORYSTATUSEX = zeroed();
@@ -168,24 +435,40 @@ impl SystemExt for System {
             //self.swap_total = auto_cast!(mem_info.ullTotalPageFile - mem_info.ullTotalPhys, u64);
             //self.swap_free = auto_cast!(mem_info.ullAvailPageFile, u64);
         }
+        self.swap_devices = get_swap_devices();
     }
 
+    #[cfg(feature = "component")]
     fn refresh_components_list(&mut self) {
         self.components = component::get_components();
+        self.fans = fan::get_fans();
+    }
+
+    fn refresh_gpus_list(&mut self) {
+        self.gpus = gpu::get_gpus();
     }
 
     #[allow(clippy::map_entry)]
+    #[cfg(feature = "process")]
     fn refresh_process(&mut self, pid: Pid) -> bool {
         if self.process_list.contains_key(&pid) {
             if !refresh_existing_process(self, pid) {
                 self.process_list.remove(&pid);
                 return false;
             }
+            if let Some(p) = self.process_list.get_mut(&pid) {
+                p.socket_tables = Some(Arc::new(SocketTables::default()));
+            }
             true
         } else if let Some(mut p) = Process::new_from_pid(pid) {
             let system_time = get_system_computation_time();
             compute_cpu_usage(&mut p, self.processors.len() as u64, system_time);
             update_disk_usage(&mut p);
+            p.run_time = get_secs_since_epoch().saturating_sub(p.start_time());
+            p.thread_count = get_thread_count(pid);
+            update_open_files_count(&mut p);
+            update_priority(&mut p);
+            p.socket_tables = Some(Arc::new(SocketTables::default()));
             self.process_list.insert(pid, p);
             true
         } else {
@@ -194,7 +477,8 @@ impl SystemExt for System {
     }
 
     #[allow(clippy::cast_ptr_alignment)]
-    fn refresh_processes(&mut self) {
+    #[cfg(feature = "process")]
+    fn refresh_processes_specifics(&mut self, refresh_kind: ProcessRefreshKind) {
         // Windows 10 notebook requires at least 512KiB of memory to make it in one go
         let mut buffer_size: usize = 512 * 1024;
 
@@ -243,6 +527,7 @@ impl SystemExt for System {
                 let nb_processors = self.processors.len() as u64;
                 let process_list = Wrap(UnsafeCell::new(&mut self.process_list));
                 let system_time = get_system_computation_time();
+                let now = get_secs_since_epoch();
 
                 #[cfg(feature = "multithread")]
                 use rayon::iter::ParallelIterator;
@@ -251,41 +536,88 @@ impl SystemExt for System {
                 //       to run it over `process_information` directly!
                 let processes = into_iter(process_ids)
                     .filter_map(|pi| unsafe {
+                        // The threads array is located right after the process information so
+                        // we need to get it before copying `pi`.
+                        let threads = from_raw_parts(
+                            (*pi.0).Threads.as_ptr(),
+                            (*pi.0).NumberOfThreads as usize,
+                        );
                         let pi = *pi.0;
-                        let pid = pi.UniqueProcessId as usize;
+                        let pid = Pid(pi.UniqueProcessId as usize);
                         if let Some(proc_) = (*process_list.0.get()).get_mut(&pid) {
-                            proc_.memory = (pi.WorkingSetSize as u64) / 1_000;
-                            proc_.virtual_memory = (pi.VirtualSize as u64) / 1_000;
-                            compute_cpu_usage(proc_, nb_processors, system_time);
-                            update_disk_usage(proc_);
+                            proc_.status = ProcessStatus::from(threads);
+                            proc_.thread_count = pi.NumberOfThreads;
+                            update_open_files_count(proc_);
+                            update_priority(proc_);
+                            if refresh_kind.memory() {
+                                proc_.memory = (pi.WorkingSetSize as u64) / 1_000;
+                                proc_.virtual_memory = (pi.VirtualSize as u64) / 1_000;
+                            }
+                            if refresh_kind.cpu() {
+                                compute_cpu_usage(proc_, nb_processors, system_time);
+                            }
+                            if refresh_kind.disk_usage() {
+                                update_disk_usage(proc_);
+                            }
+                            proc_.run_time = now.saturating_sub(proc_.start_time());
                             proc_.updated = true;
                             return None;
                         }
-                        let name = get_process_name(&pi, pid);
+                        let name = get_process_name(&pi, pid.0);
+                        let (memory, virtual_memory) = if refresh_kind.memory() {
+                            (
+                                (pi.WorkingSetSize as u64) / 1_000,
+                                (pi.VirtualSize as u64) / 1_000,
+                            )
+                        } else {
+                            (0, 0)
+                        };
                         let mut p = Process::new_full(
                             pid,
                             if pi.InheritedFromUniqueProcessId as usize != 0 {
-                                Some(pi.InheritedFromUniqueProcessId as usize)
+                                Some(Pid(pi.InheritedFromUniqueProcessId as usize))
                             } else {
                                 None
                             },
-                            (pi.WorkingSetSize as u64) / 1_000,
-                            (pi.VirtualSize as u64) / 1_000,
+                            memory,
+                            virtual_memory,
                             name,
+                            refresh_kind,
                         );
-                        compute_cpu_usage(&mut p, nb_processors, system_time);
-                        update_disk_usage(&mut p);
+                        p.status = ProcessStatus::from(threads);
+                        p.thread_count = pi.NumberOfThreads;
+                        update_open_files_count(&mut p);
+                        update_priority(&mut p);
+                        if refresh_kind.cpu() {
+                            compute_cpu_usage(&mut p, nb_processors, system_time);
+                        }
+                        if refresh_kind.disk_usage() {
+                            update_disk_usage(&mut p);
+                        }
+                        p.run_time = now.saturating_sub(p.start_time());
                         Some(p)
                     })
                     .collect::<Vec<_>>();
+                let keep_exited_processes = self.keep_exited_processes;
                 self.process_list.retain(|_, v| {
                     let x = v.updated;
                     v.updated = false;
+                    if x {
+                        v.exists = true;
+                    } else if keep_exited_processes && v.exists {
+                        // It's removed at the next refresh.
+                        v.exists = false;
+                        return true;
+                    }
                     x
                 });
                 for p in processes.into_iter() {
                     self.process_list.insert(p.pid(), p);
                 }
+                let socket_tables = Arc::new(SocketTables::default());
+                for p in self.process_list.values_mut() {
+                    p.socket_tables = Some(Arc::clone(&socket_tables));
+                }
 
                 break;
             }
@@ -301,34 +633,65 @@ impl SystemExt for System {
         }
     }
 
+    #[cfg(feature = "disk")]
     fn refresh_disks_list(&mut self) {
         self.disks = unsafe { get_disks() };
+        filter_disks(&mut self.disks, &self.disk_filter);
     }
 
     fn refresh_users_list(&mut self) {
         self.users = unsafe { get_users() };
     }
 
+    #[cfg(feature = "process")]
+    fn refresh_sockets(&mut self) {
+        self.sockets = crate::sys::socket::get_sockets();
+    }
+
+    #[cfg(feature = "process")]
     fn get_processes(&self) -> &HashMap<Pid, Process> {
         &self.process_list
     }
 
+    #[cfg(feature = "process")]
     fn get_process(&self, pid: Pid) -> Option<&Process> {
-        self.process_list.get(&(pid as usize))
+        self.process_list.get(&pid)
     }
 
+    #[cfg(feature = "process")]
+    fn watch_process_exit<F>(&self, pid: Pid, f: F) -> ProcessWatcher
+    where
+        F: FnOnce(Option<ExitStatus>) + Send + 'static,
+    {
+        ProcessWatcher::new(move |should_stop| wait_pid(pid, should_stop), f)
+    }
+
+    #[cfg(feature = "cpu")]
     fn get_global_processor_info(&self) -> &Processor {
         &self.global_processor
     }
 
+    #[cfg(feature = "cpu")]
     fn get_processors(&self) -> &[Processor] {
         &self.processors
     }
 
+    #[cfg(feature = "cpu")]
     fn get_physical_core_count(&self) -> Option<usize> {
         get_physical_core_count()
     }
 
+    #[cfg(feature = "cpu")]
+    fn get_context_switches(&self) -> u64 {
+        self.context_switches
+            .saturating_sub(self.old_context_switches)
+    }
+
+    #[cfg(feature = "cpu")]
+    fn get_interrupts(&self) -> u64 {
+        self.interrupts.saturating_sub(self.old_interrupts)
+    }
+
     fn get_total_memory(&self) -> u64 {
         self.mem_total
     }
@@ -358,30 +721,68 @@ impl SystemExt for System {
         self.swap_total - self.swap_free
     }
 
+    fn get_swap_devices(&self) -> &[SwapDevice] {
+        &self.swap_devices
+    }
+
+    #[cfg(feature = "component")]
     fn get_components(&self) -> &[Component] {
         &self.components
     }
 
+    #[cfg(feature = "component")]
     fn get_components_mut(&mut self) -> &mut [Component] {
         &mut self.components
     }
 
+    #[cfg(feature = "component")]
+    fn get_fans(&self) -> &[Fan] {
+        &self.fans
+    }
+
+    #[cfg(feature = "component")]
+    fn get_fans_mut(&mut self) -> &mut [Fan] {
+        &mut self.fans
+    }
+
+    fn get_gpus(&self) -> &[Gpu] {
+        &self.gpus
+    }
+
+    fn get_gpus_mut(&mut self) -> &mut [Gpu] {
+        &mut self.gpus
+    }
+
+    #[cfg(feature = "disk")]
     fn get_disks(&self) -> &[Disk] {
         &self.disks
     }
 
+    #[cfg(feature = "disk")]
     fn get_disks_mut(&mut self) -> &mut [Disk] {
         &mut self.disks
     }
 
+    #[cfg(feature = "disk")]
+    fn get_all_mounts(&self) -> Vec<Mount> {
+        unsafe { get_all_mounts() }
+    }
+
     fn get_users(&self) -> &[User] {
         &self.users
     }
 
+    #[cfg(feature = "process")]
+    fn get_sockets(&self) -> &[SocketInfo] {
+        &self.sockets
+    }
+
+    #[cfg(feature = "network")]
     fn get_networks(&self) -> &Networks {
         &self.networks
     }
 
+    #[cfg(feature = "network")]
     fn get_networks_mut(&mut self) -> &mut Networks {
         &mut self.networks
     }
@@ -398,6 +799,10 @@ impl SystemExt for System {
         get_load_average()
     }
 
+    fn get_battery(&self) -> Option<Battery> {
+        battery::get_battery()
+    }
+
     fn get_name(&self) -> Option<String> {
         Some("Windows".to_owned())
     }
@@ -415,31 +820,54 @@ impl SystemExt for System {
     }
 
     fn get_kernel_version(&self) -> Option<String> {
-        get_reg_string_value(
-            HKEY_LOCAL_MACHINE,
-            "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion",
-            "CurrentBuildNumber",
-        )
+        get_os_version_info().map(|info| info.dwBuildNumber.to_string())
     }
 
     fn get_os_version(&self) -> Option<String> {
-        let major = get_reg_value_u32(
-            HKEY_LOCAL_MACHINE,
-            "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion",
-            "CurrentMajorVersionNumber",
-        );
+        get_os_version_info()
+            .map(|info| format!("{} ({})", info.dwMajorVersion, info.dwBuildNumber))
+    }
 
-        let build_number = get_reg_string_value(
-            HKEY_LOCAL_MACHINE,
-            "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion",
-            "CurrentBuildNumber",
-        );
+    #[cfg(feature = "cpu")]
+    fn get_cpu_arch(&self) -> Option<String> {
+        let mut info: SYSTEM_INFO = unsafe { zeroed() };
+        // Unlike `GetSystemInfo`, it returns the real architecture when running under WOW64.
+        unsafe { GetNativeSystemInfo(&mut info) };
+        let arch = match unsafe { info.u.s() }.wProcessorArchitecture {
+            PROCESSOR_ARCHITECTURE_AMD64 => "x86_64",
+            PROCESSOR_ARCHITECTURE_INTEL => "x86",
+            PROCESSOR_ARCHITECTURE_ARM64 => "aarch64",
+            PROCESSOR_ARCHITECTURE_ARM => "arm",
+            PROCESSOR_ARCHITECTURE_IA64 => "ia64",
+            _ => return None,
+        };
+        Some(arch.to_owned())
+    }
+
+    #[cfg(feature = "cpu")]
+    fn get_cpu_features(&self) -> Vec<String> {
+        get_cpu_features()
+    }
 
-        Some(format!(
-            "{} ({})",
-            u32::from_le_bytes(major.unwrap_or_default()),
-            build_number.unwrap_or_default()
-        ))
+    #[cfg(feature = "cpu")]
+    fn get_cpu_caches(&self) -> Vec<CacheInfo> {
+        get_cpu_caches()
+    }
+
+    fn get_virtualization(&self) -> Virtualization {
+        let names = ["SystemManufacturer", "SystemProductName", "BIOSVendor"]
+            .iter()
+            .filter_map(|field| {
+                get_reg_string_value(
+                    HKEY_LOCAL_MACHINE,
+                    "HARDWARE\\DESCRIPTION\\System\\BIOS",
+                    field,
+                )
+            })
+            .collect::<Vec<_>>();
+        crate::utils::get_dmi_virtualization(&names)
+            .or_else(get_cpuid_virtualization)
+            .unwrap_or(Virtualization::None)
     }
 }
 
@@ -449,19 +877,146 @@ impl Default for System {
     }
 }
 
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[cfg(feature = "cpu")]
+fn get_cpu_features() -> Vec<String> {
+    crate::utils::get_x86_cpu_features()
+}
+
+#[cfg(feature = "cpu")]
+fn get_context_switches() -> Option<u64> {
+    unsafe {
+        let mut info: SYSTEM_PERFORMANCE_INFORMATION = zeroed();
+        let status = NtQuerySystemInformation(
+            SystemPerformanceInformation,
+            &mut info as *mut SYSTEM_PERFORMANCE_INFORMATION as PVOID,
+            size_of::<SYSTEM_PERFORMANCE_INFORMATION>() as ULONG,
+            std::ptr::null_mut(),
+        );
+        if NT_SUCCESS(status) {
+            Some(info.ContextSwitches as u64)
+        } else {
+            sysinfo_debug!(
+                "NtQuerySystemInformation(SystemPerformanceInformation) failed: {}",
+                status
+            );
+            None
+        }
+    }
+}
+
+/// Returns the pagefiles, whose sizes are given in pages by `NtQuerySystemInformation`.
+#[allow(clippy::cast_ptr_alignment)]
+fn get_swap_devices() -> Vec<SwapDevice> {
+    let page_size = unsafe {
+        let mut info: SYSTEM_INFO = zeroed();
+        GetNativeSystemInfo(&mut info);
+        info.dwPageSize as u64
+    };
+    // `u64` items to have a buffer aligned for `SYSTEM_PAGEFILE_INFORMATION`.
+    let mut buffer: Vec<u64> = vec![0; 128];
+    let len = loop {
+        let mut len = 0;
+        let status = unsafe {
+            NtQuerySystemInformation(
+                SystemPageFileInformation,
+                buffer.as_mut_ptr() as PVOID,
+                (buffer.len() * size_of::<u64>()) as ULONG,
+                &mut len,
+            )
+        };
+        if status == STATUS_INFO_LENGTH_MISMATCH {
+            let new_len = buffer.len() * 2;
+            buffer.resize(new_len, 0);
+        } else if NT_SUCCESS(status) {
+            break len as usize;
+        } else {
+            sysinfo_debug!(
+                "NtQuerySystemInformation(SystemPageFileInformation) failed: {}",
+                status
+            );
+            return Vec::new();
+        }
+    };
+
+    let mut devices = Vec::new();
+    let mut offset = 0;
+    // Nothing is returned if there is no pagefile.
+    while offset + size_of::<SYSTEM_PAGEFILE_INFORMATION>() <= len {
+        let info = unsafe {
+            &*((buffer.as_ptr() as *const u8).add(offset) as *const SYSTEM_PAGEFILE_INFORMATION)
+        };
+        let name = unsafe {
+            from_raw_parts(
+                info.PageFileName.Buffer,
+                info.PageFileName.Length as usize / size_of::<u16>(),
+            )
+        };
+        let name = String::from_utf16_lossy(name);
+        devices.push(SwapDevice {
+            // The names look like `\??\C:\pagefile.sys`.
+            name: name.strip_prefix("\\??\\").unwrap_or(&name).to_owned(),
+            type_: SwapType::File,
+            size: info.TotalSize as u64 * page_size,
+            used: info.TotalInUse as u64 * page_size,
+        });
+        if info.NextEntryOffset == 0 {
+            break;
+        }
+        offset += info.NextEntryOffset as usize;
+    }
+    devices
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn get_cpuid_virtualization() -> Option<Virtualization> {
+    match crate::utils::get_cpuid_virtualization() {
+        // With virtualization-based security enabled, the host itself runs as the root partition
+        // of Hyper-V and gets the hypervisor bit too.
+        Some(Virtualization::HyperV) => None,
+        virt => virt,
+    }
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+fn get_cpuid_virtualization() -> Option<Virtualization> {
+    None
+}
+
+#[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+#[cfg(feature = "cpu")]
+fn get_cpu_features() -> Vec<String> {
+    use winapi::um::processthreadsapi::IsProcessorFeaturePresent;
+
+    // The `PF_ARM_*` values from `winnt.h`.
+    const FEATURES: &[(&str, DWORD)] = &[("neon", 19), ("crypto", 30), ("crc32", 31)];
+
+    FEATURES
+        .iter()
+        .filter(|(_, feature)| unsafe { IsProcessorFeaturePresent(*feature) } != FALSE)
+        .map(|(name, _)| (*name).to_owned())
+        .collect()
+}
+
+#[cfg(feature = "process")]
 fn is_proc_running(handle: HANDLE) -> bool {
     let mut exit_code = 0;
     let ret = unsafe { GetExitCodeProcess(handle, &mut exit_code) };
     !(ret == FALSE || exit_code != STILL_ACTIVE)
 }
 
+#[cfg(feature = "process")]
 fn refresh_existing_process(s: &mut System, pid: Pid) -> bool {
-    if let Some(ref mut entry) = s.process_list.get_mut(&(pid as usize)) {
+    if let Some(ref mut entry) = s.process_list.get_mut(&pid) {
         if !is_proc_running(get_handle(entry)) {
             return false;
         }
         update_memory(entry);
         update_disk_usage(entry);
+        entry.run_time = get_secs_since_epoch().saturating_sub(entry.start_time());
+        entry.thread_count = get_thread_count(pid);
+        update_open_files_count(entry);
+        update_priority(entry);
         compute_cpu_usage(
             entry,
             s.processors.len() as u64,
@@ -475,6 +1030,7 @@ fn refresh_existing_process(s: &mut System, pid: Pid) -> bool {
 
 #[allow(clippy::size_of_in_element_count)]
 //^ needed for "name.Length as usize / std::mem::size_of::<u16>()"
+#[cfg(feature = "process")]
 pub(crate) fn get_process_name(process: &SYSTEM_PROCESS_INFORMATION, process_id: usize) -> String {
     let name = &process.ImageName;
     if name.Buffer.is_null() {
@@ -546,31 +1102,16 @@ fn get_reg_string_value(hkey: HKEY, path: &str, field_name: &str) -> Option<Stri
     Some(s)
 }
 
-fn get_reg_value_u32(hkey: HKEY, path: &str, field_name: &str) -> Option<[u8; 4]> {
-    let c_path = utf16_str(path);
-    let c_field_name = utf16_str(field_name);
-
-    let mut new_hkey: HKEY = std::ptr::null_mut();
-    if unsafe { RegOpenKeyExW(hkey, c_path.as_ptr(), 0, KEY_READ, &mut new_hkey) } != 0 {
-        return None;
-    }
-
-    let mut buf_len: DWORD = 4;
-    let mut buf_type: DWORD = 0;
-    let mut buf = [0u8; 4];
-
-    match unsafe {
-        RegQueryValueExW(
-            new_hkey,
-            c_field_name.as_ptr(),
-            std::ptr::null_mut(),
-            &mut buf_type,
-            buf.as_mut_ptr() as LPBYTE,
-            &mut buf_len,
-        ) as DWORD
-    } {
-        0 => Some(buf),
-        _ => None,
+/// Unlike `GetVersionEx`, `RtlGetVersion` isn't affected by the application manifest so it
+/// always returns the real version.
+fn get_os_version_info() -> Option<RTL_OSVERSIONINFOW> {
+    let mut info: RTL_OSVERSIONINFOW = unsafe { zeroed() };
+    info.dwOSVersionInfoSize = size_of::<RTL_OSVERSIONINFOW>() as _;
+    if NT_SUCCESS(unsafe { RtlGetVersion(&mut info) }) {
+        Some(info)
+    } else {
+        sysinfo_debug!("RtlGetVersion failed");
+        None
     }
 }
 
@@ -4,43 +4,70 @@
 // Copyright (c) 2018 Guillaume Gomez
 //
 
-use crate::DiskType;
+#[cfg(feature = "disk")]
+use crate::{DiskType, Mount};
 
+#[cfg(feature = "disk")]
 use crate::sys::disk::{new_disk, Disk};
+#[cfg(feature = "cpu")]
 use crate::sys::processor::{self, Processor, Query};
 
+#[cfg(feature = "cpu")]
 use std::collections::HashMap;
+#[cfg(feature = "disk")]
 use std::ffi::OsStr;
-use std::mem::{size_of, zeroed};
+#[cfg(feature = "disk")]
+use std::mem::size_of;
+use std::mem::zeroed;
+#[cfg(feature = "disk")]
+use std::path::PathBuf;
 
+#[cfg(feature = "disk")]
 use winapi::{ctypes::c_void, um::winbase::DRIVE_REMOVABLE};
 
-use winapi::shared::minwindef::{DWORD, MAX_PATH, TRUE};
+#[cfg(feature = "disk")]
+use winapi::shared::minwindef::{BYTE, DWORD, MAX_PATH, TRUE};
+#[cfg(feature = "disk")]
+use winapi::shared::ntdef::BOOLEAN;
+#[cfg(feature = "disk")]
 use winapi::um::fileapi::{
-    CreateFileW, GetDriveTypeW, GetLogicalDrives, GetVolumeInformationW, OPEN_EXISTING,
+    CreateFileW, GetDriveTypeW, GetLogicalDrives, GetVolumeInformationW,
+    GetVolumeNameForVolumeMountPointW, OPEN_EXISTING,
 };
+#[cfg(feature = "disk")]
 use winapi::um::handleapi::CloseHandle;
+#[cfg(feature = "disk")]
 use winapi::um::handleapi::INVALID_HANDLE_VALUE;
+#[cfg(feature = "disk")]
 use winapi::um::ioapiset::DeviceIoControl;
+#[cfg(feature = "cpu")]
 use winapi::um::sysinfoapi::{GetSystemInfo, SYSTEM_INFO};
+#[cfg(feature = "disk")]
 use winapi::um::winbase::DRIVE_FIXED;
+#[cfg(feature = "disk")]
 use winapi::um::winioctl::{
-    DEVICE_TRIM_DESCRIPTOR, IOCTL_DISK_GET_LENGTH_INFO, IOCTL_STORAGE_QUERY_PROPERTY,
-    GET_LENGTH_INFORMATION, STORAGE_PROPERTY_QUERY,
+    DEVICE_TRIM_DESCRIPTOR, DISK_PERFORMANCE, GET_LENGTH_INFORMATION, IOCTL_DISK_GET_LENGTH_INFO,
+    IOCTL_DISK_PERFORMANCE, IOCTL_STORAGE_QUERY_PROPERTY, STORAGE_PROPERTY_QUERY,
+};
+#[cfg(feature = "disk")]
+use winapi::um::winnt::{
+    FILE_READ_ONLY_VOLUME, FILE_SHARE_READ, FILE_SHARE_WRITE, GENERIC_READ, HANDLE,
 };
-use winapi::um::winnt::{FILE_SHARE_READ, FILE_SHARE_WRITE, GENERIC_READ, HANDLE};
 
+#[cfg(feature = "cpu")]
 pub struct KeyHandler {
     pub unique_id: String,
     pub win_key: Vec<u16>,
 }
 
+#[cfg(feature = "cpu")]
 impl KeyHandler {
     pub fn new(unique_id: String, win_key: Vec<u16>) -> KeyHandler {
         KeyHandler { unique_id, win_key }
     }
 }
 
+#[cfg(feature = "cpu")]
 pub fn init_processors() -> (Vec<Processor>, String, String) {
     unsafe {
         let mut sys_info: SYSTEM_INFO = zeroed();
@@ -60,6 +87,7 @@ pub fn init_processors() -> (Vec<Processor>, String, String) {
     }
 }
 
+#[cfg(feature = "disk")]
 pub unsafe fn open_drive(drive_name: &[u16], open_rights: DWORD) -> HANDLE {
     CreateFileW(
         drive_name.as_ptr(),
@@ -72,6 +100,7 @@ pub unsafe fn open_drive(drive_name: &[u16], open_rights: DWORD) -> HANDLE {
     )
 }
 
+#[cfg(feature = "disk")]
 pub unsafe fn get_drive_size(handle: HANDLE) -> u64 {
     let mut pdg: GET_LENGTH_INFORMATION = std::mem::zeroed();
     let mut junk = 0;
@@ -92,6 +121,126 @@ pub unsafe fn get_drive_size(handle: HANDLE) -> u64 {
     }
 }
 
+/// Returns the performance counters (like the number of read and write operations) of the volume
+/// opened with `handle`.
+#[cfg(feature = "disk")]
+pub unsafe fn get_disk_performance(handle: HANDLE) -> Option<DISK_PERFORMANCE> {
+    let mut perf: DISK_PERFORMANCE = std::mem::zeroed();
+    let mut junk = 0;
+    let result = DeviceIoControl(
+        handle,
+        IOCTL_DISK_PERFORMANCE,
+        std::ptr::null_mut(),
+        0,
+        &mut perf as *mut DISK_PERFORMANCE as *mut c_void,
+        size_of::<DISK_PERFORMANCE>() as DWORD,
+        &mut junk,
+        std::ptr::null_mut(),
+    );
+    if result == TRUE {
+        Some(perf)
+    } else {
+        None
+    }
+}
+
+/// `min_size` is the number of bytes which need to be filled for the returned descriptor to be
+/// usable.
+#[cfg(feature = "disk")]
+unsafe fn query_storage_property<T>(
+    handle: HANDLE,
+    property_id: u32,
+    min_size: usize,
+) -> Option<T> {
+    let mut query = STORAGE_PROPERTY_QUERY {
+        PropertyId: property_id,
+        // PropertyStandardQuery
+        QueryType: 0,
+        AdditionalParameters: [0],
+    };
+    let mut descriptor: T = zeroed();
+    let mut dw_size = 0;
+    if DeviceIoControl(
+        handle,
+        IOCTL_STORAGE_QUERY_PROPERTY,
+        &mut query as *mut STORAGE_PROPERTY_QUERY as *mut c_void,
+        size_of::<STORAGE_PROPERTY_QUERY>() as DWORD,
+        &mut descriptor as *mut T as *mut c_void,
+        size_of::<T>() as DWORD,
+        &mut dw_size,
+        std::ptr::null_mut(),
+    ) == 0
+        || (dw_size as usize) < min_size
+    {
+        None
+    } else {
+        Some(descriptor)
+    }
+}
+
+// Not provided by `winapi`.
+#[allow(non_snake_case)]
+#[repr(C)]
+#[cfg(feature = "disk")]
+struct DEVICE_SEEK_PENALTY_DESCRIPTOR {
+    Version: DWORD,
+    Size: DWORD,
+    IncursSeekPenalty: BOOLEAN,
+}
+
+/// Returns `None` if the drive doesn't tell whether it's a SSD or not.
+#[cfg(feature = "disk")]
+unsafe fn get_is_ssd(handle: HANDLE) -> Option<bool> {
+    // Drives without seek penalty are SSDs. Not all drivers provide this information so in this
+    // case, we fallback to the TRIM support, which is less reliable.
+    // 7 is `StorageDeviceSeekPenaltyProperty`.
+    if let Some(desc) = query_storage_property::<DEVICE_SEEK_PENALTY_DESCRIPTOR>(
+        handle,
+        7,
+        size_of::<DEVICE_SEEK_PENALTY_DESCRIPTOR>(),
+    ) {
+        return Some(desc.IncursSeekPenalty == 0);
+    }
+    // 8 is `StorageDeviceTrimProperty`.
+    query_storage_property::<DEVICE_TRIM_DESCRIPTOR>(handle, 8, size_of::<DEVICE_TRIM_DESCRIPTOR>())
+        .map(|dtd| dtd.TrimEnabled != 0)
+}
+
+// Not provided by `winapi`.
+#[allow(non_snake_case)]
+#[repr(C)]
+#[cfg(feature = "disk")]
+struct STORAGE_DEVICE_DESCRIPTOR {
+    Version: DWORD,
+    Size: DWORD,
+    DeviceType: BYTE,
+    DeviceTypeModifier: BYTE,
+    RemovableMedia: BOOLEAN,
+    CommandQueueing: BOOLEAN,
+    VendorIdOffset: DWORD,
+    ProductIdOffset: DWORD,
+    ProductRevisionOffset: DWORD,
+    SerialNumberOffset: DWORD,
+    // `STORAGE_BUS_TYPE`
+    BusType: DWORD,
+    RawPropertiesLength: DWORD,
+    RawDeviceProperties: [BYTE; 1],
+}
+
+/// Returns `true` if the device has removable media or is connected through USB (external
+/// drives are reported as fixed ones by `GetDriveTypeW`).
+#[cfg(feature = "disk")]
+unsafe fn get_is_removable_device(handle: HANDLE) -> bool {
+    // 0 is `StorageDeviceProperty`. The descriptor is followed by variable-length data so we
+    // only need it to be filled up to the `BusType` field (which ends at the 32nd byte).
+    match query_storage_property::<STORAGE_DEVICE_DESCRIPTOR>(handle, 0, 32) {
+        // 7 is `BusTypeUsb`.
+        Some(desc) => desc.RemovableMedia != 0 || desc.BusType == 7,
+        None => false,
+    }
+}
+
+#[cfg(feature = "disk")]
 pub unsafe fn get_disks() -> Vec<Disk> {
     let drives = GetLogicalDrives();
     if drives == 0 {
@@ -117,13 +266,14 @@ pub unsafe fn get_disks() -> Vec<Disk> {
             }
             let mut name = [0u16; MAX_PATH + 1];
             let mut file_system = [0u16; 32];
+            let mut file_system_flags = 0;
             if GetVolumeInformationW(
                 mount_point.as_ptr(),
                 name.as_mut_ptr(),
                 name.len() as DWORD,
                 std::ptr::null_mut(),
                 std::ptr::null_mut(),
-                std::ptr::null_mut(),
+                &mut file_system_flags,
                 file_system.as_mut_ptr(),
                 file_system.len() as DWORD,
             ) == 0
@@ -149,6 +299,28 @@ pub unsafe fn get_disks() -> Vec<Disk> {
             }
             let file_system: Vec<u8> = file_system[..pos].iter().map(|x| *x as u8).collect();
 
+            // The volume GUID path looks like "\\?\Volume{GUID}\" so 50 characters are enough.
+            let mut device_name = [0u16; 50];
+            let device_name = if GetVolumeNameForVolumeMountPointW(
+                mount_point.as_ptr(),
+                device_name.as_mut_ptr(),
+                device_name.len() as DWORD,
+            ) != 0
+            {
+                pos = 0;
+                for x in device_name.iter() {
+                    if *x == 0 {
+                        break;
+                    }
+                    pos += 1;
+                }
+                String::from_utf16_lossy(&device_name[..pos])
+            } else {
+                String::new()
+            };
+            let device_name = OsStr::new(&device_name);
+            let is_read_only = file_system_flags & FILE_READ_ONLY_VOLUME != 0;
+
             let drive_name = [
                 b'\\' as u16,
                 b'\\' as u16,
@@ -163,6 +335,7 @@ pub unsafe fn get_disks() -> Vec<Disk> {
                 CloseHandle(handle);
                 return new_disk(
                     name,
+                    device_name,
                     &mount_point,
                     &file_system,
                     if is_removable {
@@ -170,65 +343,89 @@ pub unsafe fn get_disks() -> Vec<Disk> {
                     } else {
                         DiskType::Unknown(-1)
                     },
+                    is_removable,
                     0,
+                    is_read_only,
                 );
             }
             let disk_size = get_drive_size(handle);
-            /*let mut spq_trim: STORAGE_PROPERTY_QUERY = std::mem::zeroed();
-            spq_trim.PropertyId = StorageDeviceTrimProperty;
-            spq_trim.QueryType = PropertyStandardQuery;
-            let mut dtd: DEVICE_TRIM_DESCRIPTOR = std::mem::zeroed();*/
-            let mut spq_trim = STORAGE_PROPERTY_QUERY {
-                PropertyId: 8,
-                QueryType: 0,
-                AdditionalParameters: [0],
-            };
-            let mut dtd: DEVICE_TRIM_DESCRIPTOR = std::mem::zeroed();
-
-            let mut dw_size = 0;
-            if DeviceIoControl(
-                handle,
-                IOCTL_STORAGE_QUERY_PROPERTY,
-                &mut spq_trim as *mut STORAGE_PROPERTY_QUERY as *mut c_void,
-                size_of::<STORAGE_PROPERTY_QUERY>() as DWORD,
-                &mut dtd as *mut DEVICE_TRIM_DESCRIPTOR as *mut c_void,
-                size_of::<DEVICE_TRIM_DESCRIPTOR>() as DWORD,
-                &mut dw_size,
-                std::ptr::null_mut(),
-            ) == 0
-                || dw_size != size_of::<DEVICE_TRIM_DESCRIPTOR>() as DWORD
-            {
-                CloseHandle(handle);
-                return new_disk(
-                    name,
-                    &mount_point,
-                    &file_system,
-                    if is_removable {
-                        DiskType::Removable
-                    } else {
-                        DiskType::Unknown(-1)
-                    },
-                    disk_size,
-                );
-            }
-            let is_ssd = dtd.TrimEnabled != 0;
+            let is_removable = is_removable || get_is_removable_device(handle);
+            let is_ssd = get_is_ssd(handle);
             CloseHandle(handle);
             new_disk(
                 name,
+                device_name,
                 &mount_point,
                 &file_system,
                 match (is_removable, is_ssd) {
                     (true, _) => DiskType::Removable,
-                    (false, true) => DiskType::SSD,
-                    (false, false) => DiskType::HDD,
+                    (false, Some(true)) => DiskType::SSD,
+                    (false, Some(false)) => DiskType::HDD,
+                    (false, None) => DiskType::Unknown(-1),
                 },
+                is_removable,
                 disk_size,
+                is_read_only,
             )
         })
         .collect::<Vec<_>>()
 }
 
 #[allow(non_snake_case)]
+/// Returns the content of `buf` up to its first null character.
+#[cfg(feature = "disk")]
+fn from_utf16_until_nul(buf: &[u16]) -> String {
+    let pos = buf.iter().position(|c| *c == 0).unwrap_or(buf.len());
+    String::from_utf16_lossy(&buf[..pos])
+}
+
+/// Unlike [`get_disks`], every volume mounted on a drive letter is returned, whatever the type of
+/// its drive (network shares and RAM disks included). Drives without any medium are skipped.
+#[cfg(feature = "disk")]
+pub unsafe fn get_all_mounts() -> Vec<Mount> {
+    let drives = GetLogicalDrives();
+    (0..size_of::<DWORD>() * 8)
+        .filter(|x| (drives >> x) & 1 != 0)
+        .filter_map(|x| {
+            let mount_point = [b'A' as u16 + x as u16, b':' as u16, b'\\' as u16, 0];
+            let mut file_system = [0u16; 32];
+            let mut file_system_flags = 0;
+            if GetVolumeInformationW(
+                mount_point.as_ptr(),
+                std::ptr::null_mut(),
+                0,
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                &mut file_system_flags,
+                file_system.as_mut_ptr(),
+                file_system.len() as DWORD,
+            ) == 0
+            {
+                return None;
+            }
+            // The volume GUID path looks like "\\?\Volume{GUID}\" so 50 characters are enough.
+            let mut device_name = [0u16; 50];
+            if GetVolumeNameForVolumeMountPointW(
+                mount_point.as_ptr(),
+                device_name.as_mut_ptr(),
+                device_name.len() as DWORD,
+            ) == 0
+            {
+                // Network drives don't have any volume GUID path.
+                device_name[0] = 0;
+            }
+            let is_read_only = file_system_flags & FILE_READ_ONLY_VOLUME != 0;
+            Some(Mount {
+                device: from_utf16_until_nul(&device_name),
+                mount_point: PathBuf::from(from_utf16_until_nul(&mount_point)),
+                file_system: from_utf16_until_nul(&file_system),
+                options: vec![if is_read_only { "ro" } else { "rw" }.to_owned()],
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "cpu")]
 pub unsafe fn load_symbols() -> HashMap<String, u32> {
     use winapi::um::winreg::{RegQueryValueExA, HKEY_PERFORMANCE_DATA};
 
@@ -280,6 +477,7 @@ pub unsafe fn load_symbols() -> HashMap<String, u32> {
 }
 
 #[allow(clippy::ptr_arg)]
+#[cfg(feature = "cpu")]
 pub fn get_translation(s: &String, map: &HashMap<String, u32>) -> Option<String> {
     use winapi::um::pdh::PdhLookupPerfNameByIndexW;
 
@@ -310,6 +508,7 @@ pub fn get_translation(s: &String, map: &HashMap<String, u32>) -> Option<String>
     None
 }
 
+#[cfg(feature = "cpu")]
 pub fn add_counter(
     s: String,
     query: &mut Query,
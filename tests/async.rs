@@ -0,0 +1,18 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+#![cfg(feature = "process")]
+
+#[cfg(feature = "tokio")]
+#[tokio::test]
+async fn test_refresh_all_async() {
+    use sysinfo::SystemExt;
+
+    let s = sysinfo::System::new();
+    assert!(s.get_processes().is_empty());
+    let s = s.refresh_all_async().await;
+    assert!(!s.get_processes().is_empty());
+}
@@ -0,0 +1,17 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+#![cfg(feature = "system")]
+
+#[test]
+fn test_battery() {
+    use sysinfo::SystemExt;
+
+    let s = sysinfo::System::new();
+    if let Some(battery) = s.get_battery() {
+        assert!((0.0..=100.0).contains(&battery.percentage));
+    }
+}
@@ -0,0 +1,51 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+#![cfg(feature = "c-interface")]
+
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_void};
+
+extern "C" fn collect_names(interface_name: *const c_char, data: *mut c_void) -> bool {
+    let names = unsafe { &mut *(data as *mut Vec<String>) };
+    let name = unsafe { CStr::from_ptr(interface_name) };
+    names.push(name.to_str().unwrap().to_owned());
+    true
+}
+
+#[test]
+fn test_networks_c_interface() {
+    use sysinfo::{NetworkExt, NetworksExt, SystemExt};
+
+    let networks = sysinfo::sysinfo_networks_new();
+    assert!(!networks.is_null());
+    sysinfo::sysinfo_networks_refresh(networks);
+
+    let mut names: Vec<String> = Vec::new();
+    let len = sysinfo::sysinfo_networks_iter(
+        networks,
+        Some(collect_names),
+        &mut names as *mut Vec<String> as *mut c_void,
+    );
+    assert_eq!(len, names.len());
+
+    let s = sysinfo::System::new_all();
+    for name in &names {
+        let c_name = CString::new(name.as_str()).unwrap();
+        let total_received =
+            sysinfo::sysinfo_networks_get_total_received(networks, c_name.as_ptr());
+        // The counters can only grow between the two snapshots.
+        if let Some((_, data)) = s.get_networks().iter().find(|(n, _)| *n == name) {
+            assert!(total_received <= data.get_total_received());
+        }
+    }
+    let unknown = CString::new("not an interface").unwrap();
+    assert_eq!(
+        sysinfo::sysinfo_networks_get_received(networks, unknown.as_ptr()),
+        0
+    );
+    sysinfo::sysinfo_networks_free(networks);
+}
@@ -0,0 +1,62 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+#![cfg(feature = "component")]
+
+#[test]
+fn test_components_max() {
+    use sysinfo::{ComponentExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    s.refresh_components_list();
+    s.refresh_components();
+    for component in s.get_components() {
+        assert!(component.get_max() >= component.get_temperature());
+    }
+}
+
+#[test]
+fn test_fans() {
+    use sysinfo::{FanExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    s.refresh_components_list();
+    s.refresh_components();
+    for fan in s.get_fans() {
+        // Mostly there to check that reading the fans doesn't panic.
+        let _ = fan.get_rpm();
+        assert!(!fan.get_label().is_empty());
+    }
+}
+
+#[test]
+fn test_components_refresh_only() {
+    use sysinfo::{ComponentExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    s.refresh_components_list();
+    let labels = s
+        .get_components()
+        .iter()
+        .map(|c| c.get_label().to_owned())
+        .collect::<Vec<_>>();
+    s.refresh_components();
+    // Refreshing the components doesn't change the list...
+    assert_eq!(
+        s.get_components()
+            .iter()
+            .map(|c| c.get_label())
+            .collect::<Vec<_>>(),
+        labels.iter().map(|l| l.as_str()).collect::<Vec<_>>(),
+    );
+    for component in s.get_components() {
+        assert!(component.get_max() >= component.get_temperature());
+    }
+    // ... and doesn't refresh anything else.
+    #[cfg(feature = "process")]
+    assert!(s.get_processes().is_empty());
+    assert_eq!(s.get_total_memory(), 0);
+}
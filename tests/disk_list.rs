@@ -4,6 +4,8 @@
 // Copyright (c) 2017 Guillaume Gomez
 //
 
+#![cfg(feature = "disk")]
+
 #[test]
 fn test_disks() {
     use sysinfo::SystemExt;
@@ -11,3 +13,171 @@ fn test_disks() {
     let s = sysinfo::System::new_all();
     assert!(!s.get_disks().is_empty());
 }
+
+#[test]
+fn test_disk_device_name() {
+    use std::path::Path;
+    use sysinfo::{DiskExt, SystemExt};
+
+    let s = sysinfo::System::new_all();
+    for disk in s.get_disks() {
+        if disk.get_mount_point() == Path::new("/") {
+            assert!(!disk.get_device_name().is_empty());
+        }
+    }
+}
+
+#[test]
+fn test_disk_type() {
+    use std::path::Path;
+    use sysinfo::{DiskExt, DiskType, SystemExt};
+
+    let s = sysinfo::System::new_all();
+    for disk in s.get_disks() {
+        if disk.get_mount_point() == Path::new("/") {
+            match disk.get_type() {
+                DiskType::HDD | DiskType::SSD | DiskType::Removable | DiskType::Unknown(_) => {}
+            }
+        }
+    }
+}
+
+#[test]
+fn test_disk_removable() {
+    use std::path::Path;
+    use sysinfo::{DiskExt, SystemExt};
+
+    let s = sysinfo::System::new_all();
+    for disk in s.get_disks() {
+        // The system disk can't be removed.
+        if disk.get_mount_point() == Path::new("/") {
+            assert!(!disk.is_removable());
+        }
+    }
+}
+
+#[test]
+fn test_refresh_disk() {
+    use std::path::Path;
+    use sysinfo::{DiskExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    assert!(!s.refresh_disk(Path::new("/")));
+    s.refresh_disks_list();
+    let root = Path::new("/");
+    if s.get_disks().iter().any(|d| d.get_mount_point() == root) {
+        assert!(s.refresh_disk(root));
+        let disk = s
+            .get_disks()
+            .iter()
+            .find(|d| d.get_mount_point() == root)
+            .unwrap();
+        assert!(disk.get_available_space() <= disk.get_total_space());
+    }
+    assert!(!s.refresh_disk(Path::new("/this/is/not/a/mount/point")));
+}
+
+#[test]
+fn test_disk_space_sums() {
+    use sysinfo::{DiskExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    assert_eq!(s.get_total_disk_space(), 0);
+    assert_eq!(s.get_available_disk_space(), 0);
+    s.refresh_disks_list();
+    let total = s
+        .get_disks()
+        .iter()
+        .map(|d| d.get_total_space())
+        .sum::<u64>();
+    assert!(s.get_total_disk_space() <= total);
+    assert!(s.get_available_disk_space() <= s.get_total_disk_space());
+}
+
+#[test]
+fn test_disk_operation_counts() {
+    use sysinfo::{DiskExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    s.refresh_disks_list();
+    let totals = s
+        .get_disks()
+        .iter()
+        .map(|d| (d.get_total_read_count(), d.get_total_write_count()))
+        .collect::<Vec<_>>();
+    for disk in s.get_disks() {
+        // Nothing was refreshed yet.
+        assert_eq!(disk.get_read_count(), 0);
+        assert_eq!(disk.get_write_count(), 0);
+    }
+    s.refresh_disks();
+    for (disk, (read_count, write_count)) in s.get_disks().iter().zip(totals) {
+        assert!(disk.get_total_read_count() >= read_count);
+        assert!(disk.get_total_write_count() >= write_count);
+        assert_eq!(
+            disk.get_read_count(),
+            disk.get_total_read_count() - read_count
+        );
+        assert_eq!(
+            disk.get_write_count(),
+            disk.get_total_write_count() - write_count
+        );
+    }
+}
+
+#[test]
+fn test_disk_io_utilization() {
+    use sysinfo::{DiskExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    s.refresh_disks_list();
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    s.refresh_disks();
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    s.refresh_disks();
+    for disk in s.get_disks() {
+        let utilization = disk.get_io_utilization();
+        assert!((0. ..=100.).contains(&utilization));
+    }
+}
+
+#[test]
+fn test_disk_filter() {
+    use sysinfo::{DiskExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    s.refresh_disks_list();
+    let count = s.get_disks().len();
+    s.set_disk_filter(|mount_point, _| !mount_point.starts_with("/snap"));
+    s.refresh_disks_list();
+    assert!(s
+        .get_disks()
+        .iter()
+        .all(|disk| !disk.get_mount_point().starts_with("/snap")));
+    assert!(s.get_disks().len() <= count);
+
+    // Rejecting everything leaves no disk.
+    s.set_disk_filter(|_, _| false);
+    s.refresh_disks_list();
+    assert!(s.get_disks().is_empty());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_all_mounts() {
+    use sysinfo::{DiskExt, SystemExt};
+
+    let s = sysinfo::System::new_all();
+    let mounts = s.get_all_mounts();
+    for file_system in &["proc", "sysfs"] {
+        assert!(
+            mounts.iter().any(|mount| mount.file_system == *file_system),
+            "{} not found in all mounts",
+            file_system
+        );
+        assert!(s
+            .get_disks()
+            .iter()
+            .all(|disk| disk.get_file_system() != file_system.as_bytes()));
+    }
+}
@@ -0,0 +1,24 @@
+//
+// Sysinfo
+//
+// Copyright (c) 2021 Guillaume Gomez
+//
+
+#![cfg(feature = "system")]
+
+#[test]
+fn test_gpus() {
+    use sysinfo::{GpuExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    assert!(s.get_gpus().is_empty());
+    s.refresh_gpus_list();
+    s.refresh_gpus();
+    for gpu in s.get_gpus() {
+        assert!(!gpu.get_name().is_empty());
+        if gpu.get_total_memory() > 0 {
+            assert!(gpu.get_used_memory() <= gpu.get_total_memory());
+        }
+        assert!((0.0..=100.0).contains(&gpu.get_usage()));
+    }
+}
@@ -4,10 +4,13 @@
 // Copyright (c) 2020 Guillaume Gomez
 //
 
+#![cfg(feature = "network")]
+
 // This test is used to ensure that the processors are loaded whatever the method
 // used to initialize `System`.
 
 #[test]
+#[cfg(feature = "system")]
 fn test_processor() {
     use sysinfo::{NetworksExt, SystemExt};
 
@@ -16,3 +19,345 @@ fn test_processor() {
     let s = sysinfo::System::new_all();
     assert!(s.get_networks().iter().count() > 0);
 }
+
+#[test]
+#[cfg(feature = "system")]
+fn test_wifi_signal() {
+    use sysinfo::{NetworkExt, SystemExt};
+
+    let s = sysinfo::System::new_all();
+    for (name, network) in s.get_networks() {
+        // The loopback interface is never a wireless one.
+        if name == "lo" || name == "lo0" {
+            assert_eq!(network.get_wifi_signal(), None);
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "system")]
+fn test_interface_type() {
+    use sysinfo::{InterfaceType, NetworkExt, SystemExt};
+
+    let s = sysinfo::System::new_all();
+    for (name, network) in s.get_networks() {
+        if name == "lo" || name == "lo0" {
+            assert_eq!(network.get_interface_type(), InterfaceType::Loopback);
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "system")]
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn test_loopback_status() {
+    use sysinfo::{InterfaceStatus, InterfaceType, NetworkExt, NetworksExt, SystemExt};
+
+    let s = sysinfo::System::new_all();
+    let (_, loopback) = s
+        .get_networks()
+        .iter()
+        .find(|(_, network)| network.get_interface_type() == InterfaceType::Loopback)
+        .expect("no loopback interface");
+    assert_eq!(loopback.get_admin_status(), InterfaceStatus::Up);
+    assert_eq!(loopback.get_oper_status(), InterfaceStatus::Up);
+}
+
+#[test]
+#[cfg(feature = "system")]
+fn test_networks_errors_and_drops() {
+    use sysinfo::{InterfaceType, NetworkExt, NetworksExt, SystemExt};
+
+    let s = sysinfo::System::new_all();
+    let networks = s.get_networks();
+    let (mut errors, mut total_errors, mut drops, mut total_drops) = (0, 0, 0, 0);
+    for (_, network) in networks {
+        // Loopback interfaces are skipped by default.
+        if network.get_interface_type() == InterfaceType::Loopback {
+            continue;
+        }
+        errors += network.get_errors_on_received() + network.get_errors_on_transmitted();
+        total_errors +=
+            network.get_total_errors_on_received() + network.get_total_errors_on_transmitted();
+        drops += network.get_drops_on_received() + network.get_drops_on_transmitted();
+        total_drops +=
+            network.get_total_drops_on_received() + network.get_total_drops_on_transmitted();
+    }
+    assert_eq!(networks.get_errors(), errors);
+    assert_eq!(networks.get_total_errors(), total_errors);
+    assert_eq!(networks.get_drops(), drops);
+    assert_eq!(networks.get_total_drops(), total_drops);
+}
+
+#[test]
+#[cfg(feature = "system")]
+fn test_counting_basis() {
+    use sysinfo::{CountingBasis, InterfaceType, NetworkExt, SystemExt};
+
+    let s = sysinfo::System::new_all();
+    for (_, network) in s.get_networks() {
+        let expected = if network.get_interface_type() == InterfaceType::Tunnel {
+            CountingBasis::Network
+        } else {
+            CountingBasis::LinkLayer
+        };
+        assert_eq!(network.get_counting_basis(), expected);
+    }
+}
+
+#[test]
+#[cfg(feature = "system")]
+#[cfg(windows)]
+fn test_luid() {
+    use sysinfo::SystemExt;
+
+    let s = sysinfo::System::new_all();
+    for (_, network) in s.get_networks() {
+        // Only hardware interfaces are listed and they all have a LUID.
+        assert_ne!(network.get_luid(), 0);
+    }
+}
+
+#[test]
+#[cfg(feature = "system")]
+fn test_network_history() {
+    use sysinfo::{NetworkExt, NetworksExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    s.refresh_networks_list();
+    s.get_networks_mut().set_history_len(2);
+    for _ in 0..3 {
+        s.refresh_networks();
+    }
+    for (_, network) in s.get_networks() {
+        assert_eq!(network.get_received_history().len(), 2);
+        assert_eq!(network.get_transmitted_history().len(), 2);
+        assert_eq!(
+            network.get_received_history().back(),
+            Some(&network.get_received())
+        );
+    }
+    s.get_networks_mut().set_history_len(1);
+    for (_, network) in s.get_networks() {
+        assert_eq!(network.get_received_history().len(), 1);
+    }
+}
+
+#[test]
+#[cfg(feature = "system")]
+fn test_networks_debug() {
+    use sysinfo::SystemExt;
+
+    let s = sysinfo::System::new_all();
+    let networks = format!("{:?}", s.get_networks());
+    for (name, network) in s.get_networks() {
+        let data = format!("{:?}", network);
+        assert!(data.contains("total income"));
+        assert!(networks.contains(&format!("{:?}: {}", name, data)));
+    }
+}
+
+#[test]
+#[cfg(feature = "system")]
+fn test_networks_sums() {
+    use sysinfo::{InterfaceType, NetworkExt, NetworksExt, SystemExt};
+
+    let mut s = sysinfo::System::new_all();
+    s.refresh_networks();
+    let networks = s.get_networks();
+    assert!(!networks.is_loopback_included());
+    let (received, total_received) = networks
+        .iter()
+        .filter(|(_, data)| data.get_interface_type() != InterfaceType::Loopback)
+        .fold((0, 0), |(r, t), (_, data)| {
+            (r + data.get_received(), t + data.get_total_received())
+        });
+    assert_eq!(networks.get_received(), received);
+    assert_eq!(networks.get_total_received(), total_received);
+
+    s.get_networks_mut().set_include_loopback(true);
+    let networks = s.get_networks();
+    let transmitted: u64 = networks
+        .iter()
+        .map(|(_, data)| data.get_transmitted())
+        .sum();
+    assert_eq!(networks.get_transmitted(), transmitted);
+}
+
+#[test]
+#[cfg(feature = "system")]
+fn test_networks_min_refresh_interval() {
+    use std::time::Duration;
+    use sysinfo::{NetworkExt, NetworksExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    s.refresh_networks_list();
+    let networks = s.get_networks_mut();
+    networks.set_min_refresh_interval(Duration::from_secs(3600));
+    networks.refresh();
+    let received = networks
+        .iter()
+        .map(|(name, data)| (name.clone(), data.get_total_received()))
+        .collect::<Vec<_>>();
+    // Too soon, nothing is updated.
+    networks.refresh();
+    for (name, total_received) in received {
+        let data = networks
+            .iter()
+            .find(|(n, _)| **n == name)
+            .map(|(_, data)| data)
+            .unwrap();
+        assert_eq!(data.get_total_received(), total_received);
+    }
+}
+
+#[test]
+#[cfg(feature = "system")]
+fn test_interface_names() {
+    use sysinfo::{NetworksExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    s.refresh_networks_list();
+    let names = s.get_networks().get_interface_names().collect::<Vec<_>>();
+    assert_eq!(names.len(), s.get_networks().iter().count());
+    if cfg!(any(target_os = "linux", target_os = "macos")) && !names.is_empty() {
+        assert!(names.iter().any(|name| *name == "lo" || *name == "lo0"));
+    }
+}
+
+#[test]
+#[cfg(feature = "system")]
+fn test_network_stats() {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+    use std::hash::{Hash, Hasher};
+    use sysinfo::{NetworkExt, SystemExt};
+
+    let hash = |stats: &sysinfo::NetworkStats| {
+        let mut hasher = DefaultHasher::new();
+        stats.hash(&mut hasher);
+        hasher.finish()
+    };
+
+    let s = sysinfo::System::new_all();
+    for (_, network) in s.get_networks() {
+        let stats = network.get_stats();
+        let other = network.get_stats();
+        assert_eq!(stats, other);
+        assert_eq!(hash(&stats), hash(&other));
+        assert_eq!(stats.total_received, network.get_total_received());
+        assert_eq!(stats.total_transmitted, network.get_total_transmitted());
+        let set = vec![stats, other].into_iter().collect::<HashSet<_>>();
+        assert_eq!(set.len(), 1);
+    }
+
+    let stats = sysinfo::NetworkStats::default();
+    let other = sysinfo::NetworkStats {
+        received: 1,
+        ..stats
+    };
+    assert_ne!(stats, other);
+}
+
+#[test]
+#[cfg(feature = "system")]
+#[cfg(target_os = "linux")]
+fn test_networks_in_namespace() {
+    use std::path::Path;
+    use sysinfo::{NetworksExt, SystemExt};
+
+    // Joining a network namespace requires `CAP_SYS_ADMIN`.
+    if unsafe { libc::geteuid() } != 0 {
+        return;
+    }
+    let mut s = sysinfo::System::new();
+    s.refresh_networks_list();
+    let mut networks = sysinfo::Networks::new_in_namespace(Path::new("/proc/self/ns/net"));
+    if networks.try_refresh_networks_list().is_err() {
+        // `setns` can still be forbidden, by a seccomp filter for example.
+        return;
+    }
+    let mut expected = s.get_networks().get_interface_names().collect::<Vec<_>>();
+    let mut names = networks.get_interface_names().collect::<Vec<_>>();
+    expected.sort_unstable();
+    names.sort_unstable();
+    assert_eq!(names, expected);
+}
+
+#[test]
+#[cfg(feature = "system")]
+fn test_network_rates() {
+    use sysinfo::{NetworkExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    s.refresh_networks_list();
+    for (_, network) in s.get_networks() {
+        // The counters were only read once.
+        assert_eq!(network.get_received_per_second(), 0.);
+        assert_eq!(network.get_transmitted_per_second(), 0.);
+    }
+    let before = std::time::Instant::now();
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    s.refresh_networks();
+    for (_, network) in s.get_networks() {
+        assert!(network.get_last_refreshed() >= before);
+        let received = network.get_received_per_second();
+        let transmitted = network.get_transmitted_per_second();
+        assert!(received.is_finite() && received >= 0.);
+        assert!(transmitted.is_finite() && transmitted >= 0.);
+    }
+}
+
+#[test]
+fn test_refresh_keeps_interfaces() {
+    use sysinfo::{Networks, NetworksExt};
+
+    // `refresh` only updates the interfaces which are already listed.
+    let mut networks = Networks::new();
+    networks.refresh();
+    assert_eq!(networks.iter().count(), 0);
+
+    networks.refresh_networks_list();
+    let names = networks
+        .get_interface_names()
+        .map(|name| name.to_owned())
+        .collect::<std::collections::HashSet<_>>();
+    networks.refresh();
+    assert_eq!(
+        networks
+            .get_interface_names()
+            .map(|name| name.to_owned())
+            .collect::<std::collections::HashSet<_>>(),
+        names
+    );
+}
+
+#[test]
+fn test_gateways_and_dns_servers() {
+    use sysinfo::{Networks, NetworksExt};
+
+    // There might be no default route (in a sandbox for example), it only needs to be valid if
+    // there is one.
+    let networks = Networks::new();
+    if let Some(gateway) = networks.get_default_gateway_v4() {
+        assert!(!gateway.is_unspecified());
+    }
+    if let Some(gateway) = networks.get_default_gateway_v6() {
+        assert!(!gateway.is_unspecified());
+    }
+    for server in networks.get_dns_servers() {
+        assert!(!server.is_unspecified());
+    }
+}
+
+#[test]
+fn test_networks_without_system() {
+    use sysinfo::{NetworkExt, Networks, NetworksExt};
+
+    let mut networks = Networks::new();
+    assert_eq!(networks.iter().count(), 0);
+    networks.refresh_networks_list();
+    for (_, data) in &networks {
+        assert!(data.get_received() <= data.get_total_received());
+    }
+}
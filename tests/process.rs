@@ -4,6 +4,9 @@
 // Copyright (c) 2018 Guillaume Gomez
 //
 
+#![cfg(feature = "process")]
+
+use std::convert::TryFrom;
 use sysinfo::ProcessExt;
 use sysinfo::SystemExt;
 
@@ -32,6 +35,377 @@ fn test_process_refresh() {
     );
 }
 
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+#[test]
+fn test_process_refresh_dead_process() {
+    let mut child = std::process::Command::new("sleep")
+        .arg("3")
+        .spawn()
+        .expect("failed to spawn sleep");
+    let pid = sysinfo::Pid::try_from(child.id()).unwrap();
+    let mut s = sysinfo::System::new();
+    assert!(s.refresh_process(pid));
+    assert!(s.get_process(pid).is_some());
+
+    child.kill().expect("failed to kill sleep");
+    child.wait().expect("failed to wait for sleep");
+    assert!(!s.refresh_process(pid));
+    assert!(s.get_process(pid).is_none());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_process_refresh_removes_dead_idle_process() {
+    use sysinfo::SystemExt;
+
+    // `sleep` doesn't use any CPU time, so its CPU usage is never computed.
+    let mut child = std::process::Command::new("sleep")
+        .arg("3")
+        .spawn()
+        .expect("failed to spawn sleep");
+    let pid = sysinfo::Pid::from(child.id() as i32);
+    let mut s = sysinfo::System::new();
+    s.refresh_processes();
+    assert!(s.get_process(pid).is_some());
+
+    child.kill().expect("failed to kill sleep");
+    child.wait().expect("failed to wait for sleep");
+    s.refresh_processes();
+    assert!(s.get_process(pid).is_none());
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+#[test]
+fn test_process_keep_exited() {
+    use sysinfo::{ProcessExt, SystemExt};
+
+    let mut child = std::process::Command::new("sleep")
+        .arg("3")
+        .spawn()
+        .expect("failed to spawn sleep");
+    let pid = sysinfo::Pid::try_from(child.id()).unwrap();
+    let mut s = sysinfo::System::new();
+    s.set_keep_exited_processes(true);
+    s.refresh_processes();
+    assert!(s.get_process(pid).map(|p| p.exists()).unwrap_or(false));
+
+    child.kill().expect("failed to kill sleep");
+    child.wait().expect("failed to wait for sleep");
+    s.refresh_processes();
+    // The process is kept for one refresh cycle...
+    assert!(!s.get_process(pid).expect("process was removed").exists());
+    s.refresh_processes();
+    // ... and then removed.
+    assert!(s.get_process(pid).is_none());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_process_last_error() {
+    use sysinfo::{AccessError, ProcessExt, SystemExt};
+
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    assert!(s.refresh_process(pid));
+    let current = s.get_process(pid).expect("failed to get current process");
+    assert_eq!(current.last_error(), None);
+    let current_uid = current.uid;
+
+    // Only a non-root user is denied access to the environment of another user's process.
+    if current_uid != 0 && s.refresh_process(sysinfo::Pid::from(1)) {
+        let init = s
+            .get_process(sysinfo::Pid::from(1))
+            .expect("failed to get pid 1");
+        if init.uid == 0 {
+            assert_eq!(init.last_error(), Some(AccessError::PermissionDenied));
+            assert!(init.environ().is_empty());
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_process_refresh_cpu_usage() {
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    assert!(s.refresh_process(pid));
+
+    // We need to use some CPU time between the two refreshes.
+    let start = std::time::Instant::now();
+    let mut x = 0u64;
+    while start.elapsed() < std::time::Duration::from_millis(200) {
+        x = x.wrapping_add(1);
+    }
+    assert!(x > 0);
+    assert!(s.refresh_process(pid));
+    let p = s.get_process(pid).expect("failed to get current process");
+    assert!(p.cpu_usage() > 0.);
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_process_long_name() {
+    use sysinfo::{ProcessExt, SystemExt};
+
+    // The kernel truncates the process name to 15 characters.
+    let dir = tempfile::tempdir().expect("failed to create temporary directory");
+    let exe = dir.path().join("sysinfo_long_process_name");
+    std::fs::copy("/bin/sleep", &exe).expect("failed to copy sleep");
+    let mut child = std::process::Command::new(&exe)
+        .arg("3")
+        .spawn()
+        .expect("failed to spawn sleep");
+    let pid = sysinfo::Pid::try_from(child.id()).unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    let mut s = sysinfo::System::new();
+    assert!(s.refresh_process(pid));
+    let p = s.get_process(pid).expect("failed to get child process");
+    assert_eq!(p.name(), "sysinfo_long_process_name");
+    assert_eq!(p.short_name(), "sysinfo_long_pr");
+    child.kill().expect("failed to kill sleep");
+    child.wait().expect("failed to wait for sleep");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_process_cpu_usage_normalized() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    // The threads are started before the first refresh because the CPU usage isn't computed if
+    // the process didn't use any CPU time yet.
+    let stop = Arc::new(AtomicBool::new(false));
+    let threads = (0..4)
+        .map(|_| {
+            let stop = Arc::clone(&stop);
+            std::thread::spawn(move || {
+                let mut x = 0u64;
+                while !stop.load(Ordering::Relaxed) {
+                    x = x.wrapping_add(1);
+                }
+                x
+            })
+        })
+        .collect::<Vec<_>>();
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    assert!(s.refresh_process(pid));
+    std::thread::sleep(std::time::Duration::from_millis(300));
+    assert!(s.refresh_process(pid));
+    stop.store(true, Ordering::Relaxed);
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    let p = s.get_process(pid).expect("failed to get current process");
+    let normalized = p.cpu_usage_normalized();
+    assert!(normalized > 0.);
+    assert!(normalized <= 100.);
+    if s.get_processors().len() > 1 {
+        assert!(normalized < p.cpu_usage());
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_process_refresh_without_cmd() {
+    use sysinfo::ProcessRefreshKind;
+
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let refresh_kind = ProcessRefreshKind::everything().without_cmd();
+    let mut s = sysinfo::System::new();
+    s.refresh_processes_specifics(refresh_kind);
+
+    // We need to use some CPU time between the two refreshes.
+    let start = std::time::Instant::now();
+    let mut x = 0u64;
+    while start.elapsed() < std::time::Duration::from_millis(200) {
+        x = x.wrapping_add(1);
+    }
+    assert!(x > 0);
+    s.refresh_processes_specifics(refresh_kind);
+    let p = s.get_process(pid).expect("failed to get current process");
+    assert!(p.cmd().is_empty());
+    assert!(!p.environ().is_empty());
+    assert!(p.cpu_usage() > 0.);
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+#[test]
+fn test_processes_by_name() {
+    let mut child = std::process::Command::new("sleep")
+        .arg("3")
+        .spawn()
+        .expect("failed to spawn sleep");
+    let pid = sysinfo::Pid::try_from(child.id()).unwrap();
+    let mut s = sysinfo::System::new();
+    s.refresh_processes();
+    let found = s.get_processes_by_name("slee").any(|p| p.pid() == pid);
+    let found_exact = s
+        .get_processes_by_exact_name("sleep")
+        .any(|p| p.pid() == pid);
+    let found_partial_exact = s
+        .get_processes_by_exact_name("slee")
+        .any(|p| p.pid() == pid);
+    child.kill().expect("failed to kill sleep");
+    child.wait().expect("failed to wait for sleep");
+    assert!(found);
+    assert!(found_exact);
+    assert!(!found_partial_exact);
+}
+
+#[test]
+fn test_process_exe() {
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let p = s.get_process(pid).expect("failed to get current process");
+    let current_exe = std::env::current_exe().expect("failed to get current exe");
+    assert_eq!(p.exe().file_name(), current_exe.file_name());
+}
+
+#[test]
+fn test_process_cmd_and_environ() {
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let p = s.get_process(pid).expect("failed to get current process");
+    let current_exe = std::env::current_exe().expect("failed to get current exe");
+    let exe_name = current_exe
+        .file_stem()
+        .and_then(|x| x.to_str())
+        .expect("failed to get current exe name");
+    assert!(p.cmd().iter().any(|arg| arg.contains(exe_name)));
+    if std::env::vars_os().next().is_some() {
+        assert!(!p.environ().is_empty());
+    }
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos", windows))]
+fn test_process_environ_var() {
+    use sysinfo::{ProcessExt, SystemExt};
+
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let p = s.get_process(pid).expect("failed to get current process");
+    if let Ok(path) = std::env::var("PATH") {
+        assert_eq!(p.environ_var("PATH"), Some(path.as_str()));
+    }
+    assert_eq!(p.environ_var("SYSINFO_UNSET_VARIABLE"), None);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos", windows))]
+fn test_process_status() {
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    s.refresh_processes();
+    let p = s.get_process(pid).expect("failed to get current process");
+    assert!(matches!(
+        p.status(),
+        sysinfo::ProcessStatus::Run | sysinfo::ProcessStatus::Sleep
+    ));
+}
+
+#[test]
+fn test_process_thread_count() {
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let p = s.get_process(pid).expect("failed to get current process");
+    assert!(p.thread_count() >= 1);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos", windows))]
+fn test_process_open_files_count() {
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let count = s
+        .get_process(pid)
+        .and_then(|p| p.open_files_count())
+        .expect("failed to get open files count");
+
+    let exe = std::env::current_exe().expect("failed to get current exe");
+    // Other tests might close files in the meantime so we open a few of them.
+    let _files = (0..10)
+        .map(|_| std::fs::File::open(&exe).expect("failed to open file"))
+        .collect::<Vec<_>>();
+    s.refresh_process(pid);
+    let new_count = s
+        .get_process(pid)
+        .and_then(|p| p.open_files_count())
+        .expect("failed to get open files count");
+    assert!(new_count > count);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn test_process_user_id() {
+    use sysinfo::UserExt;
+
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    s.refresh_users_list();
+    let p = s.get_process(pid).expect("failed to get current process");
+    let user_id = p.user_id().expect("failed to get user id");
+    assert_eq!(*user_id, unsafe { libc::getuid() });
+    assert_eq!(*p.group_id().expect("failed to get group id"), unsafe {
+        libc::getgid()
+    });
+    if let Some(user) = s.get_user_by_id(user_id) {
+        assert_eq!(user.get_uid(), user_id);
+    }
+}
+
+#[test]
+fn test_process_priority() {
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let p = s.get_process(pid).expect("failed to get current process");
+    let priority = p.priority();
+    assert!((-20..=19).contains(&priority));
+    // Setting the same priority doesn't require any specific rights.
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    assert!(p.set_priority(priority));
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn test_process_set_nice() {
+    use sysinfo::PriorityError;
+
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let p = s.get_process(pid).expect("failed to get current process");
+    let nice = p.priority();
+    assert_eq!(p.set_nice(nice), Ok(()));
+    // Raising the priority requires privileges (root doesn't try, to not change it for real).
+    if nice > -20 && unsafe { libc::geteuid() } != 0 {
+        // On Linux, `RLIMIT_NICE` allows unprivileged users to go down to `20 - rlim_cur`.
+        #[cfg(target_os = "linux")]
+        let allowed = unsafe {
+            let mut limit: libc::rlimit = std::mem::zeroed();
+            libc::getrlimit(libc::RLIMIT_NICE, &mut limit) == 0
+                && (20 - (nice - 1)) as u64 <= limit.rlim_cur as u64
+        };
+        #[cfg(not(target_os = "linux"))]
+        let allowed = false;
+        if !allowed {
+            assert_eq!(p.set_nice(nice - 1), Err(PriorityError::PermissionDenied));
+        }
+    }
+}
+
 #[test]
 #[cfg(windows)]
 fn test_get_cmd_line() {
@@ -44,7 +418,7 @@ fn test_get_cmd_line() {
     assert!(s.get_processes().is_empty());
     s.refresh_processes();
     assert!(!s.get_processes().is_empty());
-    if let Some(process) = s.get_process(p.id() as sysinfo::Pid) {
+    if let Some(process) = s.get_process(sysinfo::Pid::try_from(p.id()).unwrap()) {
         assert_eq!(process.cmd(), &["timeout", "/t", "3"]);
     } else {
         // We're very likely on a "linux-like" shell so let's try some unix command...
@@ -71,7 +445,9 @@ fn unix_like_cmd() {
     assert!(s.get_processes().is_empty());
     s.refresh_processes();
     assert!(!s.get_processes().is_empty());
-    let process = s.get_process(p.id() as sysinfo::Pid).unwrap();
+    let process = s
+        .get_process(sysinfo::Pid::try_from(p.id()).unwrap())
+        .unwrap();
     if process.cmd() != &["sleep", "3"] {
         panic!("cmd not equivalent to`[sleep, 3]`: {:?}", process);
     }
@@ -131,3 +507,487 @@ fn cpu_usage_is_not_nan() {
     });
     assert!(checked > 0);
 }
+
+#[test]
+fn test_process_count() {
+    let mut s = sysinfo::System::new();
+    s.refresh_processes();
+    // At least this process is running.
+    assert!(s.get_process_count() >= 1);
+    assert_eq!(s.get_process_count(), s.get_processes().len());
+    assert!(s.get_running_process_count() <= s.get_process_count());
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn test_get_processes_by_user() {
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    s.refresh_processes();
+    let user_id = s
+        .get_process(pid)
+        .expect("failed to get current process")
+        .user_id()
+        .expect("failed to get user id");
+    assert_eq!(*user_id, unsafe { libc::getuid() });
+    let processes = s.get_processes_by_user(user_id).collect::<Vec<_>>();
+    assert!(processes.iter().any(|p| p.pid() == pid));
+    assert!(processes.iter().all(|p| p.user_id() == Some(user_id)));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_memory_breakdown() {
+    use sysinfo::{ProcessExt, SystemExt};
+
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let p = s.get_process(pid).expect("failed to get current process");
+    assert!(p.memory() > 0);
+    assert!(p.memory() <= p.virtual_memory());
+    assert!(p.shared_memory() <= p.memory());
+    assert!(p.swap_memory() <= p.virtual_memory());
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn test_process_accumulated_cpu_time() {
+    use sysinfo::{ProcessExt, SystemExt};
+
+    let mut child = std::process::Command::new("sh")
+        .arg("-c")
+        .arg("while :; do :; done")
+        .spawn()
+        .expect("failed to spawn busy loop");
+    let pid = sysinfo::Pid::try_from(child.id()).unwrap();
+    let mut s = sysinfo::System::new();
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    assert!(s.refresh_process(pid));
+    let first = s.get_process(pid).unwrap().accumulated_cpu_time();
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    assert!(s.refresh_process(pid));
+    let second = s.get_process(pid).unwrap().accumulated_cpu_time();
+
+    child.kill().expect("failed to kill busy loop");
+    child.wait().expect("failed to wait for busy loop");
+    assert!(first > 0);
+    assert!(second > first);
+}
+
+#[test]
+fn test_pid_conversions() {
+    use std::str::FromStr;
+    use sysinfo::{AsU32, Pid};
+
+    let pid = Pid::try_from(1337u32).unwrap();
+    assert_eq!(pid, Pid::try_from(1337i32).unwrap());
+    assert_eq!(pid.as_u32(), 1337);
+    assert_eq!(u32::try_from(pid), Ok(1337));
+    assert_eq!(i32::try_from(pid), Ok(1337));
+    assert_eq!(Pid::try_from(u32::try_from(pid).unwrap()), Ok(pid));
+    assert_eq!(Pid::try_from(i32::try_from(pid).unwrap()), Ok(pid));
+    // Out of range values must not silently wrap around.
+    #[cfg(unix)]
+    {
+        assert!(Pid::try_from(u32::MAX).is_err());
+        assert!(u32::try_from(Pid::from(-1)).is_err());
+    }
+    #[cfg(windows)]
+    assert!(Pid::try_from(-1i32).is_err());
+    assert_eq!(Pid::from_str("1337"), Ok(pid));
+    assert!(Pid::from_str("a").is_err());
+    assert_eq!(pid.to_string(), "1337");
+    assert_eq!(format!("{:?}", pid), "1337");
+    assert!(Pid::from(1) < pid);
+
+    let current = sysinfo::get_current_pid().expect("failed to get current pid");
+    assert_eq!(current.as_u32(), std::process::id());
+    assert_eq!(Pid::try_from(std::process::id()), Ok(current));
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+fn test_user_processes() {
+    use sysinfo::{ProcessExt, SystemExt};
+
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    s.refresh_processes();
+    assert!(!s.get_process(pid).unwrap().is_kernel_thread());
+    assert!(s.get_user_processes().any(|p| p.pid() == pid));
+    assert!(s.get_user_processes().all(|p| !p.is_kernel_thread()));
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn test_effective_user_id() {
+    use sysinfo::{ProcessExt, SystemExt};
+
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let p = s.get_process(pid).expect("failed to get current process");
+    assert_eq!(*p.user_id().unwrap(), unsafe { libc::getuid() });
+    assert_eq!(*p.effective_user_id().unwrap(), unsafe { libc::geteuid() });
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn test_session_id() {
+    use sysinfo::{ProcessExt, SystemExt};
+
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let p = s.get_process(pid).expect("failed to get current process");
+    let session_id = p.session_id().expect("failed to get session id");
+    assert_ne!(session_id, 0);
+    assert_eq!(session_id, unsafe { libc::getsid(0) });
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_include_threads() {
+    use sysinfo::{ProcessExt, SystemExt};
+
+    let pid = sysinfo::get_current_pid().expect("failed to get current pid");
+    // To be sure that the current process has at least one thread other than the main one.
+    let (tx, rx) = std::sync::mpsc::channel::<()>();
+    let thread = std::thread::spawn(move || rx.recv());
+
+    let count_entries = |s: &sysinfo::System| {
+        s.get_processes()
+            .values()
+            .filter(|p| p.tgid() == pid)
+            .count()
+    };
+    let mut s = sysinfo::System::new();
+    s.refresh_processes();
+    assert_eq!(count_entries(&s), 1);
+    assert!(s.get_processes().values().all(|p| !p.is_thread()));
+
+    s.set_include_threads(true);
+    s.refresh_processes();
+    assert!(count_entries(&s) > 1);
+    assert!(!s.get_process(pid).unwrap().is_thread());
+    for thread in s
+        .get_processes()
+        .values()
+        .filter(|p| p.tgid() == pid && p.pid() != pid)
+    {
+        assert!(thread.is_thread());
+    }
+
+    s.set_include_threads(false);
+    s.refresh_processes();
+    assert_eq!(count_entries(&s), 1);
+
+    tx.send(()).unwrap();
+    thread.join().unwrap().unwrap();
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+fn test_open_sockets() {
+    use sysinfo::{ProcessExt, SocketProtocol, SocketState, SystemExt};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let local_address = listener.local_addr().unwrap();
+    let pid = sysinfo::get_current_pid().unwrap();
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let sockets = s.get_process(pid).unwrap().open_sockets();
+    assert!(sockets.iter().any(|socket| {
+        socket.local_address == local_address
+            && socket.protocol == SocketProtocol::Tcp
+            && socket.state == Some(SocketState::Listen)
+    }));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_network_usage() {
+    use std::io::{Read, Write};
+    use sysinfo::{ProcessExt, SystemExt};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let mut client = std::net::TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+    let (mut server, _) = listener.accept().unwrap();
+    let data = vec![0u8; 100_000];
+    client.write_all(&data).unwrap();
+    let mut received = vec![0u8; data.len()];
+    server.read_exact(&mut received).unwrap();
+
+    let pid = sysinfo::get_current_pid().unwrap();
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let usage = s
+        .get_process(pid)
+        .unwrap()
+        .network_usage()
+        .expect("no network usage");
+    // Both ends of the connection belong to this process. The last acknowledgement might not
+    // have been processed yet on the sending side.
+    assert!(usage.total_received_bytes >= data.len() as u64);
+    assert!(usage.total_transmitted_bytes > 0);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+fn test_system_sockets() {
+    use sysinfo::{SocketProtocol, SocketState, SystemExt};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let local_address = listener.local_addr().unwrap();
+    let pid = sysinfo::get_current_pid().unwrap();
+    let mut s = sysinfo::System::new();
+    assert!(s.get_sockets().is_empty());
+    s.refresh_sockets();
+    assert!(s.get_sockets().iter().any(|socket| {
+        socket.local_address == local_address
+            && socket.protocol == SocketProtocol::Tcp
+            && socket.state == Some(SocketState::Listen)
+            && socket.pid == Some(pid)
+    }));
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+fn test_process_by_port() {
+    use sysinfo::{SocketProtocol, SystemExt};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let tcp_port = listener.local_addr().unwrap().port();
+    let udp_socket = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let udp_port = udp_socket.local_addr().unwrap().port();
+    let pid = sysinfo::get_current_pid().unwrap();
+    let mut s = sysinfo::System::new();
+    s.refresh_sockets();
+    assert_eq!(
+        s.get_process_by_port(tcp_port, SocketProtocol::Tcp),
+        Some(pid)
+    );
+    assert_eq!(
+        s.get_process_by_port(udp_port, SocketProtocol::Udp),
+        Some(pid)
+    );
+
+    drop(listener);
+    s.refresh_sockets();
+    assert_eq!(s.get_process_by_port(tcp_port, SocketProtocol::Tcp), None);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+fn test_process_page_faults() {
+    use sysinfo::{ProcessExt, SystemExt};
+
+    let pid = sysinfo::get_current_pid().unwrap();
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let total = s.get_process(pid).unwrap().total_minor_faults();
+    assert!(total > 0);
+
+    // Touching freshly allocated pages triggers minor page faults.
+    let mut buf = vec![0u8; 16 * 1024 * 1024];
+    for i in (0..buf.len()).step_by(4096) {
+        buf[i] = 1;
+    }
+    assert_eq!(buf.iter().filter(|x| **x == 1).count(), buf.len() / 4096);
+    s.refresh_process(pid);
+    let process = s.get_process(pid).unwrap();
+    assert!(process.total_minor_faults() > total);
+    assert!(process.minor_faults() > 0);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+fn test_process_wait() {
+    use std::time::{Duration, Instant};
+    use sysinfo::{ProcessExt, SystemExt};
+
+    let mut child = if cfg!(target_os = "windows") {
+        std::process::Command::new("cmd")
+            .arg("/C")
+            .arg("ping -n 2 127.0.0.1 >nul & exit 3")
+            .spawn()
+    } else {
+        std::process::Command::new("sh")
+            .arg("-c")
+            .arg("sleep 1; exit 3")
+            .spawn()
+    }
+    .expect("failed to spawn child");
+    let pid = sysinfo::Pid::try_from(child.id()).unwrap();
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let process = s.get_process(pid).expect("failed to get child process");
+    assert_eq!(process.wait_timeout(Duration::from_millis(10)), None);
+    let start = Instant::now();
+    let status = process.wait().expect("failed to get exit status");
+    assert_eq!(status.code(), Some(3));
+    assert!(start.elapsed() < Duration::from_secs(10));
+    // The child wasn't reaped by `wait`.
+    let status = child.wait().expect("failed to wait for child");
+    assert_eq!(status.code(), Some(3));
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn test_process_wait_not_child() {
+    use std::io::Read;
+    use std::time::{Duration, Instant};
+    use sysinfo::{ProcessExt, SystemExt};
+
+    // The `sleep` process is a child of `sh`, not of the current process.
+    let mut child = std::process::Command::new("sh")
+        .arg("-c")
+        .arg("sleep 1 & echo $!")
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .expect("failed to spawn child");
+    let mut output = String::new();
+    child
+        .stdout
+        .take()
+        .unwrap()
+        .read_to_string(&mut output)
+        .unwrap();
+    child.wait().expect("failed to wait for child");
+    let pid: sysinfo::Pid = output.trim().parse().expect("invalid pid");
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let process = s.get_process(pid).expect("failed to get sleep process");
+    let start = Instant::now();
+    assert_eq!(process.wait(), None);
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_threads() {
+    use sysinfo::{ProcessExt, SystemExt};
+
+    let (sender, receiver) = std::sync::mpsc::channel::<()>();
+    let thread = std::thread::Builder::new()
+        .name("sysinfo-thread".to_owned())
+        .spawn(move || receiver.recv())
+        .unwrap();
+    let pid = sysinfo::get_current_pid().unwrap();
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    s.refresh_process(pid);
+    let threads = s.get_process(pid).unwrap().threads();
+    assert!(threads.len() > 1);
+    assert!(threads.iter().all(|thread| !thread.name.is_empty()));
+    assert!(threads.iter().any(|thread| thread.name == "sysinfo-thread"));
+    sender.send(()).unwrap();
+    thread.join().unwrap().unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_oom_score() {
+    use sysinfo::{ProcessExt, SystemExt};
+
+    let pid = sysinfo::get_current_pid().unwrap();
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let process = s.get_process(pid).unwrap();
+    assert!(process.oom_score().is_some());
+    let adj = process.oom_score_adj().unwrap();
+    assert!((-1000..=1000).contains(&adj));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_cgroup() {
+    use sysinfo::{ProcessExt, SystemExt};
+
+    let pid = sysinfo::get_current_pid().unwrap();
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let process = s.get_process(pid).unwrap();
+    // It might only be the root group.
+    assert!(process.cgroup().unwrap().starts_with('/'));
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_process_sched_delay() {
+    use sysinfo::{ProcessExt, SystemExt};
+
+    let pid = sysinfo::get_current_pid().unwrap();
+    let mut s = sysinfo::System::new();
+    s.refresh_process(pid);
+    let process = s.get_process(pid).unwrap();
+    let first = process.sched_delay().expect("failed to read schedstat");
+    s.refresh_process(pid);
+    let process = s.get_process(pid).unwrap();
+    // It's a counter, so it can only grow.
+    assert!(process.sched_delay().unwrap() >= first);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+fn test_watch_process_exit() {
+    use sysinfo::SystemExt;
+
+    let mut child = if cfg!(target_os = "windows") {
+        std::process::Command::new("cmd")
+            .arg("/C")
+            .arg("exit 4")
+            .spawn()
+    } else {
+        std::process::Command::new("sh")
+            .arg("-c")
+            .arg("sleep 0.2; exit 4")
+            .spawn()
+    }
+    .expect("failed to spawn child");
+    let pid = sysinfo::Pid::try_from(child.id()).unwrap();
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let s = sysinfo::System::new();
+    s.watch_process_exit(pid, move |status| {
+        sender.send(status).unwrap();
+    });
+    let status = receiver
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .expect("callback wasn't called");
+    if !cfg!(target_os = "windows") {
+        assert_eq!(status.and_then(|status| status.code()), Some(4));
+    }
+    // The child wasn't reaped by the watcher.
+    let status = child.wait().expect("failed to wait for child");
+    assert_eq!(status.code(), Some(4));
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+fn test_watch_process_exit_cancel() {
+    use sysinfo::SystemExt;
+
+    let mut child = if cfg!(target_os = "windows") {
+        std::process::Command::new("cmd")
+            .arg("/C")
+            .arg("ping -n 2 127.0.0.1 >nul")
+            .spawn()
+    } else {
+        std::process::Command::new("sh")
+            .arg("-c")
+            .arg("sleep 1")
+            .spawn()
+    }
+    .expect("failed to spawn child");
+    let pid = sysinfo::Pid::try_from(child.id()).unwrap();
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let s = sysinfo::System::new();
+    let watcher = s.watch_process_exit(pid, move |status| {
+        sender.send(status).unwrap();
+    });
+    watcher.cancel();
+    child.wait().expect("failed to wait for child");
+    // The callback was dropped without being called.
+    assert!(receiver.recv().is_err());
+}
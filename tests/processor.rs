@@ -4,6 +4,8 @@
 // Copyright (c) 2020 Guillaume Gomez
 //
 
+#![cfg(feature = "cpu")]
+
 // This test is used to ensure that the processors are loaded whatever the method
 // used to initialize `System`.
 
@@ -32,3 +34,131 @@ fn test_physical_core_numbers() {
     assert_ne!(count, None);
     assert!(count.unwrap() > 0);
 }
+
+#[test]
+fn test_processors_usage() {
+    use sysinfo::{ProcessorExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    s.refresh_cpu();
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    s.refresh_cpu();
+    let global = s.get_global_processor_info().get_cpu_usage();
+    assert!((0.0..=100.0).contains(&global));
+    for processor in s.get_processors() {
+        let usage = processor.get_cpu_usage();
+        assert!((0.0..=100.0).contains(&usage), "invalid usage: {}", usage);
+    }
+}
+
+#[test]
+fn test_refresh_cpu_over() {
+    use std::time::{Duration, Instant};
+    use sysinfo::{ProcessorExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    // The minimum refresh interval must not prevent the two samples from being taken.
+    s.set_min_refresh_interval(Duration::from_secs(3600));
+    let start = Instant::now();
+    s.refresh_cpu_over(Duration::from_millis(200));
+    assert!(start.elapsed() >= Duration::from_millis(200));
+    let global = s.get_global_processor_info().get_cpu_usage();
+    assert!((0.0..=100.0).contains(&global), "invalid usage: {}", global);
+    for processor in s.get_processors() {
+        let usage = processor.get_cpu_usage();
+        assert!((0.0..=100.0).contains(&usage), "invalid usage: {}", usage);
+    }
+}
+
+#[test]
+#[cfg(feature = "process")]
+fn test_refresh_cpu_keeps_processes() {
+    use sysinfo::SystemExt;
+
+    let mut s = sysinfo::System::new();
+    assert!(s.get_processes().is_empty());
+    s.refresh_cpu();
+    assert!(s.get_processes().is_empty());
+
+    s.refresh_processes();
+    let count = s.get_processes().len();
+    s.refresh_cpu();
+    assert_eq!(s.get_processes().len(), count);
+}
+
+#[test]
+fn test_processors_temperature() {
+    use sysinfo::{ProcessorExt, SystemExt};
+
+    let s = sysinfo::System::new();
+    // Not all hardware (and no virtual machine) exposes per-core sensors.
+    for processor in s.get_processors() {
+        if let Some(temperature) = processor.get_temperature() {
+            assert!(temperature > 0. && temperature < 150., "{}", temperature);
+        }
+    }
+    assert_eq!(s.get_global_processor_info().get_temperature(), None);
+}
+
+#[test]
+fn test_processors_times() {
+    use sysinfo::{ProcessorExt, SystemExt};
+
+    let mut s = sysinfo::System::new();
+    if s.get_processors().is_empty() {
+        return;
+    }
+    s.refresh_cpu();
+    let before = s
+        .get_processors()
+        .iter()
+        .map(|p| p.get_times().idle)
+        .collect::<Vec<_>>();
+    let global_before = s.get_global_processor_info().get_times();
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    s.refresh_cpu();
+    for (processor, idle) in s.get_processors().iter().zip(before) {
+        assert!(processor.get_times().idle >= idle);
+    }
+    let global = s.get_global_processor_info().get_times();
+    assert!(global.idle >= global_before.idle);
+    assert!(global.user + global.system + global.idle > 0);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+fn test_context_switches() {
+    use sysinfo::SystemExt;
+
+    let mut s = sysinfo::System::new();
+    s.refresh_cpu();
+    // Sleeping threads give the scheduler something to do.
+    let threads = (0..4)
+        .map(|_| {
+            std::thread::spawn(|| {
+                for _ in 0..10 {
+                    std::thread::sleep(std::time::Duration::from_millis(5));
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    s.refresh_cpu();
+    assert!(s.get_context_switches() > 0);
+    assert!(s.get_interrupts() > 0);
+}
+
+#[test]
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+fn test_cpu_caches() {
+    use sysinfo::SystemExt;
+
+    let s = sysinfo::System::new();
+    let caches = s.get_cpu_caches();
+    assert!(caches.iter().any(|cache| cache.level == 1));
+    for cache in &caches {
+        assert!(cache.size > 0);
+    }
+}
@@ -4,6 +4,8 @@
 // Copyright (c) 2020 Guillaume Gomez
 //
 
+#![cfg(feature = "system")]
+
 #[test]
 fn test_send_sync() {
     fn is_send<T: Send>() {}
@@ -12,3 +14,27 @@ fn test_send_sync() {
     is_send::<sysinfo::System>();
     is_sync::<sysinfo::System>();
 }
+
+#[test]
+fn test_send_sync_types() {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<sysinfo::System>();
+    #[cfg(feature = "network")]
+    assert_send_sync::<sysinfo::Networks>();
+    #[cfg(feature = "network")]
+    assert_send_sync::<sysinfo::NetworkData>();
+    #[cfg(feature = "process")]
+    assert_send_sync::<sysinfo::Process>();
+    #[cfg(feature = "process")]
+    assert_send_sync::<sysinfo::ProcessWatcher>();
+    #[cfg(feature = "cpu")]
+    assert_send_sync::<sysinfo::Processor>();
+    #[cfg(feature = "disk")]
+    assert_send_sync::<sysinfo::Disk>();
+    #[cfg(feature = "component")]
+    assert_send_sync::<sysinfo::Component>();
+    #[cfg(feature = "component")]
+    assert_send_sync::<sysinfo::Fan>();
+    assert_send_sync::<sysinfo::Gpu>();
+}
@@ -4,6 +4,8 @@
 // Copyright (c) 2018 Guillaume Gomez
 //
 
+#![cfg(feature = "system")]
+
 #[test]
 fn test_uptime() {
     use sysinfo::SystemExt;